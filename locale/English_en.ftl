@@ -32,6 +32,7 @@ menu_bar_debug = &Debug
 
 new_packfile = &New PackFile
 open_packfile = &Open PackFile
+open_packfile_from_url = Open PackFile from &URL...
 save_packfile = &Save PackFile
 save_packfile_as = Save PackFile &As...
 load_all_ca_packfiles = &Load All CA PackFiles
@@ -152,6 +153,7 @@ open_packfiles = Open PackFiles
 
 tt_packfile_new_packfile = Creates a new PackFile and open it. Remember to save it later if you want to keep it!
 tt_packfile_open_packfile = Open an existing PackFile, or multiple existing PackFiles into one.
+tt_packfile_open_packfile_from_url = Download a PackFile from an URL and open it. It has to be saved with "Save PackFile As...".
 tt_packfile_save_packfile = Save the changes made in the currently open PackFile to disk.
 tt_packfile_save_packfile_as = Save the currently open PackFile as a new PackFile, instead of overwriting the original one.
 tt_packfile_load_all_ca_packfiles = Try to load every PackedFile from every vanilla PackFile of the selected game into RPFM at the same time, using lazy-loading to load the PackedFiles. Keep in mind that if you try to save it, your PC may die.
@@ -243,6 +245,7 @@ global_search_match_packedfile_text = PackedFile/Text
 global_search_versioned_file = VersionFiled (Type, Name)/Column Name
 global_search_definition_version = Definition Version
 global_search_column_index = Column Index
+global_search_truncated = Too many matches. Only the first {"{"}{"}"} matches are shown.
 
 ## tips
 
@@ -319,6 +322,7 @@ decoder_title = PackedFile Decoder
 table_dependency_manager_title = Dependency Manager
 table_filter_case_sensitive = Case Sensitive
 table_enable_lookups = Use Lookups
+table_diff_removed_rows = The last import of this table from an external program removed {"{"}{"}"} rows.
 
 ### Contextual Menu for TreeView
 
@@ -411,12 +415,19 @@ settings_extra_title = Extra Settings
 settings_default_game = Default Game:
 settings_check_updates_on_start = Check Updates on Start:
 settings_check_schema_updates_on_start = Check Schema Updates on Start:
+settings_auto_update_schemas_on_start = Auto-Update Schemas on Start:
 settings_check_template_updates_on_start = Check Template Updates on Start:
 settings_allow_editing_of_ca_packfiles = Allow Editing of CA PackFiles:
 settings_optimize_not_renamed_packedfiles = Optimize Non-Renamed PackedFiles:
 settings_use_dependency_checker = Enable Diagnostics Tool:
 settings_use_lazy_loading = Use Lazy-Loading for PackFiles:
+settings_use_memory_mapped_files = Use Memory-Mapped Files for PackFiles:
+settings_prefetch_memory_budget = Prefetch Memory Budget (MB):
 settings_disable_uuid_regeneration_tables = Disable UUID Regeneration on DB Tables:
+settings_install_after_save = Install PackFile After Saving:
+settings_tsv_import_map_columns_by_header = Map TSV Columns by Header:
+settings_reproducible_save = Reproducible Saves:
+settings_enable_batch_operation_logs = Log Batch Operations:
 settings_packfile_treeview_resize_to_fit = Resize TreeView to content's size:
 settings_table_resize_on_edit = Resize tables on edits to content's size:
 
@@ -447,6 +458,8 @@ tt_extra_network_check_updates_on_start_tip = If you enable this, RPFM will chec
     Whether download it or not is up to you.
 tt_extra_network_check_schema_updates_on_start_tip = If you enable this, RPFM will check for schema updates at the start of the program,
     and allow you to automatically download it if there is any update available.
+tt_extra_network_auto_update_schemas_on_start_tip = If you enable this, RPFM will check for schema updates at the start of the program and, if there is any, download and load it without asking.
+    If the check or the download fails (for example, if you're offline), the schemas you already have are kept.
 tt_extra_packfile_allow_editing_of_ca_packfiles_tip = By default, only PackFiles of Type 'Mod' and 'Movie' are editables, as those are the only ones used for modding.
     If you enable this, you'll be able to edit 'Boot', 'Release' and 'Patch' PackFiles too. Just be careful of not writing over one of the game's original PackFiles!
 tt_extra_packfile_optimize_not_renamed_packedfiles_tip = If you enable this, when running the 'Optimize PackFile' feature RPFM will optimize Tables and Locs that have the same name as their vanilla counterparts.
@@ -454,7 +467,13 @@ tt_extra_packfile_optimize_not_renamed_packedfiles_tip = If you enable this, whe
 tt_extra_packfile_use_dependency_checker_tip = If you enable this, when opening a DB Table RPFM will try to get his dependencies and mark all cells with a reference to another table as 'Not Found In Table' (Red), 'Referenced Table Not Found' (Blue) or 'Correct Reference' (Black). It makes opening a big table a bit slower.
 tt_extra_packfile_use_lazy_loading_tip = If you enable this, PackFiles will load their data on-demand from the disk instead of loading the entire PackFile to Ram. This reduces Ram usage by a lot, but if something else changes/deletes the PackFile while it's open, the PackFile will likely be unrecoverable and you'll lose whatever is in it.
     If you mainly mod in Warhammer 2's /data folder LEAVE THIS DISABLED, as a bug in the Assembly Kit causes PackFiles to become broken/be deleted when you have this enabled.
+tt_extra_packfile_use_memory_mapped_files_tip = If you enable this, Lazy-Loaded PackFiles will be memory-mapped instead of being read with normal disk reads. This speeds up random access over huge PackFiles. If the PackFile cannot be memory-mapped, RPFM will fall back to normal reads. Only works if Lazy-Loading is enabled.
+tt_extra_packfile_prefetch_memory_budget_tip = Max amount of memory, in MB, the data of the PackedFiles loaded from a Lazy-Loaded PackFile can use before RPFM stops loading PackedFiles ahead of time. It doesn't limit the PackedFiles you open. 0 disables prefetching.
 tt_extra_disable_uuid_regeneration_on_db_tables_label_tip = Check this if you plan to put your binary tables under Git/Svn/any kind of version control software.
+tt_extra_packfile_install_after_save_tip = If enabled, every time you save a PackFile, it gets installed in the /data folder of the Game Selected, replacing any previous install of it. It's hardlinked if possible, and copied otherwise.
+tt_extra_packfile_tsv_import_map_columns_by_header_tip = If enabled, when importing a TSV file, its columns are matched to the table's fields by their headers instead of by their position, so TSV files with reordered columns can be imported. TSV files without recognizable headers are still imported by position.
+tt_extra_packfile_reproducible_save_tip = If enabled, saving a PackFile strips all timestamps from it and keeps the UUID of its DB Tables, so saving the same content twice produces the exact same file. Useful for automated builds.
+tt_extra_packfile_enable_batch_operation_logs_tip = If you enable this, batch operations (Mass-Import/Export TSV, Optimize PackFile and Merge Tables) will append a detailed log of each file processed, with timestamps, to the batch_operations.log file in RPFM's folder.
 
 tt_debug_check_for_missing_table_definitions_tip = If you enable this, RPFM will try to decode EVERY TABLE in the current PackFile when opening it or when changing the Game Selected, and it'll output all the tables without an schema to a \"missing_table_definitions.txt\" file.
     DEBUG FEATURE, VERY SLOW. DON'T ENABLE IT UNLESS YOU REALLY WANT TO USE IT.
@@ -508,6 +527,8 @@ template_new_update = <h4>New template update available</h4> <p>Do you want to u
 
 api_response_schema_error = <h4>Error while checking new updates :(</h4> <p>If you see this message, there has been a problem with your connection to the Github.com server. Please, make sure you can access to <a href=\"https://api.github.com\">https://api.github.com</a> and try again.</p>
 schema_update_success = <h4>Schemas updated and reloaded</h4><p>You can continue using RPFM now.</p>
+schema_auto_update_success = Schemas updated from {"{"}{"}"} to {"{"}{"}"}.
+schema_auto_update_error = Schema auto-update failed. Keeping the current schemas ({"{"}{"}"}): {"{"}{"}"}
 template_update_success = <h4>Templates updated and reloaded</h4><p>You can continue using RPFM now.</p>
 
 files_extracted_success = {"{"}{"}"} files extracted. No errors detected.
@@ -516,7 +537,7 @@ mymod_delete_success = MyMod successfully deleted: \"{"{"}{"}"}\"
 generate_pak_success = PAK File succesfully created and reloaded.
 game_selected_unsupported_operation = This operation is not supported for the Game Selected.
 
-optimize_packfile_success = PackFile optimized.
+optimize_packfile_success = PackFile optimized. {"{"}{"}"} rows identical to vanilla removed from {"{"}{"}"} tables, {"{"}{"}"} tables removed for being empty, and {"{"}{"}"} files removed in total.
 update_current_schema_from_asskit_success = Currently loaded schema updated.
 generate_schema_diff_success = Diff generated succesfully.
 settings_font_title = Font Settings
@@ -605,6 +626,7 @@ settings_autosave_interval = Autosave Interval (min)
 autosaving = Autosaving...
 autosaved = Autosaved
 error_autosave_non_editable = This PackFile cannot be autosaved.
+install_after_save_error = The PackFile has been saved, but installing it in the /data folder failed: {"{"}{"}"}
 
 settings_ui_table_use_old_column_order_label = Use Old Column Order (Keys first):
 
@@ -660,6 +682,9 @@ pfs_diagnostics_files_to_ignore_description_label = <p>------------</p><p>The Pa
 pfs_disable_autosaves_label = <p><b><i>Disable Autosaves for this PackFile</i></b></p>
 pfs_disable_autosaves_description_label = <p></p>
 
+pfs_keep_packed_file_order_label = <p><b><i>Keep the Current Order of the PackedFiles on Save</i></b></p>
+pfs_keep_packed_file_order_description_label = <p>------------</p><p>By default, the PackedFiles are sorted by path when saving. If this is enabled, they'll be saved in their current order instead, as some games use that order for overrides.</p>
+
 instructions_ca_vp8 = It's simple, the video can have 2 formats: CAMV (used by the game) and IVF (reproducible on a media player with VP8 codecs).
   To export a video, convert it to IVF and extract it.
   To make it load in-game, convert it to CAMV and save the PackFile.
@@ -723,3 +748,14 @@ schemas_cleared = Schemas folder deleted. Please, remember to re-download the sc
 
 settings_autosave_amount = Autosave Amount (min 1)
 tt_settings_autosave_amount = Sets the amount of autosaves RPFM is allowed to use. If you reduce this number, you need to hit "Clear Autosave Folder" to delete the extra autosaves. Keep in mind this resets the entire autosave folder.
+
+settings_recent_files_max_amount = Recent PackFiles Amount
+tt_settings_recent_files_max_amount = Sets the amount of recently opened PackFiles RPFM remembers in the "Open Recent" menu.
+
+settings_global_search_max_results = Global Search Max Matches
+tt_settings_global_search_max_results = Sets the max amount of matches a Global Search returns. Once it's reached, the search stops. Useful to not freeze the UI when searching for common things in big PackFiles. 0 means no limit.
+
+open_packfile_from_url_title = Open PackFile from URL
+open_packfile_from_url_label = URL of the PackFile to open:
+download_progress = Downloading... {"{"}{"}"} KB
+download_progress_total = Downloading... {"{"}{"}"} of {"{"}{"}"} KB
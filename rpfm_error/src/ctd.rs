@@ -20,14 +20,22 @@ use directories::ProjectDirs;
 use serde_derive::Serialize;
 use uuid::Uuid;
 
+use std::cell::Cell;
 use std::fs::File;
 use std::io::{BufWriter, Write};
-use std::panic::PanicInfo;
+use std::panic::{PanicInfo, UnwindSafe};
 use std::path::Path;
 use std::panic;
+use std::thread;
 
 use crate::{ErrorKind, Result, VERSION};
 
+thread_local! {
+
+	/// If the panics of the current thread are going to be caught, so they should not generate a `CrashReport`.
+	static IS_PANIC_EXPECTED: Cell<bool> = Cell::new(false);
+}
+
 /// This struct contains all the info to write into a `CrashReport` file.
 #[derive(Debug, Serialize)]
 pub struct CrashReport {
@@ -65,12 +73,24 @@ impl CrashReport {
 			};
 
 			panic::set_hook(Box::new(move |info: &panic::PanicInfo| {
-				Self::new(info, VERSION).save(&config_path).unwrap();
+				if !IS_PANIC_EXPECTED.with(|x| x.get()) {
+					Self::new(info, VERSION).save(&config_path).unwrap();
+				}
 			}));
 		}
 		Ok(())
 	}
 
+	/// This function runs the provided closure catching any panic in it, like `panic::catch_unwind`.
+	///
+	/// The difference is that panics caught by this don't generate a `CrashReport`. Only panics in the current thread are silenced.
+	pub fn catch_unwind_silently<F: FnOnce() -> R + UnwindSafe, R>(function: F) -> thread::Result<R> {
+		let was_panic_expected = IS_PANIC_EXPECTED.with(|x| x.replace(true));
+		let result = panic::catch_unwind(function);
+		IS_PANIC_EXPECTED.with(|x| x.set(was_panic_expected));
+		result
+	}
+
 	/// Create a new `CrashReport` from a `Panic`.
 	///
 	/// Remember that this creates the `CrashReport` in memory. If you want to save it to disk, you've to do it later.
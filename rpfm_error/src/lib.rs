@@ -98,9 +98,24 @@ pub enum ErrorKind {
     /// Error for when the version of a TSV file is not the one we're trying to import to.
     ImportTSVWrongVersion,
 
+    /// Error for when a column header of a TSV file doesn't match any field of the table, or it's duplicated. It contains the header.
+    ImportTSVUnknownColumn(String),
+
+    /// Error for when some fields of the table are missing from the column headers of a TSV file. It contains the missing fields.
+    ImportTSVMissingColumns(Vec<String>),
+
+    /// Error for when a column mapping for TSV files maps a column to a field the table doesn't have. It contains the column header and the field.
+    ImportTSVMappingUnknownField(String, String),
+
+    /// Error for when a column mapping for TSV files is not found. It contains the name of the mapping.
+    ImportTSVMappingNotFound(String),
+
     /// Generic TSV import/export error.
     TSVErrorGeneric,
 
+    /// Error for when exporting to a SQLite database fails. It contains the error reported by SQLite.
+    SQLiteError(String),
+
     /// Generic error for when Fluent fails to parse a sentence.
     FluentParsingError,
 
@@ -191,15 +206,39 @@ pub enum ErrorKind {
     /// Error for when the PackFile Indexes are incomplete.
     PackFileIndexesNotComplete,
 
+    /// Error for when we need the timestamps of the PackedFiles of a PackFile, but the PackFile doesn't store them.
+    PackFileTimestampsNotAvailable,
+
+    /// Error for when the open PackFile is not in the provided load order. Contains the name of the PackFile.
+    PackFileNotInLoadOrder(String),
+
     /// Error for when we try to open a PackFile and his extension is not ".pack".
     OpenPackFileInvalidExtension,
 
+    /// Error for when we fail to download a PackFile from an URL. Contains the cause of the failure.
+    DownloadPackFileError(String),
+
     /// Error for when trying to save a non-editable PackFile.
     PackFileIsNonEditable,
 
     /// Error for when the PackFile is not a file in the disk.
     PackFileIsNotAFile,
 
+    /// Error for when the PackFile has been saved, but we failed to install it in the game's data folder. Contains the reason.
+    PackFileInstallFailed(String),
+
+    /// Error for when we try to recover a PackFile from its journal, but it has no journal.
+    JournalNotFound,
+
+    /// Error for when we try to recover a PackFile from a journal that belongs to another PackFile, or to another version of it.
+    JournalPackFileMismatch,
+
+    /// Error for when we try to enable a flag in a PackFile which version doesn't support it. Contains the flag and the version.
+    PackFileFlagNotSupportedByVersion(String, String),
+
+    /// Error for when we try to enable a flag in a PackFile, but we don't support saving PackFiles with it. Contains the flag.
+    PackFileFlagNotSupportedForSaving(String),
+
     /// Error for when the PackFile is not a valid PackFile.
     PackFileIsNotAPackFile,
 
@@ -222,12 +261,48 @@ pub enum ErrorKind {
     /// Error for when we don't have a `Definition` for a specific version of a `VersionedFile`.
     SchemaDefinitionNotFound,
 
+    /// Error for when there is no definition for a specific version of a table in the `Schema`. Contains the table name and the version.
+    SchemaDefinitionNotFoundForTable(String, i32),
+
     /// Error for when we don't have schema updates available.
     NoSchemaUpdatesAvailable,
 
     /// Error for when there was an error while downloading the updated schemas.
     SchemaUpdateError,
 
+    /// Error for when we try to import a `Definition` for a table/version already in the `Schema`, without allowing overwrites. Contains the table name and the version.
+    SchemaDefinitionAlreadyExists(String, i32),
+
+    /// Error for when we try to change the `Schema` while it's locked.
+    SchemaLocked,
+
+    /// Error for when we try to merge two `Schemas` with different structural versions. Contains both versions.
+    SchemaStructuralVersionMismatch(u16, u16),
+
+    /// Error for when we try to access a field of a `Definition` that doesn't exists. Contains the index of the field.
+    SchemaFieldNotFound(usize),
+
+    /// Error for when we try to mark as key a field with a type that cannot be a key. Contains the name of the field.
+    SchemaFieldCannotBeKey(String),
+
+    /// Error for when we try to mark as ordering column a field that's not an integer. Contains the name of the field.
+    SchemaFieldCannotBeOrdering(String),
+
+    /// Error for when we try to renumber an ordering column and the rows don't fit in its type. Contains the name of the column.
+    OrderingColumnOverflow(String),
+
+    /// Error for when we try to access a field of a `Definition` by name, and there is no field with that name. Contains the name of the field.
+    SchemaFieldNameNotFound(String),
+
+    /// Error for when we try to do a string-only operation over a field that's not a string. Contains the name of the field.
+    SchemaFieldIsNotAString(String),
+
+    /// Error for when updating a table to another definition would lose data, due to a column changing to an incompatible type. Contains the name of the column.
+    TableMigrationUnsafe(String),
+
+    /// Error for when we try to compare a column with a comparison or value it doesn't support. Contains the comparison, the type of the column and the type of the value.
+    IncompatibleCompareOp(String, String, String),
+
     //-----------------------------------------------------//
     //                PackedFile Errors
     //-----------------------------------------------------//
@@ -235,6 +310,15 @@ pub enum ErrorKind {
     /// Error for when the PackedFile we want to get doesn't exists.
     PackedFileNotFound,
 
+    /// Error for when we try to revert a PackedFile without data on disk to revert it to. Contains the path of the PackedFile.
+    PackedFileHasNoDiskData(Vec<String>),
+
+    /// Error for when we try to export or import a PackedFile together with its definition, but it's not a DB Table or a Loc PackedFile.
+    PackedFileBundleNotATable,
+
+    /// Error for when we try to save a table with changes in columns marked as non-editable. Contains the `(row, column)` of each changed cell.
+    NonEditableColumnsModified(Vec<(usize, usize)>),
+
     /// Error for when we are trying to do an operation that cannot be done with the PackedFile open.
     PackedFileIsOpen,
 
@@ -284,6 +368,12 @@ pub enum ErrorKind {
     /// Error for when a Table is empty and it doesn't have an `Definition`, so it's undecodeable.
     TableEmptyWithNoDefinition,
 
+    /// Error for when we try to replace rows that don't exist in a Table. Contains the first row, the amount of rows and the amount of rows in the table.
+    TableRowsOutOfBounds(usize, usize, usize),
+
+    /// Error for when we try to use rows that don't exist in a Table. Contains the indexes of the rows that don't exist and the amount of rows in the table.
+    TableRowIndexesOutOfBounds(Vec<usize>, usize),
+
     //--------------------------------//
     // DB Table Errors
     //--------------------------------//
@@ -309,6 +399,15 @@ pub enum ErrorKind {
     /// Error for when we can't find a vanilla version of a table to compare with.
     NoTableInGameFilesToCompare,
 
+    /// Error for when we try to identify a row of a DB Table by its key, but it doesn't exists or its table has no key columns. Contains the row.
+    DBTableRowWithoutKey(usize),
+
+    /// Error for when a DB Table decoded with a specific definition doesn't use all its bytes. Contains the amount of bytes decoded and the total amount of bytes.
+    DBTableNotFullyDecoded(usize, usize),
+
+    /// Error for when a field of a row fails to decode while walking it. It contains the field (starting at 1), its type, and the byte where it starts.
+    DBTableFieldWalkError(usize, String, usize),
+
     //--------------------------------//
     // RigidModel Errors
     //--------------------------------//
@@ -344,6 +443,27 @@ pub enum ErrorKind {
     /// Error for when a Text PackedFile fails to decode due to not being a plain text file or having an unsupported encoding.
     TextDecodeWrongEncodingOrNotATextFile,
 
+    /// Error for when we try to decode something as a WsModel PackedFile and it's not a WsModel.
+    WsModelIsNotAWsModel,
+
+    /// Error for when we try to edit a reference that doesn't exist in a WsModel PackedFile. Contains the index of the reference.
+    WsModelReferenceNotFound(usize),
+
+    /// Error for when we try to decode something as a UI Layout PackedFile and it's not a UI Layout.
+    UiLayoutIsNotAUiLayout,
+
+    /// Error for when we try to decode a binary UI Layout PackedFile.
+    UiLayoutBinaryNotSupported,
+
+    /// Error for when a UI Layout PackedFile fails to parse. Contains the position in the text where it failed.
+    UiLayoutParseError(usize),
+
+    /// Error for when we try to edit an element that doesn't exist in a UI Layout PackedFile.
+    UiLayoutNodeNotFound,
+
+    /// Error for when we try to edit an attribute that doesn't exist in an element of a UI Layout PackedFile. Contains the name of the attribute.
+    UiLayoutAttributeNotFound(String),
+
     /// Error for when we try to use Kailua without a types file.
     NoTypesFileFound,
 
@@ -363,6 +483,15 @@ pub enum ErrorKind {
     /// Error for when we try to decode a Loc PackedFile and fails for corruption.
     LocPackedFileCorrupted,
 
+    /// Error for when we try to import a language from a multi-language TSV, but the TSV has no column for it. Contains the name of the column.
+    LocLanguageColumnNotFound(String),
+
+    /// Error for when we try to import a key-value file into a Loc PackedFile, and some of its lines are not valid entries. Contains the numbers of the lines.
+    LocKeyValueMalformedLines(Vec<usize>),
+
+    /// Error for when we try to convert between a Loc and a DB Table, but the DB Table doesn't have one of the Loc columns. Contains the name and type of the column.
+    LocDBColumnMismatch(String, String),
+
     //--------------------------------//
     // Image Errors
     //--------------------------------//
@@ -370,6 +499,12 @@ pub enum ErrorKind {
     /// Error for when an Image fails to decode. Contains the error message.
     ImageDecode(String),
 
+    /// Error for when an Image fails to be converted to another format. Contains the error message.
+    ImageEncode(String),
+
+    /// Error for when we try to compress an Image with DXT compression, but its dimensions are not multiples of 4. Contains the width and the height.
+    ImageDimensionsNotMultipleOfFour(u32, u32),
+
     //--------------------------------//
     // CA_VP8 Errors
     //--------------------------------//
@@ -536,6 +671,9 @@ pub enum ErrorKind {
     /// Error for when we're trying to merge two invalid files.
     InvalidFilesForMerging,
 
+    /// Error for when we try to reorder the PackedFiles of a PackFile with a list that doesn't contain exactly the same paths the PackFile has.
+    InvalidPackedFileOrder,
+
     /// Error for when we're trying to decode more bytes than we have.
     NotEnoughBytesToDecode,
 
@@ -575,6 +713,9 @@ pub enum ErrorKind {
     /// Error for when RPFM cannot find an extra PackFile in memory.
     CannotFindExtraPackFile(PathBuf),
 
+    /// Error for when we try to edit an extra PackFile, which are read-only. Contains the path of the PackFile.
+    ExtraPackFileIsReadOnly(PathBuf),
+
     /// Error for when RPFM cannot find an animtable in the currently open PackFile.
     NoAnimTableInPackFile,
 
@@ -661,7 +802,12 @@ impl Display for ErrorKind {
             ErrorKind::ImportTSVWrongTypeTable => write!(f, "<p>This TSV file either belongs to another table, to a localisation PackedFile, it's broken or it's incompatible with RPFM.</p>"),
             ErrorKind::ImportTSVWrongVersion => write!(f, "<p>This TSV file belongs to another version of this table. If you want to use it, consider creating a new empty table, fill it with enough empty rows, open this file in a TSV editor, like Excel or LibreOffice, and copy column by column.</p><p>A more automatic solution is on the way, but not yet there.</p>"),
             ErrorKind::ImportTSVInvalidVersion => write!(f, "<p>This TSV file has an invalid version value at line 1.</p>"),
+            ErrorKind::ImportTSVUnknownColumn(column) => write!(f, "<p>This TSV file has a column named <i>{}</i> that doesn't match any field of the table, or it's duplicated.</p>", column),
+            ErrorKind::ImportTSVMissingColumns(columns) => write!(f, "<p>This TSV file is missing the following columns of the table:</p><ul>{}</ul>", columns.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>()),
+            ErrorKind::ImportTSVMappingUnknownField(column, field) => write!(f, "<p>The column mapping maps the column <i>{}</i> to the field <i>{}</i>, but the table doesn't have that field.</p>", column, field),
+            ErrorKind::ImportTSVMappingNotFound(name) => write!(f, "<p>There is no column mapping called <i>{}</i> for this table.</p>", name),
             ErrorKind::TSVErrorGeneric => write!(f, "<p>Error while trying to import/export a TSV file.</p>"),
+            ErrorKind::SQLiteError(error) => write!(f, "<p>Error while trying to export to a SQLite database:</p><p>{}</p>", error),
             ErrorKind::FluentParsingError => write!(f, "<p>Error while trying to parse a fluent sentence.</p>"),
             ErrorKind::FluentResourceLoadingError => write!(f, "<p>Error while trying to load a fluent resource.</p>"),
             ErrorKind::ParsingFloatError => write!(f, "<p>Error while trying to parse a String as a Float.</p>"),
@@ -709,7 +855,10 @@ impl Display for ErrorKind {
             </ul>"),*/
             ErrorKind::PackFileHeaderNotComplete => write!(f, "<p>The header of the PackFile is incomplete, unsupported or damaged.</p>"),
             ErrorKind::PackFileIndexesNotComplete => write!(f, "<p>The indexes of this of the PackFile are incomplete, unsupported or damaged.</p>"),
+            ErrorKind::PackFileTimestampsNotAvailable => write!(f, "<p>This PackFile doesn't store the timestamps of its PackedFiles, so we cannot know when they were modified. Enable the <i>'Index Includes Timestamp'</i> flag in the PackFile and save it to start tracking them.</p>"),
+            ErrorKind::PackFileNotInLoadOrder(name) => write!(f, "<p>The PackFile <i>'{}'</i> is not in the provided load order.</p>", name),
             ErrorKind::OpenPackFileInvalidExtension => write!(f, "<p>RPFM can only open packfiles whose name ends in <i>'.pack'</i></p>"),
+            ErrorKind::DownloadPackFileError(cause) => write!(f, "<p>Error while trying to download the PackFile:</p><p>{}</p>", cause),
             ErrorKind::PackFileIsNonEditable => write!(f, "
             <p>This type of PackFile is supported in Read-Only mode.</p>
             <p>This can happen due to:</p>
@@ -722,6 +871,11 @@ impl Display for ErrorKind {
             <p><b>NOTE</b>: If you created this PackFile using the <i>'Load All CA PackedFiles'</i> feature, NEVER try to save it unless you have 64GB of ram or more. Otherwise it may hang your entire computer to dead.</p>"),
             ErrorKind::PackFileIsNotAPackFile => write!(f, "<p>This file is not a valid PackFile.</p>"),
             ErrorKind::PackFileIsNotAFile => write!(f, "<p>This PackFile doesn't exists as a file in the disk.</p>"),
+            ErrorKind::PackFileFlagNotSupportedByVersion(flag, version) => write!(f, "<p>The flag <b><i>{}</i></b> is not supported by <b><i>{}</i></b> PackFiles.</p>", flag, version),
+            ErrorKind::PackFileFlagNotSupportedForSaving(flag) => write!(f, "<p>The flag <b><i>{}</i></b> cannot be enabled, as RPFM doesn't support saving PackFiles with it yet.</p>", flag),
+            ErrorKind::JournalNotFound => write!(f, "<p>There are no changes to recover for this PackFile.</p>"),
+            ErrorKind::JournalPackFileMismatch => write!(f, "<p>The changes to recover were done to another PackFile, or the PackFile has been changed on disk since then. Recovering them could break it, so they have not been recovered.</p>"),
+            ErrorKind::PackFileInstallFailed(reason) => write!(f, "<p>The PackFile has been saved, but it could not be installed in the /data folder of the Game Selected. The reason is:</p><p>{}</p>", reason),
             ErrorKind::PackFileSizeIsNotWhatWeExpect(reported_size, expected_size) => write!(f, "<p>This PackFile's reported size is <i><b>{}</b></i> bytes, but we expected it to be <i><b>{}</b></i> bytes. This means that either the decoding logic in RPFM is broken for this PackFile, or this PackFile is corrupted.</p>", reported_size, expected_size),
             ErrorKind::NewDataIsNotDecodeableTheSameWayAsOldDAta => write!(f, "<p>The PackedFile you added is not the same type as the one you had before. So... the view showing it will get closed.</p>"),
 
@@ -732,13 +886,28 @@ impl Display for ErrorKind {
             ErrorKind::SchemaNotFound => write!(f, "<p>There is no Schema for the Game Selected.</p>"),
             ErrorKind::SchemaVersionedFileNotFound => write!(f, "<p>There is no Definition of the table in the Schema.</p>"),
             ErrorKind::SchemaDefinitionNotFound => write!(f, "<p>There is no Definition for this specific version of the table in the Schema.</p>"),
+            ErrorKind::SchemaDefinitionNotFoundForTable(name, version) => write!(f, "<p>There is no Definition for the version <b><i>{}</i></b> of the table <b><i>{}</i></b> in the loaded Schema.</p>", version, name),
             ErrorKind::NoSchemaUpdatesAvailable => write!(f, "<p>No schema updates available</p>"),
             ErrorKind::SchemaUpdateError => write!(f, "<p>There was an error while downloading the schemas. Please, try again later.</p><p>If the problem persists (like that time I force-pushed to the repo breaking the updater, good old times) go to <b><i>Preferences/Clear Schema folder</i></b>, and try again.</p>"),
+            ErrorKind::SchemaDefinitionAlreadyExists(table_name, version) => write!(f, "<p>There is already a different Definition for the version <b><i>{}</i></b> of the table <b><i>{}</i></b> in the Schema. If you want to replace it, import it again allowing overwrites.</p>", version, table_name),
+            ErrorKind::SchemaLocked => write!(f, "<p>The Schema is locked for this session, so it cannot be changed. Unlock it first if you really want to change it.</p>"),
+            ErrorKind::SchemaStructuralVersionMismatch(version, other_version) => write!(f, "<p>The Schemas cannot be merged, because they have different structural versions (<b><i>{}</i></b> and <b><i>{}</i></b>). Update them first.</p>", version, other_version),
+            ErrorKind::SchemaFieldNotFound(index) => write!(f, "<p>There is no field with the index <b><i>{}</i></b> in this Definition.</p>", index),
+            ErrorKind::SchemaFieldCannotBeKey(name) => write!(f, "<p>The field <b><i>{}</i></b> cannot be a key field, because floats are not valid keys.</p>", name),
+            ErrorKind::SchemaFieldCannotBeOrdering(name) => write!(f, "<p>The field <b><i>{}</i></b> cannot be an ordering column, because only integer fields can be.</p>", name),
+            ErrorKind::OrderingColumnOverflow(name) => write!(f, "<p>The column <b><i>{}</i></b> cannot be renumbered, because the new values don't fit in its type.</p>", name),
+            ErrorKind::SchemaFieldNameNotFound(name) => write!(f, "<p>There is no field named <b><i>{}</i></b> in this Definition.</p>", name),
+            ErrorKind::SchemaFieldIsNotAString(name) => write!(f, "<p>The field <b><i>{}</i></b> is not a string field.</p>", name),
+            ErrorKind::TableMigrationUnsafe(name) => write!(f, "<p>The table cannot be updated without losing data: the values of the column <b><i>{}</i></b> cannot be converted to its new type.</p>", name),
+            ErrorKind::IncompatibleCompareOp(op, column_type, value_type) => write!(f, "<p>The comparison <b><i>{}</i></b> cannot be used on a column of type <b><i>{}</i></b> with a value of type <b><i>{}</i></b>.</p>", op, column_type, value_type),
 
             //-----------------------------------------------------//
             //                PackedFile Errors
             //-----------------------------------------------------//
             ErrorKind::PackedFileNotFound => write!(f, "<p>This PackedFile no longer exists in the PackFile.</p>"),
+            ErrorKind::NonEditableColumnsModified(cells) => write!(f, "<p>The following cells belong to columns marked as non-editable, and have been modified. Revert them, or disable the protection of non-editable columns, and try again:</p><ul>{}</ul>", cells.iter().map(|(row, column)| format!("<li>Row {}, column {}.</li>", row + 1, column + 1)).collect::<String>()),
+            ErrorKind::PackedFileBundleNotATable => write!(f, "<p>Only DB Tables and Loc PackedFiles can be exported or imported together with their definition.</p>"),
+            ErrorKind::PackedFileHasNoDiskData(path) => write!(f, "<p>The PackedFile <i>{}</i> has no data on disk to revert it to. This happens with PackedFiles added after opening the PackFile, and with all of them after saving it.</p>", path.join("/")),
             ErrorKind::PackedFileIsOpen => write!(f, "<p>That operation cannot be done while the PackedFile involved on it is open. Please, close it by selecting a Folder/PackFile in the TreeView and try again.</p>"),
             ErrorKind::PackedFileIsOpenInAnotherView => write!(f, "<p>That PackedFile is already open in another view. Opening the same PackedFile in multiple views is not supported.</p>"),
             ErrorKind::PackedFileDataCouldNotBeLoaded => write!(f, "<p>This PackedFile's data could not be loaded. This means RPFM can no longer read the PackFile from the disk.</p>"),
@@ -757,6 +926,8 @@ impl Display for ErrorKind {
             ErrorKind::TableRowWrongFieldCount(expected, real) => write!(f, "<p>Error while trying to save a row from a table:</p><p>We expected a row with \"{}\" fields, but we got a row with \"{}\" fields instead.</p>", expected, real),
             ErrorKind::TableWrongFieldType(expected, real) => write!(f, "<p>Error while trying to save a row from a table:</p><p>We expected a field of type \"{}\", but we got a field of type \"{}\".</p>", expected, real),
             ErrorKind::TableEmptyWithNoDefinition => write!(f, "<p>This table is empty and there is not a Definition for it. That means is undecodeable.</p>"),
+            ErrorKind::TableRowsOutOfBounds(start, count, total) => write!(f, "<p>Error while trying to replace {} rows starting at row {}: the table only has {} rows.</p>", count, start + 1, total),
+            ErrorKind::TableRowIndexesOutOfBounds(rows, total) => write!(f, "<p>The following rows don't exist, as the table only has {} rows:</p><ul>{}</ul>", total, rows.iter().map(|x| format!("<li>{}</li>", x + 1)).collect::<String>()),
 
             //--------------------------------//
             // DB Table Errors
@@ -768,6 +939,9 @@ impl Display for ErrorKind {
             ErrorKind::DBMissingReferences(references) => write!(f, "<p>The currently open PackFile has reference errors in the following tables:<ul>{}</ul></p>", references.iter().map(|x| format!("<li>{}<li>", x)).collect::<String>()),
             ErrorKind::NoDefinitionUpdateAvailable => write!(f, "<p>This table already has the newer definition available.</p>"),
             ErrorKind::NoTableInGameFilesToCompare => write!(f, "<p>This table cannot be found in the Game Files, so it cannot be automatically updated (yet).</p>"),
+            ErrorKind::DBTableRowWithoutKey(row) => write!(f, "<p>The row {} of this table cannot be identified by its key. Either the row doesn't exist, or the table has no key columns.</p>", row),
+            ErrorKind::DBTableNotFullyDecoded(decoded, total) => write!(f, "<p>The definition decoded only <b><i>{}</i></b> of the <b><i>{}</i></b> bytes of the table. This means the definition doesn't match the data.</p>", decoded, total),
+            ErrorKind::DBTableFieldWalkError(field, field_type, byte) => write!(f, "<p>The field <b><i>{}</i></b> failed to decode as <b><i>{}</i></b> at byte <b><i>{}</i></b>. Either the type is wrong, or one of the fields before it is.</p>", field, field_type, byte),
 
             //--------------------------------//
             // RigidModel Errors
@@ -785,6 +959,13 @@ impl Display for ErrorKind {
             //--------------------------------//
             ErrorKind::TextDecode(cause) => write!(f, "<p>Error while trying to decode the Text PackedFile:</p><p>{}</p>", cause),
             ErrorKind::TextDecodeWrongEncodingOrNotATextFile => write!(f, "<p>This is either not a Text PackedFile, or a Text PackedFile using an unsupported encoding</p>"),
+            ErrorKind::WsModelIsNotAWsModel => write!(f, "<p>This PackedFile is not a WsModel.</p>"),
+            ErrorKind::WsModelReferenceNotFound(index) => write!(f, "<p>There is no reference number {} in this WsModel.</p>", index + 1),
+            ErrorKind::UiLayoutIsNotAUiLayout => write!(f, "<p>This PackedFile is not a UI Layout.</p>"),
+            ErrorKind::UiLayoutBinaryNotSupported => write!(f, "<p>This UI Layout is in binary format, which is not supported. Only text-based UI Layouts can be decoded.</p>"),
+            ErrorKind::UiLayoutParseError(position) => write!(f, "<p>Error while trying to parse the UI Layout PackedFile, near position {}.</p>", position),
+            ErrorKind::UiLayoutNodeNotFound => write!(f, "<p>The element you tried to edit doesn't exist in this UI Layout.</p>"),
+            ErrorKind::UiLayoutAttributeNotFound(name) => write!(f, "<p>The element you tried to edit doesn't have an attribute called \"{}\".</p>", name),
             ErrorKind::NoTypesFileFound => write!(f, "<p>There is no Types file for the current Game Selected, so you can't use Kailua.</p>"),
             ErrorKind::KailuaNotFound => write!(f, "<p>Kailua executable not found. Install it and try again.</p>"),
 
@@ -794,11 +975,16 @@ impl Display for ErrorKind {
             ErrorKind::LocDecode(cause) => write!(f, "<p>Error while trying to decode the Loc PackedFile:</p><p>{}</p>", cause),
             ErrorKind::LocPackedFileIsNotALocPackedFile => write!(f, "<p>This is either not a Loc PackedFile, or it's a Loc PackedFile but it's corrupted.</p>"),
             ErrorKind::LocPackedFileCorrupted => write!(f, "<p>This Loc PackedFile seems to be corrupted.</p>"),
+            ErrorKind::LocLanguageColumnNotFound(column) => write!(f, "<p>The TSV file doesn't have a column named <b><i>{}</i></b>.</p>", column),
+            ErrorKind::LocDBColumnMismatch(name, field_type) => write!(f, "<p>The DB Table doesn't have a column named <b><i>{}</i></b> of type <b><i>{}</i></b>. All the columns of a Loc Table must be in the DB Table to convert between them.</p>", name, field_type),
+            ErrorKind::LocKeyValueMalformedLines(lines) => write!(f, "<p>The following lines of the file are not valid <i>key-value</i> entries: <b>{}</b>.</p>", lines.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(", ")),
 
             //--------------------------------//
             // Image Errors
             //--------------------------------//
             ErrorKind::ImageDecode(cause) => write!(f, "<p>Error while trying to decode the Image PackedFile:</p><p>{}</p>", cause),
+            ErrorKind::ImageEncode(cause) => write!(f, "<p>Error while trying to convert the Image:</p><p>{}</p>", cause),
+            ErrorKind::ImageDimensionsNotMultipleOfFour(width, height) => write!(f, "<p>The Image is {}x{}, but DDS compression requires both dimensions to be multiples of 4.</p>", width, height),
 
             //--------------------------------//
             // CA_VP8 Errors
@@ -891,6 +1077,7 @@ impl Display for ErrorKind {
             ErrorKind::ReservedFiles => write!(f, "<p>One or more of the files you're trying to add/create/rename to have a reserved name. Those names are reserved for internal use in RPFM. Please, try again with another name.</p>"),
             ErrorKind::NonExistantFile => write!(f, "<p>The file you tried to... use doesn't exist. This is a bug, because if everything worked propetly, you'll never see this message.</p>"),
             ErrorKind::InvalidFilesForMerging => write!(f, "<p>The files you selected are not all LOCs, neither DB Tables of the same type and version.</p>"),
+            ErrorKind::InvalidPackedFileOrder => write!(f, "<p>The new order of the PackedFiles must contain all the PackedFiles in the PackFile, each one of them only once.</p>"),
             ErrorKind::NotEnoughBytesToDecode => write!(f, "<p>There are not enough bytes to decode in the data you provided.</p>"),
             ErrorKind::GameNotSupported => write!(f, "<p>The game you tried to get the info is not supported.</p>"),
             ErrorKind::GameSelectedPathNotCorrectlyConfigured => write!(f, "<p>The Game Selected's Path is not properly configured.</p>"),
@@ -904,6 +1091,7 @@ impl Display for ErrorKind {
             ErrorKind::DownloadTemplatesError => write!(f, "<p>Failed to download the latest templates.<p>"),
             ErrorKind::AlreadyUpdatedTemplatesError => write!(f, "<p>Templates already up-to-date.<p>"),
             ErrorKind::CannotFindExtraPackFile(path) => write!(f, "<p>Cannot find extra PackFile with path: {:?}.<p>", path),
+            ErrorKind::ExtraPackFileIsReadOnly(path) => write!(f, "<p>The extra PackFile with path {:?} is read-only. Its PackedFiles can be browsed, but not edited.<p>", path),
             ErrorKind::NoAnimTableInPackFile => write!(f, "<p>No AnimTable found in the PackFile.<p>"),
             ErrorKind::NoUpdateForYourArchitecture => write!(f, "<p>No download available for your architecture.<p>"),
             ErrorKind::ErrorExtractingUpdate => write!(f, "<p>There was an error while extracting the update. This means either I uploaded a broken file, or your download was incomplete. In any case, no changes have been done so... try again later.<p>"),
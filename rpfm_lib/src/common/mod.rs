@@ -35,6 +35,7 @@ use crate::{SETTINGS, SUPPORTED_GAMES};
 
 pub mod decoder;
 pub mod encoder;
+pub mod pause;
 
 // This tells the compiler to only compile these mods when testing. It's just to make sure
 // the encoders and decoders don't break between updates.
@@ -90,6 +91,14 @@ pub fn get_last_modified_time_from_file(file: &File) -> i64 {
     last_modified_time.naive_utc().timestamp()
 }
 
+/// This function gets the last modified date from the file in the provided path and returns it, as an i64 Unix timestamp.
+///
+/// If the path is a symlink, the date is taken from its target.
+pub fn get_last_modified_time_from_path(path: &Path) -> Result<i64> {
+    let last_modified_time: DateTime<Utc> = DateTime::from(std::fs::metadata(path)?.modified()?);
+    Ok(last_modified_time.naive_utc().timestamp())
+}
+
 /// This function gets the oldest modified file in a folder and return it.
 #[allow(dead_code)]
 pub fn get_oldest_file_in_folder(current_path: &Path) -> Result<Option<PathBuf>> {
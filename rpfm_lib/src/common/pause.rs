@@ -0,0 +1,116 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2020 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module with the code to pause and resume long operations.
+
+Pausing is cooperative: long operations get an `OperationToken` from `OperationPause::start` when they begin, and call
+`OperationToken::wait_if_paused` between the files or rows they process, blocking there until the operation is resumed.
+As they block in the middle of their work, resuming them continues exactly where they were.
+
+Pauses only apply to the operation running when they're requested, so pausing while nothing is running does nothing,
+and a pause never outlives the operation it was meant for.
+!*/
+
+use std::sync::{Condvar, Mutex};
+
+/// This struct represents the flag used to pause and resume long operations.
+#[derive(Debug, Default)]
+pub struct OperationPause {
+
+    /// The state of the operations.
+    state: Mutex<PauseState>,
+
+    /// Used to wake up the paused operations when resuming them.
+    condvar: Condvar,
+}
+
+/// This struct represents the state of the operations, as seen by `OperationPause`.
+#[derive(Debug, Default)]
+struct PauseState {
+
+    /// Id of the last operation started.
+    last_operation: u64,
+
+    /// Id of the operation currently running, if any.
+    running: Option<u64>,
+
+    /// Id of the operation that should be paused, if any.
+    paused: Option<u64>,
+}
+
+/// This struct represents a running operation that can be paused. Once it's dropped, the operation is considered finished.
+#[derive(Debug)]
+pub struct OperationToken<'a> {
+
+    /// The `OperationPause` this operation belongs to.
+    pause: &'a OperationPause,
+
+    /// The id of this operation.
+    id: u64,
+}
+
+/// Implementation of `OperationPause`.
+impl OperationPause {
+
+    /// This function marks the start of a long operation, returning the token it has to use to check if it should pause.
+    ///
+    /// Any pause left from a previous operation is cleared.
+    pub fn start(&self) -> OperationToken {
+        let mut state = self.state.lock().unwrap();
+        state.last_operation += 1;
+        state.running = Some(state.last_operation);
+        state.paused = None;
+
+        OperationToken {
+            pause: self,
+            id: state.last_operation,
+        }
+    }
+
+    /// This function pauses the running operation, the next time it checks if it should pause. If there is none, this does nothing.
+    pub fn pause(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.paused = state.running;
+    }
+
+    /// This function resumes the paused operation.
+    pub fn resume(&self) {
+        self.state.lock().unwrap().paused = None;
+        self.condvar.notify_all();
+    }
+
+    /// This function returns if the running operation is paused.
+    pub fn is_paused(&self) -> bool {
+        self.state.lock().unwrap().paused.is_some()
+    }
+}
+
+/// Implementation of `OperationToken`.
+impl<'a> OperationToken<'a> {
+
+    /// This function blocks the current thread while this operation is paused. Call it only where the operation can be safely paused.
+    pub fn wait_if_paused(&self) {
+        let mut state = self.pause.state.lock().unwrap();
+        while state.paused == Some(self.id) {
+            state = self.pause.condvar.wait(state).unwrap();
+        }
+    }
+}
+
+/// Drop implementation of `OperationToken`, to mark the operation as finished and clear any pause left for it.
+impl<'a> Drop for OperationToken<'a> {
+    fn drop(&mut self) {
+        let mut state = self.pause.state.lock().unwrap();
+        if state.running == Some(self.id) { state.running = None; }
+        if state.paused == Some(self.id) { state.paused = None; }
+        self.pause.condvar.notify_all();
+    }
+}
@@ -22,6 +22,7 @@ use std::path::PathBuf;
 
 use rpfm_error::{ErrorKind, Result};
 
+use crate::packfile::journal::JOURNAL_FOLDER;
 use crate::SETTINGS;
 
 /// Qualifier for the config folder. Only affects MacOS.
@@ -45,6 +46,7 @@ pub fn init_config_path() -> Result<()> {
 	let schemas_path = config_path.join("schemas");
     let templates_path = config_path.join("templates");
     let templates_custom_path = config_path.join("templates_custom");
+    let journals_path = config_path.join(JOURNAL_FOLDER);
 
     DirBuilder::new().recursive(true).create(&autosaves_path)?;
     DirBuilder::new().recursive(true).create(&config_path)?;
@@ -52,6 +54,7 @@ pub fn init_config_path() -> Result<()> {
     DirBuilder::new().recursive(true).create(&schemas_path)?;
     DirBuilder::new().recursive(true).create(&templates_path)?;
     DirBuilder::new().recursive(true).create(&templates_custom_path)?;
+    DirBuilder::new().recursive(true).create(&journals_path)?;
 
     // Init autosave files if they're not yet initialized. Minimum 1.
     let mut max_autosaves = SETTINGS.read().unwrap().settings_string["autosave_amount"].parse::<i32>().unwrap_or(10);
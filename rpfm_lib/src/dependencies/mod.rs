@@ -15,15 +15,21 @@ This module contains the code needed to manage the dependencies of the currently
 !*/
 
 use rayon::prelude::*;
+use serde_derive::{Serialize, Deserialize};
 
-use std::collections::BTreeMap;
-use std::sync::{Arc, RwLock};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::sync::{Arc, RwLock, RwLockReadGuard};
 
+use rpfm_error::{ErrorKind, Result};
 use rpfm_macros::*;
+
+use crate::common::{get_game_selected_content_packfiles_paths, get_game_selected_data_packfiles_paths};
 use crate::DB;
 use crate::packfile::PackFile;
 use crate::PackedFile;
+use crate::packedfile::{DecodedPackedFile, PackedFileType};
 use crate::packedfile::table::DependencyData;
+use crate::schema::VersionedFile;
 use crate::SCHEMA;
 
 //-------------------------------------------------------------------------------//
@@ -41,7 +47,21 @@ pub struct Dependencies {
     fake_dependency_database: Vec<DB>,
 
     /// Cached data for already checked tables.
-    cached_data: Arc<RwLock<BTreeMap<String, BTreeMap<i32, DependencyData>>>>
+    cached_data: Arc<RwLock<BTreeMap<String, BTreeMap<i32, DependencyData>>>>,
+
+    /// Paths of all the PackedFiles in the vanilla PackFiles, lowercased and with `/` as separator. Empty until they're first requested.
+    vanilla_paths: Arc<RwLock<HashSet<String>>>,
+}
+
+/// This struct contains the list of referenced tables a PackFile needs, but cannot be found neither in the PackFile nor in its dependencies.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct MissingTablesReport {
+
+    /// Referenced tables that cannot be found, with the list of `(table path, column name, rows)` depending on each one of them.
+    pub missing_tables: BTreeMap<String, Vec<(Vec<String>, String, Vec<usize>)>>,
+
+    /// Dependency PackFiles declared in the PackFile that cannot be found, and therefore cannot be loaded.
+    pub missing_packfiles: Vec<String>,
 }
 
 //---------------------------------------------------------------p----------------//
@@ -62,6 +82,7 @@ impl Dependencies {
         *self.get_ref_mut_dependency_database() = vec![];
         *self.get_ref_mut_fake_dependency_database() = vec![];
         *self.get_ref_cached_data().write().unwrap() = BTreeMap::new();
+        self.get_ref_vanilla_paths().write().unwrap().clear();
 
         // Only preload dependencies if we have a schema.
         if let Some(ref schema) = *SCHEMA.read().unwrap() {
@@ -75,4 +96,135 @@ impl Dependencies {
             *self.get_ref_mut_fake_dependency_database() = DB::read_pak_file();
         }
     }
+
+    /// This function removes the cached dependency data of the provided table, so it's regenerated the next time it's requested.
+    pub fn invalidate_cached_data(&self, table_name: &str) {
+        self.get_ref_cached_data().write().unwrap().remove(table_name);
+    }
+
+    /// This function returns the paths of all the PackedFiles in the vanilla PackFiles of the game selected, lowercased and with `/` as separator.
+    ///
+    /// The vanilla PackFiles are only opened the first time this is called. After that, the paths are reused until the dependencies are rebuilt.
+    pub fn get_vanilla_paths(&self) -> Result<RwLockReadGuard<HashSet<String>>> {
+        if self.get_ref_vanilla_paths().read().unwrap().is_empty() {
+            let vanilla_pack_file = PackFile::open_all_ca_packfiles()?;
+            *self.get_ref_vanilla_paths().write().unwrap() = vanilla_pack_file.get_ref_packed_files_all_paths().par_iter().map(|x| x.join("/").to_lowercase()).collect();
+        }
+
+        Ok(self.get_ref_vanilla_paths().read().unwrap())
+    }
+
+    /// This function returns a report of all the tables referenced by the DB Tables of the provided `PackFile` that
+    /// cannot be found, neither in the `PackFile` itself nor in the loaded dependencies.
+    ///
+    /// It also reports the dependency `PackFiles` declared in the `PackFile` that cannot be found, as that's usually why a table is missing.
+    pub fn get_missing_tables(&self, pack_file: &mut PackFile) -> Result<MissingTablesReport> {
+        let schema = SCHEMA.read().unwrap();
+        let schema = if let Some(ref schema) = *schema { schema } else { return Err(ErrorKind::SchemaNotFound.into()) };
+
+        // Get the list of tables we have available, from all the sources we have.
+        let mut available_tables = pack_file.get_ref_packed_files_by_type(PackedFileType::DB, false).iter()
+            .filter_map(|x| x.get_path().get(1).cloned())
+            .collect::<Vec<String>>();
+        available_tables.extend(self.dependency_database.iter().filter(|x| x.get_path().len() == 3 && x.get_path()[0] == "db").map(|x| x.get_path()[1].to_owned()));
+        available_tables.extend(self.fake_dependency_database.iter().map(|x| x.get_table_name()));
+        available_tables.sort();
+        available_tables.dedup();
+
+        let mut report = MissingTablesReport::default();
+        for packed_file in pack_file.get_ref_mut_packed_files_by_type(PackedFileType::DB, false) {
+            let path = packed_file.get_path().to_vec();
+            if let Ok(DecodedPackedFile::DB(table)) = packed_file.decode_return_ref_no_locks(&schema) {
+                for (column, field) in table.get_ref_definition().get_fields_processed().iter().enumerate() {
+                    if let Some((ref_table, _)) = field.get_is_reference() {
+                        let ref_table_name = format!("{}_tables", ref_table);
+                        if !ref_table.is_empty() && available_tables.binary_search(&ref_table_name).is_err() {
+                            let rows = table.get_ref_table_data().iter()
+                                .enumerate()
+                                .filter(|(_, row)| !row[column].data_to_string().is_empty())
+                                .map(|(row, _)| row)
+                                .collect::<Vec<usize>>();
+
+                            report.missing_tables.entry(ref_table_name).or_insert_with(Vec::new).push((path.to_vec(), field.get_name().to_owned(), rows));
+                        }
+                    }
+                }
+            }
+        }
+
+        // Then, check what dependency PackFiles are not available in the game's folders.
+        let mut available_packfiles = get_game_selected_data_packfiles_paths().unwrap_or_else(Vec::new);
+        available_packfiles.append(&mut get_game_selected_content_packfiles_paths().unwrap_or_else(Vec::new));
+        let available_packfiles = available_packfiles.iter().filter_map(|x| x.file_name()).map(|x| x.to_string_lossy().to_string()).collect::<Vec<String>>();
+        report.missing_packfiles = pack_file.get_packfiles_list().iter().filter(|x| !available_packfiles.contains(x)).cloned().collect();
+
+        Ok(report)
+    }
+
+    /// This function compares the DB Tables defined in the currently loaded `Schema` with the ones present in the dependency database.
+    ///
+    /// Tables are matched by their folder name (`xxx_tables`). It returns, in this order, the tables in both, the tables only in the `Schema`,
+    /// and the tables only in the dependency database.
+    pub fn get_schema_coverage(&self) -> Result<(Vec<String>, Vec<String>, Vec<String>)> {
+        let schema = SCHEMA.read().unwrap();
+        let schema = if let Some(ref schema) = *schema { schema } else { return Err(ErrorKind::SchemaNotFound.into()) };
+
+        let schema_tables = schema.get_ref_versioned_file_db_all().iter()
+            .filter_map(|x| if let VersionedFile::DB(name, _) = x { Some(name.to_owned()) } else { None })
+            .collect::<BTreeSet<String>>();
+
+        let dependency_tables = self.dependency_database.iter()
+            .filter(|x| x.get_path().len() == 3 && x.get_path()[0] == "db" && x.get_path()[1].ends_with("_tables"))
+            .map(|x| x.get_path()[1].to_owned())
+            .collect::<BTreeSet<String>>();
+
+        Ok((
+            schema_tables.intersection(&dependency_tables).cloned().collect(),
+            schema_tables.difference(&dependency_tables).cloned().collect(),
+            dependency_tables.difference(&schema_tables).cloned().collect(),
+        ))
+    }
+
+    /// This function returns all the distinct values the provided column of the provided table takes, sorted, in both the provided `PackFile` and the dependencies.
+    ///
+    /// Each value comes with the amount of times it appears in the `PackFile` and the amount of times it appears in the dependencies, in that order.
+    /// Tables whose definition doesn't have the column are ignored. If none of them have it, an error is returned.
+    pub fn get_distinct_column_values(&self, pack_file: &mut PackFile, table_name: &str, column_name: &str) -> Result<Vec<(String, usize, usize)>> {
+        let schema = SCHEMA.read().unwrap();
+        let schema = if let Some(ref schema) = *schema { schema } else { return Err(ErrorKind::SchemaNotFound.into()) };
+
+        let mut column_found = false;
+        let mut values: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+
+        // First, the tables of the PackFile.
+        for packed_file in pack_file.get_ref_mut_packed_files_by_type(PackedFileType::DB, false) {
+            if packed_file.get_path().get(1).map(|x| x == table_name).unwrap_or(false) {
+                if let Ok(DecodedPackedFile::DB(table)) = packed_file.decode_return_ref_no_locks(&schema) {
+                    if let Ok(table_values) = table.get_distinct_column_values(column_name) {
+                        column_found = true;
+                        table_values.iter().for_each(|(value, count)| values.entry(value.to_owned()).or_insert((0, 0)).0 += count);
+                    }
+                }
+            }
+        }
+
+        // Then, the tables of the dependencies, including the fake ones from the Pak File.
+        let dependency_tables = self.dependency_database.iter()
+            .filter(|x| x.get_path().len() == 3 && x.get_path()[0] == "db" && x.get_path()[1] == table_name)
+            .filter_map(|x| if let DecodedPackedFile::DB(table) = x.get_ref_decoded() { Some(table) } else { None })
+            .chain(self.fake_dependency_database.iter().filter(|x| x.get_ref_table_name() == table_name));
+
+        for table in dependency_tables {
+            if let Ok(table_values) = table.get_distinct_column_values(column_name) {
+                column_found = true;
+                table_values.iter().for_each(|(value, count)| values.entry(value.to_owned()).or_insert((0, 0)).1 += count);
+            }
+        }
+
+        if column_found {
+            Ok(values.into_iter().map(|(value, (pack_count, dependency_count))| (value, pack_count, dependency_count)).collect())
+        } else {
+            Err(ErrorKind::SchemaFieldNameNotFound(column_name.to_owned()).into())
+        }
+    }
 }
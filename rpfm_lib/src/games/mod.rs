@@ -16,7 +16,7 @@ You should have no business here, except for supporting a new game.
 !*/
 
 use indexmap::IndexMap;
-use crate::packfile::PFHVersion;
+use crate::packfile::{CompressionFormat, PFHVersion};
 
 // Display Name for all the Supported Games.
 pub const DISPLAY_NAME_TROY: &str = "Troy";
@@ -86,6 +86,49 @@ pub struct GameInfo {
 
     /// Name of the big icon used to display the game as `Game Selected`, in an UI.
     pub game_selected_big_icon: String,
+
+    /// Rules the paths of the PackedFiles must follow for the game to be able to load them.
+    pub path_rules: PathRules,
+
+    /// Format used to compress PackedFiles for the game, if it supports compression.
+    pub compression_format: CompressionFormat,
+
+    /// Rules the game follows to build the loc keys of the localised fields of DB Tables.
+    pub loc_key_rules: Vec<LocKeyRule>,
+}
+
+/// This struct holds the rules a PackedFile's path must follow for a game to be able to load it.
+#[derive(Clone, Debug)]
+pub struct PathRules {
+
+    /// Characters that cannot be used in any component of the path.
+    pub illegal_characters: Vec<char>,
+
+    /// Max amount of characters of the full path, with its components joined by `/`.
+    pub max_path_length: usize,
+
+    /// Max amount of components (folders + file) the path can have.
+    pub max_path_depth: usize,
+
+    /// Names that cannot be used as a path component, ignoring casing and extensions.
+    pub reserved_names: Vec<String>,
+}
+
+/// This struct holds a rule to build the loc keys of the localised fields of a DB Table.
+///
+/// Loc keys are built as `table_field_key`, with `table` being the name of the table without the `_tables` suffix,
+/// `field` the name of the localised field, and `key` the values of the key columns of the row.
+#[derive(Clone, Debug)]
+pub struct LocKeyRule {
+
+    /// Name of the table this rule applies to, with the `_tables` suffix. If None, it applies to all the tables without a specific rule.
+    pub table_name: Option<String>,
+
+    /// Columns whose values form the key, in order. If empty, the key columns of the table's definition are used.
+    pub key_columns: Vec<String>,
+
+    /// Text used to join the values of the key columns, for tables where multiple columns form the key.
+    pub key_separator: String,
 }
 
 /// This enum holds the info needed to deal with particularities of the games if they're installed from X or Y store.
@@ -102,6 +145,90 @@ pub enum InstallType {
     Wargaming,
 }
 
+/// Implementation of `PathRules`.
+impl PathRules {
+
+    /// This function returns the path rules shared by all the games we support, which are basically the Windows ones.
+    pub fn new_windows() -> Self {
+        let mut reserved_names = vec!["con".to_owned(), "prn".to_owned(), "aux".to_owned(), "nul".to_owned()];
+        reserved_names.extend((1..=9).map(|x| format!("com{}", x)));
+        reserved_names.extend((1..=9).map(|x| format!("lpt{}", x)));
+
+        Self {
+            illegal_characters: vec!['\\', '<', '>', ':', '"', '|', '?', '*'],
+            max_path_length: 255,
+            max_path_depth: 32,
+            reserved_names,
+        }
+    }
+
+    /// This function checks a path against these rules, returning the reason of every rule the path breaks.
+    pub fn validate_path(&self, path: &[String]) -> Vec<String> {
+        let mut reasons = vec![];
+        let path_joined = path.join("/");
+
+        if path_joined.chars().count() > self.max_path_length {
+            reasons.push(format!("The path is {} characters long, but the max length is {}.", path_joined.chars().count(), self.max_path_length));
+        }
+
+        if path.len() > self.max_path_depth {
+            reasons.push(format!("The path has {} levels, but the max depth is {}.", path.len(), self.max_path_depth));
+        }
+
+        for component in path {
+            if component.is_empty() {
+                reasons.push("The path contains an empty folder name.".to_owned());
+                continue;
+            }
+
+            // Backslashes get their own message, as they're usually Windows paths imported as a single file name.
+            if component.contains('\\') && self.illegal_characters.contains(&'\\') {
+                reasons.push(format!("\"{}\" contains backslashes. This is probably a Windows path imported as a single name.", component));
+            }
+
+            let illegal_characters = component.chars()
+                .filter(|x| *x != '\\' && (x.is_control() || self.illegal_characters.contains(x)))
+                .map(|x| format!("{:?}", x))
+                .collect::<Vec<String>>();
+            if !illegal_characters.is_empty() {
+                reasons.push(format!("\"{}\" contains the following illegal characters: {}.", component, illegal_characters.join(", ")));
+            }
+
+            if component.ends_with('.') || component.ends_with(' ') {
+                reasons.push(format!("\"{}\" ends with a dot or a space.", component));
+            }
+
+            let name = component.split('.').next().unwrap_or("").trim_end().to_lowercase();
+            if self.reserved_names.contains(&name) {
+                reasons.push(format!("\"{}\" uses the reserved name \"{}\".", component, name));
+            }
+        }
+
+        reasons
+    }
+}
+
+/// Implementation of `LocKeyRule`.
+impl LocKeyRule {
+
+    /// This function returns the rule shared by all the games we support: the key columns of the table, joined without separator.
+    pub fn new_default() -> Self {
+        Self {
+            table_name: None,
+            key_columns: vec![],
+            key_separator: String::new(),
+        }
+    }
+
+    /// This function returns the rule that applies to the provided table from the provided list, if any.
+    ///
+    /// Rules specific to the table take priority over the generic ones.
+    pub fn get_rule_for_table<'a>(rules: &'a [Self], table_name: &str) -> Option<&'a Self> {
+        rules.iter().find(|x| x.table_name.as_deref() == Some(table_name))
+            .or_else(|| rules.iter().find(|x| x.table_name.is_none()))
+    }
+}
+
 /// This function returns a `SupportedGames` struct with the list of all games supported by this lib inside.
 pub fn get_supported_games_list() -> SupportedGames {
     let mut list = SupportedGames::new();
@@ -134,6 +261,9 @@ pub fn get_supported_games_list() -> SupportedGames {
         supports_editing: true,
         game_selected_icon: "gs_troy.png".to_owned(),
         game_selected_big_icon: "gs_big_troy.png".to_owned(),
+        path_rules: PathRules::new_windows(),
+        compression_format: CompressionFormat::Lzma1,
+        loc_key_rules: vec![LocKeyRule::new_default()],
     });
 
     // Three Kingdoms
@@ -164,6 +294,9 @@ pub fn get_supported_games_list() -> SupportedGames {
         supports_editing: true,
         game_selected_icon: "gs_3k.png".to_owned(),
         game_selected_big_icon: "gs_big_3k.png".to_owned(),
+        path_rules: PathRules::new_windows(),
+        compression_format: CompressionFormat::Lzma1,
+        loc_key_rules: vec![LocKeyRule::new_default()],
     });
 
     // Warhammer 2
@@ -194,6 +327,9 @@ pub fn get_supported_games_list() -> SupportedGames {
         supports_editing: true,
         game_selected_icon: "gs_wh2.png".to_owned(),
         game_selected_big_icon: "gs_big_wh2.png".to_owned(),
+        path_rules: PathRules::new_windows(),
+        compression_format: CompressionFormat::Lzma1,
+        loc_key_rules: vec![LocKeyRule::new_default()],
     });
 
     // Warhammer
@@ -228,6 +364,9 @@ pub fn get_supported_games_list() -> SupportedGames {
         supports_editing: true,
         game_selected_icon: "gs_wh.png".to_owned(),
         game_selected_big_icon: "gs_big_wh.png".to_owned(),
+        path_rules: PathRules::new_windows(),
+        compression_format: CompressionFormat::Lzma1,
+        loc_key_rules: vec![LocKeyRule::new_default()],
     });
 
     // Thrones of Britannia
@@ -258,6 +397,9 @@ pub fn get_supported_games_list() -> SupportedGames {
         supports_editing: true,
         game_selected_icon: "gs_tob.png".to_owned(),
         game_selected_big_icon: "gs_big_tob.png".to_owned(),
+        path_rules: PathRules::new_windows(),
+        compression_format: CompressionFormat::Lzma1,
+        loc_key_rules: vec![LocKeyRule::new_default()],
     });
 
     // Attila
@@ -288,6 +430,9 @@ pub fn get_supported_games_list() -> SupportedGames {
         supports_editing: true,
         game_selected_icon: "gs_att.png".to_owned(),
         game_selected_big_icon: "gs_big_att.png".to_owned(),
+        path_rules: PathRules::new_windows(),
+        compression_format: CompressionFormat::Lzma1,
+        loc_key_rules: vec![LocKeyRule::new_default()],
     });
 
     // Rome 2
@@ -318,6 +463,9 @@ pub fn get_supported_games_list() -> SupportedGames {
         supports_editing: true,
         game_selected_icon: "gs_rom2.png".to_owned(),
         game_selected_big_icon: "gs_big_rom2.png".to_owned(),
+        path_rules: PathRules::new_windows(),
+        compression_format: CompressionFormat::Lzma1,
+        loc_key_rules: vec![LocKeyRule::new_default()],
     });
 
     // Shogun 2
@@ -348,6 +496,9 @@ pub fn get_supported_games_list() -> SupportedGames {
         supports_editing: true,
         game_selected_icon: "gs_sho2.png".to_owned(),
         game_selected_big_icon: "gs_big_sho2.png".to_owned(),
+        path_rules: PathRules::new_windows(),
+        compression_format: CompressionFormat::Lzma1,
+        loc_key_rules: vec![LocKeyRule::new_default()],
     });
 
     // Napoleon
@@ -398,6 +549,9 @@ pub fn get_supported_games_list() -> SupportedGames {
         supports_editing: true,
         game_selected_icon: "gs_nap.png".to_owned(),
         game_selected_big_icon: "gs_big_nap.png".to_owned(),
+        path_rules: PathRules::new_windows(),
+        compression_format: CompressionFormat::Lzma1,
+        loc_key_rules: vec![LocKeyRule::new_default()],
     });
 
     // Empire
@@ -449,6 +603,9 @@ pub fn get_supported_games_list() -> SupportedGames {
         supports_editing: true,
         game_selected_icon: "gs_emp.png".to_owned(),
         game_selected_big_icon: "gs_big_emp.png".to_owned(),
+        path_rules: PathRules::new_windows(),
+        compression_format: CompressionFormat::Lzma1,
+        loc_key_rules: vec![LocKeyRule::new_default()],
     });
 
     // NOTE: There are things that depend on the order of this list, and this game must ALWAYS be the last one.
@@ -467,6 +624,9 @@ pub fn get_supported_games_list() -> SupportedGames {
         supports_editing: false,
         game_selected_icon: "gs_are.png".to_owned(),
         game_selected_big_icon: "gs_big_are.png".to_owned(),
+        path_rules: PathRules::new_windows(),
+        compression_format: CompressionFormat::Lzma1,
+        loc_key_rules: vec![LocKeyRule::new_default()],
     });
 
     list
@@ -35,6 +35,9 @@ pub mod schema;
 pub mod table;
 pub mod text;
 
+/// Amount of `PackedFiles` searched in parallel between checks of the result limit, when there is one.
+const SEARCH_BATCH_SIZE: usize = 64;
+
 //-------------------------------------------------------------------------------//
 //                              Enums & Structs
 //-------------------------------------------------------------------------------//
@@ -67,6 +70,15 @@ pub struct GlobalSearch {
     /// If we should search on the currently loaded Schema.
     pub search_on_schema: bool,
 
+    /// Paths to restrict the search and replace operations to. If empty, the entire PackFile is used.
+    pub path_filter: Vec<Vec<String>>,
+
+    /// Maximum amount of matches to return. If `None`, all matches are returned.
+    pub max_results: Option<usize>,
+
+    /// If the search stopped before getting all the matches, due to reaching `max_results`.
+    pub truncated: bool,
+
     /// Matches on DB Tables.
     pub matches_db: Vec<TableMatches>,
 
@@ -112,6 +124,9 @@ impl Default for GlobalSearch {
             search_on_locs: true,
             search_on_texts: true,
             search_on_schema: false,
+            path_filter: vec![],
+            max_results: None,
+            truncated: false,
             matches_db: vec![],
             matches_loc: vec![],
             matches_text: vec![],
@@ -124,6 +139,10 @@ impl Default for GlobalSearch {
 impl GlobalSearch {
 
     /// This function performs a search over the parts of a `PackFile` you specify it, storing his results.
+    ///
+    /// If `max_results` is set, the search stops once it has been reached, and `truncated` is set. To make sure
+    /// the matches we keep are always the same, they're taken in order: DBs, Locs, Texts and Schema, and by path within them.
+    /// `PackedFiles` are searched in batches sorted by path, so once the limit is reached the rest of them are not decoded nor searched.
     pub fn search(&mut self, pack_file: &mut PackFile) {
 
         // If we want to use regex and the pattern is invalid, don't search.
@@ -139,47 +158,73 @@ impl GlobalSearch {
         self.matches_loc = vec![];
         self.matches_text = vec![];
         self.matches_schema = vec![];
+        self.truncated = false;
 
         // If we got no schema, don't even decode.
         if let Some(ref schema) = *SCHEMA.read().unwrap() {
             if self.search_on_dbs {
                 let mut packed_files = pack_file.get_ref_mut_packed_files_by_type(PackedFileType::DB, false);
-                self.matches_db = packed_files.par_iter_mut().filter_map(|packed_file| {
-                    let path = packed_file.get_path().to_vec();
-                    if let Ok(decoded_packed_file) = packed_file.decode_return_ref_no_locks(&schema) {
-                        if let DecodedPackedFile::DB(data) = decoded_packed_file {
-                            Some(self.search_on_db(&path, &data, &matching_mode))
+                packed_files.sort_by(|x, y| x.get_path().cmp(y.get_path()));
+                for packed_files in packed_files.chunks_mut(self.get_search_batch_size()) {
+                    let mut matches = packed_files.par_iter_mut().filter_map(|packed_file| {
+                        let path = packed_file.get_path().to_vec();
+                        if !self.is_path_in_filter(&path) { return None }
+                        if let Ok(decoded_packed_file) = packed_file.decode_return_ref_no_locks(&schema) {
+                            if let DecodedPackedFile::DB(data) = decoded_packed_file {
+                                Some(self.search_on_db(&path, &data, &matching_mode))
+                            } else { None }
                         } else { None }
-                    } else { None }
-                }).collect();
+                    }).collect::<Vec<_>>();
+
+                    self.matches_db.append(&mut matches);
+                    self.apply_max_results();
+                    if self.truncated { return; }
+                }
             }
 
             if self.search_on_locs {
                 let mut packed_files = pack_file.get_ref_mut_packed_files_by_type(PackedFileType::Loc, false);
-                self.matches_loc = packed_files.par_iter_mut().filter_map(|packed_file| {
-                    let path = packed_file.get_path().to_vec();
-                    if let Ok(decoded_packed_file) = packed_file.decode_return_ref_no_locks(&schema) {
-                        if let DecodedPackedFile::Loc(data) = decoded_packed_file {
-                            Some(self.search_on_loc(&path, &data, &matching_mode))
+                packed_files.sort_by(|x, y| x.get_path().cmp(y.get_path()));
+                for packed_files in packed_files.chunks_mut(self.get_search_batch_size()) {
+                    let mut matches = packed_files.par_iter_mut().filter_map(|packed_file| {
+                        let path = packed_file.get_path().to_vec();
+                        if !self.is_path_in_filter(&path) { return None }
+                        if let Ok(decoded_packed_file) = packed_file.decode_return_ref_no_locks(&schema) {
+                            if let DecodedPackedFile::Loc(data) = decoded_packed_file {
+                                Some(self.search_on_loc(&path, &data, &matching_mode))
+                            } else { None }
                         } else { None }
-                    } else { None }
-                }).collect();
+                    }).collect::<Vec<_>>();
+
+                    self.matches_loc.append(&mut matches);
+                    self.apply_max_results();
+                    if self.truncated { return; }
+                }
             }
 
             if self.search_on_texts {
                 let mut packed_files = pack_file.get_ref_mut_packed_files_by_type(PackedFileType::Text(TextType::Plain), false);
-                self.matches_text = packed_files.par_iter_mut().filter_map(|packed_file| {
-                    let path = packed_file.get_path().to_vec();
-                    if let Ok(decoded_packed_file) = packed_file.decode_return_ref_no_locks(&schema) {
-                        if let DecodedPackedFile::Text(data) = decoded_packed_file {
-                            Some(self.search_on_text(&path, &data, &matching_mode))
+                packed_files.sort_by(|x, y| x.get_path().cmp(y.get_path()));
+                for packed_files in packed_files.chunks_mut(self.get_search_batch_size()) {
+                    let mut matches = packed_files.par_iter_mut().filter_map(|packed_file| {
+                        let path = packed_file.get_path().to_vec();
+                        if !self.is_path_in_filter(&path) { return None }
+                        if let Ok(decoded_packed_file) = packed_file.decode_return_ref_no_locks(&schema) {
+                            if let DecodedPackedFile::Text(data) = decoded_packed_file {
+                                Some(self.search_on_text(&path, &data, &matching_mode))
+                            } else { None }
                         } else { None }
-                    } else { None }
-                }).collect();
+                    }).collect::<Vec<_>>();
+
+                    self.matches_text.append(&mut matches);
+                    self.apply_max_results();
+                    if self.truncated { return; }
+                }
             }
 
             if self.search_on_schema {
                 self.search_on_schema(schema, &matching_mode);
+                self.apply_max_results();
             }
         }
     }
@@ -217,6 +262,7 @@ impl GlobalSearch {
         }
 
         // We remove the added/edited/deleted files from all the search.
+        paths.retain(|path| self.is_path_in_filter(path));
         for path in &paths {
             self.matches_db.retain(|x| &x.path != path);
             self.matches_loc.retain(|x| &x.path != path);
@@ -247,6 +293,9 @@ impl GlobalSearch {
                     }
                 }
             }
+
+            // Keep the matches sorted and within the limit, as if we did a full search.
+            self.apply_max_results();
         }
     }
 
@@ -262,7 +311,7 @@ impl GlobalSearch {
         if self.search_on_locs { types.push(PackedFileType::Loc); }
         if self.search_on_texts { types.push(PackedFileType::Text(TextType::Plain)); }
         let packed_files = pack_file.get_ref_packed_files_by_types(&types, false);
-        packed_files.iter().map(|x| From::from(*x)).collect()
+        packed_files.iter().filter(|x| self.is_path_in_filter(x.get_path())).map(|x| From::from(*x)).collect()
     }
 
     /// This function returns the PackedFileInfo for all the PackedFiles with the provided paths.
@@ -291,6 +340,7 @@ impl GlobalSearch {
             for match_file in matches {
                 match match_file {
                     MatchHolder::Table(match_table) => {
+                        if !self.is_path_in_filter(&match_table.path) { continue; }
                         if let Some(packed_file) = pack_file.get_ref_mut_packed_file_by_path(&match_table.path) {
                             if let Ok(packed_file) = packed_file.decode_return_ref_mut_no_locks(&schema) {
                                 match packed_file {
@@ -349,9 +399,17 @@ impl GlobalSearch {
     }
 
     /// This function performs a replace operation over the entire match set, except schemas..
+    ///
+    /// If the current results are truncated, the search is redone without limit first, so all the matches are replaced.
     pub fn replace_all(&mut self, pack_file: &mut PackFile) -> Vec<Vec<String>> {
         let mut errors = vec![];
 
+        if self.truncated {
+            let max_results = self.max_results.take();
+            self.search(pack_file);
+            self.max_results = max_results;
+        }
+
         // If we want to use regex and the pattern is invalid, don't search.
         let matching_mode = if self.use_regex {
             if let Ok(regex) = RegexBuilder::new(&self.pattern).case_insensitive(self.case_sensitive).build() {
@@ -363,6 +421,7 @@ impl GlobalSearch {
         if let Some(ref schema) = schema {
             let mut changed_files = vec![];
             for match_table in &self.matches_db {
+                if !self.is_path_in_filter(&match_table.path) { continue; }
                 if let Some(packed_file) = pack_file.get_ref_mut_packed_file_by_path(&match_table.path) {
                     if let Ok(packed_file) = packed_file.decode_return_ref_mut_no_locks(&schema) {
                         if let DecodedPackedFile::DB(ref mut table) = packed_file {
@@ -387,6 +446,7 @@ impl GlobalSearch {
             }
 
             for match_table in &self.matches_loc {
+                if !self.is_path_in_filter(&match_table.path) { continue; }
                 if let Some(packed_file) = pack_file.get_ref_mut_packed_file_by_path(&match_table.path) {
                     if let Ok(packed_file) = packed_file.decode_return_ref_mut_no_locks(&schema) {
                         if let DecodedPackedFile::Loc(ref mut table) = packed_file {
@@ -417,6 +477,60 @@ impl GlobalSearch {
         errors
     }
 
+    /// This function returns if the provided path is within the path filter of this `GlobalSearch`.
+    ///
+    /// An empty filter means all paths are valid.
+    pub fn is_path_in_filter(&self, path: &[String]) -> bool {
+        self.path_filter.is_empty() || self.path_filter.iter().any(|filter| path.starts_with(filter))
+    }
+
+    /// This function returns the amount of `PackedFiles` to search before checking if we reached the result limit.
+    fn get_search_batch_size(&self) -> usize {
+        if self.max_results.is_some() { SEARCH_BATCH_SIZE } else { usize::MAX }
+    }
+
+    /// This function sorts the matches by path and, if `max_results` is set, removes all the matches over it, marking the search as `truncated`.
+    fn apply_max_results(&mut self) {
+        self.matches_db.sort_by(|x, y| x.path.cmp(&y.path));
+        self.matches_loc.sort_by(|x, y| x.path.cmp(&y.path));
+        self.matches_text.sort_by(|x, y| x.path.cmp(&y.path));
+
+        if let Some(max_results) = self.max_results {
+            let mut remaining = max_results;
+            let truncated_db = Self::truncate_matches(&mut self.matches_db, &mut remaining, |x| &mut x.matches);
+            let truncated_loc = Self::truncate_matches(&mut self.matches_loc, &mut remaining, |x| &mut x.matches);
+            let truncated_text = Self::truncate_matches(&mut self.matches_text, &mut remaining, |x| &mut x.matches);
+            let truncated_schema = Self::truncate_matches(&mut self.matches_schema, &mut remaining, |x| &mut x.matches);
+            self.truncated |= truncated_db || truncated_loc || truncated_text || truncated_schema;
+        }
+    }
+
+    /// This function removes from the provided list of matches all the matches over the remaining amount, returning if any match was removed.
+    fn truncate_matches<T, M>(matches: &mut Vec<T>, remaining: &mut usize, get_matches: fn(&mut T) -> &mut Vec<M>) -> bool {
+        let mut truncated = false;
+        let mut kept = 0;
+        for file_matches in matches.iter_mut() {
+            if *remaining == 0 { break; }
+
+            let file_matches = get_matches(file_matches);
+            if file_matches.len() > *remaining {
+                file_matches.truncate(*remaining);
+                truncated = true;
+            }
+
+            *remaining -= file_matches.len();
+            kept += 1;
+        }
+
+        // Files without matches don't count as truncated.
+        if matches.iter_mut().skip(kept).any(|x| !get_matches(x).is_empty()) {
+            truncated = true;
+        }
+
+        matches.truncate(kept);
+        truncated
+    }
+
     /// This function tries to replace data in a Table PackedFile. It fails if the data is not suitable for that column.
     fn replace_match_table(
         &self,
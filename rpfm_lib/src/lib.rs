@@ -24,7 +24,9 @@ use lazy_static::lazy_static;
 
 use std::sync::{Arc, RwLock};
 
+use crate::common::pause::OperationPause;
 use crate::games::{SupportedGames, get_supported_games_list};
+use crate::packedfile::custom::PackedFileDecoder;
 use crate::packedfile::table::db::DB;
 use crate::packfile::packedfile::PackedFile;
 use crate::schema::Schema;
@@ -60,6 +62,12 @@ lazy_static! {
 
     /// Currently loaded schema.
     pub static ref SCHEMA: Arc<RwLock<Option<Schema>>> = Arc::new(RwLock::new(None));
+
+    /// Custom decoders for PackedFiles, in order of registration. Use the functions in `packedfile::custom` to edit it.
+    pub static ref CUSTOM_DECODERS: Arc<RwLock<Vec<Arc<dyn PackedFileDecoder>>>> = Arc::new(RwLock::new(vec![]));
+
+    /// Flag to pause long operations. It can be changed from any thread, even while the operation is running.
+    pub static ref OPERATION_PAUSE: OperationPause = OperationPause::default();
 }
 
 pub const DOCS_BASE_URL: &str = "https://frodo45127.github.io/rpfm/";
@@ -0,0 +1,134 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2020 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module with all the code to register and use custom decoders for PackedFiles.
+
+Custom decoders let external code add support for formats this lib doesn't know about. When decoding a PackedFile,
+registered decoders are checked in order of registration, before the built-in ones. The data they decode is stored
+in `DecodedPackedFile::Custom`, and encoded back through the decoded content itself, so extracting, saving
+and editing them in external programs work the same as with built-in types.
+!*/
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use rpfm_error::Result;
+
+use crate::CUSTOM_DECODERS;
+
+/// This trait represents the decoded data of a `PackedFile` decoded by a custom decoder.
+pub trait DecodedContent: Debug + Send + Sync {
+
+    /// This function encodes the decoded data back to binary.
+    fn encode(&self) -> Result<Vec<u8>>;
+
+    /// This function returns a boxed copy of the decoded data.
+    fn clone_box(&self) -> Box<dyn DecodedContent>;
+}
+
+/// This trait represents a custom decoder for `PackedFiles`.
+pub trait PackedFileDecoder: Send + Sync {
+
+    /// This function returns the name of the decoder. It should be unique between all registered decoders.
+    fn get_name(&self) -> &str;
+
+    /// This function returns if this decoder can decode the `PackedFile` with the provided path.
+    fn can_decode(&self, path: &[String]) -> bool;
+
+    /// This function decodes the provided data.
+    fn decode(&self, data: &[u8]) -> Result<Box<dyn DecodedContent>>;
+}
+
+/// This struct represents a `PackedFile` decoded by a custom decoder.
+#[derive(Debug)]
+pub struct CustomPackedFile {
+
+    /// Name of the decoder used to decode the `PackedFile`.
+    decoder_name: String,
+
+    /// The decoded data.
+    content: Box<dyn DecodedContent>,
+}
+
+/// Implementation of `CustomPackedFile`.
+impl CustomPackedFile {
+
+    /// This function creates a new `CustomPackedFile` from the name of its decoder and its decoded data.
+    pub fn new(decoder_name: &str, content: Box<dyn DecodedContent>) -> Self {
+        Self {
+            decoder_name: decoder_name.to_owned(),
+            content,
+        }
+    }
+
+    /// This function returns the name of the decoder used to decode this `PackedFile`.
+    pub fn get_decoder_name(&self) -> &str {
+        &self.decoder_name
+    }
+
+    /// This function returns a reference to the decoded data.
+    pub fn get_ref_content(&self) -> &dyn DecodedContent {
+        &*self.content
+    }
+
+    /// This function returns a mutable reference to the decoded data.
+    pub fn get_ref_mut_content(&mut self) -> &mut Box<dyn DecodedContent> {
+        &mut self.content
+    }
+
+    /// This function encodes the decoded data back to binary.
+    pub fn save(&self) -> Result<Vec<u8>> {
+        self.content.encode()
+    }
+}
+
+/// Implementation of `Clone` for `CustomPackedFile`.
+impl Clone for CustomPackedFile {
+    fn clone(&self) -> Self {
+        Self {
+            decoder_name: self.decoder_name.to_owned(),
+            content: self.content.clone_box(),
+        }
+    }
+}
+
+/// Implementation of `PartialEq` for `CustomPackedFile`.
+///
+/// As we know nothing about the decoded data, two of them are equal if they come from the same decoder and encode to the same data.
+impl PartialEq for CustomPackedFile {
+    fn eq(&self, other: &Self) -> bool {
+        self.decoder_name == other.decoder_name && match (self.save(), other.save()) {
+            (Ok(data), Ok(other_data)) => data == other_data,
+            _ => false,
+        }
+    }
+}
+
+/// This function registers a custom decoder, so it's used when decoding `PackedFiles`.
+///
+/// If a decoder with the same name is already registered, it's replaced.
+pub fn register_packed_file_decoder(decoder: Arc<dyn PackedFileDecoder>) {
+    let mut decoders = CUSTOM_DECODERS.write().unwrap();
+    match decoders.iter().position(|x| x.get_name() == decoder.get_name()) {
+        Some(index) => decoders[index] = decoder,
+        None => decoders.push(decoder),
+    }
+}
+
+/// This function removes the custom decoder with the provided name, if it's registered.
+pub fn unregister_packed_file_decoder(name: &str) {
+    CUSTOM_DECODERS.write().unwrap().retain(|x| x.get_name() != name);
+}
+
+/// This function returns the first registered custom decoder that can decode the `PackedFile` with the provided path, if any.
+pub fn get_custom_decoder(path: &[String]) -> Option<Arc<dyn PackedFileDecoder>> {
+    CUSTOM_DECODERS.read().unwrap().iter().find(|x| x.can_decode(path)).cloned()
+}
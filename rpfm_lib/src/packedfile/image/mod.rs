@@ -12,11 +12,22 @@
 Module with all the code to interact with Image PackedFiles.
 
 Images... we really just get their that to memory. Nothing more.
+
+Well, except when we want to convert them from/to DDS, the format the games use for textures.
 !*/
 
+use image::{DynamicImage, ImageOutputFormat};
+use image::codecs::dxt::{DxtEncoder, DxtVariant};
+use image::imageops::FilterType;
 use serde_derive::{Serialize, Deserialize};
 
-use rpfm_error::Result;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use rpfm_error::{ErrorKind, Result};
+
+use crate::common::encoder::Encoder;
 
 /// Extensions used by Image PackedFiles.
 pub const EXTENSIONS: [&str; 5] = [
@@ -27,10 +38,45 @@ pub const EXTENSIONS: [&str; 5] = [
     ".png",
 ];
 
+/// Signature of a DDS file.
+const DDS_SIGNATURE: &[u8; 4] = b"DDS ";
+
+/// Size of the header of a DDS file, without the signature.
+const DDS_HEADER_SIZE: u32 = 124;
+
+/// Size of the pixel format struct inside the header of a DDS file.
+const DDS_PIXEL_FORMAT_SIZE: u32 = 32;
+
+/// Flags used in the header of our DDS files.
+const DDSD_CAPS: u32 = 0x1;
+const DDSD_HEIGHT: u32 = 0x2;
+const DDSD_WIDTH: u32 = 0x4;
+const DDSD_PIXELFORMAT: u32 = 0x1000;
+const DDSD_MIPMAPCOUNT: u32 = 0x20000;
+const DDSD_LINEARSIZE: u32 = 0x80000;
+const DDPF_FOURCC: u32 = 0x4;
+const DDSCAPS_COMPLEX: u32 = 0x8;
+const DDSCAPS_TEXTURE: u32 = 0x1000;
+const DDSCAPS_MIPMAP: u32 = 0x400000;
+
 //---------------------------------------------------------------------------//
 //                              Enum & Structs
 //---------------------------------------------------------------------------//
 
+/// This enum represents the compressed formats we can convert images to when making DDS files.
+///
+/// BC7 is not supported. The `image` crate can only encode DXT formats, and the BC7 encoders available depend on native code
+/// we don't want to add to the build. BC7 DDS files made with other tools can still be added as normal files.
+#[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum DdsFormat {
+
+    /// DXT1/BC1 compression, for textures without alpha.
+    DXT1,
+
+    /// DXT5/BC3 compression, for textures with alpha.
+    DXT5,
+}
+
 /// This holds an entire Image PackedFile decoded in memory.
 #[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub struct Image {
@@ -71,4 +117,88 @@ impl Image {
     pub fn get_data(&self) -> &[u8] {
         &self.data
     }
+
+    /// This function creates a DDS `Image` from an image file on disk (png, jpg, tga,...), compressing it with the provided format.
+    ///
+    /// If `generate_mipmaps` is true, it also generates mipmaps for it, down to the smallest size we can compress (4x4).
+    pub fn new_dds_from_file(source_path: &Path, format: DdsFormat, generate_mipmaps: bool) -> Result<Self> {
+        let mut data = vec![];
+        File::open(source_path)?.read_to_end(&mut data)?;
+
+        let source = image::load_from_memory(&data).map_err(|error| ErrorKind::ImageDecode(error.to_string()))?;
+        let width = source.width();
+        let height = source.height();
+        if width == 0 || height == 0 || width % 4 != 0 || height % 4 != 0 {
+            return Err(ErrorKind::ImageDimensionsNotMultipleOfFour(width, height).into());
+        }
+
+        // Get all the levels we have to encode, starting by the full image.
+        let mut levels = vec![source];
+        if generate_mipmaps {
+            loop {
+                let last = levels.last().unwrap();
+                let (width, height) = (last.width() / 2, last.height() / 2);
+                if width < 4 || height < 4 || width % 4 != 0 || height % 4 != 0 { break; }
+                let mipmap = last.resize_exact(width, height, FilterType::Triangle);
+                levels.push(mipmap);
+            }
+        }
+
+        // Write the header. We only use the fields we need, the rest are zeros.
+        let (four_cc, block_size) = match format {
+            DdsFormat::DXT1 => (b"DXT1", 8),
+            DdsFormat::DXT5 => (b"DXT5", 16),
+        };
+
+        let mut flags = DDSD_CAPS | DDSD_HEIGHT | DDSD_WIDTH | DDSD_PIXELFORMAT | DDSD_LINEARSIZE;
+        let mut caps = DDSCAPS_TEXTURE;
+        if levels.len() > 1 {
+            flags |= DDSD_MIPMAPCOUNT;
+            caps |= DDSCAPS_COMPLEX | DDSCAPS_MIPMAP;
+        }
+
+        let mut dds = vec![];
+        dds.extend_from_slice(DDS_SIGNATURE);
+        dds.encode_integer_u32(DDS_HEADER_SIZE);
+        dds.encode_integer_u32(flags);
+        dds.encode_integer_u32(height);
+        dds.encode_integer_u32(width);
+        dds.encode_integer_u32((width / 4) * (height / 4) * block_size);
+        dds.encode_integer_u32(0);
+        dds.encode_integer_u32(levels.len() as u32);
+        dds.extend_from_slice(&[0; 44]);
+
+        dds.encode_integer_u32(DDS_PIXEL_FORMAT_SIZE);
+        dds.encode_integer_u32(DDPF_FOURCC);
+        dds.extend_from_slice(four_cc);
+        dds.extend_from_slice(&[0; 20]);
+
+        dds.encode_integer_u32(caps);
+        dds.extend_from_slice(&[0; 16]);
+
+        // Then, compress every level and append it to the file.
+        for level in &levels {
+            let (pixels, variant) = match format {
+                DdsFormat::DXT1 => (level.to_rgb8().into_raw(), DxtVariant::DXT1),
+                DdsFormat::DXT5 => (level.to_rgba8().into_raw(), DxtVariant::DXT5),
+            };
+
+            let encoder = DxtEncoder::new(&mut dds);
+            encoder.encode(&pixels, level.width(), level.height(), variant).map_err(|error| ErrorKind::ImageEncode(error.to_string()))?;
+        }
+
+        Ok(Self {
+            data: dds,
+        })
+    }
+
+    /// This function converts the provided `Image` to PNG, returning the PNG data.
+    ///
+    /// Useful to quickly check a DDS file in any image viewer.
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        let image: DynamicImage = image::load_from_memory(&self.data).map_err(|error| ErrorKind::ImageDecode(error.to_string()))?;
+        let mut png = vec![];
+        image.write_to(&mut png, ImageOutputFormat::Png).map_err(|error| ErrorKind::ImageEncode(error.to_string()))?;
+        Ok(png)
+    }
 }
@@ -27,20 +27,26 @@ use rpfm_error::{Error, ErrorKind, Result};
 use crate::dependencies::Dependencies;
 use crate::packedfile::animpack::AnimPack;
 use crate::packedfile::ca_vp8::CaVp8;
+use crate::packedfile::custom::{CustomPackedFile, get_custom_decoder};
 use crate::packedfile::image::Image;
 use crate::packedfile::table::{anim_fragment::AnimFragment, animtable::AnimTable, db::DB, loc::Loc, matched_combat::MatchedCombat};
 use crate::packedfile::text::{Text, TextType};
 use crate::packedfile::rigidmodel::RigidModel;
+use crate::packedfile::uilayout::UiLayout;
+use crate::packedfile::wsmodel::WsModel;
 use crate::packfile::packedfile::{PackedFile, RawPackedFile};
 use crate::schema::Schema;
 use crate::SCHEMA;
 
 pub mod animpack;
 pub mod ca_vp8;
+pub mod custom;
 pub mod image;
 pub mod rigidmodel;
 pub mod table;
 pub mod text;
+pub mod uilayout;
+pub mod wsmodel;
 
 //---------------------------------------------------------------------------//
 //                              Enum & Structs
@@ -57,6 +63,9 @@ pub enum DecodedPackedFile {
     AnimTable(AnimTable),
     CaVp8(CaVp8),
     CEO,
+
+    /// This one is for PackedFiles decoded by a custom decoder. Check the `custom` module for more info.
+    Custom(CustomPackedFile),
     DB(DB),
     Image(Image),
     GroupFormations,
@@ -65,6 +74,12 @@ pub enum DecodedPackedFile {
     RigidModel(RigidModel),
     StarPos,
     Text(Text),
+
+    /// This one is a structured view over a UI Layout PackedFile, like WsModel. It's never returned by the normal decoding functions.
+    UiLayout(UiLayout),
+
+    /// This one is an exception, as it's a structured view over a Text PackedFile. It's never returned by the normal decoding functions.
+    WsModel(WsModel),
     Unknown,
 }
 
@@ -104,7 +119,13 @@ pub enum PackedFileType {
 impl DecodedPackedFile {
 
     /// This function decodes a `RawPackedFile` into a `DecodedPackedFile`, returning it.
+    ///
+    /// Custom decoders are checked before the built-in ones.
     pub fn decode(raw_packed_file: &mut RawPackedFile) -> Result<Self> {
+        if let Some(decoded) = Self::decode_custom(raw_packed_file) {
+            return decoded;
+        }
+
         match PackedFileType::get_packed_file_type(raw_packed_file.get_path()) {
 
             PackedFileType::AnimFragment => {
@@ -200,7 +221,13 @@ impl DecodedPackedFile {
     }
 
     /// This function decodes a `RawPackedFile` into a `DecodedPackedFile`, returning it.
+    ///
+    /// Custom decoders are checked before the built-in ones.
     pub fn decode_no_locks(raw_packed_file: &mut RawPackedFile, schema: &Schema) -> Result<Self> {
+        if let Some(decoded) = Self::decode_custom(raw_packed_file) {
+            return decoded;
+        }
+
         match PackedFileType::get_packed_file_type(raw_packed_file.get_path()) {
 
             PackedFileType::AnimFragment => {
@@ -245,6 +272,17 @@ impl DecodedPackedFile {
         }
     }
 
+    /// This function decodes a `RawPackedFile` with the first custom decoder that supports it.
+    ///
+    /// If no custom decoder supports it, this returns None.
+    fn decode_custom(raw_packed_file: &mut RawPackedFile) -> Option<Result<Self>> {
+        let decoder = get_custom_decoder(raw_packed_file.get_path())?;
+        Some(raw_packed_file.get_data_and_keep_it()
+            .and_then(|data| decoder.decode(&data))
+            .map(|content| DecodedPackedFile::Custom(CustomPackedFile::new(decoder.get_name(), content)))
+        )
+    }
+
     /// This function encodes a `DecodedPackedFile` into a `Vec<u8>`, returning it.
     ///
     /// Keep in mind this should only work for PackedFiles with saving support.
@@ -253,11 +291,14 @@ impl DecodedPackedFile {
             DecodedPackedFile::AnimFragment(data) => Some(data.save()),
             DecodedPackedFile::AnimPack(data) => Some(Ok(data.save())),
             DecodedPackedFile::AnimTable(data) => Some(data.save()),
+            DecodedPackedFile::Custom(data) => Some(data.save()),
             DecodedPackedFile::CaVp8(data) => Some(data.save()),
             DecodedPackedFile::DB(data) => Some(data.save()),
             DecodedPackedFile::Loc(data) => Some(data.save()),
             DecodedPackedFile::MatchedCombat(data) => Some(data.save()),
             DecodedPackedFile::Text(data) => Some(data.save()),
+            DecodedPackedFile::UiLayout(data) => Some(data.save()),
+            DecodedPackedFile::WsModel(data) => Some(data.save()),
             _=> None,
         }
     }
@@ -461,6 +502,7 @@ impl From<&DecodedPackedFile> for PackedFileType {
             DecodedPackedFile::AnimTable(_) => PackedFileType::AnimTable,
             DecodedPackedFile::CaVp8(_) => PackedFileType::CaVp8,
             DecodedPackedFile::CEO => PackedFileType::CEO,
+            DecodedPackedFile::Custom(_) => PackedFileType::Unknown,
             DecodedPackedFile::DB(_) => PackedFileType::DB,
             DecodedPackedFile::Image(_) => PackedFileType::Image,
             DecodedPackedFile::GroupFormations => PackedFileType::GroupFormations,
@@ -469,6 +511,8 @@ impl From<&DecodedPackedFile> for PackedFileType {
             DecodedPackedFile::RigidModel(_) => PackedFileType::RigidModel,
             DecodedPackedFile::StarPos => PackedFileType::StarPos,
             DecodedPackedFile::Text(text) => PackedFileType::Text(text.get_text_type()),
+            DecodedPackedFile::UiLayout(_) => PackedFileType::Text(TextType::Xml),
+            DecodedPackedFile::WsModel(_) => PackedFileType::Text(TextType::Xml),
             DecodedPackedFile::Unknown => PackedFileType::Unknown,
         }
     }
@@ -16,16 +16,21 @@ effects data, projectile parameters.... It's what modders use the most.
 !*/
 
 use bincode::deserialize;
+use csv::{QuoteStyle, WriterBuilder};
 use rayon::prelude::*;
+use rusqlite::{Connection, params_from_iter, types::Value};
 use serde_derive::{Serialize, Deserialize};
+use serde_json::{from_reader, to_string_pretty};
 use uuid::Uuid;
 
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::fs::File;
-use std::io::{BufReader, Read};
-use std::path::PathBuf;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
-use rpfm_error::{ErrorKind, Result};
+use rpfm_error::{Error, ErrorKind, Result};
 
 use crate::assembly_kit::table_data::RawTable;
 use crate::common::{decoder::Decoder, encoder::Encoder};
@@ -39,7 +44,8 @@ use crate::packfile::packedfile::PackedFile;
 use crate::schema::*;
 use crate::SETTINGS;
 use crate::SCHEMA;
-use super::{DecodedData, Table, DependencyData};
+use super::{DecodedData, Table, TableDiff, DependencyData};
+use super::loc::Loc;
 
 /// If this sequence is found, the DB Table has a GUID after it.
 const GUID_MARKER: &[u8] = &[253, 254, 252, 255];
@@ -47,6 +53,9 @@ const GUID_MARKER: &[u8] = &[253, 254, 252, 255];
 /// If this sequence is found, the DB Table has a version number after it.
 const VERSION_MARKER: &[u8] = &[252, 253, 254, 255];
 
+/// Suffix added to the table name in the first line of TSV files with resolved references, so they're not imported by mistake.
+const RESOLVED_TSV_SUFFIX: &str = "#resolved_references";
+
 //---------------------------------------------------------------------------//
 //                              Enum & Structs
 //---------------------------------------------------------------------------//
@@ -69,6 +78,99 @@ pub struct DB {
     table: Table,
 }
 
+/// This struct represents a patch with the changes made to a DB Table over a base version of it.
+///
+/// Rows are identified by the key fields of the table's definition, so patches survive row reordering.
+/// Values are stored as text, by field name, so patches survive definition changes too.
+#[derive(PartialEq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TablePatch {
+
+    /// Path of the table this patch is for.
+    pub path: Vec<String>,
+
+    /// Rows added to the table, with all their values.
+    pub added: Vec<TablePatchRow>,
+
+    /// Rows modified in the table, with the old and new values of their changed fields.
+    pub modified: Vec<TablePatchRow>,
+
+    /// Rows deleted from the table, with all their old values.
+    pub deleted: Vec<TablePatchRow>,
+}
+
+/// This struct represents the changes done to a single row in a `TablePatch`.
+#[derive(PartialEq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TablePatchRow {
+
+    /// Key of the row, as returned by `DB::get_row_key`.
+    pub key: String,
+
+    /// Values of the row before the change, by field name. Empty for added rows.
+    pub old: BTreeMap<String, String>,
+
+    /// Values of the row after the change, by field name. Empty for deleted rows.
+    pub new: BTreeMap<String, String>,
+}
+
+/// This enum represents the comparisons that can be used to find rows in a DB Table.
+///
+/// Which ones can be used depends on the type of the column:
+/// - Numeric columns (`F32`, `I16`, `I32`, `I64`): all except `Contains`. The value can be of any numeric type.
+/// - String columns: `Equal`, `NotEqual` and `Contains`. The value must be a string.
+/// - Boolean columns: `Equal` and `NotEqual`. The value must be a boolean.
+/// - Sequence columns: none.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum CompareOp {
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+
+    /// The cell contains the value. Case-sensitive.
+    Contains,
+}
+
+/// This struct represents how many bytes of a DB Table's data its definition consumes when decoding it.
+///
+/// A definition that's wrong only for some rows (usually, with variable-width fields) may decode the table without errors,
+/// but it'll either leave bytes after the last row, or run out of bytes before decoding all the rows the header says the table has.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TableByteConsumption {
+
+    /// Size of the table's data, header included.
+    pub data_size: usize,
+
+    /// Size of the table's header.
+    pub header_size: usize,
+
+    /// Amount of rows the header says the table has.
+    pub entry_count: u32,
+
+    /// Amount of rows decoded before running out of data, or finding data the definition cannot decode.
+    pub decoded_rows: u32,
+
+    /// Position in the data where the last decoded row ends.
+    pub consumed: usize,
+}
+
+/// This enum represents the result of a `TableByteConsumption` analysis.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ByteConsumptionResult {
+
+    /// The definition consumed exactly all the bytes of the table.
+    Exact,
+
+    /// The definition decoded all the rows, but left the contained amount of bytes unused. Its fields are too small.
+    Underrun(usize),
+
+    /// The definition couldn't decode all the rows with the available bytes. Its fields are too big.
+    ///
+    /// Contains an estimation of the missing bytes, based on the average size of the decoded rows, if any row was decoded.
+    Overrun(Option<usize>),
+}
+
 //---------------------------------------------------------------------------//
 //                           Implementation of DB
 //---------------------------------------------------------------------------//
@@ -135,6 +237,197 @@ impl DB {
         self.table.get_entry_count()
     }
 
+    /// This function returns a new row for this DB Table, with the default values of its definition.
+    ///
+    /// Fields without a default value in the definition get the generic default of their type.
+    pub fn new_row(&self) -> Vec<DecodedData> {
+        Table::get_new_row(self.get_ref_definition())
+    }
+
+    /// This function converts this `DB` Table into a `Loc`, using the last Loc definition of the currently loaded schema.
+    ///
+    /// This table must have a column with the same name and type as each column of the Loc definition. Any other column is lost.
+    pub fn to_loc(&self) -> Result<Loc> {
+        match *SCHEMA.read().unwrap() {
+            Some(ref schema) => self.to_loc_with_definition(schema.get_ref_last_definition_loc()?),
+            None => Err(ErrorKind::SchemaNotFound.into()),
+        }
+    }
+
+    /// This function converts this `DB` Table into a `Loc` with the provided Loc definition.
+    ///
+    /// Same as `to_loc`, but without depending on the currently loaded schema.
+    pub fn to_loc_with_definition(&self, definition: &Definition) -> Result<Loc> {
+        let column_map = Loc::get_column_map(definition, self.get_ref_definition())?;
+        let entries = self.get_ref_table_data().iter()
+            .map(|row| column_map.iter().map(|(_, db_column)| row[*db_column].clone()).collect())
+            .collect::<Vec<Vec<DecodedData>>>();
+
+        let mut table = Loc::new(definition);
+        table.set_table_data(&entries)?;
+        Ok(table)
+    }
+
+    /// This function returns the cells of non-editable columns that have different values in the provided DB Table, as `(row, column)`.
+    ///
+    /// Rows are matched by their key columns, and the rows returned are the ones of the provided DB Table.
+    pub fn get_non_editable_changes(&self, new_table: &Self) -> Vec<(usize, usize)> {
+        self.table.get_non_editable_changes(&new_table.table)
+    }
+
+    /// This function returns the differences between this DB Table and the provided one, comparing their rows by position.
+    pub fn get_diff(&self, new_table: &Self) -> TableDiff {
+        self.table.get_diff(&new_table.table)
+    }
+
+    /// This function returns the differences between this DB Table and the provided TSV file, without importing it.
+    ///
+    /// The TSV is validated against the name and definition of this table first. If it's not compatible, the reason is returned
+    /// in the diff, without comparing anything. Rows are matched by their key columns, so reordered rows are not reported as changes.
+    pub fn diff_tsv(&self, external_path: &Path) -> Result<TableDiff> {
+        match Table::import_tsv(self.get_ref_definition(), &external_path.to_path_buf(), &self.name, true) {
+            Ok(new_table) => Ok(self.table.get_diff_by_keys(&new_table)),
+            Err(error) => match error.kind() {
+                ErrorKind::ImportTSVWrongTypeTable |
+                ErrorKind::ImportTSVWrongVersion |
+                ErrorKind::ImportTSVUnknownColumn(_) |
+                ErrorKind::ImportTSVMissingColumns(_) => Ok(TableDiff {
+                    incompatibility: Some(error.to_string()),
+                    ..Default::default()
+                }),
+                _ => Err(error),
+            }
+        }
+    }
+
+    /// This function returns all the distinct values of the provided column in this DB Table, with the amount of times each one appears.
+    ///
+    /// The column is resolved by name using the table's definition, so it works the same no matter the version of the table.
+    pub fn get_distinct_column_values(&self, column_name: &str) -> Result<BTreeMap<String, usize>> {
+        let column = self.get_ref_definition().get_fields_processed().iter()
+            .position(|x| x.get_name() == column_name)
+            .ok_or_else(|| Error::from(ErrorKind::SchemaFieldNameNotFound(column_name.to_owned())))?;
+
+        let mut values = BTreeMap::new();
+        for row in self.get_ref_table_data() {
+            *values.entry(row[column].data_to_string()).or_insert(0) += 1;
+        }
+        Ok(values)
+    }
+
+    /// This function returns the indexes of the rows whose value in the provided column satisfies the provided comparison against the provided value.
+    ///
+    /// If the comparison or the type of the value cannot be used with the type of the column, this returns an error. Check `CompareOp` for the valid combinations.
+    pub fn find_rows(&self, column: usize, op: CompareOp, value: &DecodedData) -> Result<Vec<usize>> {
+        let fields = self.get_ref_definition().get_fields_processed();
+        let field_type = fields.get(column).ok_or_else(|| Error::from(ErrorKind::SchemaFieldNotFound(column)))?.get_field_type();
+        let incompatible_error = || Error::from(ErrorKind::IncompatibleCompareOp(format!("{:?}", op), field_type.to_string(), value.to_string()));
+
+        let rows = self.get_ref_table_data().iter().enumerate();
+        match field_type {
+            FieldType::F32 |
+            FieldType::I16 |
+            FieldType::I32 |
+            FieldType::I64 => {
+                let value = Self::get_numeric_value(value).ok_or_else(incompatible_error)?;
+                if op == CompareOp::Contains { return Err(incompatible_error()) }
+
+                Ok(rows.filter(|(_, row)| Self::get_numeric_value(&row[column])
+                    .and_then(|cell| cell.partial_cmp(&value))
+                    .map_or(false, |ordering| op.matches_ordering(ordering)))
+                    .map(|(index, _)| index)
+                    .collect())
+            }
+
+            FieldType::StringU8 |
+            FieldType::StringU16 |
+            FieldType::OptionalStringU8 |
+            FieldType::OptionalStringU16 => {
+                let value = match value {
+                    DecodedData::StringU8(value) |
+                    DecodedData::StringU16(value) |
+                    DecodedData::OptionalStringU8(value) |
+                    DecodedData::OptionalStringU16(value) => value,
+                    _ => return Err(incompatible_error()),
+                };
+
+                match op {
+                    CompareOp::Equal | CompareOp::NotEqual | CompareOp::Contains => {},
+                    _ => return Err(incompatible_error()),
+                }
+
+                Ok(rows.filter(|(_, row)| match &row[column] {
+                        DecodedData::StringU8(cell) |
+                        DecodedData::StringU16(cell) |
+                        DecodedData::OptionalStringU8(cell) |
+                        DecodedData::OptionalStringU16(cell) => match op {
+                            CompareOp::Equal => cell == value,
+                            CompareOp::NotEqual => cell != value,
+                            _ => cell.contains(value.as_str()),
+                        }
+                        _ => false,
+                    })
+                    .map(|(index, _)| index)
+                    .collect())
+            }
+
+            FieldType::Boolean => {
+                let value = if let DecodedData::Boolean(value) = value { *value } else { return Err(incompatible_error()) };
+                let expected = match op {
+                    CompareOp::Equal => value,
+                    CompareOp::NotEqual => !value,
+                    _ => return Err(incompatible_error()),
+                };
+
+                Ok(rows.filter(|(_, row)| row[column] == DecodedData::Boolean(expected))
+                    .map(|(index, _)| index)
+                    .collect())
+            }
+
+            FieldType::SequenceU16(_) |
+            FieldType::SequenceU32(_) => Err(incompatible_error()),
+        }
+    }
+
+    /// This function sets the provided value in the target column of all the rows whose value in the filter column satisfies the provided comparison.
+    ///
+    /// The value is converted to the type of the target column if needed. If that, or the comparison, cannot be done, this returns an error
+    /// without changing any row. It returns the amount of rows whose value actually changed.
+    pub fn set_column_where(&mut self, target_column: usize, value: DecodedData, filter_column: usize, op: CompareOp, filter_value: &DecodedData) -> Result<usize> {
+        let fields = self.get_ref_definition().get_fields_processed();
+        let field_type = fields.get(target_column).ok_or_else(|| Error::from(ErrorKind::SchemaFieldNotFound(target_column)))?.get_field_type();
+        let value = match field_type {
+            FieldType::SequenceU16(_) | FieldType::SequenceU32(_) => return Err(ErrorKind::TableWrongFieldType(field_type.to_string(), value.to_string()).into()),
+            _ => value.convert_between_types(&field_type).map_err(|_| Error::from(ErrorKind::TableWrongFieldType(field_type.to_string(), value.to_string())))?,
+        };
+
+        let rows = self.find_rows(filter_column, op, filter_value)?;
+        let mut data = self.get_table_data();
+        let mut changed_rows = 0;
+        for row in rows {
+            if data[row][target_column] != value {
+                data[row][target_column] = value.clone();
+                changed_rows += 1;
+            }
+        }
+
+        if changed_rows > 0 {
+            self.set_table_data(&data)?;
+        }
+        Ok(changed_rows)
+    }
+
+    /// This function returns the value of a numeric `DecodedData` as a `f64`, or `None` if it's not numeric.
+    fn get_numeric_value(data: &DecodedData) -> Option<f64> {
+        match data {
+            DecodedData::F32(value) => Some(f64::from(*value)),
+            DecodedData::I16(value) => Some(f64::from(*value)),
+            DecodedData::I32(value) => Some(f64::from(*value)),
+            DecodedData::I64(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
     /// This function replaces the definition of this table with the one provided.
     ///
     /// This updates the table's data to follow the format marked by the new definition, so you can use it to *update* the version of your table.
@@ -142,6 +435,25 @@ impl DB {
         self.table.set_definition(new_definition);
     }
 
+    /// This function rewrites the values of all the ordering columns of this table to a clean sequence, returning the names of the renumbered columns.
+    ///
+    /// If it fails, the table is left untouched.
+    pub fn renumber_ordering_columns(&mut self) -> Result<Vec<String>> {
+        self.table.renumber_ordering_columns()
+    }
+
+    /// This function replaces the definition of this table with the one provided, only if none of its data is lost in the process.
+    ///
+    /// If it fails, the table is left untouched.
+    pub fn try_set_definition(&mut self, new_definition: &Definition) -> Result<()> {
+        self.table.try_set_definition(new_definition)
+    }
+
+    /// This function converts the provided string column between its required and optional variants, updating both its data and its definition.
+    pub fn convert_column_string_optionality(&mut self, column: usize, optional: bool) -> Result<()> {
+        self.table.convert_column_string_optionality(column, optional)
+    }
+
     /// This function replaces the data of this table with the one provided.
     ///
     /// This can (and will) fail if the data is not of the format defined by the definition of the table.
@@ -149,16 +461,231 @@ impl DB {
         self.table.set_table_data(data)
     }
 
+    /// This function returns up to `count` rows of this DB Table, starting at the row `start`.
+    pub fn get_rows_paged(&self, start: usize, count: usize) -> Vec<Vec<DecodedData>> {
+        self.table.get_rows_paged(start, count)
+    }
+
+    /// This function replaces the rows of this DB Table starting at the row `start` with the provided ones.
+    pub fn set_rows_paged(&mut self, start: usize, data: &[Vec<DecodedData>]) -> Result<()> {
+        self.table.set_rows_paged(start, data)
+    }
+
+    /// This function returns the key of the provided row, used to identify it independently of its position in the table.
+    ///
+    /// The key is made by joining the values of all the key columns of the row. If the table has no key columns, or the row doesn't exist, it returns `None`.
+    pub fn get_row_key(&self, row: usize) -> Option<String> {
+        let row_data = self.get_ref_table_data().get(row)?;
+        Self::get_key_from_row(&self.get_ref_definition().get_fields_processed(), row_data)
+    }
+
+    /// This function returns all the rows whose key columns match the provided values, with their index.
+    ///
+    /// Values must be in the same order as the key columns in the definition. If the table has no key columns,
+    /// or the amount of values doesn't match the amount of key columns, it returns nothing.
+    pub fn find_rows_by_key(&self, key_values: &[String]) -> Vec<(usize, &Vec<DecodedData>)> {
+        let key_columns = self.get_ref_definition().get_fields_processed().iter()
+            .enumerate()
+            .filter(|(_, field)| field.get_is_key())
+            .map(|(column, _)| column)
+            .collect::<Vec<usize>>();
+
+        if key_columns.is_empty() || key_columns.len() != key_values.len() { return vec![]; }
+
+        self.get_ref_table_data().iter()
+            .enumerate()
+            .filter(|(_, row)| key_columns.iter().zip(key_values.iter()).all(|(column, value)| row[*column].data_to_string() == *value))
+            .collect()
+    }
+
+    /// This function returns the comment of the provided row, if it has one.
+    ///
+    /// Comments are not stored in the table, but in the `PackFile`, so you need to pass them from there.
+    pub fn get_row_comment(&self, comments: &BTreeMap<String, String>, row: usize) -> Option<String> {
+        self.get_row_key(row).and_then(|key| comments.get(&key).cloned())
+    }
+
+    /// This function sets the comment of the provided row. An empty comment removes it.
+    ///
+    /// Comments are not stored in the table, but in the `PackFile`, so you need to pass them from there.
+    pub fn set_row_comment(&self, comments: &mut BTreeMap<String, String>, row: usize, comment: &str) -> Result<()> {
+        let key = self.get_row_key(row).ok_or_else(|| Error::from(ErrorKind::DBTableRowWithoutKey(row)))?;
+        if comment.is_empty() { comments.remove(&key); }
+        else { comments.insert(key, comment.to_owned()); }
+        Ok(())
+    }
+
+    /// This function returns the comments of all the rows of this table, in the same order as the rows.
+    pub fn get_row_comments(&self, comments: &BTreeMap<String, String>) -> Vec<Option<String>> {
+        (0..self.get_entry_count()).map(|row| self.get_row_comment(comments, row)).collect()
+    }
+
+    /// This function generates a patch with the changes needed to turn the provided base table into this one.
+    ///
+    /// Rows are matched by their key fields, so all rows of both tables need to have them. Sequence columns are ignored.
+    /// The path of the patch is left empty, as tables don't know where they are.
+    pub fn generate_patch(&self, against: &DB) -> Result<TablePatch> {
+        let rows = self.get_rows_by_key()?;
+        let base_rows = against.get_rows_by_key()?;
+
+        let mut patch = TablePatch::default();
+        for (key, row) in &rows {
+            match base_rows.get(key) {
+                Some(base_row) => {
+                    let old = base_row.iter().filter(|(field, value)| row.get(*field) != Some(value)).map(|(field, value)| (field.to_owned(), value.to_owned())).collect::<BTreeMap<String, String>>();
+                    let new = row.iter().filter(|(field, value)| base_row.get(*field) != Some(value)).map(|(field, value)| (field.to_owned(), value.to_owned())).collect::<BTreeMap<String, String>>();
+                    if !old.is_empty() || !new.is_empty() {
+                        patch.modified.push(TablePatchRow { key: key.to_owned(), old, new });
+                    }
+                }
+                None => patch.added.push(TablePatchRow { key: key.to_owned(), old: BTreeMap::new(), new: row.clone() }),
+            }
+        }
+
+        for (key, base_row) in &base_rows {
+            if !rows.contains_key(key) {
+                patch.deleted.push(TablePatchRow { key: key.to_owned(), old: base_row.clone(), new: BTreeMap::new() });
+            }
+        }
+
+        Ok(patch)
+    }
+
+    /// This function applies the provided patch to this table, returning the keys of the rows with conflicts.
+    ///
+    /// A row has a conflict if it no longer matches what the patch expects: an added row whose key already exists with other values,
+    /// or a modified/deleted row that cannot be found or whose current values are not the old values in the patch.
+    /// Rows with conflicts are skipped if `skip_conflicts` is true. Otherwise, if there are conflicts, nothing is applied.
+    pub fn apply_patch(&mut self, patch: &TablePatch, skip_conflicts: bool) -> Result<Vec<String>> {
+        let fields = self.get_ref_definition().get_fields_processed();
+        let mut entries = self.get_table_data();
+        let mut conflicts = vec![];
+
+        let find_row = |entries: &[Vec<DecodedData>], key: &str| entries.iter().position(|row| Self::get_key_from_row(&fields, row).map(|x| x == key).unwrap_or(false));
+        let matches_values = |row: &[DecodedData], values: &BTreeMap<String, String>| fields.iter().zip(row.iter())
+            .filter_map(|(field, data)| values.get(field.get_name()).map(|value| (data, value)))
+            .all(|(data, value)| data.data_to_string() == *value);
+
+        // Modifications go first, as they don't move rows around.
+        for change in &patch.modified {
+            match find_row(&entries, &change.key) {
+                Some(row) if matches_values(&entries[row], &change.old) => {
+                    if Self::set_row_values(&fields, &mut entries[row], &change.new).is_err() {
+                        conflicts.push(change.key.to_owned());
+                    }
+                }
+                _ => conflicts.push(change.key.to_owned()),
+            }
+        }
+
+        // Then deletions, from the bottom up so the indexes don't change.
+        let mut rows_to_delete = vec![];
+        for change in &patch.deleted {
+            match find_row(&entries, &change.key) {
+                Some(row) if matches_values(&entries[row], &change.old) => rows_to_delete.push(row),
+                _ => conflicts.push(change.key.to_owned()),
+            }
+        }
+        rows_to_delete.sort_unstable();
+        rows_to_delete.dedup();
+        rows_to_delete.iter().rev().for_each(|row| { entries.remove(*row); });
+
+        // And finally, additions. If the row is already there as the patch wants it, we don't consider it a conflict.
+        for change in &patch.added {
+            match find_row(&entries, &change.key) {
+                Some(row) => if !matches_values(&entries[row], &change.new) {
+                    conflicts.push(change.key.to_owned());
+                }
+                None => {
+                    let mut row = Table::get_new_row(self.get_ref_definition());
+                    match Self::set_row_values(&fields, &mut row, &change.new) {
+                        Ok(_) => entries.push(row),
+                        Err(_) => conflicts.push(change.key.to_owned()),
+                    }
+                }
+            }
+        }
+
+        if skip_conflicts || conflicts.is_empty() {
+            self.set_table_data(&entries)?;
+        }
+
+        Ok(conflicts)
+    }
+
+    /// This function returns the values of all the rows of this table, by field name, indexed by their key.
+    ///
+    /// If more than one row has the same key, only the last one is kept.
+    fn get_rows_by_key(&self) -> Result<BTreeMap<String, BTreeMap<String, String>>> {
+        let fields = self.get_ref_definition().get_fields_processed();
+        let mut rows = BTreeMap::new();
+        for (index, row) in self.get_ref_table_data().iter().enumerate() {
+            let key = Self::get_key_from_row(&fields, row).ok_or_else(|| Error::from(ErrorKind::DBTableRowWithoutKey(index)))?;
+            let values = fields.iter().zip(row.iter())
+                .filter(|(field, _)| match field.get_ref_field_type() {
+                    FieldType::SequenceU16(_) | FieldType::SequenceU32(_) => false,
+                    _ => true,
+                })
+                .map(|(field, data)| (field.get_name().to_owned(), data.data_to_string()))
+                .collect::<BTreeMap<String, String>>();
+            rows.insert(key, values);
+        }
+        Ok(rows)
+    }
+
+    /// This function returns the key of the provided row, built the same way as `DB::get_row_key`.
+    fn get_key_from_row(fields: &[Field], row: &[DecodedData]) -> Option<String> {
+        let key = fields.iter()
+            .zip(row.iter())
+            .filter(|(field, _)| field.get_is_key())
+            .map(|(_, data)| data.data_to_string())
+            .collect::<Vec<String>>();
+
+        if key.is_empty() { None } else { Some(key.join("|")) }
+    }
+
+    /// This function replaces the values of the provided fields in the provided row, converting them to the type of each field.
+    ///
+    /// Values for fields not in the row are ignored.
+    fn set_row_values(fields: &[Field], row: &mut [DecodedData], values: &BTreeMap<String, String>) -> Result<()> {
+        for (index, field) in fields.iter().enumerate() {
+            if let Some(value) = values.get(field.get_name()) {
+                row[index] = DecodedData::StringU8(value.to_owned()).convert_between_types(field.get_ref_field_type())?;
+            }
+        }
+        Ok(())
+    }
+
     /// This function creates a `DB` from a `Vec<u8>`.
+    ///
+    /// If the decoded rows don't consume exactly all the bytes of the table, the table is considered corrupt and this fails.
+    /// Use `DB::read_with_byte_consumption_check` to get the table with a warning instead.
     pub fn read(
         packed_file_data: &[u8],
         name: &str,
         schema: &Schema,
         return_incomplete: bool
     ) -> Result<Self> {
+        match Self::read_with_byte_consumption_check(packed_file_data, name, schema, return_incomplete)? {
+            (table, None) => Ok(table),
+            (_, Some(warning)) => Err(ErrorKind::PackedFileSizeIsNotWhatWeExpect(warning.data_size, warning.consumed).into()),
+        }
+    }
+
+    /// This function creates a `DB` from a `Vec<u8>`, like `DB::read`, but tolerating bytes left over after the last row.
+    ///
+    /// If the decoded rows didn't consume exactly all the bytes of the table, it returns a `TableByteConsumption` as a warning together with the table.
+    /// If not all the rows could be decoded, it fails the same way `DB::read` does.
+    pub fn read_with_byte_consumption_check(
+        packed_file_data: &[u8],
+        name: &str,
+        schema: &Schema,
+        return_incomplete: bool
+    ) -> Result<(Self, Option<TableByteConsumption>)> {
 
         // Get the header of the `DB`.
         let (version, mysterious_byte, uuid, entry_count, mut index) = Self::read_header(&packed_file_data)?;
+        let header_size = index;
 
         // Try to get the table_definition for this table, if exists.
         let versioned_file = schema.get_ref_versioned_file_db(&name);
@@ -170,10 +697,125 @@ impl DB {
         let mut table = Table::new(definition?);
         table.decode(&packed_file_data, entry_count, &mut index, return_incomplete)?;
 
-        // If we are not in the last byte, it means we didn't parse the entire file, which means this file is corrupt.
-        if index != packed_file_data.len() { return Err(ErrorKind::PackedFileSizeIsNotWhatWeExpect(packed_file_data.len(), index).into()) }
+        // If we are not in the last byte, it means we didn't parse the entire file, which means the definition is probably wrong.
+        let warning = if index != packed_file_data.len() {
+            Some(TableByteConsumption {
+                data_size: packed_file_data.len(),
+                header_size,
+                entry_count,
+                decoded_rows: entry_count,
+                consumed: index,
+            })
+        } else { None };
+
+        Ok((Self {
+            name: name.to_owned(),
+            mysterious_byte,
+            uuid,
+            table,
+        }, warning))
+    }
+
+    /// This function analyzes how many bytes of the provided DB Table data the provided definition consumes.
+    ///
+    /// Rows are decoded one by one until all the rows in the header are decoded, or one of them fails to decode.
+    pub fn analyze_byte_consumption(packed_file_data: &[u8], definition: &Definition) -> Result<TableByteConsumption> {
+        let (_, _, _, entry_count, header_size) = Self::read_header(&packed_file_data)?;
+
+        let mut table = Table::new(definition);
+        let mut consumed = header_size;
+        let mut decoded_rows = 0;
+        while decoded_rows < entry_count {
+            let mut index = consumed;
+            if table.decode(&packed_file_data, 1, &mut index, false).is_err() { break; }
+            consumed = index;
+            decoded_rows += 1;
+        }
+
+        Ok(TableByteConsumption {
+            data_size: packed_file_data.len(),
+            header_size,
+            entry_count,
+            decoded_rows,
+            consumed,
+        })
+    }
+
+    /// This function returns the loc keys the game expects for the localised fields of this table, following the provided rules.
+    ///
+    /// If there is no rule for this table, or the table has no localised fields, this returns an empty list.
+    pub fn generate_expected_loc_keys(&self, rules: &[LocKeyRule]) -> Vec<String> {
+        let rule = match LocKeyRule::get_rule_for_table(rules, &self.name) {
+            Some(rule) => rule,
+            None => return vec![],
+        };
+
+        let definition = self.get_ref_definition();
+        let fields = definition.get_fields_processed();
+        let key_columns = if rule.key_columns.is_empty() {
+            fields.iter().enumerate().filter(|(_, field)| field.get_is_key()).map(|(column, _)| column).collect::<Vec<usize>>()
+        } else {
+            rule.key_columns.iter().filter_map(|name| fields.iter().position(|field| field.get_name() == name)).collect::<Vec<usize>>()
+        };
+        if key_columns.is_empty() { return vec![]; }
+
+        let table_name = if self.name.ends_with("_tables") { &self.name[..self.name.len() - 7] } else { &self.name };
+        let mut loc_keys = self.get_ref_table_data().iter().flat_map(|row| {
+            let key = key_columns.iter().map(|column| row[*column].data_to_string()).collect::<Vec<String>>().join(&rule.key_separator);
+            definition.get_localised_fields().iter().map(move |field| format!("{}_{}_{}", table_name, field.get_name(), key))
+        }).collect::<Vec<String>>();
+
+        loc_keys.sort();
+        loc_keys.dedup();
+        loc_keys
+    }
+
+    /// This function decodes the first row of the provided table data field by field, using the provided field types.
+    ///
+    /// For each field, it returns its type, the range of bytes it uses and its decoded value, so the bytes of each field can be highlighted.
+    /// If a field fails to decode, it returns an error with the field and the byte where the field starts.
+    pub fn walk_first_row(raw_data: &[u8], fields: &[FieldType]) -> Result<Vec<(FieldType, Range<usize>, DecodedData)>> {
+        let (_, _, _, _, mut index) = Self::read_header(raw_data)?;
+
+        let mut walked_fields = Vec::with_capacity(fields.len());
+        for (column, field_type) in fields.iter().enumerate() {
+            let mut field = Field::default();
+            field.set_field_type(field_type.clone());
+            let mut definition = Definition::new(0);
+            definition.get_ref_mut_fields().push(field);
+
+            let start = index;
+            let mut table = Table::new(&definition);
+            if table.decode(raw_data, 1, &mut index, false).is_err() {
+                return Err(ErrorKind::DBTableFieldWalkError(column + 1, field_type.to_string(), start).into());
+            }
+
+            let data = table.get_ref_table_data()[0][0].clone();
+            walked_fields.push((field_type.clone(), start..index, data));
+        }
+
+        Ok(walked_fields)
+    }
+
+    /// This function creates a `DB` from raw bytes using the provided definition, ignoring the version in the table's header.
+    ///
+    /// Unlike `DB::read`, this doesn't need a `Schema`, so it can be used to test definitions against raw data. If the definition
+    /// doesn't use all the bytes of the table, it returns an error with the amount of bytes it managed to decode.
+    pub fn read_with_definition(
+        packed_file_data: &[u8],
+        name: &str,
+        definition: &Definition
+    ) -> Result<Self> {
+
+        // Get the header of the `DB`.
+        let (_, mysterious_byte, uuid, entry_count, mut index) = Self::read_header(&packed_file_data)?;
+
+        // Then try to decode all the entries.
+        let mut table = Table::new(definition);
+        table.decode(&packed_file_data, entry_count, &mut index, false)?;
+
+        if index != packed_file_data.len() { return Err(ErrorKind::DBTableNotFullyDecoded(index, packed_file_data.len()).into()) }
 
-        // If we've reached this, we've succesfully decoded the table.
         Ok(Self {
             name: name.to_owned(),
             mysterious_byte,
@@ -221,9 +863,14 @@ impl DB {
         let game_selected = GAME_SELECTED.read().unwrap().to_owned();
         if game_selected != KEY_EMPIRE && game_selected != KEY_NAPOLEON {
             packed_file.extend_from_slice(GUID_MARKER);
-            if SETTINGS.read().unwrap().settings_bool["disable_uuid_regeneration_on_db_tables"] && !self.uuid.is_empty() {
+            // On reproducible saves, the UUID must not change between saves, so we keep it, or use an empty one if we don't have it.
+            let reproducible_save = SETTINGS.read().unwrap().settings_bool["reproducible_save"];
+            if (SETTINGS.read().unwrap().settings_bool["disable_uuid_regeneration_on_db_tables"] || reproducible_save) && !self.uuid.is_empty() {
                 packed_file.encode_packedfile_string_u16(&self.uuid);
             }
+            else if reproducible_save {
+                packed_file.encode_packedfile_string_u16(&format!("{}", Uuid::nil()));
+            }
             else {
                 packed_file.encode_packedfile_string_u16(&format!("{}", Uuid::new_v4()));
             }
@@ -276,6 +923,19 @@ impl DB {
         Ok((version, mysterious_byte, uuid, entry_count, index))
     }
 
+    /// This function returns the definition used to decode the provided DB Table data, using the version in its header.
+    pub fn get_definition_from_data(
+        packed_file_data: &[u8],
+        name: &str,
+        schema: &Schema,
+    ) -> Result<Definition> {
+        let (version, _, _, _, _) = Self::read_header(&packed_file_data)?;
+        schema.get_ref_versioned_file_db(&name)
+            .and_then(|versioned_file| versioned_file.get_version(version))
+            .map(|definition| definition.clone())
+            .map_err(|_| Error::from(ErrorKind::SchemaDefinitionNotFoundForTable(name.to_owned(), version)))
+    }
+
     /// This function loads the PAK file of the game selected (if exists) into memory.
     ///
     /// This is useful to help resolving dependencies.
@@ -617,17 +1277,35 @@ impl DB {
     }
 
     /// This function imports a TSV file into a decoded table.
+    ///
+    /// If `map_columns_by_header` is true, the columns of the TSV are mapped to the fields of the table by their headers, if they have them.
     pub fn import_tsv(
         definition: &Definition,
         path: &PathBuf,
         name: &str,
+        map_columns_by_header: bool,
     ) -> Result<Self> {
-        let table = Table::import_tsv(definition, path, name)?;
+        let table = Table::import_tsv(definition, path, name, map_columns_by_header)?;
         let mut db = DB::from(table);
         db.name = name.to_owned();
         Ok(db)
     }
 
+    /// This function imports a TSV file into a decoded table, mapping its columns to the fields of the table with the provided mapping.
+    ///
+    /// Fields without a column in the mapping are filled with their default value in the schema, and their names are returned with the table.
+    pub fn import_tsv_with_mapping(
+        definition: &Definition,
+        path: &Path,
+        name: &str,
+        mapping: &BTreeMap<String, String>,
+    ) -> Result<(Self, Vec<String>)> {
+        let (table, defaulted_fields) = Table::import_tsv_with_mapping(definition, path, name, mapping)?;
+        let mut db = DB::from(table);
+        db.name = name.to_owned();
+        Ok((db, defaulted_fields))
+    }
+
     /// This function exports the provided data to a TSV file.
     pub fn export_tsv(
         &self,
@@ -637,6 +1315,133 @@ impl DB {
         self.table.export_tsv(path, table_name)
     }
 
+    /// This function returns the row with the provided index as a list of `field: value` lines, one per field.
+    ///
+    /// Useful to inspect rows with too many columns to read them in a single line.
+    pub fn export_row_vertical(&self, row: usize) -> Result<String> {
+        self.table.export_row_vertical(row)
+    }
+
+    /// This function exports only the provided rows of this table to a TSV file, in the provided order.
+    ///
+    /// The TSV has the same header as the one from `export_tsv`, so it can be imported back like any other TSV.
+    /// If any of the rows doesn't exist, nothing is exported.
+    pub fn export_tsv_rows(
+        &self,
+        path: &Path,
+        table_name: &str,
+        rows: &[usize],
+    ) -> Result<()> {
+        let data = self.get_ref_table_data();
+        let out_of_bounds = rows.iter().filter(|x| **x >= data.len()).copied().collect::<Vec<usize>>();
+        if !out_of_bounds.is_empty() {
+            return Err(ErrorKind::TableRowIndexesOutOfBounds(out_of_bounds, data.len()).into());
+        }
+
+        let entries = rows.iter().map(|x| data[*x].to_vec()).collect::<Vec<Vec<DecodedData>>>();
+        let mut table = Table::new(self.get_ref_definition());
+        table.set_table_data(&entries)?;
+        table.export_tsv(&path.to_path_buf(), table_name)
+    }
+
+    /// This function exports this table to a TSV file with the values of its reference columns resolved to the lookup values of the rows they reference.
+    ///
+    /// This is meant for documentation. Referenced tables are searched in the provided `PackFile` and in the provided `Dependencies`, decoding
+    /// them with the provided `Schema` if they're not decoded yet. If a value is in both, the one in the `PackFile` is used. Reference columns without lookup columns in the definition are not resolved. By default, the resolved
+    /// values are added in a new column after each reference column. If `replace_values` is true, they replace the referenced values instead.
+    /// References that cannot be resolved keep their raw value.
+    ///
+    /// The table name in the TSV is marked, so it cannot be imported back as a normal TSV.
+    pub fn export_tsv_resolved(
+        &self,
+        path: &Path,
+        pack_file: &PackFile,
+        dependencies: &Dependencies,
+        schema: &Schema,
+        replace_values: bool,
+    ) -> Result<()> {
+        let fields = self.get_ref_definition().get_fields_processed();
+
+        // Get the lookup values of each reference column we can resolve, from the tables of the dependencies first,
+        // and then from the tables of the PackFile, so the values of the PackFile overwrite the ones of the dependencies.
+        let mut resolved_values: BTreeMap<usize, BTreeMap<String, String>> = BTreeMap::new();
+        for (column, field) in fields.iter().enumerate() {
+            if let (Some((ref_table, ref_column)), Some(lookup_columns)) = (field.get_is_reference(), field.get_lookup()) {
+                if ref_table.is_empty() || ref_column.is_empty() || lookup_columns.is_empty() { continue; }
+
+                let ref_table_name = format!("{}_tables", ref_table);
+                let mut values = BTreeMap::new();
+                let ref_packed_files = dependencies.get_ref_dependency_database().iter()
+                    .filter(|x| x.get_path().len() == 3 && x.get_path()[0] == "db" && x.get_path()[1] == ref_table_name)
+                    .chain(pack_file.get_ref_packed_files_by_path_start(&["db".to_owned(), ref_table_name.to_owned()]))
+                    .filter_map(|packed_file| match packed_file.get_decoded_from_memory() {
+                        Ok(DecodedPackedFile::DB(table)) => Some(table.clone()),
+                        _ => packed_file.get_raw_data().and_then(|data| Self::read(&data, &ref_table_name, schema, false)).ok(),
+                    });
+
+                let ref_tables = dependencies.get_ref_fake_dependency_database().iter()
+                    .filter(|x| x.get_ref_table_name() == ref_table_name)
+                    .cloned()
+                    .chain(ref_packed_files);
+
+                for table in ref_tables {
+                    let ref_fields = table.get_ref_definition().get_fields_processed();
+                    let ref_index = match ref_fields.iter().position(|x| x.get_name() == ref_column) {
+                        Some(ref_index) => ref_index,
+                        None => continue,
+                    };
+
+                    let lookup_indexes = lookup_columns.iter().filter_map(|x| ref_fields.iter().position(|y| y.get_name() == x)).collect::<Vec<usize>>();
+                    for row in table.get_ref_table_data() {
+                        let lookup = lookup_indexes.iter().map(|x| row[*x].data_to_string()).collect::<Vec<String>>().join(" ");
+                        values.insert(row[ref_index].data_to_string(), lookup);
+                    }
+                }
+
+                resolved_values.insert(column, values);
+            }
+        }
+
+        // Same format as normal TSV files, but without a reimportable table name.
+        let mut writer = WriterBuilder::new()
+            .delimiter(b'\t')
+            .quote_style(QuoteStyle::Never)
+            .has_headers(false)
+            .flexible(true)
+            .from_writer(vec![]);
+
+        writer.serialize((format!("{}{}", self.name, RESOLVED_TSV_SUFFIX), self.get_ref_definition().get_version()))?;
+
+        let mut header = vec![];
+        for (column, field) in fields.iter().enumerate() {
+            header.push(field.get_name().to_owned());
+            if !replace_values && resolved_values.contains_key(&column) {
+                header.push(format!("{}_resolved", field.get_name()));
+            }
+        }
+        writer.serialize(header)?;
+
+        for row in self.get_ref_table_data() {
+            let mut line = vec![];
+            for (column, data) in row.iter().enumerate() {
+                let value = data.data_to_string();
+                match resolved_values.get(&column) {
+                    Some(values) => {
+                        let resolved = values.get(&value).cloned().unwrap_or_else(|| value.to_owned());
+                        if !replace_values { line.push(value); }
+                        line.push(resolved);
+                    }
+                    None => line.push(value),
+                }
+            }
+            writer.serialize(line)?;
+        }
+
+        let mut file = File::create(&path)?;
+        file.write_all(String::from_utf8(writer.into_inner().unwrap())?.as_bytes())?;
+        Ok(())
+    }
+
     /// This function imports a TSV file into a binary file on disk.
     pub fn import_tsv_to_binary_file(
         schema: &Schema,
@@ -664,6 +1469,133 @@ impl DB {
 
         Ok(())
     }
+
+    /// This function exports this table to a table with the provided name in the SQLite database at `conn_path`, creating the database if it doesn't exist.
+    ///
+    /// If the database already has a table with that name, it's replaced.
+    pub fn export_to_sqlite(&self, conn_path: &Path, table_name: &str) -> Result<()> {
+        let mut connection = Self::open_sqlite_connection(conn_path)?;
+        self.export_to_sqlite_connection(&mut connection, table_name)
+    }
+
+    /// This function opens the SQLite database at the provided path, creating it if it doesn't exist.
+    pub(crate) fn open_sqlite_connection(conn_path: &Path) -> Result<Connection> {
+        Connection::open(conn_path).map_err(|error| ErrorKind::SQLiteError(error.to_string()).into())
+    }
+
+    /// This function exports this table to a table with the provided name in the provided SQLite connection.
+    ///
+    /// Table and column names are sanitized into valid SQL identifiers. Sequence columns are not exported.
+    pub(crate) fn export_to_sqlite_connection(&self, connection: &mut Connection, table_name: &str) -> Result<()> {
+        self.write_to_sqlite_connection(connection, table_name).map_err(|error| ErrorKind::SQLiteError(error.to_string()).into())
+    }
+
+    /// This function does the actual export of `DB::export_to_sqlite_connection`, returning the errors as SQLite reports them.
+    fn write_to_sqlite_connection(&self, connection: &mut Connection, table_name: &str) -> rusqlite::Result<()> {
+        let fields = self.get_ref_definition().get_fields_processed();
+
+        // Get the columns we can export, with their SQLite type, making sure there are no duplicated names after sanitizing them.
+        let mut columns: Vec<(usize, String, &str)> = vec![];
+        for (index, field) in fields.iter().enumerate() {
+            let sql_type = match field.get_ref_field_type() {
+                FieldType::Boolean |
+                FieldType::I16 |
+                FieldType::I32 |
+                FieldType::I64 => "INTEGER",
+                FieldType::F32 => "REAL",
+                FieldType::StringU8 |
+                FieldType::StringU16 |
+                FieldType::OptionalStringU8 |
+                FieldType::OptionalStringU16 => "TEXT",
+                FieldType::SequenceU16(_) |
+                FieldType::SequenceU32(_) => continue,
+            };
+
+            let base_name = Self::get_sql_identifier(field.get_name());
+            let mut name = base_name.to_owned();
+            let mut suffix = 1;
+            while columns.iter().any(|(_, column_name, _)| column_name.eq_ignore_ascii_case(&name)) {
+                name = format!("{}_{}", base_name, suffix);
+                suffix += 1;
+            }
+
+            columns.push((index, name, sql_type));
+        }
+
+        // A table without columns is not valid in SQL, so there is nothing to export.
+        if columns.is_empty() { return Ok(()) }
+
+        let table_name = Self::get_sql_identifier(table_name);
+        let transaction = connection.transaction()?;
+        transaction.execute(&format!("DROP TABLE IF EXISTS \"{}\"", table_name), [])?;
+        transaction.execute(&format!("CREATE TABLE \"{}\" ({})", table_name, columns.iter().map(|(_, name, sql_type)| format!("\"{}\" {}", name, sql_type)).collect::<Vec<String>>().join(", ")), [])?;
+
+        {
+            let mut statement = transaction.prepare(&format!("INSERT INTO \"{}\" VALUES ({})", table_name, vec!["?"; columns.len()].join(", ")))?;
+            for row in self.get_ref_table_data() {
+                let values = columns.iter().map(|(index, _, _)| match &row[*index] {
+                    DecodedData::Boolean(data) => Value::Integer(*data as i64),
+                    DecodedData::F32(data) => Value::Real(f64::from(*data)),
+                    DecodedData::I16(data) => Value::Integer(i64::from(*data)),
+                    DecodedData::I32(data) => Value::Integer(i64::from(*data)),
+                    DecodedData::I64(data) => Value::Integer(*data),
+                    DecodedData::StringU8(data) |
+                    DecodedData::StringU16(data) |
+                    DecodedData::OptionalStringU8(data) |
+                    DecodedData::OptionalStringU16(data) => Value::Text(data.to_owned()),
+                    DecodedData::SequenceU16(_) |
+                    DecodedData::SequenceU32(_) => Value::Null,
+                });
+
+                statement.execute(params_from_iter(values))?;
+            }
+        }
+
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// This function exports this table to a GitHub-flavored Markdown table at the provided path, using the names of the fields as headers.
+    ///
+    /// If `max_rows` is provided, only that amount of rows is exported, followed by a note with the amount of rows left out.
+    pub fn export_markdown(&self, path: &Path, max_rows: Option<usize>) -> Result<()> {
+        let fields = self.get_ref_definition().get_fields_processed();
+        let rows = self.get_ref_table_data();
+        let rows_to_export = max_rows.map(|max_rows| max_rows.min(rows.len())).unwrap_or_else(|| rows.len());
+
+        let mut markdown = format!("| {} |\n", fields.iter().map(|x| Self::get_markdown_cell(x.get_name())).collect::<Vec<String>>().join(" | "));
+        markdown.push_str(&format!("|{}\n", " --- |".repeat(fields.len())));
+        for row in &rows[..rows_to_export] {
+            markdown.push_str(&format!("| {} |\n", row.iter().map(|x| Self::get_markdown_cell(&x.data_to_string())).collect::<Vec<String>>().join(" | ")));
+        }
+
+        if rows_to_export < rows.len() {
+            markdown.push_str(&format!("\n*{} more rows not shown.*\n", rows.len() - rows_to_export));
+        }
+
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(markdown.as_bytes())?;
+        Ok(())
+    }
+
+    /// This function escapes the provided text so it can be used as a cell of a Markdown table without breaking it.
+    fn get_markdown_cell(text: &str) -> String {
+        text.replace('\\', "\\\\")
+            .replace('|', "\\|")
+            .replace("\r\n", "<br>")
+            .replace('\n', "<br>")
+            .replace('\r', "<br>")
+    }
+
+    /// This function turns the provided name into a valid SQL identifier, replacing any invalid character with an underscore.
+    pub(crate) fn get_sql_identifier(name: &str) -> String {
+        let mut identifier = name.chars().map(|x| if x.is_ascii_alphanumeric() || x == '_' { x } else { '_' }).collect::<String>();
+        if identifier.is_empty() || identifier.starts_with(|x: char| x.is_ascii_digit()) {
+            identifier.insert(0, '_');
+        }
+
+        identifier
+    }
 }
 
 /// Implementation to create a `DB` from a `Table`.
@@ -704,3 +1636,83 @@ impl From<&RawTable> for DB {
         }
     }
 }
+
+//---------------------------------------------------------------------------//
+//                        Implementation of CompareOp
+//---------------------------------------------------------------------------//
+
+/// Implementation of `CompareOp`.
+impl CompareOp {
+
+    /// This function returns if the provided ordering of a cell against the compared value satisfies this comparison.
+    fn matches_ordering(self, ordering: Ordering) -> bool {
+        match self {
+            CompareOp::Equal => ordering == Ordering::Equal,
+            CompareOp::NotEqual => ordering != Ordering::Equal,
+            CompareOp::Greater => ordering == Ordering::Greater,
+            CompareOp::GreaterOrEqual => ordering != Ordering::Less,
+            CompareOp::Less => ordering == Ordering::Less,
+            CompareOp::LessOrEqual => ordering != Ordering::Greater,
+            CompareOp::Contains => false,
+        }
+    }
+}
+
+//---------------------------------------------------------------------------//
+//                  Implementation of TableByteConsumption
+//---------------------------------------------------------------------------//
+
+/// Implementation of `TableByteConsumption`.
+impl TableByteConsumption {
+
+    /// This function returns if the definition consumed exactly, less or more bytes than the table has.
+    pub fn get_result(&self) -> ByteConsumptionResult {
+        if self.decoded_rows < self.entry_count {
+            let decoded_size = self.consumed - self.header_size;
+            let estimation = if self.decoded_rows > 0 {
+                let average_row_size = decoded_size as f64 / self.decoded_rows as f64;
+                let needed = (average_row_size * (self.entry_count - self.decoded_rows) as f64).ceil() as usize;
+                Some(needed.saturating_sub(self.data_size - self.consumed))
+            } else { None };
+
+            ByteConsumptionResult::Overrun(estimation)
+        }
+        else if self.consumed < self.data_size {
+            ByteConsumptionResult::Underrun(self.data_size - self.consumed)
+        }
+        else {
+            ByteConsumptionResult::Exact
+        }
+    }
+}
+
+//---------------------------------------------------------------------------//
+//                       Implementation of TablePatch
+//---------------------------------------------------------------------------//
+
+/// Implementation of `TablePatch`.
+impl TablePatch {
+
+    /// This function returns if the patch has no changes.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.modified.is_empty() && self.deleted.is_empty()
+    }
+
+    /// This function returns the keys of all the rows changed by the patch.
+    pub fn get_keys(&self) -> Vec<String> {
+        self.added.iter().chain(self.modified.iter()).chain(self.deleted.iter()).map(|x| x.key.to_owned()).collect()
+    }
+
+    /// This function saves the provided patches to a JSON file in the provided path.
+    pub fn save_to_file(patches: &[Self], path: &Path) -> Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(to_string_pretty(patches)?.as_bytes())?;
+        Ok(())
+    }
+
+    /// This function loads the patches from the JSON file in the provided path.
+    pub fn load_from_file(path: &Path) -> Result<Vec<Self>> {
+        let file = BufReader::new(File::open(path)?);
+        from_reader(file).map_err(From::from)
+    }
+}
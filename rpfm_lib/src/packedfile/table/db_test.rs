@@ -0,0 +1,258 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2020 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing test for the operations over `DB` Tables.
+!*/
+
+use std::collections::BTreeMap;
+use std::env::temp_dir;
+use std::path::PathBuf;
+
+use rpfm_error::ErrorKind;
+
+use crate::schema::{Definition, Field, FieldType};
+use super::{DecodedData, TableDiff};
+use super::db::{CompareOp, DB};
+use super::loc::Loc;
+
+//---------------------------------------------------------------------------//
+//                              Helpers
+//---------------------------------------------------------------------------//
+
+/// This function creates a `Field` with the provided name, type and key status, and everything else empty.
+fn new_field(name: &str, field_type: FieldType, is_key: bool) -> Field {
+    Field::new(name.to_owned(), field_type, is_key, None, 0, false, None, None, None, String::new(), -1, 0, BTreeMap::new())
+}
+
+/// This function creates a `Definition` of the provided version with the provided fields. Only the first one is a key field.
+fn new_definition(version: i32, fields: &[(&str, FieldType)]) -> Definition {
+    let mut definition = Definition::new(version);
+    for (index, (name, field_type)) in fields.iter().enumerate() {
+        definition.get_ref_mut_fields().push(new_field(name, field_type.clone(), index == 0));
+    }
+    definition
+}
+
+/// This function returns the fields used by the test tables.
+fn get_test_fields() -> Vec<(&'static str, FieldType)> {
+    vec![("key", FieldType::StringU8), ("number", FieldType::I32), ("active", FieldType::Boolean), ("ratio", FieldType::F32)]
+}
+
+/// This function creates a `DB` Table with a key, a number, a boolean and a float column, and the provided rows.
+fn new_table(rows: &[(&str, i32, bool, f32)]) -> DB {
+    let definition = new_definition(1, &get_test_fields());
+    let mut table = DB::new("test_tables", None, &definition);
+    let data = rows.iter().map(|(key, number, active, ratio)| vec![
+        DecodedData::StringU8(key.to_string()),
+        DecodedData::I32(*number),
+        DecodedData::Boolean(*active),
+        DecodedData::F32(*ratio),
+    ]).collect::<Vec<Vec<DecodedData>>>();
+
+    table.set_table_data(&data).unwrap();
+    table
+}
+
+/// This function exports the provided `DB` Table with the provided name to a TSV file in the temp folder, returning its path.
+fn export_to_temp_tsv(table: &DB, table_name: &str, file_name: &str) -> PathBuf {
+    let path = temp_dir().join(format!("rpfm_db_test_{}.tsv", file_name));
+    table.export_tsv(&path, table_name).unwrap();
+    path
+}
+
+/// This function returns the `TableDiff` expected when diffing against a TSV that's not compatible with the table for the provided reason.
+fn get_incompatible_diff(reason: ErrorKind) -> TableDiff {
+    TableDiff {
+        incompatibility: Some(reason.to_string()),
+        ..Default::default()
+    }
+}
+
+//---------------------------------------------------------------------------//
+//                              CompareOp
+//---------------------------------------------------------------------------//
+
+/// Test to make sure numeric columns can be compared with any numeric value, but not with `Contains` or with strings.
+#[test]
+fn test_find_rows_numeric() {
+    let table = new_table(&[("a", 1, true, 0.5), ("b", 5, false, 1.0), ("abc", 10, true, 1.5)]);
+
+    assert_eq!(table.find_rows(1, CompareOp::Equal, &DecodedData::I32(5)).unwrap(), vec![1]);
+    assert_eq!(table.find_rows(1, CompareOp::NotEqual, &DecodedData::I32(5)).unwrap(), vec![0, 2]);
+    assert_eq!(table.find_rows(1, CompareOp::Greater, &DecodedData::I32(1)).unwrap(), vec![1, 2]);
+    assert_eq!(table.find_rows(1, CompareOp::GreaterOrEqual, &DecodedData::I64(5)).unwrap(), vec![1, 2]);
+    assert_eq!(table.find_rows(1, CompareOp::Less, &DecodedData::F32(5.5)).unwrap(), vec![0, 1]);
+    assert_eq!(table.find_rows(3, CompareOp::LessOrEqual, &DecodedData::I16(1)).unwrap(), vec![0, 1]);
+
+    assert!(table.find_rows(1, CompareOp::Contains, &DecodedData::I32(1)).is_err());
+    assert!(table.find_rows(1, CompareOp::Equal, &DecodedData::StringU8("1".to_owned())).is_err());
+}
+
+/// Test to make sure string columns only allow equality and `Contains`, and only with string values.
+#[test]
+fn test_find_rows_string() {
+    let table = new_table(&[("a", 1, true, 0.5), ("b", 5, false, 1.0), ("abc", 10, true, 1.5)]);
+
+    assert_eq!(table.find_rows(0, CompareOp::Equal, &DecodedData::StringU8("b".to_owned())).unwrap(), vec![1]);
+    assert_eq!(table.find_rows(0, CompareOp::NotEqual, &DecodedData::StringU8("b".to_owned())).unwrap(), vec![0, 2]);
+    assert_eq!(table.find_rows(0, CompareOp::Contains, &DecodedData::StringU8("a".to_owned())).unwrap(), vec![0, 2]);
+
+    // Contains is case-sensitive.
+    assert!(table.find_rows(0, CompareOp::Contains, &DecodedData::StringU8("A".to_owned())).unwrap().is_empty());
+
+    assert!(table.find_rows(0, CompareOp::Greater, &DecodedData::StringU8("a".to_owned())).is_err());
+    assert!(table.find_rows(0, CompareOp::Equal, &DecodedData::I32(1)).is_err());
+}
+
+/// Test to make sure boolean columns only allow equality, and only with boolean values.
+#[test]
+fn test_find_rows_boolean() {
+    let table = new_table(&[("a", 1, true, 0.5), ("b", 5, false, 1.0), ("abc", 10, true, 1.5)]);
+
+    assert_eq!(table.find_rows(2, CompareOp::Equal, &DecodedData::Boolean(true)).unwrap(), vec![0, 2]);
+    assert_eq!(table.find_rows(2, CompareOp::NotEqual, &DecodedData::Boolean(true)).unwrap(), vec![1]);
+
+    assert!(table.find_rows(2, CompareOp::Less, &DecodedData::Boolean(true)).is_err());
+    assert!(table.find_rows(2, CompareOp::Equal, &DecodedData::I32(1)).is_err());
+    assert!(table.find_rows(4, CompareOp::Equal, &DecodedData::Boolean(true)).is_err());
+}
+
+//---------------------------------------------------------------------------//
+//                              Set Column Where
+//---------------------------------------------------------------------------//
+
+/// Test to make sure `set_column_where` only changes the matching rows, and only reports the rows that really changed.
+#[test]
+fn test_set_column_where() {
+    let mut table = new_table(&[("a", 1, true, 0.5), ("b", 5, false, 1.0), ("abc", 10, true, 1.5)]);
+
+    assert_eq!(table.set_column_where(1, DecodedData::I32(0), 2, CompareOp::Equal, &DecodedData::Boolean(true)).unwrap(), 2);
+    assert_eq!(table.get_ref_table_data()[0][1], DecodedData::I32(0));
+    assert_eq!(table.get_ref_table_data()[1][1], DecodedData::I32(5));
+    assert_eq!(table.get_ref_table_data()[2][1], DecodedData::I32(0));
+
+    // Setting the same value again doesn't change anything.
+    assert_eq!(table.set_column_where(1, DecodedData::I32(0), 2, CompareOp::Equal, &DecodedData::Boolean(true)).unwrap(), 0);
+
+    // If the comparison is not valid, no row is changed.
+    assert!(table.set_column_where(1, DecodedData::I32(7), 0, CompareOp::Greater, &DecodedData::StringU8("a".to_owned())).is_err());
+    assert_eq!(table.get_ref_table_data()[1][1], DecodedData::I32(5));
+}
+
+//---------------------------------------------------------------------------//
+//                              TSV Diffs
+//---------------------------------------------------------------------------//
+
+/// Test to make sure rows are matched by key, so just reordering the rows of a table is not reported as a change.
+#[test]
+fn test_diff_tsv_reordered_rows() {
+    let table = new_table(&[("a", 1, true, 0.5), ("b", 5, false, 1.0), ("c", 10, true, 1.5)]);
+    let reordered = new_table(&[("c", 10, true, 1.5), ("a", 1, true, 0.5), ("b", 5, false, 1.0)]);
+    let path = export_to_temp_tsv(&reordered, "test_tables", "reordered_rows");
+
+    assert_eq!(table.diff_tsv(&path).unwrap(), TableDiff::default());
+}
+
+/// Test to make sure changed cells and added rows use the rows of the TSV, and removed rows the ones of the table.
+#[test]
+fn test_diff_tsv_added_removed_rows() {
+    let table = new_table(&[("a", 1, true, 0.5), ("b", 5, false, 1.0), ("c", 10, true, 1.5)]);
+    let edited = new_table(&[("c", 10, true, 1.5), ("a", 2, true, 0.5), ("d", 3, false, 2.0)]);
+    let path = export_to_temp_tsv(&edited, "test_tables", "added_removed_rows");
+
+    assert_eq!(table.diff_tsv(&path).unwrap(), TableDiff {
+        changed_cells: vec![(1, 1)],
+        added_rows: vec![2],
+        removed_rows: vec![1],
+        incompatibility: None,
+    });
+}
+
+/// Test to make sure TSV files that don't fit the name or the definition of the table are reported instead of diffed.
+#[test]
+fn test_diff_tsv_incompatible_schema() {
+    let table = new_table(&[("a", 1, true, 0.5)]);
+
+    let path = export_to_temp_tsv(&table, "other_tables", "wrong_table");
+    assert_eq!(table.diff_tsv(&path).unwrap(), get_incompatible_diff(ErrorKind::ImportTSVWrongTypeTable));
+
+    let newer_version = DB::new("test_tables", None, &new_definition(2, &get_test_fields()));
+    let path = export_to_temp_tsv(&newer_version, "test_tables", "wrong_version");
+    assert_eq!(table.diff_tsv(&path).unwrap(), get_incompatible_diff(ErrorKind::ImportTSVWrongVersion));
+
+    let mut fields = get_test_fields();
+    fields.push(("extra", FieldType::I32));
+    let extra_column = DB::new("test_tables", None, &new_definition(1, &fields));
+    let path = export_to_temp_tsv(&extra_column, "test_tables", "extra_column");
+    assert_eq!(table.diff_tsv(&path).unwrap(), get_incompatible_diff(ErrorKind::ImportTSVUnknownColumn("extra".to_owned())));
+
+    let mut fields = get_test_fields();
+    fields.pop();
+    let missing_column = DB::new("test_tables", None, &new_definition(1, &fields));
+    let path = export_to_temp_tsv(&missing_column, "test_tables", "missing_column");
+    assert_eq!(table.diff_tsv(&path).unwrap(), get_incompatible_diff(ErrorKind::ImportTSVMissingColumns(vec!["ratio".to_owned()])));
+}
+
+/// Test to make sure fields without a column in the mapping get the default value of the schema, or the one of their type if it's not valid.
+#[test]
+fn test_import_tsv_with_mapping_defaults() {
+    let mut definition = new_definition(1, &get_test_fields()[..1]);
+    let default_values = [("number", FieldType::I32, "7"), ("active", FieldType::Boolean, "true"), ("ratio", FieldType::F32, "not_a_number")];
+    for (name, field_type, default_value) in &default_values {
+        definition.get_ref_mut_fields().push(Field::new(name.to_string(), field_type.clone(), false, Some(default_value.to_string()), 0, false, None, None, None, String::new(), -1, 0, BTreeMap::new()));
+    }
+
+    let path = temp_dir().join("rpfm_db_test_mapping_defaults.tsv");
+    std::fs::write(&path, "Key\na\nb\n").unwrap();
+
+    let mut mapping = BTreeMap::new();
+    mapping.insert("Key".to_owned(), "key".to_owned());
+    let (table, defaulted_fields) = DB::import_tsv_with_mapping(&definition, &path, "test_tables", &mapping).unwrap();
+
+    assert_eq!(defaulted_fields, vec!["number".to_owned(), "active".to_owned(), "ratio".to_owned()]);
+    assert_eq!(table.get_ref_table_data(), &[
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::I32(7), DecodedData::Boolean(true), DecodedData::F32(0.0)],
+        vec![DecodedData::StringU8("b".to_owned()), DecodedData::I32(7), DecodedData::Boolean(true), DecodedData::F32(0.0)],
+    ]);
+}
+
+//---------------------------------------------------------------------------//
+//                              Loc Conversions
+//---------------------------------------------------------------------------//
+
+/// Test to make sure converting a Loc to a DB Table and back keeps all its data, even if the columns are in a different order in the DB Table.
+#[test]
+fn test_loc_db_loc_round_trip() {
+    let loc_definition = new_definition(1, &[("key", FieldType::StringU16), ("text", FieldType::StringU16), ("tooltip", FieldType::Boolean)]);
+    let db_definition = new_definition(1, &[("key", FieldType::StringU16), ("number", FieldType::I32), ("tooltip", FieldType::Boolean), ("text", FieldType::StringU16)]);
+
+    let mut loc = Loc::new(&loc_definition);
+    loc.set_table_data(&[
+        vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("Text 1".to_owned()), DecodedData::Boolean(true)],
+        vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16("Text 2".to_owned()), DecodedData::Boolean(false)],
+    ]).unwrap();
+
+    let table = loc.to_db("test_tables", &db_definition).unwrap();
+    assert_eq!(table.get_ref_table_data()[0], vec![
+        DecodedData::StringU16("key_1".to_owned()),
+        DecodedData::I32(0),
+        DecodedData::Boolean(true),
+        DecodedData::StringU16("Text 1".to_owned()),
+    ]);
+
+    let new_loc = table.to_loc_with_definition(&loc_definition).unwrap();
+    assert_eq!(new_loc.get_ref_table_data(), loc.get_ref_table_data());
+
+    // Tables missing any of the Loc columns cannot be converted.
+    let wrong_definition = new_definition(1, &[("key", FieldType::StringU16), ("text", FieldType::StringU8), ("tooltip", FieldType::Boolean)]);
+    assert!(loc.to_db("test_tables", &wrong_definition).is_err());
+    assert!(DB::new("test_tables", None, &wrong_definition).to_loc_with_definition(&loc_definition).is_err());
+}
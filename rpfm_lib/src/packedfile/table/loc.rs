@@ -15,13 +15,19 @@ Loc Tables are the files which contain all the localisation strings used by the
 They're just tables with a key, a text, and a boolean column.
 !*/
 
-use std::path::PathBuf;
+use serde_derive::{Serialize, Deserialize};
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
 
 use rpfm_error::{ErrorKind, Result};
 
 use crate::common::{decoder::Decoder, encoder::Encoder};
 use super::DecodedData;
-use super::Table;
+use super::db::DB;
+use super::{Table, TableDiff};
 
 use crate::schema::*;
 
@@ -52,6 +58,21 @@ pub struct Loc {
     table: Table,
 }
 
+/// This enum represents the rules we can use to decide if a row of a Loc is untranslated.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub enum UntranslatedRule {
+
+    /// The text of the row is empty.
+    EmptyText,
+
+    /// The text of the row is the same as its key.
+    TextEqualsKey,
+
+    /// The text of the row is the same as the text of the row with the same key in the source Loc, usually the English one.
+    /// Contains the texts of the source Loc, by key. Rows without a source text are considered translated.
+    TextEqualsSource(BTreeMap<String, String>),
+}
+
 //---------------------------------------------------------------------------//
 //                           Implementation of Loc
 //---------------------------------------------------------------------------//
@@ -99,6 +120,11 @@ impl Loc {
         self.table.get_entry_count()
     }
 
+    /// This function returns the differences between this Loc Table and the provided one, comparing their rows by position.
+    pub fn get_diff(&self, new_table: &Self) -> TableDiff {
+        self.table.get_diff(&new_table.table)
+    }
+
     /// This function replaces the definition of this table with the one provided.
     ///
     /// This updates the table's data to follow the format marked by the new definition, so you can use it to *update* the version of your table.
@@ -113,6 +139,16 @@ impl Loc {
         self.table.set_table_data(data)
     }
 
+    /// This function returns up to `count` rows of this Loc Table, starting at the row `start`.
+    pub fn get_rows_paged(&self, start: usize, count: usize) -> Vec<Vec<DecodedData>> {
+        self.table.get_rows_paged(start, count)
+    }
+
+    /// This function replaces the rows of this Loc Table starting at the row `start` with the provided ones.
+    pub fn set_rows_paged(&mut self, start: usize, data: &[Vec<DecodedData>]) -> Result<()> {
+        self.table.set_rows_paged(start, data)
+    }
+
     /// This function creates a new `Loc` from a `Vec<u8>`.
     pub fn read(packed_file_data: &[u8], schema: &Schema, return_incomplete: bool) -> Result<Self> {
 
@@ -139,6 +175,21 @@ impl Loc {
         })
     }
 
+    /// This function creates a new `Loc` from a `Vec<u8>` using the provided definition, ignoring the version in its header.
+    pub fn read_with_definition(packed_file_data: &[u8], definition: &Definition) -> Result<Self> {
+        let (_, entry_count) = Self::read_header(packed_file_data)?;
+
+        let mut index = HEADER_SIZE as usize;
+        let mut table = Table::new(definition);
+        table.decode(&packed_file_data, entry_count, &mut index, false)?;
+
+        if index != packed_file_data.len() { return Err(ErrorKind::LocPackedFileCorrupted.into()) }
+
+        Ok(Self {
+            table,
+        })
+    }
+
     /// This function tries to read the header of a Loc PackedFile from raw data.
     pub fn read_header(packed_file_data: &[u8]) -> Result<(i32, u32)> {
 
@@ -202,16 +253,221 @@ impl Loc {
         self.table.get_ref_table_data().is_empty()
     }
 
+    /// This function replaces the text of the rows of this Loc with the provided translations, matching them by their key.
+    ///
+    /// Rows without a translation are left untouched. It returns the keys of the rows updated, and the keys of the rows without a translation.
+    pub fn import_translations(&mut self, translations: &BTreeMap<String, String>) -> Result<(Vec<String>, Vec<String>)> {
+        let fields = self.get_ref_definition().get_fields_processed();
+        let key_column = fields.iter().position(|x| x.get_name() == "key").unwrap_or(0);
+        let text_column = fields.iter().position(|x| x.get_name() == "text").unwrap_or(1);
+
+        let mut updated_keys = vec![];
+        let mut missing_keys = vec![];
+        let mut entries = self.get_table_data();
+        for row in &mut entries {
+            let key = row[key_column].data_to_string();
+            match translations.get(&key) {
+                Some(translation) => {
+                    match row[text_column] {
+                        DecodedData::StringU8(ref mut text) |
+                        DecodedData::StringU16(ref mut text) |
+                        DecodedData::OptionalStringU8(ref mut text) |
+                        DecodedData::OptionalStringU16(ref mut text) => *text = translation.to_owned(),
+                        _ => return Err(ErrorKind::LocPackedFileCorrupted.into()),
+                    }
+                    updated_keys.push(key);
+                }
+                None => missing_keys.push(key),
+            }
+        }
+
+        if !updated_keys.is_empty() {
+            self.set_table_data(&entries)?;
+        }
+
+        Ok((updated_keys, missing_keys))
+    }
+
+    /// This function converts this `Loc` into a `DB` Table with the provided name and definition.
+    ///
+    /// The definition must have a column with the same name and type as each column of the Loc, so the conversion can be reverted
+    /// with `DB::to_loc` without losing data. Any other column of the definition gets its default value.
+    pub fn to_db(&self, table_name: &str, definition: &Definition) -> Result<DB> {
+        let column_map = Self::get_column_map(self.get_ref_definition(), definition)?;
+        let mut table = DB::new(table_name, None, definition);
+        let entries = self.get_ref_table_data().iter().map(|row| {
+            let mut new_row = table.new_row();
+            for (loc_column, db_column) in &column_map {
+                new_row[*db_column] = row[*loc_column].clone();
+            }
+            new_row
+        }).collect::<Vec<Vec<DecodedData>>>();
+
+        table.set_table_data(&entries)?;
+        Ok(table)
+    }
+
+    /// This function returns the position of each column of the provided Loc definition in the provided DB definition,
+    /// as pairs of `(loc column, db column)` sorted by Loc column.
+    ///
+    /// It fails if any of the Loc columns doesn't have a column with the same name and type in the DB definition.
+    pub(crate) fn get_column_map(loc_definition: &Definition, db_definition: &Definition) -> Result<Vec<(usize, usize)>> {
+        let db_fields = db_definition.get_fields_processed();
+        loc_definition.get_fields_processed().iter().enumerate().map(|(loc_column, loc_field)| {
+            match db_fields.iter().position(|x| x.get_name() == loc_field.get_name() && x.get_ref_field_type() == loc_field.get_ref_field_type()) {
+                Some(db_column) => Ok((loc_column, db_column)),
+                None => Err(ErrorKind::LocDBColumnMismatch(loc_field.get_name().to_owned(), loc_field.get_ref_field_type().to_string()).into()),
+            }
+        }).collect()
+    }
+
+    /// This function imports a plain-text file with one `key<delimiter>value` entry per line into a new Loc with the definition of this one.
+    ///
+    /// Blank lines and lines starting with `#` are skipped. Only the first delimiter of each line splits the key from the value,
+    /// so values containing the delimiter are kept whole. The rest of the columns get their default values.
+    /// If there are lines without a delimiter or with an empty key, it returns an error with their line numbers.
+    pub fn import_from_keyvalue(&self, path: &Path, delimiter: char) -> Result<Self> {
+        let mut contents = String::new();
+        BufReader::new(File::open(path)?).read_to_string(&mut contents)?;
+
+        let definition = self.get_ref_definition();
+        let fields = definition.get_fields_processed();
+        let key_column = fields.iter().position(|x| x.get_name() == "key").unwrap_or(0);
+        let text_column = fields.iter().position(|x| x.get_name() == "text").unwrap_or(1);
+
+        let mut entries = vec![];
+        let mut malformed_lines = vec![];
+        for (line_number, line) in contents.trim_start_matches('\u{feff}').lines().enumerate() {
+            if line.trim().is_empty() || line.trim_start().starts_with('#') { continue; }
+
+            match line.find(delimiter) {
+                Some(position) if position > 0 => {
+                    let key = &line[..position];
+                    let value = &line[position + delimiter.len_utf8()..];
+
+                    let mut row = Table::get_new_row(definition);
+                    for (column, text) in &[(key_column, key), (text_column, value)] {
+                        match row[*column] {
+                            DecodedData::StringU8(ref mut data) |
+                            DecodedData::StringU16(ref mut data) |
+                            DecodedData::OptionalStringU8(ref mut data) |
+                            DecodedData::OptionalStringU16(ref mut data) => *data = text.to_string(),
+                            _ => return Err(ErrorKind::LocPackedFileCorrupted.into()),
+                        }
+                    }
+                    entries.push(row);
+                }
+                _ => malformed_lines.push(line_number + 1),
+            }
+        }
+
+        if !malformed_lines.is_empty() {
+            return Err(ErrorKind::LocKeyValueMalformedLines(malformed_lines).into());
+        }
+
+        let mut loc = Self::new(definition);
+        loc.set_table_data(&entries)?;
+        Ok(loc)
+    }
+
+    /// This function returns the texts of this Loc, by key. Useful to build a `UntranslatedRule::TextEqualsSource` from a source Loc.
+    pub fn get_texts_by_key(&self) -> BTreeMap<String, String> {
+        let (key_column, text_column) = self.get_key_and_text_columns();
+        self.get_ref_table_data().iter().map(|row| (row[key_column].data_to_string(), row[text_column].data_to_string())).collect()
+    }
+
+    /// This function returns if the provided row is untranslated, according to the provided rule.
+    pub fn is_row_untranslated(&self, row: &[DecodedData], rule: &UntranslatedRule) -> bool {
+        let (key_column, text_column) = self.get_key_and_text_columns();
+        let key = row[key_column].data_to_string();
+        let text = row[text_column].data_to_string();
+        match rule {
+            UntranslatedRule::EmptyText => text.is_empty(),
+            UntranslatedRule::TextEqualsKey => text == key,
+            UntranslatedRule::TextEqualsSource(source) => source.get(&key).map_or(false, |source_text| *source_text == text),
+        }
+    }
+
+    /// This function exports to a TSV file only the untranslated rows of this Loc, or only the translated ones, according to the provided rule.
+    ///
+    /// The exported TSV can be imported back with `import_tsv_partial` to only update the exported rows.
+    pub fn export_tsv_filtered(&self, path: &Path, only_untranslated: bool, rule: &UntranslatedRule) -> Result<()> {
+        let entries = self.get_ref_table_data().iter()
+            .filter(|row| self.is_row_untranslated(row, rule) == only_untranslated)
+            .cloned()
+            .collect::<Vec<Vec<DecodedData>>>();
+
+        let mut table = Table::new(self.get_ref_definition());
+        table.set_table_data(&entries)?;
+        table.export_tsv(&path.to_path_buf(), TSV_NAME_LOC)
+    }
+
+    /// This function imports a TSV file containing only some of the rows of this Loc, like the ones exported with `export_tsv_filtered`,
+    /// returning a copy of this Loc with them applied.
+    ///
+    /// Rows are matched by key. Rows of the TSV replace the ones with the same key, rows with new keys are added at the end,
+    /// and rows not in the TSV are left intact.
+    pub fn import_tsv_partial(&self, path: &Path, map_columns_by_header: bool) -> Result<Self> {
+        let imported = Table::import_tsv(self.get_ref_definition(), &path.to_path_buf(), TSV_NAME_LOC, map_columns_by_header)?;
+        let (key_column, _) = self.get_key_and_text_columns();
+
+        let mut entries = self.get_table_data();
+        let mut rows_by_key = entries.iter().enumerate().map(|(index, row)| (row[key_column].data_to_string(), index)).collect::<BTreeMap<String, usize>>();
+        for row in imported.get_ref_table_data() {
+            let key = row[key_column].data_to_string();
+            match rows_by_key.get(&key) {
+                Some(index) => entries[*index] = row.to_vec(),
+                None => {
+                    rows_by_key.insert(key, entries.len());
+                    entries.push(row.to_vec());
+                }
+            }
+        }
+
+        let mut loc = self.clone();
+        loc.set_table_data(&entries)?;
+        Ok(loc)
+    }
+
+    /// This function returns the columns of the key and the text of this Loc.
+    fn get_key_and_text_columns(&self) -> (usize, usize) {
+        let fields = self.get_ref_definition().get_fields_processed();
+        let key_column = fields.iter().position(|x| x.get_name() == "key").unwrap_or(0);
+        let text_column = fields.iter().position(|x| x.get_name() == "text").unwrap_or(1);
+        (key_column, text_column)
+    }
+
     /// This function imports a TSV file into a decoded table.
+    ///
+    /// If `map_columns_by_header` is true, the columns of the TSV are mapped to the fields of the table by their headers, if they have them.
     pub fn import_tsv(
         definition: &Definition,
         path: &PathBuf,
         name: &str,
+        map_columns_by_header: bool,
     ) -> Result<Self> {
-        let table = Table::import_tsv(definition, path, name)?;
+        let table = Table::import_tsv(definition, path, name, map_columns_by_header)?;
         Ok(Loc::from(table))
     }
 
+    /// This function imports a TSV file into a decoded table, mapping its columns to the fields of the table with the provided mapping.
+    ///
+    /// Fields without a column in the mapping are filled with their default value in the schema, and their names are returned with the table.
+    pub fn import_tsv_with_mapping(
+        definition: &Definition,
+        path: &Path,
+        name: &str,
+        mapping: &BTreeMap<String, String>,
+    ) -> Result<(Self, Vec<String>)> {
+        let (table, defaulted_fields) = Table::import_tsv_with_mapping(definition, path, name, mapping)?;
+        Ok((Loc::from(table), defaulted_fields))
+    }
+
+    /// This function returns the row with the provided index as a list of `field: value` lines, one per field.
+    pub fn export_row_vertical(&self, row: usize) -> Result<String> {
+        self.table.export_row_vertical(row)
+    }
+
     /// This function exports the provided data to a TSV file.
     pub fn export_tsv(
         &self,
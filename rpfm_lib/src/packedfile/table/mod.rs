@@ -18,11 +18,12 @@ use bincode::serialize;
 use csv::{QuoteStyle, ReaderBuilder, WriterBuilder};
 use serde_derive::{Serialize, Deserialize};
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::convert::TryFrom;
 use std::{fmt, fmt::Display};
 use std::fs::File;
 use std::io::{BufReader, BufWriter, Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use rpfm_error::{Error, ErrorKind, Result};
 
@@ -36,6 +37,10 @@ pub mod db;
 pub mod loc;
 pub mod matched_combat;
 
+// This tells the compiler to only compile this mod when testing. It's just to make sure the table operations don't break between updates.
+#[cfg(test)]
+mod db_test;
+
 //---------------------------------------------------------------------------//
 //                              Enum & Structs
 //---------------------------------------------------------------------------//
@@ -72,6 +77,25 @@ pub enum DecodedData {
     SequenceU32(Table)
 }
 
+/// This struct contains the differences between two versions of the same table.
+///
+/// Depending on the function used to get it, rows are compared by position or by their key columns.
+#[derive(PartialEq, Clone, Default, Debug, Serialize, Deserialize)]
+pub struct TableDiff {
+
+    /// Cells with different values in rows present in both tables, as `(row, column)`.
+    pub changed_cells: Vec<(usize, usize)>,
+
+    /// Rows only present in the new table.
+    pub added_rows: Vec<usize>,
+
+    /// Rows only present in the old table.
+    pub removed_rows: Vec<usize>,
+
+    /// If the new table is not compatible with the old one, the reason. In that case, the tables are not compared.
+    pub incompatibility: Option<String>,
+}
+
 /// This holds the dependency data for a specific column of a table.
 #[derive(PartialEq, Clone, Default, Debug, Serialize, Deserialize)]
 pub struct DependencyData {
@@ -273,6 +297,19 @@ impl DecodedData {
         }
     }
 
+    /// This function converts a string to its optional variant, or an optional string to its non-optional variant, keeping its value.
+    ///
+    /// If the data is not a string, it returns `None`.
+    pub fn convert_string_optionality(&self, optional: bool) -> Option<Self> {
+        match self {
+            Self::StringU8(ref data) |
+            Self::OptionalStringU8(ref data) => if optional { Some(Self::OptionalStringU8(data.to_owned())) } else { Some(Self::StringU8(data.to_owned())) },
+            Self::StringU16(ref data) |
+            Self::OptionalStringU16(ref data) => if optional { Some(Self::OptionalStringU16(data.to_owned())) } else { Some(Self::StringU16(data.to_owned())) },
+            _ => None,
+        }
+    }
+
     /// This function prints whatever you have in each variants to a String.
     pub fn data_to_string(&self) -> String {
         match self {
@@ -354,6 +391,7 @@ impl Table {
         positions.sort_by_key(|x| x.1);
 
         // Then, we create the new data using the old one and the column changes.
+        let new_fields_processed = new_definition.get_fields_processed();
         let mut new_entries: Vec<Vec<DecodedData>> = vec![];
         for row in &mut self.entries {
             let mut entry = vec![];
@@ -364,12 +402,27 @@ impl Table {
 
                 // If the old position is -1, it means we got a new column. We need to get his type and create a `Default` field with it.
                 else if *old_pos == -1 {
-                    entry.push(DecodedData::default(&new_definition.get_fields_processed()[*new_pos as usize].get_ref_field_type()));
+                    entry.push(DecodedData::default(&new_fields_processed[*new_pos as usize].get_ref_field_type()));
                 }
 
                 // Otherwise, we got a moved column. Grab his field from the old data and put it in his new place.
+                // If the column went from required to optional string (or vice versa), convert the data to match.
                 else {
-                    entry.push(row[*old_pos as usize].clone());
+                    let data = &row[*old_pos as usize];
+                    let new_field_type = new_fields_processed[*new_pos as usize].get_ref_field_type();
+                    let optional = match new_field_type {
+                        FieldType::OptionalStringU8 | FieldType::OptionalStringU16 => Some(true),
+                        FieldType::StringU8 | FieldType::StringU16 => Some(false),
+                        _ => None,
+                    };
+
+                    match optional {
+                        Some(optional) if !data.is_field_type_correct(new_field_type) => match data.convert_string_optionality(optional) {
+                            Some(data) => entry.push(data),
+                            None => entry.push(data.clone()),
+                        },
+                        _ => entry.push(data.clone()),
+                    }
                 }
             }
             new_entries.push(entry);
@@ -380,10 +433,123 @@ impl Table {
         self.entries = new_entries;
     }
 
+    /// This function rewrites the values of all the ordering columns of this table to a clean sequence, returning the names of the renumbered columns.
+    ///
+    /// The sequence starts at the lowest current value of each column, and keeps the current order of the values. Rows with the same value keep their relative order.
+    /// Only integer columns are touched. If the sequence doesn't fit in the type of a column, this fails and the table is left untouched.
+    pub fn renumber_ordering_columns(&mut self) -> Result<Vec<String>> {
+        let mut entries = self.entries.clone();
+        let mut renumbered = vec![];
+        for (column, field) in self.definition.get_fields_processed().iter().enumerate() {
+            if !field.get_is_ordering() || !field.is_integer() { continue; }
+
+            let mut values = entries.iter().enumerate().filter_map(|(row, data)| match data.get(column) {
+                Some(DecodedData::I16(value)) => Some((i64::from(*value), row)),
+                Some(DecodedData::I32(value)) => Some((i64::from(*value), row)),
+                Some(DecodedData::I64(value)) => Some((*value, row)),
+                _ => None,
+            }).collect::<Vec<(i64, usize)>>();
+            values.sort();
+
+            let start = values.first().map_or(0, |(value, _)| *value);
+            for (index, (_, row)) in values.iter().enumerate() {
+                let value = start + index as i64;
+                let overflow = || Error::from(ErrorKind::OrderingColumnOverflow(field.get_name().to_owned()));
+                entries[*row][column] = match field.get_ref_field_type() {
+                    FieldType::I16 => DecodedData::I16(i16::try_from(value).map_err(|_| overflow())?),
+                    FieldType::I32 => DecodedData::I32(i32::try_from(value).map_err(|_| overflow())?),
+                    _ => DecodedData::I64(value),
+                };
+            }
+
+            renumbered.push(field.get_name().to_owned());
+        }
+
+        self.entries = entries;
+        Ok(renumbered)
+    }
+
+    /// This function replaces the definition of this table with the one provided, like `set_definition`, but only if no data is lost in the process.
+    ///
+    /// Columns that changed their type are converted to the new one. If any value cannot be converted, this fails and the table is left untouched.
+    /// Columns removed in the new definition are not considered lost data, as that's what updating a table is about.
+    pub fn try_set_definition(&mut self, new_definition: &Definition) -> Result<()> {
+        let mut table = self.clone();
+        table.set_definition(new_definition);
+
+        let fields = new_definition.get_fields_processed();
+        for row in &mut table.entries {
+            for (field, cell) in fields.iter().zip(row.iter_mut()) {
+                if !cell.is_field_type_correct(field.get_ref_field_type()) {
+                    *cell = cell.convert_between_types(field.get_ref_field_type())
+                        .map_err(|_| Error::from(ErrorKind::TableMigrationUnsafe(field.get_name().to_owned())))?;
+                }
+            }
+        }
+
+        *self = table;
+        Ok(())
+    }
+
+    /// This function converts the provided string column between its required and optional variants,
+    /// updating both the data of each row and the type of the field in the table's definition.
+    ///
+    /// The value of each cell is kept as is, so converting a column with empty values is lossless.
+    pub fn convert_column_string_optionality(&mut self, column: usize, optional: bool) -> Result<()> {
+        let field = self.definition.get_fields_processed().get(column).cloned().ok_or_else(|| Error::from(ErrorKind::SchemaFieldNotFound(column)))?;
+        let new_field_type = match field.get_ref_field_type() {
+            FieldType::StringU8 | FieldType::OptionalStringU8 => if optional { FieldType::OptionalStringU8 } else { FieldType::StringU8 },
+            FieldType::StringU16 | FieldType::OptionalStringU16 => if optional { FieldType::OptionalStringU16 } else { FieldType::StringU16 },
+            _ => return Err(ErrorKind::SchemaFieldIsNotAString(field.get_name().to_owned()).into()),
+        };
+
+        // String fields are never split when processing the definition, so we can find the original one by name.
+        if let Some(field) = self.definition.get_ref_mut_fields().iter_mut().find(|x| x.get_name() == field.get_name()) {
+            field.set_field_type(new_field_type);
+        }
+
+        for row in &mut self.entries {
+            if let Some(data) = row[column].convert_string_optionality(optional) {
+                row[column] = data;
+            }
+        }
+
+        Ok(())
+    }
+
     /// This function replaces the data of this table with the one provided.
     ///
     /// This can (and will) fail if the data is not of the format defined by the definition of the table.
     pub fn set_table_data(&mut self, data: &[Vec<DecodedData>]) -> Result<()> {
+        self.check_rows(data)?;
+
+        // If we passed all the checks, replace the data.
+        self.entries = data.to_vec();
+        Ok(())
+    }
+
+    /// This function returns up to `count` rows of this table, starting at the row `start`.
+    ///
+    /// The range is clamped to the amount of rows of the table, so out-of-range requests return a partial or empty result.
+    pub fn get_rows_paged(&self, start: usize, count: usize) -> Vec<Vec<DecodedData>> {
+        self.entries.iter().skip(start).take(count).cloned().collect()
+    }
+
+    /// This function replaces the rows of this table starting at the row `start` with the provided ones.
+    ///
+    /// This fails if any of the rows doesn't exist in the table, or if the data is not of the format defined by the definition of the table.
+    pub fn set_rows_paged(&mut self, start: usize, data: &[Vec<DecodedData>]) -> Result<()> {
+        if start + data.len() > self.entries.len() {
+            return Err(ErrorKind::TableRowsOutOfBounds(start, data.len(), self.entries.len()).into())
+        }
+
+        self.check_rows(data)?;
+        self.entries.splice(start..start + data.len(), data.iter().cloned());
+        Ok(())
+    }
+
+    /// This function checks that the provided rows follow the format defined by the definition of this table.
+    fn check_rows(&self, data: &[Vec<DecodedData>]) -> Result<()> {
         for row in data {
 
             // First, we need to make sure all rows we have are exactly what we expect.
@@ -400,8 +566,6 @@ impl Table {
             }
         }
 
-        // If we passed all the checks, replace the data.
-        self.entries = data.to_vec();
         Ok(())
     }
 
@@ -739,15 +903,97 @@ impl Table {
 
     }
 
+    /// This function returns the differences between this table and the provided one, comparing their rows by position.
+    pub fn get_diff(&self, new_table: &Self) -> TableDiff {
+        let changed_cells = self.entries.iter()
+            .zip(new_table.entries.iter())
+            .enumerate()
+            .flat_map(|(row, (old_entry, new_entry))| (0..old_entry.len().max(new_entry.len()))
+                .filter(|column| old_entry.get(*column) != new_entry.get(*column))
+                .map(|column| (row, column))
+                .collect::<Vec<(usize, usize)>>()
+            )
+            .collect();
+
+        TableDiff {
+            changed_cells,
+            added_rows: (self.entries.len()..new_table.entries.len()).collect(),
+            removed_rows: (new_table.entries.len()..self.entries.len()).collect(),
+            incompatibility: None,
+        }
+    }
+
+    /// This function returns the differences between this table and the provided one, matching their rows by the key columns of the definition.
+    ///
+    /// Changed cells and added rows use the row indexes of the new table, and removed rows the ones of this table. If the definition has no key columns,
+    /// or the tables have different definitions, rows are compared by position.
+    pub fn get_diff_by_keys(&self, new_table: &Self) -> TableDiff {
+        let fields = self.definition.get_fields_processed();
+        let key_columns = fields.iter().enumerate().filter(|(_, field)| field.get_is_key()).map(|(column, _)| column).collect::<Vec<usize>>();
+        if key_columns.is_empty() || self.definition != new_table.definition {
+            return self.get_diff(new_table);
+        }
+
+        let get_key = |row: &[DecodedData]| key_columns.iter().map(|column| row[*column].data_to_string()).collect::<Vec<String>>();
+
+        // Duplicated keys are matched in order.
+        let mut old_rows: HashMap<Vec<String>, VecDeque<usize>> = HashMap::new();
+        for (row, entry) in self.entries.iter().enumerate() {
+            old_rows.entry(get_key(entry)).or_default().push_back(row);
+        }
+
+        let mut diff = TableDiff::default();
+        for (row, new_entry) in new_table.entries.iter().enumerate() {
+            match old_rows.get_mut(&get_key(new_entry)).and_then(|rows| rows.pop_front()) {
+                Some(old_row) => {
+                    let old_entry = &self.entries[old_row];
+                    diff.changed_cells.extend((0..old_entry.len().max(new_entry.len()))
+                        .filter(|column| old_entry.get(*column) != new_entry.get(*column))
+                        .map(|column| (row, column))
+                    );
+                }
+                None => diff.added_rows.push(row),
+            }
+        }
+
+        diff.removed_rows = old_rows.values().flatten().copied().collect();
+        diff.removed_rows.sort_unstable();
+        diff
+    }
+
+    /// This function returns the cells of non-editable columns that have different values in the provided table, as `(row, column)`.
+    ///
+    /// Rows are matched by their key columns like in `get_diff_by_keys`, so only rows present in both tables are checked, and the rows
+    /// returned are the ones of the provided table. If the definitions have different versions, nothing is checked.
+    pub fn get_non_editable_changes(&self, new_table: &Self) -> Vec<(usize, usize)> {
+        if self.definition.get_version() != new_table.definition.get_version() { return vec![]; }
+
+        let non_editable_columns = self.definition.get_fields_processed().iter()
+            .enumerate()
+            .filter(|(_, field)| !field.get_is_editable())
+            .map(|(column, _)| column)
+            .collect::<Vec<usize>>();
+
+        if non_editable_columns.is_empty() { return vec![]; }
+
+        self.get_diff_by_keys(new_table).changed_cells.into_iter()
+            .filter(|(_, column)| non_editable_columns.contains(column))
+            .collect()
+    }
+
     //----------------------------------------------------------------//
     // TSV Functions for PackedFiles.
     //----------------------------------------------------------------//
 
     /// This function imports a TSV file into a decoded table.
+    ///
+    /// If `map_columns_by_header` is true and the column headers of the TSV match the names of the fields of the definition,
+    /// the columns are mapped to the fields by name instead of by position.
     fn import_tsv(
         definition: &Definition,
         path: &PathBuf,
         name: &str,
+        map_columns_by_header: bool,
     ) -> Result<Self> {
 
         // We want the reader to have no quotes, tab as delimiter and custom headers, because otherwise
@@ -761,6 +1007,8 @@ impl Table {
 
         // If we succesfully load the TSV file into a reader, check the first two lines to ensure
         // it's a valid TSV for our specific table.
+        let fields = definition.get_fields_processed();
+        let mut column_mapping = (0..fields.len()).collect::<Vec<usize>>();
         let mut entries = vec![];
         for (row, record) in reader.records().enumerate() {
             if let Ok(record) = record {
@@ -774,33 +1022,19 @@ impl Table {
                     }
                 }
 
-                // The second line contains the column headers. Is just to help people in other programs, so we skip it.
-                else if row == 1 { continue }
+                // The second line contains the column headers. Unless we want to map the columns by them, is just to help people in other programs, so we skip it.
+                else if row == 1 {
+                    if map_columns_by_header {
+                        column_mapping = Self::get_tsv_column_mapping(&fields, &record.iter().collect::<Vec<&str>>())?;
+                    }
+                }
 
                 // Then read the rest of the rows as a normal TSV.
-                else if record.len() == definition.get_fields_processed().len() {
+                else if record.len() == fields.len() {
                     let mut entry = vec![];
-                    for (column, field) in record.iter().enumerate() {
-                        match definition.get_fields_processed()[column].get_ref_field_type() {
-                            FieldType::Boolean => {
-                                let value = field.to_lowercase();
-                                if value == "true" || value == "1" { entry.push(DecodedData::Boolean(true)); }
-                                else if value == "false" || value == "0" { entry.push(DecodedData::Boolean(false)); }
-                                else { return Err(ErrorKind::ImportTSVIncorrectRow(row, column).into()); }
-                            }
-                            FieldType::F32 => entry.push(DecodedData::F32(field.parse::<f32>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?)),
-                            FieldType::I16 => entry.push(DecodedData::I16(field.parse::<i16>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?)),
-                            FieldType::I32 => entry.push(DecodedData::I32(field.parse::<i32>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?)),
-                            FieldType::I64 => entry.push(DecodedData::I64(field.parse::<i64>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?)),
-                            FieldType::StringU8 => entry.push(DecodedData::StringU8(field.to_owned())),
-                            FieldType::StringU16 => entry.push(DecodedData::StringU16(field.to_owned())),
-                            FieldType::OptionalStringU8 => entry.push(DecodedData::OptionalStringU8(field.to_owned())),
-                            FieldType::OptionalStringU16 => entry.push(DecodedData::OptionalStringU16(field.to_owned())),
-
-                            // For now fail on Sequences. These are a bit special and I don't know if the're even possible in TSV.
-                            FieldType::SequenceU16(_) => return Err(ErrorKind::ImportTSVIncorrectRow(row, column).into()),
-                            FieldType::SequenceU32(_) => return Err(ErrorKind::ImportTSVIncorrectRow(row, column).into())
-                        }
+                    for (field_index, field_definition) in fields.iter().enumerate() {
+                        let column = column_mapping[field_index];
+                        entry.push(Self::parse_tsv_field(field_definition.get_ref_field_type(), &record[column], row, column)?);
                     }
                     entries.push(entry);
                 }
@@ -817,6 +1051,140 @@ impl Table {
         Ok(table)
     }
 
+    /// This function imports a TSV file into a decoded table, mapping its columns to the fields of the definition with the provided mapping.
+    ///
+    /// The mapping goes from the headers of the TSV to the names of the fields. Columns not in the mapping are ignored, and fields
+    /// without a column are filled with the default value of the field in the schema, or the default for their type if it has none or it's not valid.
+    /// The names of these fields are returned with the table.
+    /// As TSV files from other programs don't have our first line with the name and version of the table, it's optional here.
+    fn import_tsv_with_mapping(
+        definition: &Definition,
+        path: &Path,
+        name: &str,
+        mapping: &BTreeMap<String, String>,
+    ) -> Result<(Self, Vec<String>)> {
+
+        // Check the mapping before reading anything, so we don't fail halfway.
+        let fields = definition.get_fields_processed();
+        if let Some((header, field)) = mapping.iter().find(|(_, field)| !fields.iter().any(|x| x.get_name() == *field)) {
+            return Err(ErrorKind::ImportTSVMappingUnknownField(header.to_owned(), field.to_owned()).into());
+        }
+
+        let mut reader = ReaderBuilder::new()
+            .delimiter(b'\t')
+            .quoting(false)
+            .has_headers(false)
+            .flexible(true)
+            .from_path(&path)?;
+
+        let mut records = reader.records().enumerate().peekable();
+        let has_info_line = match records.peek() {
+            Some((_, Ok(record))) if record.get(0) == Some(name) => {
+                if record.get(1).unwrap_or("-1").parse::<i32>().map_err(|_| Error::from(ErrorKind::ImportTSVInvalidVersion))? != definition.get_version() {
+                    return Err(ErrorKind::ImportTSVWrongVersion.into());
+                }
+                true
+            }
+            _ => false,
+        };
+
+        if has_info_line { records.next(); }
+
+        // The next line contains the headers of the columns, which we use to know which column goes to each field.
+        let headers = match records.next() {
+            Some((_, Ok(record))) => record.iter().map(|x| x.to_owned()).collect::<Vec<String>>(),
+            Some((row, Err(_))) => return Err(ErrorKind::ImportTSVIncorrectRow(row, 0).into()),
+            None => vec![],
+        };
+
+        let column_mapping = fields.iter()
+            .map(|field| headers.iter().position(|header| mapping.get(header).map_or(false, |x| x == field.get_name())))
+            .collect::<Vec<Option<usize>>>();
+
+        let defaulted_fields = fields.iter().zip(column_mapping.iter())
+            .filter(|(_, column)| column.is_none())
+            .map(|(field, _)| field.get_name().to_owned())
+            .collect::<Vec<String>>();
+
+        // Default values in the schema are stored as text, so we parse them the same way as the values of the TSV.
+        let default_row = fields.iter()
+            .map(|field| field.get_default_value().as_ref()
+                .and_then(|value| Self::parse_tsv_field(field.get_ref_field_type(), value, 0, 0).ok())
+                .unwrap_or_else(|| DecodedData::default(field.get_ref_field_type())))
+            .collect::<Vec<DecodedData>>();
+
+        let mut entries = vec![];
+        for (row, record) in records {
+            let record = record.map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, 0)))?;
+            if record.len() != headers.len() { return Err(ErrorKind::ImportTSVIncorrectRow(row, record.len()).into()); }
+
+            let mut entry = vec![];
+            for (index, (field, column)) in fields.iter().zip(column_mapping.iter()).enumerate() {
+                match column {
+                    Some(column) => entry.push(Self::parse_tsv_field(field.get_ref_field_type(), &record[*column], row, *column)?),
+                    None => entry.push(default_row[index].clone()),
+                }
+            }
+            entries.push(entry);
+        }
+
+        let mut table = Table::new(definition);
+        table.entries = entries;
+        Ok((table, defaulted_fields))
+    }
+
+    /// This function parses the value of a field of a TSV file, according to the provided type.
+    ///
+    /// The row and column are only used to report where the error was if the value is not valid.
+    fn parse_tsv_field(field_type: &FieldType, field: &str, row: usize, column: usize) -> Result<DecodedData> {
+        match field_type {
+            FieldType::Boolean => {
+                let value = field.to_lowercase();
+                if value == "true" || value == "1" { Ok(DecodedData::Boolean(true)) }
+                else if value == "false" || value == "0" { Ok(DecodedData::Boolean(false)) }
+                else { Err(ErrorKind::ImportTSVIncorrectRow(row, column).into()) }
+            }
+            FieldType::F32 => Ok(DecodedData::F32(field.parse::<f32>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?)),
+            FieldType::I16 => Ok(DecodedData::I16(field.parse::<i16>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?)),
+            FieldType::I32 => Ok(DecodedData::I32(field.parse::<i32>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?)),
+            FieldType::I64 => Ok(DecodedData::I64(field.parse::<i64>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?)),
+            FieldType::StringU8 => Ok(DecodedData::StringU8(field.to_owned())),
+            FieldType::StringU16 => Ok(DecodedData::StringU16(field.to_owned())),
+            FieldType::OptionalStringU8 => Ok(DecodedData::OptionalStringU8(field.to_owned())),
+            FieldType::OptionalStringU16 => Ok(DecodedData::OptionalStringU16(field.to_owned())),
+
+            // For now fail on Sequences. These are a bit special and I don't know if the're even possible in TSV.
+            FieldType::SequenceU16(_) => Err(ErrorKind::ImportTSVIncorrectRow(row, column).into()),
+            FieldType::SequenceU32(_) => Err(ErrorKind::ImportTSVIncorrectRow(row, column).into())
+        }
+    }
+
+    /// This function returns, for each field of the provided list, the column of the TSV containing it, using the TSV's column headers.
+    ///
+    /// If none of the headers match a field, we assume there are no recognizable headers and the columns are returned in positional order.
+    /// Otherwise, unknown or duplicated headers and missing fields are reported as errors.
+    fn get_tsv_column_mapping(fields: &[Field], headers: &[&str]) -> Result<Vec<usize>> {
+        if !headers.iter().any(|header| fields.iter().any(|field| field.get_name() == *header)) {
+            return Ok((0..fields.len()).collect());
+        }
+
+        let mut column_mapping = vec![None; fields.len()];
+        for (column, header) in headers.iter().enumerate() {
+            match fields.iter().position(|field| field.get_name() == *header) {
+                Some(field_index) if column_mapping[field_index].is_none() => column_mapping[field_index] = Some(column),
+                _ => return Err(ErrorKind::ImportTSVUnknownColumn(header.to_string()).into()),
+            }
+        }
+
+        let missing_fields = fields.iter().zip(column_mapping.iter())
+            .filter(|(_, column)| column.is_none())
+            .map(|(field, _)| field.get_name().to_owned())
+            .collect::<Vec<String>>();
+
+        if missing_fields.is_empty() { Ok(column_mapping.iter().flatten().copied().collect()) }
+        else { Err(ErrorKind::ImportTSVMissingColumns(missing_fields).into()) }
+    }
+
     /// This function imports a TSV file into a new Table File.
     fn import_tsv_to_binary_file(
         schema: &Schema,
@@ -937,6 +1305,51 @@ impl Table {
         Ok(())
     }
 
+    /// This function returns the provided row as a list of `field: value` lines, one per field, to make wide rows easier to read.
+    ///
+    /// Reference fields have the table and column they reference after their value, and sequences have their rows listed below them, indented.
+    fn export_row_vertical(&self, row: usize) -> Result<String> {
+        let data = self.entries.get(row).ok_or_else(|| Error::from(ErrorKind::TableRowIndexesOutOfBounds(vec![row], self.entries.len())))?;
+        let mut output = String::new();
+        Self::write_row_vertical(&self.definition.get_fields_processed(), data, 0, &mut output);
+        Ok(output)
+    }
+
+    /// This function writes the provided row as a list of `field: value` lines to the provided string, with the provided indentation level.
+    fn write_row_vertical(fields: &[Field], row: &[DecodedData], indent: usize, output: &mut String) {
+        let padding = "    ".repeat(indent);
+        let name_width = fields.iter().map(|x| x.get_name().chars().count()).max().unwrap_or(0);
+        for (field, data) in fields.iter().zip(row.iter()) {
+            let value = match data {
+                DecodedData::Boolean(data) => data.to_string(),
+                DecodedData::F32(data) => data.to_string(),
+                DecodedData::I16(data) => data.to_string(),
+                DecodedData::I32(data) => data.to_string(),
+                DecodedData::I64(data) => data.to_string(),
+                DecodedData::StringU8(data) |
+                DecodedData::StringU16(data) |
+                DecodedData::OptionalStringU8(data) |
+                DecodedData::OptionalStringU16(data) => format!("\"{}\"", data),
+                DecodedData::SequenceU16(table) |
+                DecodedData::SequenceU32(table) => format!("{} rows", table.entries.len()),
+            };
+
+            output.push_str(&format!("{}{:<width$} ({}): {}", padding, field.get_name(), field.get_ref_field_type(), value, width = name_width));
+            if let Some((ref_table, ref_column)) = field.get_is_reference() {
+                output.push_str(&format!(" -> {}.{}", ref_table, ref_column));
+            }
+            output.push('\n');
+
+            if let DecodedData::SequenceU16(table) | DecodedData::SequenceU32(table) = data {
+                let sub_fields = table.definition.get_fields_processed();
+                for (index, sub_row) in table.entries.iter().enumerate() {
+                    output.push_str(&format!("{}    [{}]\n", padding, index + 1));
+                    Self::write_row_vertical(&sub_fields, sub_row, indent + 2, output);
+                }
+            }
+        }
+    }
+
     /// This function exports the provided file to a TSV file..
     fn export_tsv_from_binary_file(
         schema: &Schema,
@@ -45,8 +45,8 @@ pub const EXTENSIONS: [(&str, TextType); 23] = [
     (".benchmark", TextType::Xml),
     (".cindyscene", TextType::Xml),
     (".cindyscenemanager", TextType::Xml),
-    (".csv", TextType::Plain),
-    (".tsv", TextType::Plain),
+    (".csv", TextType::Csv),
+    (".tsv", TextType::Csv),
     (".tai", TextType::Plain),
     (".battle_speech_camera", TextType::Plain),
     (".bob", TextType::Plain),
@@ -96,6 +96,7 @@ pub enum TextType {
     Cpp,
     Markdown,
     Json,
+    Csv,
     Plain,
 }
 
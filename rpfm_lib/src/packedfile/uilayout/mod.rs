@@ -0,0 +1,310 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2020 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module with all the code to interact with UI Layout PackedFiles.
+
+This covers the text-based `.layout` and `.atlas` files, which are XML-like trees of UI components. We parse the
+hierarchy of elements and their attributes, remembering where each attribute value is in the original text.
+When saving, only the values are replaced over the original text, so everything we don't understand (comments,
+text nodes, formatting,...) is saved back exactly as it was. Binary layouts are not supported.
+!*/
+
+use serde_derive::{Serialize, Deserialize};
+
+use rpfm_error::{ErrorKind, Result};
+
+use crate::packedfile::text::{Text, TextType};
+
+/// Extensions used by UI Layout PackedFiles.
+pub const EXTENSIONS: [&str; 2] = [".layout", ".atlas"];
+
+/// Start of binary layouts. We cannot decode these.
+const BINARY_LAYOUT_PREAMBLE: &str = "Version";
+
+//---------------------------------------------------------------------------//
+//                              Enum & Structs
+//---------------------------------------------------------------------------//
+
+/// This holds an entire UI Layout PackedFile decoded in memory.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct UiLayout {
+
+    /// The original text of the PackedFile. Attribute values are replaced over it when saving, so nothing else gets lost.
+    text: Text,
+
+    /// The root elements of the PackedFile, in the order they appear.
+    nodes: Vec<UiLayoutNode>,
+}
+
+/// This holds an element of a UI Layout, with its attributes and children.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct UiLayoutNode {
+
+    /// Name of the element.
+    tag: String,
+
+    /// Attributes of the element, in the order they appear.
+    attributes: Vec<UiLayoutAttribute>,
+
+    /// Child elements of the element, in the order they appear.
+    children: Vec<UiLayoutNode>,
+}
+
+/// This holds an attribute of an element of a UI Layout.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct UiLayoutAttribute {
+
+    /// Name of the attribute.
+    name: String,
+
+    /// Value of the attribute, as it is in the file, without unescaping XML entities.
+    value: String,
+
+    /// Quote character surrounding the value.
+    quote: char,
+
+    /// Position of the first byte of the original value in the original text.
+    start: usize,
+
+    /// Position after the last byte of the original value in the original text.
+    end: usize,
+}
+
+//---------------------------------------------------------------------------//
+//                           Implementation of UiLayout
+//---------------------------------------------------------------------------//
+
+/// Implementation of `UiLayout`.
+impl UiLayout {
+
+    /// This function returns if the provided path belongs to a UI Layout PackedFile.
+    pub fn is_ui_layout(path: &[String]) -> bool {
+        path.last().map_or(false, |name| {
+            let name = name.to_lowercase();
+            EXTENSIONS.iter().any(|extension| name.ends_with(extension))
+        })
+    }
+
+    /// This function creates a `UiLayout` from a `Vec<u8>`.
+    pub fn read(packed_file_data: &[u8]) -> Result<Self> {
+        if packed_file_data.starts_with(BINARY_LAYOUT_PREAMBLE.as_bytes()) {
+            return Err(ErrorKind::UiLayoutBinaryNotSupported.into());
+        }
+
+        let mut text = Text::read(packed_file_data)?;
+        text.set_text_type(TextType::Xml);
+        let nodes = Self::parse(text.get_ref_contents())?;
+
+        Ok(Self {
+            text,
+            nodes,
+        })
+    }
+
+    /// This function takes a `UiLayout` and encodes it to `Vec<u8>`.
+    ///
+    /// Only the attribute values are updated. The rest of the original text is saved as it was.
+    pub fn save(&self) -> Result<Vec<u8>> {
+        let mut attributes = vec![];
+        Self::get_attributes_recursive(&self.nodes, &mut attributes);
+        attributes.sort_unstable_by_key(|x| x.start);
+
+        let original = self.text.get_ref_contents();
+        let mut contents = String::with_capacity(original.len());
+        let mut last_position = 0;
+        for attribute in attributes {
+            contents.push_str(&original[last_position..attribute.start]);
+            contents.push_str(&attribute.value);
+            last_position = attribute.end;
+        }
+        contents.push_str(&original[last_position..]);
+
+        let mut text = self.text.clone();
+        text.set_contents(&contents);
+        text.save()
+    }
+
+    /// This function returns a reference to the root elements of this `UiLayout`.
+    pub fn get_ref_nodes(&self) -> &[UiLayoutNode] {
+        &self.nodes
+    }
+
+    /// This function returns the element at the provided path of indexes, starting from the root elements.
+    pub fn get_node(&self, node_path: &[usize]) -> Option<&UiLayoutNode> {
+        let (first, rest) = node_path.split_first()?;
+        rest.iter().try_fold(self.nodes.get(*first)?, |node, index| node.children.get(*index))
+    }
+
+    /// This function changes the value of an attribute of the element at the provided path of indexes.
+    ///
+    /// The value must be passed unescaped. `&`, `<` and the quote character surrounding it are escaped before writing it, so it's always valid XML.
+    pub fn set_attribute_value(&mut self, node_path: &[usize], name: &str, value: &str) -> Result<()> {
+        let (first, rest) = node_path.split_first().ok_or(ErrorKind::UiLayoutNodeNotFound)?;
+        let mut node = self.nodes.get_mut(*first).ok_or(ErrorKind::UiLayoutNodeNotFound)?;
+        for index in rest {
+            node = node.children.get_mut(*index).ok_or(ErrorKind::UiLayoutNodeNotFound)?;
+        }
+
+        let attribute = node.attributes.iter_mut().find(|x| x.name == name).ok_or_else(|| ErrorKind::UiLayoutAttributeNotFound(name.to_owned()))?;
+        let value = value.replace('&', "&amp;").replace('<', "&lt;");
+        attribute.value = if attribute.quote == '"' { value.replace('"', "&quot;") } else { value.replace('\'', "&apos;") };
+        Ok(())
+    }
+
+    /// This function adds the attributes of the provided elements and all their children to the provided list.
+    fn get_attributes_recursive<'a>(nodes: &'a [UiLayoutNode], attributes: &mut Vec<&'a UiLayoutAttribute>) {
+        for node in nodes {
+            attributes.extend(node.attributes.iter());
+            Self::get_attributes_recursive(&node.children, attributes);
+        }
+    }
+
+    /// This function parses the element tree of the provided text.
+    ///
+    /// Comments, declarations, CDATA sections and text between elements are skipped, as they're kept in the original text.
+    fn parse(text: &str) -> Result<Vec<UiLayoutNode>> {
+        let bytes = text.as_bytes();
+        let mut roots = vec![];
+        let mut stack: Vec<UiLayoutNode> = vec![];
+        let mut position = 0;
+
+        while let Some(offset) = text[position..].find('<') {
+            let start = position + offset;
+            let remaining = &text[start..];
+
+            // Things we skip, with their end marker.
+            let skip_end = if remaining.starts_with("<!--") { Some("-->") }
+                else if remaining.starts_with("<![CDATA[") { Some("]]>") }
+                else if remaining.starts_with("<?") { Some("?>") }
+                else if remaining.starts_with("<!") { Some(">") }
+                else { None };
+
+            if let Some(end_marker) = skip_end {
+                let end = remaining.find(end_marker).ok_or(ErrorKind::UiLayoutParseError(start))?;
+                position = start + end + end_marker.len();
+                continue;
+            }
+
+            // Closing tags.
+            if remaining.starts_with("</") {
+                let end = remaining.find('>').ok_or(ErrorKind::UiLayoutParseError(start))?;
+                let tag = remaining[2..end].trim();
+                let node = stack.pop().ok_or(ErrorKind::UiLayoutParseError(start))?;
+                if node.tag != tag { return Err(ErrorKind::UiLayoutParseError(start).into()); }
+
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => roots.push(node),
+                }
+                position = start + end + 1;
+                continue;
+            }
+
+            // Opening tags. First the name, then the attributes.
+            let mut index = start + 1;
+            while index < bytes.len() && !bytes[index].is_ascii_whitespace() && bytes[index] != b'/' && bytes[index] != b'>' { index += 1; }
+            if index == start + 1 { return Err(ErrorKind::UiLayoutParseError(start).into()); }
+
+            let mut node = UiLayoutNode {
+                tag: text[start + 1..index].to_owned(),
+                attributes: vec![],
+                children: vec![],
+            };
+
+            let is_self_closing = loop {
+                while index < bytes.len() && bytes[index].is_ascii_whitespace() { index += 1; }
+                match bytes.get(index) {
+                    Some(b'>') => { index += 1; break false; }
+                    Some(b'/') if bytes.get(index + 1) == Some(&b'>') => { index += 2; break true; }
+                    Some(_) => {
+                        let name_start = index;
+                        while index < bytes.len() && !bytes[index].is_ascii_whitespace() && bytes[index] != b'=' && bytes[index] != b'>' && bytes[index] != b'/' { index += 1; }
+                        let name = text[name_start..index].to_owned();
+                        while index < bytes.len() && bytes[index].is_ascii_whitespace() { index += 1; }
+                        if name.is_empty() || bytes.get(index) != Some(&b'=') { return Err(ErrorKind::UiLayoutParseError(name_start).into()); }
+                        index += 1;
+                        while index < bytes.len() && bytes[index].is_ascii_whitespace() { index += 1; }
+
+                        let quote = match bytes.get(index) {
+                            Some(b'"') => '"',
+                            Some(b'\'') => '\'',
+                            _ => return Err(ErrorKind::UiLayoutParseError(index).into()),
+                        };
+                        let value_start = index + 1;
+                        let value_end = value_start + text[value_start..].find(quote).ok_or(ErrorKind::UiLayoutParseError(index))?;
+                        node.attributes.push(UiLayoutAttribute {
+                            name,
+                            value: text[value_start..value_end].to_owned(),
+                            quote,
+                            start: value_start,
+                            end: value_end,
+                        });
+                        index = value_end + 1;
+                    }
+                    None => return Err(ErrorKind::UiLayoutParseError(start).into()),
+                }
+            };
+
+            if is_self_closing {
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => roots.push(node),
+                }
+            }
+            else {
+                stack.push(node);
+            }
+            position = index;
+        }
+
+        // If there are elements left open, the file is broken.
+        if stack.is_empty() { Ok(roots) }
+        else { Err(ErrorKind::UiLayoutParseError(text.len()).into()) }
+    }
+}
+
+/// Implementation of `UiLayoutNode`.
+impl UiLayoutNode {
+
+    /// This function returns the name of this element.
+    pub fn get_tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// This function returns the attributes of this element.
+    pub fn get_ref_attributes(&self) -> &[UiLayoutAttribute] {
+        &self.attributes
+    }
+
+    /// This function returns the value of the attribute with the provided name, if this element has it.
+    pub fn get_attribute_value(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|x| x.name == name).map(|x| x.value.as_str())
+    }
+
+    /// This function returns the child elements of this element.
+    pub fn get_ref_children(&self) -> &[UiLayoutNode] {
+        &self.children
+    }
+}
+
+/// Implementation of `UiLayoutAttribute`.
+impl UiLayoutAttribute {
+
+    /// This function returns the name of this attribute.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// This function returns the value of this attribute, as it is in the file.
+    pub fn get_value(&self) -> &str {
+        &self.value
+    }
+}
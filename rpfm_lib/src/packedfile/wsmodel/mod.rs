@@ -0,0 +1,161 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2020 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module with all the code to interact with WsModel PackedFiles.
+
+WsModels are XML files listing the models and materials used by something. We only parse the references
+to other files within them. Everything else is kept as it is, so it's saved back exactly as it was.
+!*/
+
+use regex::Regex;
+use serde_derive::{Serialize, Deserialize};
+
+use rpfm_error::{ErrorKind, Result};
+
+use crate::packedfile::text::{Text, TextType};
+
+/// Extension used by WsModel PackedFiles.
+pub const EXTENSION: &str = ".wsmodel";
+
+/// Regex to find XML elements containing only text. References in WsModels are always in elements like these.
+const REFERENCE_REGEX: &str = r#"<([A-Za-z_][\w\-.]*)([^<>]*)>([^<>]+)</([A-Za-z_][\w\-.]*)>"#;
+
+//---------------------------------------------------------------------------//
+//                              Enum & Structs
+//---------------------------------------------------------------------------//
+
+/// This holds an entire WsModel PackedFile decoded in memory.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct WsModel {
+
+    /// The original text of the PackedFile. References are replaced over it when saving, so nothing else gets lost.
+    text: Text,
+
+    /// The references to other files found in the PackedFile, in the order they appear.
+    references: Vec<WsModelReference>,
+}
+
+/// This holds a single reference to another file within a WsModel.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct WsModelReference {
+
+    /// Name of the XML element containing the reference.
+    tag: String,
+
+    /// Attributes of the XML element containing the reference, as they are in the file.
+    attributes: String,
+
+    /// The referenced path.
+    path: String,
+
+    /// Position of the first byte of the original path in the original text.
+    start: usize,
+
+    /// Position after the last byte of the original path in the original text.
+    end: usize,
+}
+
+//---------------------------------------------------------------------------//
+//                           Implementation of WsModel
+//---------------------------------------------------------------------------//
+
+/// Implementation of `WsModel`.
+impl WsModel {
+
+    /// This function creates a `WsModel` from a `Vec<u8>`.
+    pub fn read(packed_file_data: &[u8]) -> Result<Self> {
+        let mut text = Text::read(packed_file_data)?;
+        text.set_text_type(TextType::Xml);
+
+        let regex = Regex::new(REFERENCE_REGEX).unwrap();
+        let references = regex.captures_iter(text.get_ref_contents())
+            .filter(|captures| captures[1] == captures[4])
+            .filter_map(|captures| {
+                let value = captures.get(3)?;
+                let path = value.as_str().trim();
+                if !path.contains('.') || (!path.contains('/') && !path.contains('\\')) { return None }
+
+                let start = value.start() + value.as_str().find(path)?;
+                Some(WsModelReference {
+                    tag: captures[1].to_owned(),
+                    attributes: captures[2].trim().to_owned(),
+                    path: path.to_owned(),
+                    start,
+                    end: start + path.len(),
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            text,
+            references,
+        })
+    }
+
+    /// This function takes a `WsModel` and encodes it to `Vec<u8>`.
+    ///
+    /// Only the paths of the references are updated. The rest of the original text is saved as it was.
+    pub fn save(&self) -> Result<Vec<u8>> {
+        let original = self.text.get_ref_contents();
+        let mut contents = String::with_capacity(original.len());
+        let mut last_position = 0;
+        for reference in &self.references {
+            contents.push_str(&original[last_position..reference.start]);
+            contents.push_str(&reference.path);
+            last_position = reference.end;
+        }
+        contents.push_str(&original[last_position..]);
+
+        let mut text = self.text.clone();
+        text.set_contents(&contents);
+        text.save()
+    }
+
+    /// This function returns a reference to the list of references of this `WsModel`.
+    pub fn get_ref_references(&self) -> &[WsModelReference] {
+        &self.references
+    }
+
+    /// This function changes the path of the reference at the provided index.
+    pub fn set_reference_path(&mut self, index: usize, path: &str) -> Result<()> {
+        match self.references.get_mut(index) {
+            Some(reference) => {
+                reference.path = path.to_owned();
+                Ok(())
+            }
+            None => Err(ErrorKind::WsModelReferenceNotFound(index).into()),
+        }
+    }
+}
+
+/// Implementation of `WsModelReference`.
+impl WsModelReference {
+
+    /// This function returns the name of the XML element containing this reference.
+    pub fn get_tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// This function returns the attributes of the XML element containing this reference, as they are in the file.
+    pub fn get_attributes(&self) -> &str {
+        &self.attributes
+    }
+
+    /// This function returns the path this reference points to.
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    /// This function returns the path this reference points to, normalized to the format used for paths within a PackFile.
+    pub fn get_path_normalized(&self) -> String {
+        self.path.replace('\\', "/").trim_start_matches('/').to_lowercase()
+    }
+}
@@ -9,7 +9,11 @@
 //---------------------------------------------------------------------------//
 
 // Here should go all the functions related to the compresion/decompression of PackedFiles.
+//
+// Compressed PackedFiles start with the size of their decompressed data, followed by the compressed data itself.
+// For now, all supported games use LZMA1 for that data.
 
+use serde_derive::{Serialize, Deserialize};
 use xz2::read::XzDecoder;
 use xz2::stream::Stream;
 
@@ -17,6 +21,7 @@ use std::env::temp_dir;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::{BufReader, Read, SeekFrom};
+use std::{fmt, fmt::Display};
 
 use std::process::Command;
 use std::u64;
@@ -27,8 +32,46 @@ use crate::common::decoder::Decoder;
 use crate::SETTINGS;
 use crate::settings::ZIP_PATH;
 
+/// This enum represents the formats the data of a compressed PackedFile can be in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum CompressionFormat {
+
+    /// LZMA Alone (or legacy), with CA's tweaked header. Used by all games supporting compression.
+    Lzma1,
+}
+
+/// Implementation of `CompressionFormat`.
+impl CompressionFormat {
+
+    /// This function returns the format of the provided compressed data.
+    ///
+    /// LZMA1 has no magic number to detect it and it's the only format we support for now, so this always returns it.
+    /// Once a game uses another format, its detection should go here.
+    pub fn detect(_data: &[u8]) -> Self {
+        Self::Lzma1
+    }
+}
+
+/// Display implementation of `CompressionFormat`.
+impl Display for CompressionFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Lzma1 => write!(f, "LZMA1"),
+        }
+    }
+}
+
 /// This function decompress the data of a PackedFile, returning the decompressed data.
+///
+/// The format of the data is detected from the data itself.
 pub fn decompress_data(data: &[u8]) -> Result<Vec<u8>> {
+    match CompressionFormat::detect(data) {
+        CompressionFormat::Lzma1 => decompress_data_lzma1(data),
+    }
+}
+
+/// This function decompress the data of a LZMA1 compressed PackedFile, returning the decompressed data.
+fn decompress_data_lzma1(data: &[u8]) -> Result<Vec<u8>> {
     if !data.is_empty() {
         if data.len() >= 9 {
 
@@ -56,7 +99,21 @@ pub fn decompress_data(data: &[u8]) -> Result<Vec<u8>> {
     else { Ok(vec![]) }
 }
 
-/// This function compress the data of a PackedFile, returning the compressed data.
+/// This function returns the size the data of a compressed PackedFile will have once decompressed, without decompressing it.
+///
+/// CA stores it in the first 4 bytes of their compressed data, so we just need to read it from there.
+pub fn get_decompressed_size(data: &[u8]) -> Result<u32> {
+    data.decode_integer_u32(0)
+}
+
+/// This function compress the data of a PackedFile with the provided format, returning the compressed data.
+pub fn compress_data(data: &[u8], format: CompressionFormat) -> Result<Vec<u8>> {
+    match format {
+        CompressionFormat::Lzma1 => compress_data_lzma1(data),
+    }
+}
+
+/// This function compress the data of a PackedFile in LZMA1, returning the compressed data.
 ///
 /// Now, some explanation: CA uses Non-Streamed LZMA1 (or LZMA Alone) compressed files.
 /// Xz, the `standard` linux lib to deal with LZMA files has a fucking exception for
@@ -65,7 +122,7 @@ pub fn decompress_data(data: &[u8]) -> Result<Vec<u8>> {
 /// to 7z LZMA1 Level 3 format, read the compressed file, and remove the 7z part.
 /// Sadly, this means we have to ship 7z with RPFM. But hey, we're not the ones doing a
 /// fucking exception to a known format because we don't want to support the original format.
-pub fn compress_data(data: &[u8]) -> Result<Vec<u8>> {
+fn compress_data_lzma1(data: &[u8]) -> Result<Vec<u8>> {
 
     match SETTINGS.read().unwrap().paths.get(ZIP_PATH) {
         Some(zip_path) => {
@@ -0,0 +1,66 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2020 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing test for the compression of `PackedFiles`.
+!*/
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::SETTINGS;
+use crate::settings::ZIP_PATH;
+use super::compression::{CompressionFormat, compress_data, decompress_data, get_decompressed_size};
+
+/// Data used for the compression tests.
+const TEST_DATA: &[u8] = b"RPFM compression test. RPFM compression test. RPFM compression test. RPFM compression test. ";
+
+/// `TEST_DATA`, LZMA1 compressed with CA's tweaked header.
+const TEST_DATA_LZMA1: [u8; 46] = [
+    0x5C, 0x00, 0x00, 0x00, 0x5D, 0x00, 0x00, 0x40, 0x00, 0x00, 0x29, 0x14, 0x04, 0xCF, 0x41, 0x7F,
+    0xD6, 0xCE, 0xC4, 0x0F, 0x4A, 0xEA, 0x84, 0x2B, 0x44, 0xB9, 0x17, 0x76, 0xE3, 0x23, 0x4D, 0xAC,
+    0x4F, 0x4C, 0xD4, 0xBC, 0x50, 0xCA, 0xA1, 0x7F, 0xFF, 0xFF, 0xC4, 0xDF, 0x00, 0x00,
+];
+
+/// This function returns the formats we can compress to in this environment.
+///
+/// LZMA1 compression needs 7Zip. If it's not configured in the settings we try the one in the PATH, and skip the format if there is none.
+fn available_formats() -> Vec<CompressionFormat> {
+    let mut formats = vec![];
+
+    let mut settings = SETTINGS.write().unwrap();
+    let has_zip = match settings.paths.get(ZIP_PATH) {
+        Some(Some(_)) => true,
+        _ => if Command::new("7z").output().is_ok() {
+            settings.paths.insert(ZIP_PATH.to_owned(), Some(PathBuf::from("7z")));
+            true
+        } else { false }
+    };
+
+    if has_zip { formats.push(CompressionFormat::Lzma1); }
+    formats
+}
+
+#[test]
+fn test_decompress_data_lzma1() {
+    assert_eq!(CompressionFormat::detect(&TEST_DATA_LZMA1), CompressionFormat::Lzma1);
+    assert_eq!(get_decompressed_size(&TEST_DATA_LZMA1).unwrap() as usize, TEST_DATA.len());
+    assert_eq!(decompress_data(&TEST_DATA_LZMA1).unwrap(), TEST_DATA);
+}
+
+#[test]
+fn test_compress_decompress_data() {
+    for format in available_formats() {
+        let compressed_data = compress_data(TEST_DATA, format).unwrap();
+        assert_eq!(CompressionFormat::detect(&compressed_data), format);
+        assert_eq!(get_decompressed_size(&compressed_data).unwrap() as usize, TEST_DATA.len());
+        assert_eq!(decompress_data(&compressed_data).unwrap(), TEST_DATA);
+    }
+}
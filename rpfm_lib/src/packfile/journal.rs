@@ -0,0 +1,430 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2020 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module with all the code related to the recovery journal of a `PackFile`.
+
+The journal is an append-only log in the config folder, with every change done to a `PackFile` since it was opened or saved.
+If the program crashes, the journal can be replayed onto the `PackFile` on disk to recover the lost changes.
+
+Changes are recorded as the final data of the `PackedFiles` that changed, with their compression and encryption state, or their removal,
+so replaying them doesn't depend on the operation that caused them.
+Which `PackedFiles` changed is tracked by the `PackedFiles` themselves, so only the ones with changes not yet journaled are checked.
+The metadata of the `PackFile` (version, type, flags, dependencies, notes, row comments, settings and custom order) is journaled as a whole every time it changes.
+!*/
+
+use bincode::{deserialize, serialize};
+use serde_derive::{Serialize, Deserialize};
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{DirBuilder, File, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+use rpfm_error::{ErrorKind, Result};
+
+use crate::common::{decoder::Decoder, encoder::Encoder, get_last_modified_time_from_path};
+use crate::config::get_config_path;
+use crate::packfile::{PackFile, PackFileSettings, PFHFileType, PFHFlags, PFHVersion};
+use crate::packfile::packedfile::{PackedFile, RawPackedFile};
+use crate::packedfile::DecodedPackedFile;
+
+/// Name of the folder in the config folder where the journals are stored.
+pub const JOURNAL_FOLDER: &str = "journals";
+
+/// Extension of the journal files.
+const JOURNAL_EXTENSION: &str = "journal";
+
+/// This struct represents the recovery journal of the `PackFile` open.
+///
+/// A journal for a `PackFile` that's not on disk is disabled, as we have nothing to replay it onto.
+#[derive(Clone, Debug, Default)]
+pub struct Journal {
+
+    /// Path of the `PackFile` this journal belongs to.
+    pack_file_path: PathBuf,
+
+    /// Fingerprint of the `PackFile` on disk when the journal was started.
+    fingerprint: u64,
+
+    /// If the journal file has been created for this session. Until then, any previous journal file is left untouched.
+    started: bool,
+
+    /// If the journal records changes.
+    enabled: bool,
+
+    /// Paths of the `PackedFiles` of the `PackFile` the last time we recorded changes.
+    known_paths: HashSet<Vec<String>>,
+
+    /// Hashes of the data and compression/encryption state of the `PackedFiles` we have journaled, as they were the last time we journaled them.
+    hashes: HashMap<Vec<String>, u64>,
+
+    /// Metadata of the `PackFile` the last time we recorded changes.
+    metadata: JournalMetadata,
+}
+
+/// This struct represents the metadata of a `PackFile`, as it's stored in the journal.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct JournalMetadata {
+    pfh_version: String,
+    pfh_file_type: u32,
+    bitmask: u32,
+    pack_files: Vec<String>,
+    notes: Option<String>,
+    row_comments: BTreeMap<String, BTreeMap<String, String>>,
+    settings: PackFileSettings,
+
+    /// Order of the `PackedFiles`. Only stored if the `PackFile` keeps a custom order, as otherwise they get sorted on save.
+    order: Option<Vec<Vec<String>>>,
+}
+
+/// This enum represents an entry of a journal file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+enum JournalEntry {
+
+    /// First entry of every journal, used to check the journal belongs to a `PackFile`.
+    Header {
+        pack_file_path: PathBuf,
+        fingerprint: u64,
+    },
+
+    /// A `PackedFile` has been added or changed. Contains its path, timestamp, data, and if it should be compressed and encrypted on save.
+    Write {
+        path: Vec<String>,
+        timestamp: i64,
+        data: Vec<u8>,
+        should_be_compressed: bool,
+
+        /// Version of the `PackFile` the `PackedFile` should be encrypted for, if any.
+        should_be_encrypted: Option<String>,
+    },
+
+    /// A `PackedFile` has been removed.
+    Delete {
+        path: Vec<String>,
+    },
+
+    /// The metadata of the `PackFile` has changed. Contains all of it, not only the parts that changed.
+    Metadata(JournalMetadata),
+}
+
+/// Implementation of `Journal`.
+impl Journal {
+
+    /// This function creates a new journal for the provided `PackFile`.
+    ///
+    /// Nothing is written to disk until the first change is recorded.
+    pub fn new(pack_file: &PackFile) -> Self {
+        let pack_file_path = pack_file.get_file_path().to_path_buf();
+        match Self::get_fingerprint(&pack_file_path) {
+            Ok(fingerprint) => Self {
+                pack_file_path,
+                fingerprint,
+                started: false,
+                enabled: true,
+                known_paths: pack_file.get_ref_packed_files_all_paths().iter().map(|x| x.to_vec()).collect(),
+                hashes: HashMap::new(),
+                metadata: JournalMetadata::from(pack_file),
+            },
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// This function returns the path of the journal file of the `PackFile` in the provided path.
+    pub fn get_journal_path(pack_file_path: &Path) -> Result<PathBuf> {
+        let pack_file_path = pack_file_path.canonicalize().unwrap_or_else(|_| pack_file_path.to_path_buf());
+        let mut hasher = DefaultHasher::new();
+        pack_file_path.hash(&mut hasher);
+        Ok(get_config_path()?.join(JOURNAL_FOLDER).join(format!("{:016x}.{}", hasher.finish(), JOURNAL_EXTENSION)))
+    }
+
+    /// This function returns if there is a journal for the `PackFile` in the provided path.
+    pub fn exists(pack_file_path: &Path) -> bool {
+        Self::get_journal_path(pack_file_path).map(|x| x.is_file()).unwrap_or(false)
+    }
+
+    /// This function checks the provided `PackFile` for changes since the last time this was called, and appends them to the journal.
+    ///
+    /// The `PackedFiles` whose changes get journaled are marked as journaled. If writing to the journal fails, they're left as they were.
+    pub fn record_changes(&mut self, pack_file: &mut PackFile) -> Result<()> {
+        if !self.enabled { return Ok(()) }
+
+        let mut entries = vec![];
+        let current_paths = pack_file.get_ref_packed_files_all_paths().iter().map(|x| x.to_vec()).collect::<HashSet<Vec<String>>>();
+        for path in self.known_paths.difference(&current_paths) {
+            entries.push(JournalEntry::Delete { path: path.to_vec() });
+        }
+
+        // Only the PackedFiles with changes not yet journaled need to be checked. That includes new and reverted ones.
+        let mut hashes = vec![];
+        for packed_file in pack_file.get_ref_packed_files_all() {
+            if !packed_file.has_unjournaled_changes() { continue; }
+
+            // Changes to the compression or encryption don't change the data, so they need to be part of the hash.
+            let path = packed_file.get_path();
+            let data = Self::get_packed_file_data(packed_file)?;
+            let should_be_compressed = packed_file.get_ref_raw().get_should_be_compressed();
+            let should_be_encrypted = packed_file.get_ref_raw().get_should_be_encrypted().as_ref().map(|x| x.get_value().to_owned());
+            let hash = Self::get_hash(&data, should_be_compressed, &should_be_encrypted);
+            if self.hashes.get(path) != Some(&hash) {
+                hashes.push((path.to_vec(), hash));
+                entries.push(JournalEntry::Write {
+                    path: path.to_vec(),
+                    timestamp: packed_file.get_ref_raw().get_timestamp(),
+                    data,
+                    should_be_compressed,
+                    should_be_encrypted,
+                });
+            }
+        }
+
+        // The metadata goes after the PackedFiles, so the custom order, if any, matches the PackedFiles when replaying it.
+        let metadata = JournalMetadata::from(&*pack_file);
+        if metadata != self.metadata {
+            entries.push(JournalEntry::Metadata(metadata.clone()));
+        }
+
+        self.append(&entries)?;
+
+        // Only once they're on disk we can consider the changes journaled.
+        for path in self.known_paths.difference(&current_paths) {
+            self.hashes.remove(path);
+        }
+        self.hashes.extend(hashes);
+        self.known_paths = current_paths;
+        self.metadata = metadata;
+        pack_file.get_ref_mut_packed_files_all().into_iter().for_each(|x| x.set_unjournaled_changes(false));
+        Ok(())
+    }
+
+    /// This function removes the journal file, if this journal created it or took over it, and disables the journal.
+    ///
+    /// Use it after the `PackFile` has been saved, then create a new journal for it.
+    pub fn clear(&mut self) -> Result<()> {
+        if self.started {
+            let journal_path = Self::get_journal_path(&self.pack_file_path)?;
+            if journal_path.is_file() {
+                std::fs::remove_file(journal_path)?;
+            }
+        }
+
+        *self = Self::default();
+        Ok(())
+    }
+
+    /// This function opens the `PackFile` in the provided path and replays its journal onto it, returning the recovered `PackFile`
+    /// and a journal to keep recording changes on top of the replayed ones.
+    ///
+    /// If the `PackFile` on disk is not the same the journal was started on, this fails without replaying anything.
+    pub fn recover(pack_file_path: &Path, use_lazy_loading: bool) -> Result<(PackFile, Self)> {
+        let journal_path = Self::get_journal_path(pack_file_path)?;
+        if !journal_path.is_file() { return Err(ErrorKind::JournalNotFound.into()) }
+
+        let mut data = vec![];
+        BufReader::new(File::open(&journal_path)?).read_to_end(&mut data)?;
+        let mut entries = Self::read_entries(&data).into_iter();
+
+        match entries.next() {
+            Some(JournalEntry::Header { pack_file_path: journal_pack_file_path, fingerprint }) => {
+                let same_path = journal_pack_file_path.canonicalize().ok() == pack_file_path.canonicalize().ok();
+                if !same_path || Self::get_fingerprint(pack_file_path)? != fingerprint {
+                    return Err(ErrorKind::JournalPackFileMismatch.into());
+                }
+            }
+            _ => return Err(ErrorKind::JournalPackFileMismatch.into()),
+        }
+
+        let mut pack_file = PackFile::open_packfiles(&[pack_file_path.to_path_buf()], use_lazy_loading, false, false, false)?;
+        let mut journal = Self::new(&pack_file);
+        let pack_file_name = pack_file.get_file_name();
+        for entry in entries {
+            match entry {
+                JournalEntry::Write { path, timestamp, data, should_be_compressed, should_be_encrypted } => {
+                    journal.hashes.insert(path.to_vec(), Self::get_hash(&data, should_be_compressed, &should_be_encrypted));
+                    let should_be_encrypted = match should_be_encrypted {
+                        Some(version) => Some(PFHVersion::get_version(&version)?),
+                        None => None,
+                    };
+
+                    match pack_file.get_ref_mut_packed_file_by_path(&path) {
+                        Some(packed_file) => {
+                            packed_file.set_decoded(&DecodedPackedFile::Unknown);
+                            packed_file.set_raw_data(&data);
+
+                            let raw = packed_file.get_ref_mut_raw();
+                            raw.set_timestamp(timestamp);
+                            raw.set_should_be_compressed(should_be_compressed);
+                            raw.set_should_be_encrypted(should_be_encrypted);
+                        }
+                        None => {
+                            let mut raw_data = RawPackedFile::read_from_vec(path, pack_file_name.to_owned(), timestamp, should_be_compressed, data);
+                            raw_data.set_should_be_encrypted(should_be_encrypted);
+                            pack_file.add_packed_file(&PackedFile::new_from_raw(&raw_data), true)?;
+                        }
+                    }
+                }
+                JournalEntry::Delete { path } => {
+                    journal.hashes.remove(&path);
+                    pack_file.remove_packed_file_by_path(&path);
+                }
+                JournalEntry::Metadata(metadata) => metadata.apply(&mut pack_file)?,
+                JournalEntry::Header { .. } => {}
+            }
+        }
+
+        // The journal file is still valid for this PackFile, so we keep appending to it. Everything replayed is already in it.
+        journal.known_paths = pack_file.get_ref_packed_files_all_paths().iter().map(|x| x.to_vec()).collect();
+        journal.metadata = JournalMetadata::from(&pack_file);
+        journal.started = journal.enabled;
+        pack_file.get_ref_mut_packed_files_all().into_iter().for_each(|x| x.set_unjournaled_changes(false));
+        Ok((pack_file, journal))
+    }
+
+    /// This function appends the provided entries to the journal file, creating it if this is the first change of the session.
+    fn append(&mut self, entries: &[JournalEntry]) -> Result<()> {
+        if entries.is_empty() { return Ok(()) }
+
+        let journal_path = Self::get_journal_path(&self.pack_file_path)?;
+        let mut data = vec![];
+        if !self.started {
+            if let Some(parent) = journal_path.parent() {
+                DirBuilder::new().recursive(true).create(parent)?;
+            }
+
+            // Start from an empty file, so changes from previous sessions are not mixed with these ones.
+            File::create(&journal_path)?;
+            Self::encode_entry(&mut data, &JournalEntry::Header {
+                pack_file_path: self.pack_file_path.to_path_buf(),
+                fingerprint: self.fingerprint,
+            })?;
+            self.started = true;
+        }
+
+        for entry in entries {
+            Self::encode_entry(&mut data, entry)?;
+        }
+
+        // Write everything in one go, so a crash leaves at most the last entry incomplete.
+        let mut file = OpenOptions::new().append(true).open(&journal_path)?;
+        file.write_all(&data)?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    /// This function encodes an entry into the provided buffer, prefixed with its length.
+    fn encode_entry(data: &mut Vec<u8>, entry: &JournalEntry) -> Result<()> {
+        let entry = serialize(entry)?;
+        data.encode_integer_u64(entry.len() as u64);
+        data.extend_from_slice(&entry);
+        Ok(())
+    }
+
+    /// This function reads all the complete entries of a journal file. Incomplete or broken entries at the end, from a crash while writing them, are ignored.
+    fn read_entries(data: &[u8]) -> Vec<JournalEntry> {
+        let mut entries = vec![];
+        let mut index = 0;
+        while let Ok(len) = data.decode_integer_u64(index) {
+            let start = index + 8;
+            let end = start + len as usize;
+            if end > data.len() { break; }
+
+            match deserialize(&data[start..end]) {
+                Ok(entry) => entries.push(entry),
+                Err(_) => break,
+            }
+            index = end;
+        }
+        entries
+    }
+
+    /// This function returns the current data of the provided `PackedFile`, encoding it first if it's decoded.
+    fn get_packed_file_data(packed_file: &PackedFile) -> Result<Vec<u8>> {
+        match packed_file.get_ref_decoded().encode() {
+            Some(data) => data,
+            None => packed_file.get_raw_data(),
+        }
+    }
+
+    /// This function returns the hash of the provided data, and the compression and encryption state it should be saved with.
+    fn get_hash(data: &[u8], should_be_compressed: bool, should_be_encrypted: &Option<String>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        data.hash(&mut hasher);
+        should_be_compressed.hash(&mut hasher);
+        should_be_encrypted.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// This function returns a fingerprint of the `PackFile` on disk, to detect if it has changed since the journal was started.
+    ///
+    /// We use its size and last modified date instead of its contents, as hashing entire PackFiles is too slow.
+    fn get_fingerprint(pack_file_path: &Path) -> Result<u64> {
+        if !pack_file_path.is_file() { return Err(ErrorKind::PackFileIsNotAFile.into()) }
+        let mut hasher = DefaultHasher::new();
+        std::fs::metadata(pack_file_path)?.len().hash(&mut hasher);
+        get_last_modified_time_from_path(pack_file_path)?.hash(&mut hasher);
+        Ok(hasher.finish())
+    }
+}
+
+/// Implementation of `JournalMetadata`.
+impl JournalMetadata {
+
+    /// This function applies this metadata to the provided `PackFile`, replacing the one it had.
+    fn apply(&self, pack_file: &mut PackFile) -> Result<()> {
+        pack_file.set_pfh_version(PFHVersion::get_version(&self.pfh_version)?);
+        pack_file.set_pfh_file_type(PFHFileType::get_type(self.pfh_file_type));
+        pack_file.set_bitmask(PFHFlags::from_bits_truncate(self.bitmask));
+        pack_file.set_packfiles_list(&self.pack_files);
+        pack_file.set_notes(&self.notes);
+
+        // Row comments are stored per table, so we need to remove the ones of the tables that no longer have them.
+        let old_tables = pack_file.get_ref_row_comments_all().keys().filter(|x| !self.row_comments.contains_key(*x)).cloned().collect::<Vec<String>>();
+        for table in &old_tables {
+            pack_file.set_row_comments(&Self::get_table_path(table), &BTreeMap::new());
+        }
+
+        for (table, comments) in &self.row_comments {
+            pack_file.set_row_comments(&Self::get_table_path(table), comments);
+        }
+
+        // Setting the order changes the settings, so we need to restore them after it.
+        if let Some(ref order) = self.order {
+            pack_file.set_packed_file_order(order)?;
+        }
+
+        pack_file.set_settings(&self.settings);
+        Ok(())
+    }
+
+    /// This function turns the key of the row comments of a table back into its path.
+    fn get_table_path(table: &str) -> Vec<String> {
+        table.split('/').map(|x| x.to_owned()).collect()
+    }
+}
+
+/// Implementation of `From<&PackFile>` for `JournalMetadata`.
+impl From<&PackFile> for JournalMetadata {
+    fn from(pack_file: &PackFile) -> Self {
+        let settings = pack_file.get_settings();
+        let keeps_order = settings.settings_bool.get("keep_packed_file_order").copied().unwrap_or(false);
+        Self {
+            pfh_version: pack_file.get_pfh_version().get_value().to_owned(),
+            pfh_file_type: pack_file.get_pfh_file_type().get_value(),
+            bitmask: pack_file.get_bitmask().bits(),
+            pack_files: pack_file.get_packfiles_list().to_vec(),
+            notes: pack_file.get_notes().clone(),
+            row_comments: pack_file.get_ref_row_comments_all().clone(),
+            settings: settings.clone(),
+            order: if keeps_order { Some(pack_file.get_packed_file_order()) } else { None },
+        }
+    }
+}
@@ -20,38 +20,59 @@ so you don't have to worry about that.
 !*/
 
 use bitflags::bitflags;
-use csv::ReaderBuilder;
+use chrono::NaiveDateTime;
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use csv::{ReaderBuilder, WriterBuilder};
 use itertools::{Itertools, Either};
+use memmap2::Mmap;
 use serde_derive::{Serialize, Deserialize};
 use serde_json::{from_slice, to_string_pretty};
 use rayon::prelude::*;
+use regex::Regex;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
 use std::{fmt, fmt::Display};
 use std::fs::{DirBuilder, File};
+use std::hash::{Hash, Hasher};
 use std::io::{prelude::*, BufReader, BufWriter, SeekFrom, Read, Write};
+use std::panic::AssertUnwindSafe;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
-use rpfm_error::{Error, ErrorKind, Result};
+use rpfm_error::{ctd::CrashReport, Error, ErrorKind, Result};
 
 use crate::GAME_SELECTED;
+use crate::OPERATION_PAUSE;
 use crate::SCHEMA;
 use crate::SETTINGS;
 use crate::SUPPORTED_GAMES;
+use crate::games::PathRules;
 use crate::common::{*, decoder::Decoder, encoder::Encoder};
 use crate::dependencies::Dependencies;
 use crate::packfile::compression::*;
 use crate::packfile::crypto::*;
 use crate::packfile::packedfile::*;
 use crate::packedfile::{DecodedPackedFile, PackedFileType};
-use crate::packedfile::table::db::DB;
-use crate::packedfile::table::loc::{Loc, TSV_NAME_LOC};
+use crate::packedfile::rigidmodel::RigidModel;
+use crate::packedfile::table::DecodedData;
+use crate::packedfile::table::db::{DB, TablePatch};
+use crate::packedfile::table::loc::{self, Loc, TSV_NAME_LOC};
+use crate::packedfile::text::TextType;
+use crate::packedfile::uilayout::UiLayout;
+use crate::packedfile::wsmodel::{self, WsModel};
+use crate::schema::{Definition, FieldType, Schema, VersionedFile};
 
 mod compression;
 mod crypto;
+pub mod journal;
 pub mod packedfile;
 
+pub use self::compression::CompressionFormat;
+
+#[cfg(test)]
+mod compression_test;
+
 #[cfg(test)]
 mod packfile_test;
 
@@ -81,9 +102,43 @@ const SIEGE_AREA_NODE_HINT: &[u8; 19] = b"AIH_SIEGE_AREA_NODE";
 pub const RESERVED_NAME_EXTRA_PACKFILE: &str = "extra_packfile.rpfm_reserved";
 pub const RESERVED_NAME_SETTINGS: &str = "settings.rpfm_reserved";
 pub const RESERVED_NAME_NOTES: &str = "notes.rpfm_reserved";
+pub const RESERVED_NAME_ROW_COMMENTS: &str = "row_comments.rpfm_reserved";
+
+/// Name of the file with the raw data of a PackedFile exported together with its definition.
+const BUNDLE_DATA_FILE_NAME: &str = "packed_file.bin";
+
+/// Name of the file with the path and definition of a PackedFile exported together with its definition.
+const BUNDLE_DEFINITION_FILE_NAME: &str = "definition.json";
+
+/// Name of the manifest file of an exploded PackFile.
+const EXPLODE_MANIFEST_FILE_NAME: &str = "rpfm_explode_manifest.json";
+
+/// Name of the folder where the PackedFiles of an exploded PackFile are extracted.
+const EXPLODE_DATA_FOLDER_NAME: &str = "data";
+
+/// Name of the manifest file of a PackFile exported for version control.
+const VERSION_CONTROL_MANIFEST_FILE_NAME: &str = "rpfm_version_control_manifest.json";
+
+/// Name of the folder where the tables of a PackFile exported for version control are written as TSV.
+const VERSION_CONTROL_TABLES_FOLDER_NAME: &str = "tables";
+
+/// Max amount of mismatches reported when checking if a `PackFile` loads the same with and without lazy loading.
+const MAX_LAZY_LOADING_MISMATCHES: usize = 10;
+
+/// These are the root folders of a `PackFile` the games expect to always be in lowercase.
+const LOWERCASE_ROOT_FOLDERS: [&str; 12] = ["db", "text", "script", "ui", "audio", "variantmeshes", "models", "animations", "terrain", "prefabs", "movies", "shaders"];
+
+/// This regex is used to find the paths of models and textures referenced in `.variantmeshdefinition` files.
+const VMD_REFERENCE_REGEX: &str = r#"(?i)["']([^"']+\.(?:rigid_model_v2|dds))["']"#;
 
 /// This is the list of ***Reserved PackedFile Names***. They're packedfile names used by RPFM for special porpouses.
-pub const RESERVED_PACKED_FILE_NAMES: [&str; 3] = [RESERVED_NAME_EXTRA_PACKFILE, RESERVED_NAME_SETTINGS, RESERVED_NAME_NOTES];
+pub const RESERVED_PACKED_FILE_NAMES: [&str; 4] = [RESERVED_NAME_EXTRA_PACKFILE, RESERVED_NAME_SETTINGS, RESERVED_NAME_NOTES, RESERVED_NAME_ROW_COMMENTS];
+
+/// Magic bytes at the start of any gzip-compressed file.
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+
+/// Extension of gzip-compressed PackFiles.
+const GZIP_EXTENSION: &str = ".pack.gz";
 
 const SUBHEADER_MARK: u32 = 0x12345678;
 const SUBHEADER_VERSION: u32 = 1;
@@ -166,8 +221,14 @@ pub struct PackFile {
     /// Notes added to the PackFile. Exclusive of this lib.
     notes: Option<String>,
 
+    /// Comments added to rows of DB Tables, by table path and row key. Exclusive of this lib.
+    row_comments: BTreeMap<String, BTreeMap<String, String>>,
+
     /// Settings stored in the PackFile itself, to be able to share them between instalations.
     settings: PackFileSettings,
+
+    /// If the PackFile has been modified since it was loaded or saved. This doesn't include changes in its PackedFiles, as they track that themselves.
+    modified: bool,
 }
 
 /// This struct is a reduced version of the `PackFile` one, used to pass just the needed data to an UI.
@@ -198,6 +259,263 @@ pub struct PackFileInfo {
     pub timestamp: i64,
 }
 
+/// This struct represents a broken reference found in a `.variantmeshdefinition` file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VmdIssue {
+
+    /// The path of the `.variantmeshdefinition` containing the reference.
+    pub path: Vec<String>,
+
+    /// The referenced path, as it's written in the file.
+    pub reference: String,
+
+    /// The line (starting at 1) where the reference is.
+    pub line: usize,
+
+    /// The error that stopped the file from being checked, if any. If there is one, `reference` is empty and `line` is 0.
+    pub error: Option<String>,
+}
+
+/// This struct represents a reference from one `PackedFile` to another one.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Reference {
+
+    /// The path of the `PackedFile` containing the reference.
+    pub referrer: Vec<String>,
+
+    /// The path of the referenced `PackedFile`.
+    pub referenced: Vec<String>,
+
+    /// The row (for DB Tables) or line (for Text files) where the reference is, starting at 1.
+    pub position: usize,
+}
+
+/// This struct represents the changes done to a `PackFile` while optimizing it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct OptimizeReport {
+
+    /// The paths of all the `PackedFiles` removed from the `PackFile`, for any reason.
+    pub removed_files: Vec<Vec<String>>,
+
+    /// The paths of the tables that had rows removed because they were identical to vanilla ones, with the amount of rows removed.
+    pub removed_rows: Vec<(Vec<String>, usize)>,
+
+    /// The paths of the tables removed because they were empty after removing their vanilla rows. These are also in `removed_files`.
+    pub emptied_tables: Vec<Vec<String>>,
+}
+
+/// This struct represents what happened when opening a broken `PackFile` in recovery mode.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecoveryReport {
+
+    /// The error we got when trying to open the `PackFile` normally.
+    pub original_error: String,
+
+    /// Where we stopped reading the `PackFile`, if we couldn't read all of it.
+    pub parsing_stopped_at: Option<String>,
+
+    /// The paths of the `PackedFiles` in the index that couldn't be read, with the reason.
+    pub unreadable_packed_files: Vec<(Vec<String>, String)>,
+}
+
+/// This struct represents the conflicts of the `PackedFiles` of a `PackFile` with the other `PackFiles` of a load order.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoadOrderImpactReport {
+
+    /// The paths of the `PackedFiles` that exist in other `PackFiles` of the load order, with the result of the conflict.
+    pub conflicts: Vec<(Vec<String>, LoadOrderConflict)>,
+
+    /// The names of the `PackFiles` of the load order we couldn't find or open.
+    pub missing_pack_files: Vec<String>,
+}
+
+/// This enum represents the result of a conflict between a `PackedFile` and the ones with the same path in other `PackFiles` of a load order.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum LoadOrderConflict {
+
+    /// Our `PackedFile` is the one the game uses. Contains the `PackFiles` whose `PackedFile` is overridden by ours.
+    Wins(Vec<String>),
+
+    /// Our `PackedFile` is overridden. Contains the `PackFiles` loaded before ours with the same `PackedFile`. The first one is the one the game uses.
+    Overridden(Vec<String>),
+}
+
+/// This enum represents what to do when extracting a `PackedFile` to a path where there is already a file.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ExtractOverwritePolicy {
+
+    /// Don't extract the `PackedFile`.
+    Skip,
+
+    /// Replace the file with the `PackedFile`.
+    Overwrite,
+
+    /// Extract the `PackedFile` with a numeric suffix in its name, using the first number not yet in use.
+    RenameWithSuffix,
+}
+
+/// This struct represents the options used to name the extracted `PackedFiles`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExtractOptions {
+
+    /// If true, all the `PackedFiles` are extracted directly in the destination folder, instead of recreating their folder structure.
+    pub flatten: bool,
+
+    /// Text to prepend to the name of each extracted `PackedFile`. `{folder}` is replaced with the name of the folder containing the `PackedFile`,
+    /// so for DB Tables it's replaced with the name of their table.
+    pub prefix: String,
+
+    /// If true, the paths of the extracted `PackedFiles` are lowercased.
+    pub lowercase: bool,
+
+    /// What to do if a file already exists in the destination path.
+    pub overwrite_policy: ExtractOverwritePolicy,
+}
+
+/// This struct represents the result of extracting `PackedFiles` with `ExtractOptions`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExtractReport {
+
+    /// Amount of `PackedFiles` extracted with their intended name, without colliding with an existing file.
+    pub extracted: u32,
+
+    /// Amount of `PackedFiles` not extracted because a file already existed in their destination path.
+    pub skipped: u32,
+
+    /// Amount of `PackedFiles` extracted with a suffix in their name because a file already existed in their destination path.
+    pub renamed: u32,
+
+    /// Amount of `PackedFiles` extracted replacing a file that already existed in their destination path.
+    pub overwritten: u32,
+}
+
+/// This struct represents the path and definition of a table `PackedFile` exported so it can be decoded without a `Schema`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct PackedFileDefinitionBundle {
+
+    /// The path of the `PackedFile` within its `PackFile`.
+    path: Vec<String>,
+
+    /// The definition needed to decode the `PackedFile`.
+    definition: Definition,
+}
+
+/// This struct represents the manifest of a `PackFile` exploded into a folder, with all the data needed to implode it back.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct ExplodeManifest {
+
+    /// The name of the exploded `PackFile`.
+    file_name: String,
+
+    /// The preamble of the version of the `PackFile`.
+    pfh_version: String,
+
+    /// The numeric value of the type of the `PackFile`.
+    pfh_file_type: u32,
+
+    /// The bits of the flags of the `PackFile`.
+    bitmask: u32,
+
+    /// The timestamp of the `PackFile`.
+    timestamp: i64,
+
+    /// Game version this `PackFile` is intended for.
+    game_version: u32,
+
+    /// Build number of the game.
+    build_number: u32,
+
+    /// Tool that created the `PackFile`.
+    authoring_tool: String,
+
+    /// Extra subheader data of the `PackFile`.
+    extra_subheader_data: Vec<u8>,
+
+    /// The dependency list of the `PackFile`.
+    pack_files: Vec<String>,
+
+    /// Notes added to the `PackFile`.
+    notes: Option<String>,
+
+    /// Comments added to rows of DB Tables.
+    row_comments: BTreeMap<String, BTreeMap<String, String>>,
+
+    /// Settings stored in the `PackFile`.
+    settings: PackFileSettings,
+
+    /// Per-file metadata of the `PackedFiles`, in the same order they have in the `PackFile`.
+    packed_files: Vec<ExplodeManifestEntry>,
+}
+
+/// This struct represents the metadata of a `PackedFile` in the manifest of an exploded `PackFile`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct ExplodeManifestEntry {
+
+    /// The path of the `PackedFile` within its `PackFile`.
+    path: Vec<String>,
+
+    /// The '*Last Modified Date*' of the `PackedFile`.
+    timestamp: i64,
+
+    /// If the `PackedFile` should be compressed when saving.
+    should_be_compressed: bool,
+
+    /// The preamble of the version the `PackedFile` is encrypted for, if it should be encrypted.
+    should_be_encrypted: Option<String>,
+
+    /// If the `PackedFile` was exported as a TSV, the data needed to rebuild the binary table from it.
+    #[serde(default)]
+    table: Option<VersionControlTable>,
+}
+
+/// This enum represents the data needed to rebuild a table exported as TSV for version control.
+///
+/// The definition is stored too, so tables are rebuilt with the same version and fields they had, no matter the schema in use when importing them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+enum VersionControlTable {
+    DB {
+        name: String,
+        mysterious_byte: bool,
+        uuid: String,
+        definition: Definition,
+    },
+    Loc {
+        definition: Definition,
+    },
+}
+
+/// This enum represents the problems a `PackedFile` can have when checking if it survives a decode/encode round trip unchanged.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RoundTripIssue {
+
+    /// The re-encoded data is different, but the type of the `PackedFile` is known to not be byte-stable.
+    KnownNonStable,
+
+    /// The re-encoded data is different, and it shouldn't be.
+    UnexpectedDifference,
+
+    /// The `PackedFile` was decoded, but failed to be encoded again. Contains the error message.
+    EncodeError(String),
+}
+
+/// This enum represents the differences a `PackedFile` can have between a lazy-loaded and a fully-loaded copy of the same `PackFile`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum LazyLoadingMismatch {
+
+    /// The `PackedFile` is only in one of the copies. Contains if it's the lazy-loaded one.
+    MissingPackedFile(bool),
+
+    /// The data is different. Contains the offset of the first different byte, and the size of the lazy-loaded and fully-loaded data.
+    DataDifference {
+        offset: usize,
+        lazy_size: usize,
+        full_size: usize,
+    },
+
+    /// The data failed to be read from one of the copies. Contains the error message.
+    ReadError(String),
+}
+
 /// This struct represents the entire **Manifest.txt** from the /data folder.
 ///
 /// Private for now, because I see no public use for this.
@@ -406,6 +724,45 @@ impl Display for PFHVersion {
     }
 }
 
+/// Implementation of `PFHFlags`.
+impl PFHFlags {
+
+    /// This function returns all the individual flags a PackFile can have, with their names.
+    pub fn get_all_flags() -> Vec<(&'static str, Self)> {
+        vec![
+            ("HAS_EXTENDED_HEADER", Self::HAS_EXTENDED_HEADER),
+            ("HAS_ENCRYPTED_INDEX", Self::HAS_ENCRYPTED_INDEX),
+            ("HAS_INDEX_WITH_TIMESTAMPS", Self::HAS_INDEX_WITH_TIMESTAMPS),
+            ("HAS_ENCRYPTED_DATA", Self::HAS_ENCRYPTED_DATA),
+        ]
+    }
+
+    /// This function returns the name of the provided individual flag, or its debug representation if it's not a single known flag.
+    pub fn get_name(self) -> String {
+        match Self::get_all_flags().iter().find(|(_, flag)| *flag == self) {
+            Some((name, _)) => name.to_string(),
+            None => format!("{:?}", self),
+        }
+    }
+
+    /// This function returns if the provided individual flag can be found in PackFiles of the provided `PFHVersion`.
+    pub fn is_supported_by_version(self, version: PFHVersion) -> bool {
+        match version {
+            PFHVersion::PFH6 => self == Self::HAS_INDEX_WITH_TIMESTAMPS || self == Self::HAS_ENCRYPTED_INDEX || self == Self::HAS_ENCRYPTED_DATA,
+            PFHVersion::PFH5 | PFHVersion::PFH4 => Self::get_all_flags().iter().any(|(_, flag)| *flag == self),
+            PFHVersion::PFH3 | PFHVersion::PFH2 => self == Self::HAS_INDEX_WITH_TIMESTAMPS,
+            PFHVersion::PFH0 => false,
+        }
+    }
+
+    /// This function returns if we can save PackFiles with the provided individual flag enabled.
+    ///
+    /// Remember to update this if support for saving any of the other flags is ever added.
+    pub fn is_supported_for_saving(self) -> bool {
+        self == Self::HAS_INDEX_WITH_TIMESTAMPS
+    }
+}
+
 /// Implementation of `PathType`.
 impl PathType {
 
@@ -508,7 +865,9 @@ impl PackFile {
             packed_files: vec![],
 
             notes: None,
+            row_comments: BTreeMap::new(),
             settings: PackFileSettings::default(),
+            modified: false,
         }
     }
 
@@ -529,7 +888,9 @@ impl PackFile {
             packed_files: vec![],
 
             notes: None,
+            row_comments: BTreeMap::new(),
             settings: PackFileSettings::default(),
+            modified: false,
         }
     }
 
@@ -551,6 +912,7 @@ impl PackFile {
     /// This function allows you to change the game version of this PackFile.
     pub fn set_game_version(&mut self, version: u32) {
         self.game_version = version;
+        self.modified = true;
     }
 
     /// This function returns the authoring tool used to make this PackFile initially.
@@ -566,6 +928,7 @@ impl PackFile {
             return Err(ErrorKind::StringTooLong(AUTHORING_TOOL_SIZE).into());
         }
         self.authoring_tool = authoring_tool.to_owned();
+        self.modified = true;
         Ok(())
     }
 
@@ -574,9 +937,80 @@ impl PackFile {
         &self.pack_files
     }
 
+    /// This function checks if the `PackFiles` in the `PackFile List` of our `PackFile` exist in the `/data` folder of the provided game.
+    ///
+    /// For each `PackFile` in the list, it returns its name and, if found, its full path. The names are compared the same way
+    /// the game's filesystem does: case-insensitive on Windows, case-sensitive everywhere else.
+    pub fn resolve_dependency_packfiles(&self, game: &str) -> Vec<(String, Option<PathBuf>)> {
+        let data_path = SETTINGS.read().unwrap().paths.get(game).cloned().flatten().map(|path| path.join("data"));
+        let available_packfiles = match data_path.map(std::fs::read_dir) {
+            Some(Ok(entries)) => entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).filter(|path| path.is_file()).collect::<Vec<PathBuf>>(),
+            _ => vec![],
+        };
+
+        self.pack_files.iter().map(|pack_file_name| {
+            let path = available_packfiles.iter().find(|path| {
+                match path.file_name() {
+                    Some(file_name) => {
+                        let file_name = file_name.to_string_lossy();
+                        if cfg!(target_os = "windows") { file_name.eq_ignore_ascii_case(pack_file_name) }
+                        else { file_name == *pack_file_name }
+                    }
+                    None => false,
+                }
+            }).cloned();
+            (pack_file_name.to_owned(), path)
+        }).collect()
+    }
+
+    /// This function returns the list of PackFiles containing the provided path, sorted from most to least priority, so the first one is the one the game uses.
+    ///
+    /// It checks our `PackFile`, the `PackFiles` in its `PackFile List` and the vanilla `PackFiles` of the currently selected game.
+    /// The priority follows the same rules the game uses: PackFiles of types that load later win, and within the same type, the first one alphabetically wins.
+    pub fn get_override_chain(&self, path: &[String]) -> Result<Vec<(String, PFHFileType)>> {
+        let data_path = get_game_selected_data_path().ok_or_else(|| ErrorKind::GameSelectedPathNotCorrectlyConfigured)?;
+        let manifest = Manifest::read_from_game_selected()?;
+
+        let mut pack_file_paths = self.resolve_dependency_packfiles(&GAME_SELECTED.read().unwrap())
+            .into_iter()
+            .filter_map(|(_, path)| path)
+            .collect::<Vec<PathBuf>>();
+
+        for entry in &manifest.0 {
+            if entry.relative_path.ends_with(".pack") {
+                let pack_file_path = data_path.join(&entry.relative_path);
+                if !pack_file_paths.contains(&pack_file_path) {
+                    pack_file_paths.push(pack_file_path);
+                }
+            }
+        }
+
+        let mut chain = vec![];
+        if self.packedfile_exists(path) {
+            chain.push((self.get_file_name(), self.get_pfh_file_type()));
+        }
+
+        for pack_file_path in pack_file_paths.iter().filter(|x| *x != &self.file_path) {
+            let pack_file = Self::read(pack_file_path, true)?;
+            if pack_file.packedfile_exists(path) {
+                chain.push((pack_file.get_file_name(), pack_file.get_pfh_file_type()));
+            }
+        }
+
+        // Unknown types are not loaded by the game, so they go last.
+        let load_position = |pfh_file_type: &PFHFileType| match pfh_file_type {
+            PFHFileType::Other(_) => -1,
+            _ => pfh_file_type.get_value() as i64,
+        };
+
+        chain.sort_by(|(name_a, type_a), (name_b, type_b)| load_position(type_b).cmp(&load_position(type_a)).then_with(|| name_a.cmp(name_b)));
+        Ok(chain)
+    }
+
     /// This function replaces the `PackFile List` of our `PackFile` with the provided one.
     pub fn set_packfiles_list(&mut self, pack_files: &[String]) {
         self.pack_files = pack_files.to_vec();
+        self.modified = true;
     }
 
     /// This function retuns the list of PackedFiles inside a `PackFile`.
@@ -584,6 +1018,34 @@ impl PackFile {
         self.packed_files.par_iter().map(|x| x.get_path().to_vec()).collect()
     }
 
+    /// This function creates a new `PackFile` in memory with all the files under the provided folder, using their paths relative to it.
+    ///
+    /// The `PackFile` is named after the folder, and gets the main `PFHVersion` of the provided game. It's not saved to disk.
+    pub fn from_folder(root: &Path, game: &str) -> Result<Self> {
+        let game_info = SUPPORTED_GAMES.get(game).ok_or(ErrorKind::GameNotSupported)?;
+        let pfh_version = *game_info.pfh_version.get(0).ok_or(ErrorKind::GameNotSupported)?;
+        if !root.is_dir() { return Err(ErrorKind::IOReadFolder(root.to_path_buf()).into()) }
+
+        let name = root.file_name().map(|x| x.to_string_lossy().to_string()).unwrap_or_else(|| "unknown".to_owned());
+        let mut pack_file = Self::new_with_name(&format!("{}.pack", name), pfh_version);
+
+        let mut packed_files = vec![];
+        for file_path in get_files_from_subdir(root)? {
+            let path = file_path.strip_prefix(root)
+                .map_err(|_| Error::from(ErrorKind::IOReadFile(file_path.to_path_buf())))?
+                .components()
+                .map(|x| x.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<String>>();
+
+            let mut raw_data = RawPackedFile::read_from_path(&file_path, path)?;
+            raw_data.set_timestamp(Self::get_timestamp_for_added_file(&file_path, true)?);
+            packed_files.push(PackedFile::new_from_raw(&raw_data));
+        }
+
+        pack_file.add_packed_files(&packed_files.iter().collect::<Vec<&PackedFile>>(), true)?;
+        Ok(pack_file)
+    }
+
     /// This function adds a `PackedFile` to an existing `PackFile`.
     ///
     /// This function returns the path of the `PackedFile` which got added succesfully. Also, if you set `overwrite` to `true`,
@@ -606,6 +1068,7 @@ impl PackFile {
         let pack_file_name = self.get_file_name();
         let reserved_names = Self::get_reserved_packed_file_names();
         if packed_files.par_iter().any(|x| reserved_names.iter().any(|y| x.get_path() == &**y)) { return Err(ErrorKind::ReservedFiles.into()) }
+        self.modified = true;
 
         // Prepare the list of added paths and get all the PackedFiles with all the info needed for them to be added.
         let mut destination_paths = Vec::with_capacity(packed_files.len());
@@ -693,8 +1156,10 @@ impl PackFile {
         path_as_file: &PathBuf,
         path_as_packed_file: Vec<String>,
         overwrite: bool,
+        preserve_timestamps: bool,
     ) -> Result<Vec<String>> {
-        let raw_data = RawPackedFile::read_from_path(path_as_file, path_as_packed_file)?;
+        let mut raw_data = RawPackedFile::read_from_path(path_as_file, path_as_packed_file)?;
+        raw_data.set_timestamp(Self::get_timestamp_for_added_file(path_as_file, preserve_timestamps)?);
         let packed_file = PackedFile::new_from_raw(&raw_data);
         self.add_packed_file(&packed_file, overwrite)
     }
@@ -707,10 +1172,12 @@ impl PackFile {
         &mut self,
         paths_as_file_and_packed_file: &[(PathBuf, Vec<String>)],
         overwrite: bool,
+        preserve_timestamps: bool,
     ) -> Result<Vec<Vec<String>>> {
         let mut packed_files = vec![];
         for (path_as_file, path_as_packed_file) in paths_as_file_and_packed_file.iter() {
-            let raw_data = RawPackedFile::read_from_path(path_as_file, path_as_packed_file.to_vec())?;
+            let mut raw_data = RawPackedFile::read_from_path(path_as_file, path_as_packed_file.to_vec())?;
+            raw_data.set_timestamp(Self::get_timestamp_for_added_file(path_as_file, preserve_timestamps)?);
             packed_files.push(PackedFile::new_from_raw(&raw_data));
         }
         let ref_packed_files = packed_files.iter().map(|x| x).collect::<Vec<&PackedFile>>();
@@ -725,6 +1192,7 @@ impl PackFile {
         &mut self,
         paths_as_folder_and_destination: &[(PathBuf, Vec<String>)],
         overwrite: bool,
+        preserve_timestamps: bool,
     ) -> Result<Vec<Vec<String>>> {
 
         let mut packed_files_to_add = vec![];
@@ -744,7 +1212,8 @@ impl PackFile {
                             .collect::<Vec<String>>();
                         let mut new_path = base_path.to_vec();
                         new_path.extend_from_slice(&new_path_filtered);
-                        let raw_data = RawPackedFile::read_from_path(file_path, new_path)?;
+                        let mut raw_data = RawPackedFile::read_from_path(file_path, new_path)?;
+                        raw_data.set_timestamp(Self::get_timestamp_for_added_file(file_path, preserve_timestamps)?);
                         let packed_file = PackedFile::new_from_raw(&raw_data);
                         packed_files_to_add.push(packed_file);
                     }
@@ -756,6 +1225,15 @@ impl PackFile {
         self.add_packed_files(&packed_files_to_add.iter().map(|x|x).collect::<Vec<&PackedFile>>(), overwrite)
     }
 
+    /// This function returns the timestamp a file from disk should have once added to a `PackFile`.
+    ///
+    /// If `preserve_timestamps` is true, it's the last modified time of the file (or of its target, if it's a symlink). Otherwise, it's the current time.
+    /// The timestamp is stored even if the `PackFile` doesn't have the `HAS_INDEX_WITH_TIMESTAMPS` flag. It's just not saved in that case.
+    fn get_timestamp_for_added_file(path: &Path, preserve_timestamps: bool) -> Result<i64> {
+        if preserve_timestamps { get_last_modified_time_from_path(path) }
+        else { Ok(get_current_time()) }
+    }
+
     /// This function is used to add a `PackedFile` from one `PackFile` into another.
     ///
     /// It's a ***Copy from another PackFile*** kind of function. It returns the PathTypes
@@ -909,6 +1387,13 @@ impl PackFile {
         self.packed_files.par_iter().find_any(|x| x.get_path() == path)
     }
 
+    /// This function turns a path in string form, like `db/foo_tables/data`, into the path format used by the `PackFile`.
+    ///
+    /// Leading, trailing and repeated slashes are ignored.
+    pub fn path_from_string(path: &str) -> Vec<String> {
+        path.split('/').filter(|x| !x.is_empty()).map(|x| x.to_owned()).collect()
+    }
+
     /// This function returns a mutable reference to the `PackedFile` with the provided path, if exists.
     pub fn get_ref_mut_packed_file_by_path(&mut self, path: &[String]) -> Option<&mut PackedFile> {
         self.packed_files.par_iter_mut().find_any(|x| x.get_path() == path)
@@ -1081,6 +1566,45 @@ impl PackFile {
         self.packed_files.par_iter().map(|x| x.get_path()).collect()
     }
 
+    /// This function returns the paths of all the Text `PackedFiles` in the provided `PackFile`, with their language.
+    ///
+    /// The language is detected by the extension of each `PackedFile`, so no data is loaded.
+    pub fn list_text_files(&self) -> Vec<(Vec<String>, TextType)> {
+        self.packed_files.par_iter()
+            .filter_map(|x| match PackedFileType::get_packed_file_type(x.get_path()) {
+                PackedFileType::Text(text_type) => Some((x.get_path().to_vec(), text_type)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// This function returns the paths of all the `PackedFiles` in the provided `PackFile`, in the order they'll be saved.
+    ///
+    /// Unless a custom order has been set, this order may change on save, as the `PackedFiles` get sorted by path.
+    pub fn get_packed_file_order(&self) -> Vec<Vec<String>> {
+        self.packed_files.iter().map(|x| x.get_path().to_vec()).collect()
+    }
+
+    /// This function reorders the `PackedFiles` of the `PackFile` so they get saved in the provided order.
+    ///
+    /// The provided list must contain all the paths of the `PackedFiles` in the `PackFile`, each one only once.
+    /// This also marks the `PackFile` to keep this order on save, instead of sorting the `PackedFiles` by path.
+    pub fn set_packed_file_order(&mut self, ordered_paths: &[Vec<String>]) -> Result<()> {
+        let mut positions = self.packed_files.iter().enumerate().map(|(index, packed_file)| (packed_file.get_path().to_vec(), index)).collect::<BTreeMap<Vec<String>, usize>>();
+        if ordered_paths.len() != self.packed_files.len() || positions.len() != self.packed_files.len() {
+            return Err(ErrorKind::InvalidPackedFileOrder.into());
+        }
+
+        let order = ordered_paths.iter().map(|path| positions.remove(path).ok_or_else(|| Error::from(ErrorKind::InvalidPackedFileOrder))).collect::<Result<Vec<usize>>>()?;
+
+        // Move the PackedFiles themselves, so lazy-loaded ones keep their data source.
+        let mut packed_files = self.packed_files.drain(..).map(Some).collect::<Vec<Option<PackedFile>>>();
+        self.packed_files = order.iter().filter_map(|index| packed_files[*index].take()).collect();
+        self.settings.settings_bool.insert("keep_packed_file_order".to_owned(), true);
+        self.modified = true;
+        Ok(())
+    }
+
     /// This function returns a copy of all the `PackedFileInfo` corresponding to the provided `PackFile`.
     pub fn get_packed_files_all_info(&self) -> Vec<PackedFileInfo> {
         self.packed_files.par_iter().map(From::from).collect()
@@ -1091,10 +1615,126 @@ impl PackFile {
         self.packed_files.par_iter().find_first(|x| x.get_path() == path).map(From::from)
     }
 
+    /// This function returns the size of every folder in the `PackFile`, aggregated from the `PackedFiles` inside it.
+    ///
+    /// The returned data is `folder_path => (compressed_size, uncompressed_size, file_count)`, with the empty path being the entire `PackFile`.
+    /// Sizes are taken from the index where possible, so this doesn't load the data of lazy-loaded `PackedFiles` into memory.
+    pub fn get_tree_size_summary(&self) -> BTreeMap<Vec<String>, (u64, u64, usize)> {
+        let sizes = self.packed_files.par_iter().map(|packed_file| {
+            let raw = packed_file.get_ref_raw();
+            let size = raw.get_size() as u64;
+            let decompressed_size = raw.get_decompressed_size().map(|x| x as u64).unwrap_or(size);
+            (packed_file.get_path(), size, decompressed_size)
+        }).collect::<Vec<(&[String], u64, u64)>>();
+
+        let mut summary: BTreeMap<Vec<String>, (u64, u64, usize)> = BTreeMap::new();
+        for (path, size, decompressed_size) in sizes {
+            for index in 0..path.len() {
+                let folder = summary.entry(path[..index].to_vec()).or_insert((0, 0, 0));
+                folder.0 += size;
+                folder.1 += decompressed_size;
+                folder.2 += 1;
+            }
+        }
+
+        summary
+    }
+
+    /// This function exports the list of `PackedFiles` in the `PackFile` to a CSV file, with their type, sizes, encryption state and timestamp.
+    ///
+    /// It doesn't load the data of lazy-loaded `PackedFiles`. Paths are written with forward slashes, and timestamps as ISO 8601 dates.
+    pub fn export_file_list_csv(&self, path: &Path) -> Result<()> {
+        let mut writer = WriterBuilder::new().from_path(path)?;
+        writer.write_record(&["path", "type", "compressed_size", "decompressed_size", "is_encrypted", "timestamp"])?;
+
+        let mut packed_files = self.packed_files.iter().collect::<Vec<&PackedFile>>();
+        packed_files.sort_unstable_by_key(|x| x.get_path().join("/").to_lowercase());
+        for packed_file in packed_files {
+            let raw = packed_file.get_ref_raw();
+            let size = raw.get_size();
+            let decompressed_size = raw.get_decompressed_size().unwrap_or(size);
+            let timestamp = if raw.get_timestamp() == 0 { String::new() }
+                else { NaiveDateTime::from_timestamp(raw.get_timestamp(), 0).format("%Y-%m-%dT%H:%M:%S").to_string() };
+
+            writer.write_record(&[
+                packed_file.get_path().join("/"),
+                PackedFileType::get_packed_file_type(packed_file.get_path()).to_string(),
+                size.to_string(),
+                decompressed_size.to_string(),
+                raw.get_encryption_state().to_string(),
+                timestamp,
+            ])?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// This function returns the groups of `PackedFiles` with identical contents in the `PackFile`.
+    ///
+    /// The contents are compared decompressed, so compression differences don't hide duplicates. To keep memory usage bounded,
+    /// each `PackedFile` is loaded and hashed on its own, and only its hash and size are kept, so the groups may contain false positives
+    /// in case of hash collisions. Empty `PackedFiles` are ignored.
+    pub fn find_duplicate_files(&self) -> Vec<Vec<Vec<String>>> {
+        let hashes = self.packed_files.par_iter().filter_map(|packed_file| {
+            let data = Self::get_current_data(packed_file).ok()?;
+            if data.is_empty() { return None; }
+            let mut hasher = DefaultHasher::new();
+            data.hash(&mut hasher);
+            Some(((hasher.finish(), data.len()), packed_file.get_path().to_vec()))
+        }).collect::<Vec<((u64, usize), Vec<String>)>>();
+
+        let mut groups: BTreeMap<(u64, usize), Vec<Vec<String>>> = BTreeMap::new();
+        for (key, path) in hashes {
+            groups.entry(key).or_insert_with(Vec::new).push(path);
+        }
+
+        let mut groups = groups.into_iter()
+            .map(|(_, mut paths)| { paths.sort(); paths })
+            .filter(|paths| paths.len() > 1)
+            .collect::<Vec<Vec<Vec<String>>>>();
+        groups.sort();
+        groups
+    }
+
+    /// This function removes the duplicated `PackedFiles` of the `PackFile`, keeping only the first one (by path) of each group of duplicates.
+    ///
+    /// Before removing a `PackedFile`, its full contents are compared with the ones of the `PackedFile` we keep, so hash collisions never
+    /// remove anything. It returns the paths of the removed `PackedFiles`.
+    pub fn deduplicate_files(&mut self) -> Vec<Vec<String>> {
+        let mut removed_paths = vec![];
+        for paths in self.find_duplicate_files() {
+            let kept_data = match self.get_ref_packed_file_by_path(&paths[0]).map(|packed_file| Self::get_current_data(packed_file)) {
+                Some(Ok(data)) => data,
+                _ => continue,
+            };
+
+            for path in &paths[1..] {
+                if let Some(Ok(data)) = self.get_ref_packed_file_by_path(path).map(|packed_file| Self::get_current_data(packed_file)) {
+                    if data == kept_data {
+                        removed_paths.push(path.to_vec());
+                    }
+                }
+            }
+        }
+
+        removed_paths.iter().for_each(|path| self.remove_packed_file_by_path(path));
+        removed_paths
+    }
+
+    /// This function returns the current data of the provided `PackedFile`, decompressed, encoding it first if it's decoded.
+    fn get_current_data(packed_file: &PackedFile) -> Result<Vec<u8>> {
+        match packed_file.get_ref_decoded().encode() {
+            Some(data) => data,
+            None => packed_file.get_raw_data(),
+        }
+    }
+
     /// This function removes, if exists, a `PackedFile` with the provided path from the `PackFile`.
     pub fn remove_packed_file_by_path(&mut self, path: &[String]) {
         if let Some(position) = self.packed_files.par_iter().position_any(|x| x.get_path() == path) {
             self.packed_files.remove(position);
+            self.modified = true;
         }
     }
 
@@ -1107,6 +1747,7 @@ impl PackFile {
             .collect();
         for position in positions.iter().rev() {
             self.packed_files.remove(*position);
+            self.modified = true;
         }
     }
 
@@ -1119,6 +1760,7 @@ impl PackFile {
             .collect();
         for position in positions.iter().rev() {
             self.packed_files.remove(*position);
+            self.modified = true;
         }
     }
 
@@ -1164,50 +1806,305 @@ impl PackFile {
         item_types_clean
     }
 
-    /// This function extracts, if exists, a `PackedFile` with the provided path from the `PackFile`.
+    /// This function removes the provided items from the `PackFile`, but only if nothing else in the `PackFile` references them, or if `force` is true.
     ///
-    /// The destination path is always `destination_path/packfile_name/path_to_packedfile/packed_file`.
-    pub fn extract_packed_file_by_path(&mut self, path: &[String], destination_path: &Path) -> Result<()> {
-        match self.get_ref_mut_packed_file_by_path(path) {
-            Some(ref mut packed_file) => {
-
-                // Save it, in case it's cached.
-                packed_file.encode()?;
+    /// It returns the list of removed items (empty if nothing was removed) and the references found to the `PackedFiles` we tried to remove.
+    pub fn remove_packed_files_checked(&mut self, item_types: &[PathType], force: bool) -> Result<(Vec<PathType>, Vec<Reference>)> {
+        let paths = self.get_paths_from_path_types(item_types);
+        let references = self.find_references_to_paths(&paths)?;
+        if !references.is_empty() && !force {
+            return Ok((vec![], references));
+        }
 
-                // We get his internal path without his name.
-                let mut internal_path = packed_file.get_path().to_vec();
-                let file_name = internal_path.pop().unwrap();
+        Ok((self.remove_packed_files_by_type(item_types), references))
+    }
 
-                // Then, we join his internal path with his destination path, so we have his almost-full path (his final path without his name).
-                // This way we can create the entire folder structure up to the file itself.
-                let mut current_path = destination_path.to_path_buf().join(internal_path.iter().collect::<PathBuf>());
-                DirBuilder::new().recursive(true).create(&current_path)?;
+    /// This function returns the amount of entries of each DB Table in the `PackFile`, by table name.
+    ///
+    /// Tables split across multiple `PackedFiles` get their entries summed. The paths of the tables that couldn't be decoded are returned separately.
+    pub fn count_entries_by_table(&mut self) -> Result<(BTreeMap<String, usize>, Vec<Vec<String>>)> {
+        let schema = SCHEMA.read().unwrap();
+        let schema = if let Some(ref schema) = *schema { schema } else { return Err(ErrorKind::SchemaNotFound.into()) };
 
-                // Finish the path and try to save the file to disk.
-                current_path.push(&file_name);
-                let mut file = BufWriter::new(File::create(&current_path)?);
-                if file.write_all(&packed_file.get_raw_data()?).is_err() {
-                    return Err(ErrorKind::ExtractError(path.to_vec()).into());
+        let results = self.packed_files.par_iter_mut()
+            .filter(|packed_file| packed_file.get_packed_file_type_by_path() == PackedFileType::DB)
+            .map(|packed_file| {
+                let path = packed_file.get_path().to_vec();
+                match packed_file.decode_return_ref_no_locks(&schema) {
+                    Ok(DecodedPackedFile::DB(table)) => Ok((path[1].to_owned(), table.get_entry_count())),
+                    _ => Err(path),
                 }
-                Ok(())
+            })
+            .collect::<Vec<std::result::Result<(String, usize), Vec<String>>>>();
+
+        let mut entry_counts = BTreeMap::new();
+        let mut undecodable_tables = vec![];
+        for result in results {
+            match result {
+                Ok((table_name, entry_count)) => *entry_counts.entry(table_name).or_insert(0) += entry_count,
+                Err(path) => undecodable_tables.push(path),
             }
-            None => Err(ErrorKind::PackedFileNotFound.into())
         }
+
+        Ok((entry_counts, undecodable_tables))
     }
 
-    /// This function extract, if exists, all `PackedFile` of the provided types from the `PackFile` to disk.
+    /// This function returns the location on disk of the data of the provided `PackedFiles` that are not loaded to memory yet.
     ///
-    /// As this can fail for some files, and work for others, we return `Ok(amount_files_extracted)` only if all files were extracted correctly.
-    /// If any of them failed, we return `Error` with a list of the paths that failed to get extracted.
-    pub fn extract_packed_files_by_type(
-        &mut self,
-        item_types: &[PathType],
-        extracted_path: &PathBuf,
-    ) -> Result<u32> {
+    /// This is used to read their data from another thread without locking the `PackFile`. Paths not found are ignored.
+    pub fn get_prefetch_sources(&self, paths: &[Vec<String>]) -> Vec<(Vec<String>, RawOnDisk)> {
+        paths.iter()
+            .filter_map(|path| self.get_ref_packed_file_by_path(path))
+            .filter_map(|packed_file| packed_file.get_ref_raw().get_data_on_disk().map(|raw_on_disk| (packed_file.get_path().to_vec(), raw_on_disk)))
+            .collect()
+    }
+
+    /// This function returns the size in bytes of the data of the `PackedFiles` of the `PackFile` loaded to memory.
+    pub fn get_loaded_data_size(&self) -> u64 {
+        self.packed_files.iter()
+            .filter(|packed_file| packed_file.get_ref_raw().is_loaded())
+            .map(|packed_file| u64::from(packed_file.get_raw_data_size()))
+            .sum()
+    }
+
+    /// This function generates a `Schema` with an empty `Definition` for each version of each DB Table in the `PackFile` we cannot decode.
+    ///
+    /// A table is undecodable if the loaded `Schema` has no definition for the version in its header. Tables with broken headers are ignored,
+    /// as we cannot know their version. The data of the tables is read without caching it, so this doesn't load the entire `PackFile` into memory.
+    pub fn generate_schema_skeleton(&self) -> Result<Schema> {
+        let schema = SCHEMA.read().unwrap();
+        let mut skeleton = Schema::default();
+        for packed_file in self.get_ref_packed_files_by_type(PackedFileType::DB, false) {
+            if let DecodedPackedFile::DB(_) = packed_file.get_ref_decoded() { continue; }
+            let table_name = match packed_file.get_path().get(1) {
+                Some(table_name) => table_name.to_owned(),
+                None => continue,
+            };
+
+            let version = match DB::read_header(&packed_file.get_raw_data()?) {
+                Ok((version, _, _, _, _)) => version,
+                Err(_) => continue,
+            };
+
+            let is_decodable = match *schema {
+                Some(ref schema) => schema.get_ref_versioned_file_db(&table_name).and_then(|x| x.get_version(version)).is_ok(),
+                None => false,
+            };
+
+            if !is_decodable {
+                match skeleton.get_ref_mut_versioned_file_db(&table_name) {
+                    Ok(versioned_file) => versioned_file.add_version(&Definition::new(version)),
+                    Err(_) => skeleton.add_versioned_file(&VersionedFile::DB(table_name, vec![Definition::new(version)])),
+                }
+            }
+        }
+
+        Ok(skeleton)
+    }
+
+    /// This function returns the DB Tables of the `PackFile` with a version older than the one of the same table in the dependency database.
+    ///
+    /// For each outdated table, it returns its path, its version, and the last version of the table in the dependency database.
+    /// Tables without a counterpart in the dependency database are ignored. Versions are read from the headers, so undecodable tables are checked too.
+    pub fn find_outdated_table_versions(&self, dependencies: &Dependencies) -> Vec<(Vec<String>, i32, i32)> {
+        let mut vanilla_versions: BTreeMap<String, i32> = BTreeMap::new();
+        for vanilla_table in dependencies.get_ref_dependency_database() {
+            let path = vanilla_table.get_path();
+            if path.len() == 3 && path[0] == "db" {
+                if let Ok((vanilla_version, ..)) = vanilla_table.get_raw_data().and_then(|data| DB::read_header(&data)) {
+                    let version = vanilla_versions.entry(path[1].to_owned()).or_insert(vanilla_version);
+                    *version = (*version).max(vanilla_version);
+                }
+            }
+        }
+
+        let mut outdated_tables = self.packed_files.par_iter()
+            .filter(|packed_file| packed_file.get_packed_file_type_by_path() == PackedFileType::DB)
+            .filter_map(|packed_file| {
+                let path = packed_file.get_path();
+                let vanilla_version = *vanilla_versions.get(path.get(1)?)?;
+                let version = match packed_file.get_ref_decoded() {
+                    DecodedPackedFile::DB(table) => table.get_ref_definition().get_version(),
+                    _ => DB::read_header(&packed_file.get_raw_data().ok()?).ok()?.0,
+                };
+
+                if version < vanilla_version { Some((path.to_vec(), version, vanilla_version)) }
+                else { None }
+            })
+            .collect::<Vec<(Vec<String>, i32, i32)>>();
+
+        outdated_tables.sort();
+        outdated_tables
+    }
+
+    /// This function finds all the references to the provided `PackedFiles` from the rest of the `PackedFiles` in the `PackFile`.
+    ///
+    /// This checks:
+    /// - Asset paths (whole path segments) in string columns of DB Tables and in Text files.
+    /// - Values in reference columns of DB Tables that point to the values of the provided DB Tables.
+    ///
+    /// References from the provided `PackedFiles` to themselves are ignored.
+    pub fn find_references_to_paths(&mut self, paths: &[Vec<String>]) -> Result<Vec<Reference>> {
+        let schema = SCHEMA.read().unwrap();
+        let schema = if let Some(ref schema) = *schema { schema } else { return Err(ErrorKind::SchemaNotFound.into()) };
+
+        let targets = paths.iter().cloned().collect::<HashSet<Vec<String>>>();
+        let target_assets = paths.iter().map(|x| (x.to_vec(), x.join("/").to_ascii_lowercase())).collect::<Vec<(Vec<String>, String)>>();
+
+        // Get the DB Tables we want to check, so we can check later if someone references their data.
+        let mut target_tables: BTreeMap<String, Vec<(Vec<String>, DB)>> = BTreeMap::new();
+        for packed_file in self.get_ref_mut_packed_files_by_type(PackedFileType::DB, false) {
+            let path = packed_file.get_path().to_vec();
+            if targets.contains(&path) {
+                if let Ok(DecodedPackedFile::DB(table)) = packed_file.decode_return_ref_no_locks(&schema) {
+                    target_tables.entry(path[1].to_owned()).or_insert_with(Vec::new).push((path, table.clone()));
+                }
+            }
+        }
+
+        // Cache of the values of each referenced table/column, with the path of the table containing each value.
+        let mut referenced_values: BTreeMap<(String, String), BTreeMap<String, Vec<String>>> = BTreeMap::new();
+        let mut references = vec![];
+
+        for packed_file in self.get_ref_mut_packed_files_by_types(&[PackedFileType::DB, PackedFileType::Text(TextType::Plain)], false) {
+            let referrer = packed_file.get_path().to_vec();
+            if targets.contains(&referrer) { continue; }
+
+            match packed_file.decode_return_ref_no_locks(&schema) {
+                Ok(DecodedPackedFile::DB(table)) => {
+                    let fields = table.get_ref_definition().get_fields_processed();
+                    for (row, data) in table.get_ref_table_data().iter().enumerate() {
+                        for (field, cell) in fields.iter().zip(data.iter()) {
+                            let text = match cell {
+                                DecodedData::StringU8(text) |
+                                DecodedData::StringU16(text) |
+                                DecodedData::OptionalStringU8(text) |
+                                DecodedData::OptionalStringU16(text) => text,
+                                _ => continue,
+                            };
+
+                            for (referenced, asset) in &target_assets {
+                                if !Self::find_path_segments(text, asset).is_empty() {
+                                    references.push(Reference { referrer: referrer.to_vec(), referenced: referenced.to_vec(), position: row + 1 });
+                                }
+                            }
+
+                            if let Some((ref_table, ref_column)) = field.get_is_reference() {
+                                let ref_table = format!("{}_tables", ref_table);
+                                if let Some(tables) = target_tables.get(&ref_table) {
+                                    let values = referenced_values.entry((ref_table, ref_column.to_owned())).or_insert_with(|| {
+                                        let mut values: BTreeMap<String, Vec<String>> = BTreeMap::new();
+                                        for (path, table) in tables {
+                                            if let Some(column) = table.get_ref_definition().get_fields_processed().iter().position(|x| x.get_name() == ref_column) {
+                                                for row in table.get_ref_table_data() {
+                                                    values.entry(row[column].data_to_string()).or_insert_with(|| path.to_vec());
+                                                }
+                                            }
+                                        }
+                                        values
+                                    });
+
+                                    if let Some(referenced) = values.get(text).filter(|_| !text.is_empty()) {
+                                        references.push(Reference { referrer: referrer.to_vec(), referenced: referenced.to_vec(), position: row + 1 });
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                Ok(DecodedPackedFile::Text(text)) => {
+                    for (line_index, line) in text.get_ref_contents().lines().enumerate() {
+                        for (referenced, asset) in &target_assets {
+                            if !Self::find_path_segments(line, asset).is_empty() {
+                                references.push(Reference { referrer: referrer.to_vec(), referenced: referenced.to_vec(), position: line_index + 1 });
+                            }
+                        }
+                    }
+                }
+                _ => continue,
+            }
+        }
+
+        references.dedup();
+        Ok(references)
+    }
+
+    /// This function extracts, if exists, a `PackedFile` with the provided path from the `PackFile`.
+    ///
+    /// The destination path is always `destination_path/packfile_name/path_to_packedfile/packed_file`.
+    pub fn extract_packed_file_by_path(&mut self, path: &[String], destination_path: &Path) -> Result<()> {
+        match self.get_ref_mut_packed_file_by_path(path) {
+            Some(ref mut packed_file) => {
+
+                // Save it, in case it's cached.
+                packed_file.encode()?;
+
+                // We get his internal path without his name.
+                let mut internal_path = packed_file.get_path().to_vec();
+                let file_name = internal_path.pop().unwrap();
+
+                // Then, we join his internal path with his destination path, so we have his almost-full path (his final path without his name).
+                // This way we can create the entire folder structure up to the file itself.
+                let mut current_path = destination_path.to_path_buf().join(internal_path.iter().collect::<PathBuf>());
+                DirBuilder::new().recursive(true).create(&current_path)?;
+
+                // Finish the path and try to save the file to disk.
+                current_path.push(&file_name);
+                let mut file = BufWriter::new(File::create(&current_path)?);
+                if file.write_all(&packed_file.get_raw_data()?).is_err() {
+                    return Err(ErrorKind::ExtractError(path.to_vec()).into());
+                }
+                Ok(())
+            }
+            None => Err(ErrorKind::PackedFileNotFound.into())
+        }
+    }
+
+    /// This function returns the paths of all the `PackedFiles` modified after the provided timestamp, sorted.
+    ///
+    /// If the `PackFile` doesn't store the timestamps of its `PackedFiles`, this returns an error, as we cannot know which ones were modified.
+    pub fn get_files_modified_after(&self, timestamp: i64) -> Result<Vec<Vec<String>>> {
+        if !self.bitmask.contains(PFHFlags::HAS_INDEX_WITH_TIMESTAMPS) {
+            return Err(ErrorKind::PackFileTimestampsNotAvailable.into());
+        }
+
+        let mut paths = self.packed_files.par_iter()
+            .filter(|x| x.get_ref_raw().get_timestamp() > timestamp)
+            .map(|x| x.get_path().to_vec())
+            .collect::<Vec<Vec<String>>>();
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// This function extract, if exists, all `PackedFile` of the provided types from the `PackFile` to disk.
+    ///
+    /// As this can fail for some files, and work for others, we return `Ok(amount_files_extracted)` only if all files were extracted correctly.
+    /// If any of them failed, we return `Error` with a list of the paths that failed to get extracted.
+    pub fn extract_packed_files_by_type(
+        &mut self,
+        item_types: &[PathType],
+        extracted_path: &PathBuf,
+    ) -> Result<u32> {
+        let options = ExtractOptions::default();
+        self.extract_packed_files_by_type_with_options(item_types, extracted_path, &options).map(|report| report.extracted + report.overwritten)
+    }
+
+    /// This function extract, if exists, all `PackedFile` of the provided types from the `PackFile` to disk, naming them according to the provided options.
+    ///
+    /// `PackedFiles` are extracted in order of their paths, so the names given by `ExtractOverwritePolicy::RenameWithSuffix` are deterministic.
+    /// Like `extract_packed_files_by_type`, this returns an `Error` with a list of the paths that failed to get extracted if any of them failed.
+    pub fn extract_packed_files_by_type_with_options(
+        &mut self,
+        item_types: &[PathType],
+        extracted_path: &Path,
+        options: &ExtractOptions,
+    ) -> Result<ExtractReport> {
 
         // These variables are here to keep track of what we have extracted and what files failed.
-        let mut files_extracted = 0;
+        let mut report = ExtractReport::default();
         let mut error_files = vec![];
+        let mut paths = vec![];
 
         // We need to "clean" the selected path list to ensure we don't pass stuff already extracted.
         let item_types_clean = PathType::dedup(item_types);
@@ -1233,45 +2130,54 @@ impl PackFile {
                 // There should be no duplicates here thanks to the filters from before.
                 for item_type in &item_types_clean {
                     match item_type {
-
-                        // For individual `PackedFiles`, we extract them one by one.
-                        PathType::File(path) => {
-                            match self.extract_packed_file_by_path(path, extracted_path) {
-                                Ok(_) => files_extracted += 1,
-                                Err(_) => error_files.push(format!("{:?}", path)),
-                            }
-                        },
-
-                        PathType::Folder(path) => {
-                            for packed_file in self.get_ref_mut_packed_files_by_path_start(path) {
-                                match packed_file.extract_packed_file(extracted_path) {
-                                    Ok(_) => files_extracted += 1,
-                                    Err(_) => error_files.push(format!("{:?}", path)),
-                                }
-                            }
-                        },
-
+                        PathType::File(path) => paths.push(path.to_vec()),
+                        PathType::Folder(path) => paths.extend(self.get_ref_packed_files_by_path_start(path).iter().map(|x| x.get_path().to_vec())),
                         _ => unreachable!(),
                     }
                 }
             },
 
             // If the `PackFile` is selected, just extract it and everything will get extracted with it.
-            4 | 5 | 6 | 7 => {
-
-                // For each PackedFile we have, just extracted in the folder we got, under the PackFile's folder.
-                for packed_file in self.get_ref_mut_packed_files_all() {
-                    match packed_file.extract_packed_file(extracted_path) {
-                        Ok(_) => files_extracted += 1,
-                        Err(_) => error_files.push(format!("{:?}", packed_file.get_path())),
-                    }
-                }
-            },
+            4 | 5 | 6 | 7 => paths.extend(self.get_ref_packed_files_all_paths().iter().map(|x| x.to_vec())),
 
             // No paths selected, none selected, invalid path selected, or invalid value.
             0 | 8..=255 => return Err(ErrorKind::NonExistantFile.into()),
         }
 
+        // Sort them, so the names given when renaming are always the same.
+        paths.sort();
+        paths.dedup();
+        let operation = OPERATION_PAUSE.start();
+        for path in &paths {
+            operation.wait_if_paused();
+            let destination_path = Self::get_extract_destination_path(path, extracted_path, options);
+
+            // Only existing files are collisions. Anything else in the way is reported as an error when extracting.
+            let is_collision = destination_path.is_file();
+            let destination_path = match options.overwrite_policy {
+                ExtractOverwritePolicy::Skip if is_collision => {
+                    report.skipped += 1;
+                    continue;
+                }
+                ExtractOverwritePolicy::RenameWithSuffix if is_collision => Self::get_extract_unique_path(&destination_path),
+                _ => destination_path,
+            };
+
+            let result = match self.get_ref_mut_packed_file_by_path(path) {
+                Some(packed_file) => packed_file.extract_packed_file_to_path(&destination_path),
+                None => Err(ErrorKind::PackedFileNotFound.into()),
+            };
+
+            match result {
+                Ok(_) => match options.overwrite_policy {
+                    _ if !is_collision => report.extracted += 1,
+                    ExtractOverwritePolicy::Overwrite => report.overwritten += 1,
+                    _ => report.renamed += 1,
+                },
+                Err(_) => error_files.push(format!("{:?}", path)),
+            }
+        }
+
         // If there is any error in the list, report it.
         if !error_files.is_empty() {
             let error_files_string = error_files.iter().map(|x| format!("<li>{}</li>", x)).collect::<Vec<String>>();
@@ -1279,7 +2185,36 @@ impl PackFile {
         }
 
         // If we reach this, return the amount of extracted files.
-        Ok(files_extracted)
+        Ok(report)
+    }
+
+    /// This function returns the path a `PackedFile` should be extracted to, according to the provided options.
+    fn get_extract_destination_path(path: &[String], extracted_path: &Path, options: &ExtractOptions) -> PathBuf {
+        let mut internal_path = path.to_vec();
+        let file_name = internal_path.pop().unwrap_or_default();
+        let folder_name = internal_path.last().cloned().unwrap_or_default();
+        let mut file_name = format!("{}{}", options.prefix.replace("{folder}", &folder_name), file_name);
+
+        if options.lowercase {
+            file_name = file_name.to_lowercase();
+            internal_path.iter_mut().for_each(|x| *x = x.to_lowercase());
+        }
+
+        let mut destination_path = extracted_path.to_path_buf();
+        if !options.flatten {
+            destination_path.push(internal_path.iter().collect::<PathBuf>());
+        }
+        destination_path.push(file_name);
+        destination_path
+    }
+
+    /// This function returns the provided path with the first numeric suffix that makes it not exist on disk: `name_1.ext`, `name_2.ext`,...
+    fn get_extract_unique_path(path: &Path) -> PathBuf {
+        let stem = path.file_stem().map(|x| x.to_string_lossy().to_string()).unwrap_or_default();
+        let extension = path.extension().map(|x| format!(".{}", x.to_string_lossy())).unwrap_or_default();
+        (1..).map(|index| path.with_file_name(format!("{}_{}{}", stem, index, extension)))
+            .find(|x| !x.exists())
+            .unwrap()
     }
 
     /// This function enables/disables compression in all `PackedFiles` inside the `PackFile`. Partial compression is not supported.
@@ -1287,6 +2222,29 @@ impl PackFile {
         self.packed_files.par_iter_mut().for_each(|x| x.get_ref_mut_raw().set_should_be_compressed(enable));
     }
 
+    /// This function decompresses only the `PackedFiles` with the provided paths, and disables their compression when saving.
+    ///
+    /// The decompressed `PackedFiles` are loaded to memory. The rest of the `PackedFiles` are left as they were, so if they're
+    /// lazy-loaded, they stay on disk. If any of the paths is not in the `PackFile`, nothing is decompressed.
+    pub fn decompress_paths(&mut self, paths: &[Vec<String>]) -> Result<()> {
+        if paths.iter().any(|path| self.get_ref_packed_file_by_path(path).is_none()) {
+            return Err(ErrorKind::PackedFileNotFound.into());
+        }
+
+        for path in paths {
+            if let Some(packed_file) = self.get_ref_mut_packed_file_by_path(path) {
+                let raw = packed_file.get_ref_mut_raw();
+                if raw.get_compression_state() || raw.get_should_be_compressed() {
+                    raw.get_data_and_keep_it()?;
+                    raw.set_should_be_compressed(false);
+                    packed_file.set_modified(true);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// This function returns the notes contained within the provided `PackFile`.
     pub fn get_notes(&self) -> &Option<String> {
         &self.notes
@@ -1295,6 +2253,435 @@ impl PackFile {
     /// This function saves your notes within the provided `PackFile`.
     pub fn set_notes(&mut self, notes: &Option<String>) {
         self.notes = notes.clone();
+        self.modified = true;
+    }
+
+    /// This function exports the notes of the `PackFile` to the provided path. If there are no notes, the file is created empty.
+    pub fn export_notes(&self, path: &Path) -> Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        if let Some(ref notes) = self.notes {
+            file.write_all(notes.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// This function imports the notes of the `PackFile` from the provided path.
+    ///
+    /// If `append` is true, the imported notes are added after the current ones. Otherwise, they replace them.
+    pub fn import_notes(&mut self, path: &Path, append: bool) -> Result<()> {
+        let mut imported_notes = String::new();
+        BufReader::new(File::open(path)?).read_to_string(&mut imported_notes)?;
+
+        let notes = match self.notes {
+            Some(ref notes) if append && !notes.is_empty() => format!("{}\n{}", notes, imported_notes),
+            _ => imported_notes,
+        };
+
+        self.set_notes(&Some(notes));
+        Ok(())
+    }
+
+    /// This function exports the raw data of the table `PackedFile` with the provided path to the provided folder,
+    /// together with the `Definition` needed to decode it.
+    ///
+    /// The definition is resolved from the header of the table, so this works even if the table fails to decode.
+    pub fn export_packed_file_with_definition(&mut self, path: &[String], destination_path: &Path) -> Result<()> {
+        let schema = SCHEMA.read().unwrap();
+        let schema = if let Some(ref schema) = *schema { schema } else { return Err(ErrorKind::SchemaNotFound.into()) };
+
+        let packed_file = self.get_ref_mut_packed_file_by_path(path).ok_or_else(|| Error::from(ErrorKind::PackedFileNotFound))?;
+        let data = packed_file.encode_and_return()?.get_data()?;
+        let definition = match PackedFileType::get_packed_file_type(path) {
+            PackedFileType::DB => DB::get_definition_from_data(&data, &path[1], schema)?,
+            PackedFileType::Loc => {
+                let (version, _) = Loc::read_header(&data)?;
+                schema.get_ref_versioned_file_loc()?.get_version(version)?.clone()
+            }
+            _ => return Err(ErrorKind::PackedFileBundleNotATable.into()),
+        };
+
+        let bundle = PackedFileDefinitionBundle {
+            path: path.to_vec(),
+            definition,
+        };
+
+        DirBuilder::new().recursive(true).create(destination_path)?;
+        File::create(destination_path.join(BUNDLE_DATA_FILE_NAME))?.write_all(&data)?;
+        File::create(destination_path.join(BUNDLE_DEFINITION_FILE_NAME))?.write_all(to_string_pretty(&bundle)?.as_bytes())?;
+        Ok(())
+    }
+
+    /// This function imports a table `PackedFile` exported with `export_packed_file_with_definition` from the provided folder,
+    /// decoding it with its bundled `Definition` instead of the one in the currently loaded `Schema`.
+    ///
+    /// The `PackedFile` is added to its original path, replacing any `PackedFile` already there. Returns the path of the imported `PackedFile`.
+    pub fn import_packed_file_with_definition(&mut self, source_path: &Path) -> Result<Vec<String>> {
+        let mut data = vec![];
+        BufReader::new(File::open(source_path.join(BUNDLE_DATA_FILE_NAME))?).read_to_end(&mut data)?;
+        let bundle: PackedFileDefinitionBundle = serde_json::from_reader(BufReader::new(File::open(source_path.join(BUNDLE_DEFINITION_FILE_NAME))?))?;
+
+        let decoded = match PackedFileType::get_packed_file_type(&bundle.path) {
+            PackedFileType::DB => DecodedPackedFile::DB(DB::read_with_definition(&data, &bundle.path[1], &bundle.definition)?),
+            PackedFileType::Loc => DecodedPackedFile::Loc(Loc::read_with_definition(&data, &bundle.definition)?),
+            _ => return Err(ErrorKind::PackedFileBundleNotATable.into()),
+        };
+
+        let raw_data = RawPackedFile::read_from_vec(bundle.path.to_vec(), self.get_file_name(), 0, false, data);
+        let mut packed_file = PackedFile::new_from_raw(&raw_data);
+        packed_file.set_decoded(&decoded);
+        self.add_packed_file(&packed_file, true)
+    }
+
+    /// This function explodes the `PackFile` into the provided folder: its `PackedFiles` are extracted decompressed and decrypted
+    /// to a `data` subfolder, and everything else needed to rebuild the `PackFile` is written to a manifest.
+    ///
+    /// If the folder already contains an exploded `PackFile`, its `data` subfolder is replaced.
+    pub fn explode(&self, destination_path: &Path) -> Result<()> {
+        let data_path = destination_path.join(EXPLODE_DATA_FOLDER_NAME);
+        if destination_path.join(EXPLODE_MANIFEST_FILE_NAME).is_file() && data_path.is_dir() {
+            std::fs::remove_dir_all(&data_path)?;
+        }
+
+        let mut entries = vec![];
+        for packed_file in &self.packed_files {
+            let raw = packed_file.get_ref_raw();
+
+            // If we decoded it, re-encode it, so we get the same data we would get when saving.
+            let data = match packed_file.get_ref_decoded().encode() {
+                Some(data) => data?,
+                None => raw.get_data()?,
+            };
+
+            let mut file_path = data_path.to_path_buf();
+            file_path.extend(packed_file.get_path());
+            if let Some(parent) = file_path.parent() {
+                DirBuilder::new().recursive(true).create(parent)?;
+            }
+            File::create(&file_path)?.write_all(&data)?;
+            entries.push(ExplodeManifestEntry::new(packed_file, None));
+        }
+
+        let manifest = ExplodeManifest::new(self, entries);
+        DirBuilder::new().recursive(true).create(destination_path)?;
+        File::create(destination_path.join(EXPLODE_MANIFEST_FILE_NAME))?.write_all(to_string_pretty(&manifest)?.as_bytes())?;
+        Ok(())
+    }
+
+    /// This function implodes a `PackFile` exploded with `explode` from the provided folder.
+    ///
+    /// `PackedFiles` keep the order and metadata they have in the manifest. `PackedFiles` removed from the folder are not added,
+    /// and new files found in the folder are added at the end, with their last modified date as timestamp.
+    pub fn implode(source_path: &Path) -> Result<Self> {
+        let manifest: ExplodeManifest = serde_json::from_reader(BufReader::new(File::open(source_path.join(EXPLODE_MANIFEST_FILE_NAME))?))?;
+        let data_path = source_path.join(EXPLODE_DATA_FOLDER_NAME);
+        let mut pack_file = Self::new_from_explode_manifest(&manifest)?;
+
+        let mut known_paths = HashSet::new();
+        for entry in &manifest.packed_files {
+            let mut file_path = data_path.to_path_buf();
+            file_path.extend(&entry.path);
+            if !file_path.is_file() { continue; }
+
+            let mut data = vec![];
+            BufReader::new(File::open(&file_path)?).read_to_end(&mut data)?;
+
+            let raw_data = entry.get_raw_packed_file(&manifest.file_name, data)?;
+            pack_file.packed_files.push(PackedFile::new_from_raw(&raw_data));
+            known_paths.insert(file_path);
+        }
+
+        pack_file.add_new_exploded_files(&data_path, &known_paths)?;
+        Ok(pack_file)
+    }
+
+    /// This function exports the `PackFile` into the provided folder in a format friendly to version control systems, like git.
+    ///
+    /// It works like `explode`, but DB and Loc Tables are written as TSV files instead of binary files, so their changes can be diffed.
+    /// The TSV files go to their own folder, with the same path they have in the `PackFile`, so they cannot collide with the binary files.
+    /// The definition used by each table is stored in the manifest, so they can be rebuilt exactly as they were.
+    ///
+    /// Tables that cannot be decoded, or with sequence fields (which cannot be imported from TSV), are written as binary files.
+    pub fn export_for_version_control(&self, destination_path: &Path) -> Result<()> {
+        let data_path = destination_path.join(EXPLODE_DATA_FOLDER_NAME);
+        let tables_path = destination_path.join(VERSION_CONTROL_TABLES_FOLDER_NAME);
+        if destination_path.join(VERSION_CONTROL_MANIFEST_FILE_NAME).is_file() {
+            if data_path.is_dir() {
+                std::fs::remove_dir_all(&data_path)?;
+            }
+
+            if tables_path.is_dir() {
+                std::fs::remove_dir_all(&tables_path)?;
+            }
+        }
+
+        let schema = SCHEMA.read().unwrap();
+        let mut entries = vec![];
+        for packed_file in &self.packed_files {
+
+            // Decode a copy of the tables that are not decoded yet, so we don't leave them cached.
+            let decoded = match packed_file.get_ref_decoded() {
+                DecodedPackedFile::Unknown => match *schema {
+                    Some(ref schema) => match packed_file.get_packed_file_type_by_path() {
+                        PackedFileType::DB | PackedFileType::Loc => DecodedPackedFile::decode_no_locks(&mut packed_file.get_ref_raw().clone(), schema).unwrap_or(DecodedPackedFile::Unknown),
+                        _ => DecodedPackedFile::Unknown,
+                    }
+                    None => DecodedPackedFile::Unknown,
+                }
+                decoded => decoded.clone(),
+            };
+
+            let table = match decoded {
+                DecodedPackedFile::DB(ref data) if !Self::has_sequence_fields(data.get_ref_definition()) => {
+                    let file_path = Self::get_version_control_file_path(&tables_path, packed_file.get_path())?;
+                    data.export_tsv(&file_path, &data.name)?;
+                    Some(VersionControlTable::DB {
+                        name: data.name.to_owned(),
+                        mysterious_byte: data.mysterious_byte,
+                        uuid: data.uuid.to_owned(),
+                        definition: data.get_ref_definition().clone(),
+                    })
+                }
+                DecodedPackedFile::Loc(ref data) if !Self::has_sequence_fields(data.get_ref_definition()) => {
+                    let file_path = Self::get_version_control_file_path(&tables_path, packed_file.get_path())?;
+                    data.export_tsv(&file_path, TSV_NAME_LOC)?;
+                    Some(VersionControlTable::Loc {
+                        definition: data.get_ref_definition().clone(),
+                    })
+                }
+                _ => {
+                    let data = match packed_file.get_ref_decoded().encode() {
+                        Some(data) => data?,
+                        None => packed_file.get_ref_raw().get_data()?,
+                    };
+                    let file_path = Self::get_version_control_file_path(&data_path, packed_file.get_path())?;
+                    File::create(&file_path)?.write_all(&data)?;
+                    None
+                }
+            };
+
+            entries.push(ExplodeManifestEntry::new(packed_file, table));
+        }
+
+        let manifest = ExplodeManifest::new(self, entries);
+        DirBuilder::new().recursive(true).create(destination_path)?;
+        File::create(destination_path.join(VERSION_CONTROL_MANIFEST_FILE_NAME))?.write_all(to_string_pretty(&manifest)?.as_bytes())?;
+        Ok(())
+    }
+
+    /// This function rebuilds a `PackFile` exported with `export_for_version_control` from the provided folder.
+    ///
+    /// Tables exported as TSV are imported with the definition stored in the manifest and encoded back to binary.
+    /// As with `implode`, files removed from the folder are not added, and new binary files are added at the end as they are.
+    pub fn import_from_version_control(source_path: &Path) -> Result<Self> {
+        let manifest: ExplodeManifest = serde_json::from_reader(BufReader::new(File::open(source_path.join(VERSION_CONTROL_MANIFEST_FILE_NAME))?))?;
+        let data_path = source_path.join(EXPLODE_DATA_FOLDER_NAME);
+        let tables_path = source_path.join(VERSION_CONTROL_TABLES_FOLDER_NAME);
+        let mut pack_file = Self::new_from_explode_manifest(&manifest)?;
+
+        let mut known_paths = HashSet::new();
+        for entry in &manifest.packed_files {
+            let mut file_path = if entry.table.is_some() { tables_path.to_path_buf() } else { data_path.to_path_buf() };
+            file_path.extend(&entry.path);
+            if !file_path.is_file() { continue; }
+
+            let (data, decoded) = match entry.table {
+                Some(VersionControlTable::DB { ref name, mysterious_byte, ref uuid, ref definition }) => {
+                    let mut table = DB::import_tsv(definition, &file_path, name, false)?;
+                    table.mysterious_byte = mysterious_byte;
+                    table.uuid = uuid.to_owned();
+                    (table.save()?, DecodedPackedFile::DB(table))
+                }
+                Some(VersionControlTable::Loc { ref definition }) => {
+                    let table = Loc::import_tsv(definition, &file_path, TSV_NAME_LOC, false)?;
+                    (table.save()?, DecodedPackedFile::Loc(table))
+                }
+                None => {
+                    let mut data = vec![];
+                    BufReader::new(File::open(&file_path)?).read_to_end(&mut data)?;
+                    (data, DecodedPackedFile::Unknown)
+                }
+            };
+
+            let raw_data = entry.get_raw_packed_file(&manifest.file_name, data)?;
+            let mut packed_file = PackedFile::new_from_raw(&raw_data);
+            packed_file.set_decoded(&decoded);
+            pack_file.packed_files.push(packed_file);
+            known_paths.insert(file_path);
+        }
+
+        pack_file.add_new_exploded_files(&data_path, &known_paths)?;
+        Ok(pack_file)
+    }
+
+    /// This function returns the path a `PackedFile` is written to when exporting for version control, creating its parent folders.
+    fn get_version_control_file_path(folder_path: &Path, path: &[String]) -> Result<PathBuf> {
+        let mut file_path = folder_path.to_path_buf();
+        file_path.extend(path);
+        if let Some(parent) = file_path.parent() {
+            DirBuilder::new().recursive(true).create(parent)?;
+        }
+
+        Ok(file_path)
+    }
+
+    /// This function returns if the provided definition has sequence fields, which cannot be imported from TSV.
+    fn has_sequence_fields(definition: &Definition) -> bool {
+        definition.get_ref_fields().iter().any(|field| match field.get_ref_field_type() {
+            FieldType::SequenceU16(_) | FieldType::SequenceU32(_) => true,
+            _ => false,
+        })
+    }
+
+    /// This function creates an empty `PackFile` with the metadata of the provided manifest.
+    fn new_from_explode_manifest(manifest: &ExplodeManifest) -> Result<Self> {
+        let mut pack_file = Self::new_with_name(&manifest.file_name, PFHVersion::get_version(&manifest.pfh_version)?);
+        pack_file.pfh_file_type = PFHFileType::get_type(manifest.pfh_file_type);
+        pack_file.bitmask = PFHFlags::from_bits_truncate(manifest.bitmask);
+        pack_file.timestamp = manifest.timestamp;
+        pack_file.game_version = manifest.game_version;
+        pack_file.build_number = manifest.build_number;
+        pack_file.authoring_tool = manifest.authoring_tool.to_owned();
+        pack_file.extra_subheader_data = manifest.extra_subheader_data.to_vec();
+        pack_file.pack_files = manifest.pack_files.to_vec();
+        pack_file.notes = manifest.notes.clone();
+        pack_file.row_comments = manifest.row_comments.clone();
+        pack_file.settings = manifest.settings.clone();
+        Ok(pack_file)
+    }
+
+    /// This function adds to the end of the `PackFile` the files in the provided folder that are not in the provided list of known files.
+    ///
+    /// Used to add files added to the folder of an exploded `PackFile` after exploding it, with their last modified date as timestamp.
+    fn add_new_exploded_files(&mut self, data_path: &Path, known_paths: &HashSet<PathBuf>) -> Result<()> {
+        if data_path.is_dir() {
+            let mut new_paths = get_files_from_subdir(&data_path)?.into_iter().filter(|x| !known_paths.contains(x)).collect::<Vec<PathBuf>>();
+            new_paths.sort();
+            for file_path in &new_paths {
+                let path = file_path.strip_prefix(&data_path).unwrap().iter().map(|x| x.to_string_lossy().to_string()).collect::<Vec<String>>();
+                let mut raw_data = RawPackedFile::read_from_path(file_path, path)?;
+                raw_data.set_packfile_name(&self.get_file_name());
+                self.packed_files.push(PackedFile::new_from_raw(&raw_data));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// This function searches all the DB Tables of the provided table in the `PackFile` for rows with the provided key values.
+    ///
+    /// It returns, for each matching row, the path of its table, the index of the row and the row itself.
+    /// Tables that fail to decode or have no key columns are ignored.
+    pub fn get_rows_by_key(&mut self, table_name: &str, key_values: &[String]) -> Vec<(Vec<String>, usize, Vec<DecodedData>)> {
+        let table_name = if table_name.ends_with("_tables") { table_name.to_owned() } else { format!("{}_tables", table_name) };
+        self.get_ref_mut_packed_files_by_path_start(&["db".to_owned(), table_name]).into_iter()
+            .flat_map(|packed_file| {
+                let path = packed_file.get_path().to_vec();
+                if let Ok(DecodedPackedFile::DB(table)) = packed_file.decode_return_ref() {
+                    table.find_rows_by_key(key_values).into_iter().map(|(row, data)| (path.to_vec(), row, data.to_vec())).collect::<Vec<_>>()
+                } else { vec![] }
+            })
+            .collect()
+    }
+
+    /// This function searches all the DB Tables in the `PackFile` for rows referencing the provided key value of the provided table.
+    ///
+    /// It returns the path of the table, the index of the row and the name of the referencing column for each match, including references from the table to itself.
+    /// Tables are checked using the definition they were decoded with. Tables that fail to decode are ignored.
+    pub fn find_referencing_rows(&mut self, schema: &Schema, target_table: &str, key_value: &str) -> Vec<(Vec<String>, usize, String)> {
+        let target_table = if target_table.ends_with("_tables") { &target_table[..target_table.len() - 7] } else { target_table };
+        let mut rows = self.get_ref_mut_packed_files_by_type(PackedFileType::DB, false).into_par_iter()
+            .filter_map(|packed_file| {
+                let path = packed_file.get_path().to_vec();
+                if let Ok(DecodedPackedFile::DB(table)) = packed_file.decode_return_ref_no_locks(schema) {
+                    let columns = table.get_ref_definition().get_fields_processed().iter().enumerate()
+                        .filter(|(_, field)| field.get_is_reference().as_ref().map_or(false, |(ref_table, _)| ref_table == target_table))
+                        .map(|(column, field)| (column, field.get_name().to_owned()))
+                        .collect::<Vec<(usize, String)>>();
+
+                    if columns.is_empty() { return None; }
+                    Some(table.get_ref_table_data().iter().enumerate()
+                        .flat_map(|(row, data)| columns.iter()
+                            .filter(|(column, _)| data.get(*column).map_or(false, |cell| cell.data_to_string() == key_value))
+                            .map(|(_, name)| (path.to_vec(), row, name.to_owned()))
+                            .collect::<Vec<_>>())
+                        .collect::<Vec<_>>())
+                } else { None }
+            })
+            .flatten()
+            .collect::<Vec<(Vec<String>, usize, String)>>();
+
+        rows.sort();
+        rows
+    }
+
+    /// This function updates all the DB Tables in the `PackFile` to the latest definition of their table in the provided `Schema`.
+    ///
+    /// It returns the path, old version and new version of each updated table, and the path and reason of each table that couldn't be updated.
+    /// Tables already in their latest version are skipped. Each table is updated all-or-nothing: if updating it would lose data, it's left untouched.
+    pub fn migrate_all_tables_to_latest(&mut self, schema: &Schema) -> Result<(Vec<(Vec<String>, i32, i32)>, Vec<(Vec<String>, String)>)> {
+        let results = self.get_ref_mut_packed_files_by_type(PackedFileType::DB, false).into_par_iter()
+            .filter_map(|packed_file| {
+                let path = packed_file.get_path().to_vec();
+                let (table_name, old_version) = match packed_file.decode_return_ref_no_locks(schema) {
+                    Ok(DecodedPackedFile::DB(table)) => (table.name.to_owned(), table.get_ref_definition().get_version()),
+                    Ok(_) => return None,
+                    Err(error) => return Some(Err((path, error.to_string()))),
+                };
+
+                let definition = match schema.get_ref_versioned_file_db(&table_name) {
+                    Ok(VersionedFile::DB(_, definitions)) => match definitions.get(0) {
+                        Some(definition) => definition,
+                        None => return Some(Err((path, ErrorKind::SchemaDefinitionNotFound.to_string()))),
+                    },
+                    Ok(_) => return Some(Err((path, ErrorKind::SchemaVersionedFileNotFound.to_string()))),
+                    Err(error) => return Some(Err((path, error.to_string()))),
+                };
+
+                let new_version = definition.get_version();
+                if old_version == new_version { return None; }
+
+                // Only get it as mutable once we know it needs to be updated, so untouched tables are not marked as modified.
+                match packed_file.decode_return_ref_mut_no_locks(schema) {
+                    Ok(DecodedPackedFile::DB(table)) => match table.try_set_definition(definition) {
+                        Ok(_) => Some(Ok((path, old_version, new_version))),
+                        Err(error) => Some(Err((path, error.to_string()))),
+                    },
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut migrated = vec![];
+        let mut failed = vec![];
+        for result in results {
+            match result {
+                Ok(data) => migrated.push(data),
+                Err(data) => failed.push(data),
+            }
+        }
+
+        migrated.sort();
+        failed.sort();
+        Ok((migrated, failed))
+    }
+
+    /// This function returns the row comments of the DB Table with the provided path, if it has any.
+    pub fn get_row_comments(&self, path: &[String]) -> Option<&BTreeMap<String, String>> {
+        self.row_comments.get(&path.join("/"))
+    }
+
+    /// This function returns a reference to the row comments of all the DB Tables of the provided `PackFile`, by table path.
+    pub fn get_ref_row_comments_all(&self) -> &BTreeMap<String, BTreeMap<String, String>> {
+        &self.row_comments
+    }
+
+    /// This function saves the row comments of the DB Table with the provided path within the provided `PackFile`.
+    ///
+    /// If there are no comments left, the entry of the table is removed.
+    pub fn set_row_comments(&mut self, path: &[String], comments: &BTreeMap<String, String>) {
+        if comments.is_empty() { self.row_comments.remove(&path.join("/")); }
+        else { self.row_comments.insert(path.join("/"), comments.clone()); }
+        self.modified = true;
     }
 
     /// This function returns the settings contained within the provided `PackFile`.
@@ -1305,6 +2692,7 @@ impl PackFile {
     /// This function saves your settings within the provided `PackFile`.
     pub fn set_settings(&mut self, settings: &PackFileSettings) {
         self.settings = settings.clone();
+        self.modified = true;
     }
 
     /// This function returns the timestamp of the provided `PackFile`.
@@ -1312,19 +2700,156 @@ impl PackFile {
         self.timestamp
     }
 
-    /// This function sets the timestamp of the provided `PackFile`.
-    pub fn set_timestamp(&mut self, timestamp: i64) {
-        self.timestamp = timestamp;
-    }
+    /// This function sets the timestamp of the provided `PackFile`.
+    pub fn set_timestamp(&mut self, timestamp: i64) {
+        self.timestamp = timestamp;
+    }
+
+    /// This function returns the `PFHVersion` of the provided `PackFile`.
+    pub fn get_pfh_version(&self) -> PFHVersion {
+        self.pfh_version
+    }
+
+    /// This function returns the keys of the games this `PackFile` may belong to, from the most to the least likely.
+    ///
+    /// The candidates are the games using the `PFHVersion` of this `PackFile`. If more than one game uses it, they're ranked by how many
+    /// of the DB Tables of this `PackFile` are in their schemas, then by if said `PFHVersion` is the main one of the game.
+    /// As games we cannot tell apart are all returned, the first one is only a guess if there are more than one.
+    pub fn guess_game(&self) -> Vec<String> {
+        let candidates = SUPPORTED_GAMES.iter()
+            .enumerate()
+            .filter(|(_, (_, game))| game.pfh_version.contains(&self.pfh_version))
+            .collect::<Vec<_>>();
+
+        if candidates.len() < 2 {
+            return candidates.iter().map(|(_, (key, _))| key.to_string()).collect();
+        }
+
+        let table_names = self.packed_files.iter()
+            .map(|x| x.get_path())
+            .filter(|x| x.len() == 3 && x[0] == "db")
+            .map(|x| x[1].to_owned())
+            .collect::<BTreeSet<String>>();
+
+        // Schemas may not be downloaded. In that case, we just don't use them for the ranking.
+        let mut candidates = candidates.par_iter().map(|(index, (key, game))| {
+            let table_matches = if table_names.is_empty() { 0 } else {
+                match Schema::load(&game.schema) {
+                    Ok(schema) => table_names.iter().filter(|x| schema.get_ref_versioned_file_db(x).is_ok()).count(),
+                    Err(_) => 0,
+                }
+            };
+
+            let is_main_version = game.pfh_version.get(0) == Some(&self.pfh_version);
+            (table_matches, is_main_version, *index, key.to_string())
+        }).collect::<Vec<(usize, bool, usize, String)>>();
+
+        candidates.sort_by(|x, y| y.0.cmp(&x.0).then(y.1.cmp(&x.1)).then(x.2.cmp(&y.2)));
+        candidates.into_iter().map(|(_, _, _, key)| key).collect()
+    }
+
+    /// This function checks which `PackedFiles` of this `PackFile` override or are overridden by the ones in the other `PackFiles` of the provided load order.
+    ///
+    /// The load order is a list of `PackFile` names, which must include this `PackFile`. Like the game does with `PackFiles` of the same type,
+    /// the `PackedFiles` of the first `PackFiles` in the load order take priority over the ones in later `PackFiles`.
+    /// The other `PackFiles` are searched in the content and data folders of the game selected, the same way dependencies are.
+    /// `PackedFiles` without conflicts are not included in the report.
+    pub fn analyze_load_order_impact(&self, load_order: &[String]) -> Result<LoadOrderImpactReport> {
+        let pack_file_name = self.get_file_name();
+        let position = load_order.iter().position(|x| x == &pack_file_name).ok_or_else(|| Error::from(ErrorKind::PackFileNotInLoadOrder(pack_file_name.to_owned())))?;
+
+        let data_paths = get_game_selected_data_packfiles_paths();
+        let contents_paths = get_game_selected_content_packfiles_paths();
+
+        // Paths are not case sensitive for the game, so we don't make them case sensitive here either.
+        let mut report = LoadOrderImpactReport::default();
+        let mut overridden_by: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut overrides: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (index, name) in load_order.iter().enumerate().filter(|(index, _)| *index != position) {
+            let path = contents_paths.iter().chain(data_paths.iter())
+                .flatten()
+                .find(|x| x.file_name().map_or(false, |x| x.to_string_lossy() == *name));
+
+            let pack_file = match path.map(|path| PackFile::open_packfiles(&[path.to_path_buf()], true, false, false, false)) {
+                Some(Ok(pack_file)) => pack_file,
+                _ => {
+                    report.missing_pack_files.push(name.to_owned());
+                    continue;
+                }
+            };
+
+            let conflicts = if index < position { &mut overridden_by } else { &mut overrides };
+            for path in pack_file.get_ref_packed_files_all_paths() {
+                conflicts.entry(path.join("/").to_lowercase()).or_insert_with(Vec::new).push(name.to_owned());
+            }
+        }
 
-    /// This function returns the `PFHVersion` of the provided `PackFile`.
-    pub fn get_pfh_version(&self) -> PFHVersion {
-        self.pfh_version
+        let mut paths = self.get_ref_packed_files_all_paths().iter().map(|x| x.to_vec()).collect::<Vec<Vec<String>>>();
+        paths.sort();
+        for path in paths {
+            let path_lowercase = path.join("/").to_lowercase();
+            if let Some(pack_files) = overridden_by.get(&path_lowercase) {
+                report.conflicts.push((path, LoadOrderConflict::Overridden(pack_files.to_vec())));
+            }
+            else if let Some(pack_files) = overrides.get(&path_lowercase) {
+                report.conflicts.push((path, LoadOrderConflict::Wins(pack_files.to_vec())));
+            }
+        }
+
+        Ok(report)
     }
 
     /// This function sets the `PFHVersion` of the provided `PackFile`.
     pub fn set_pfh_version(&mut self, pfh_version: PFHVersion) {
         self.pfh_version = pfh_version;
+        self.modified = true;
+    }
+
+    /// This function converts the `PackFile` to the provided `PFHVersion`, adapting its data to what that version supports.
+    ///
+    /// The changes are applied when saving the `PackFile`. This means compressed `PackedFiles` get decompressed on save if the
+    /// new version doesn't support compression. It returns a list of warnings about the data that gets lost in the conversion.
+    pub fn convert_to_pfh_version(&mut self, pfh_version: PFHVersion) -> Result<Vec<String>> {
+        let mut warnings = vec![];
+        if self.pfh_version == pfh_version { return Ok(warnings) }
+
+        // Compression is only supported from PFH5 onwards.
+        if pfh_version != PFHVersion::PFH6 && pfh_version != PFHVersion::PFH5 {
+            let compressed = self.packed_files.iter()
+                .filter(|x| x.get_ref_raw().get_should_be_compressed() || x.get_ref_raw().get_compression_state())
+                .count();
+
+            if compressed > 0 {
+                self.toggle_compression(false);
+                warnings.push(format!("{} PackedFiles will be decompressed, as {} doesn't support compression.", compressed, pfh_version));
+            }
+        }
+
+        // PFH0 has no timestamps.
+        if pfh_version == PFHVersion::PFH0 && self.bitmask.contains(PFHFlags::HAS_INDEX_WITH_TIMESTAMPS) {
+            self.bitmask.remove(PFHFlags::HAS_INDEX_WITH_TIMESTAMPS);
+            warnings.push(format!("The timestamps of the PackedFiles will be removed, as {} doesn't support them.", pfh_version));
+        }
+
+        // Extended headers and encryption are not supported when saving, so they're removed no matter the version.
+        if self.bitmask.contains(PFHFlags::HAS_EXTENDED_HEADER) {
+            self.bitmask.remove(PFHFlags::HAS_EXTENDED_HEADER);
+            warnings.push("The extended header of the PackFile will be removed.".to_owned());
+        }
+
+        if self.bitmask.contains(PFHFlags::HAS_ENCRYPTED_INDEX) || self.bitmask.contains(PFHFlags::HAS_ENCRYPTED_DATA) {
+            self.bitmask.remove(PFHFlags::HAS_ENCRYPTED_INDEX);
+            self.bitmask.remove(PFHFlags::HAS_ENCRYPTED_DATA);
+            warnings.push("The PackFile will be decrypted.".to_owned());
+        }
+
+        // Only PFH6 has the game version and build number in its header.
+        if self.pfh_version == PFHVersion::PFH6 {
+            warnings.push(format!("The game version and build number of the PackFile will be lost, as {} doesn't support them.", pfh_version));
+        }
+
+        self.set_pfh_version(pfh_version);
+        Ok(warnings)
     }
 
     /// This function returns the `PFHFileType` of the provided `PackFile`.
@@ -1335,6 +2860,7 @@ impl PackFile {
     /// This function sets the `PFHFileType` of the provided `PackFile`.
     pub fn set_pfh_file_type(&mut self, pfh_file_type: PFHFileType) {
         self.pfh_file_type = pfh_file_type;
+        self.modified = true;
     }
 
     /// This function returns the `Bitmask` of the provided `PackFile`.
@@ -1349,17 +2875,54 @@ impl PackFile {
 
     /// This function returns a mutable reference to the `Bitmask` of the provided `PackFile`.
     pub fn get_ref_mut_bitmask(&mut self) -> &mut PFHFlags {
+        self.modified = true;
         &mut self.bitmask
     }
 
     /// This function sets the `Bitmask` of the provided `PackFile`.
     pub fn set_bitmask(&mut self, bitmask: PFHFlags) {
         self.bitmask = bitmask;
+        self.modified = true;
+    }
+
+    /// This function returns the name and state of every individual flag of the `Bitmask` of the provided `PackFile`.
+    pub fn get_pfh_flags(&self) -> Vec<(String, PFHFlags, bool)> {
+        PFHFlags::get_all_flags().iter().map(|(name, flag)| (name.to_string(), *flag, self.bitmask.contains(*flag))).collect()
+    }
+
+    /// This function enables or disables an individual flag of the `Bitmask` of the provided `PackFile`.
+    ///
+    /// Disabling a flag is always allowed. Enabling it fails if the flag is not supported by the `PFHVersion` of the `PackFile`,
+    /// or if we cannot save PackFiles with it, as that would result in a broken header.
+    pub fn set_pfh_flag(&mut self, flag: PFHFlags, state: bool) -> Result<()> {
+        if PFHFlags::get_all_flags().iter().all(|(_, known_flag)| *known_flag != flag) {
+            return Err(ErrorKind::PackFileFlagNotSupportedByVersion(flag.get_name(), self.pfh_version.to_string()).into());
+        }
+
+        if state {
+            if !flag.is_supported_by_version(self.pfh_version) {
+                return Err(ErrorKind::PackFileFlagNotSupportedByVersion(flag.get_name(), self.pfh_version.to_string()).into());
+            }
+
+            if !flag.is_supported_for_saving() {
+                return Err(ErrorKind::PackFileFlagNotSupportedForSaving(flag.get_name()).into());
+            }
+        }
+
+        self.bitmask.set(flag, state);
+        self.modified = true;
+        Ok(())
     }
 
     /// This function remove all `PackedFiles` from a `PackFile`.
     pub fn remove_all_packedfiles(&mut self) {
         self.packed_files = vec![];
+        self.modified = true;
+    }
+
+    /// This function returns if the `PackFile` or any of its `PackedFiles` has been modified since it was loaded or saved.
+    pub fn is_modified(&self) -> bool {
+        self.modified || self.packed_files.par_iter().any(|x| x.is_modified())
     }
 
     /// This function checks if a `PackedFile` with a certain path exists in a `PackFile`.
@@ -1542,6 +3105,158 @@ impl PackFile {
         successes
     }
 
+    /// This function returns the lowercased version of a path, but only on the parts of it the games expect to be lowercase.
+    ///
+    /// Currently these are the known root folders and, for DB Tables, the `xxx_tables` folder. File names are never touched,
+    /// as some of them (like texture names) are case-sensitive.
+    fn get_path_with_normalized_casing(path: &[String]) -> Vec<String> {
+        let mut new_path = path.to_vec();
+        if new_path.len() > 1 {
+            let root_folder = new_path[0].to_lowercase();
+            if LOWERCASE_ROOT_FOLDERS.contains(&&*root_folder) {
+                if root_folder == "db" && new_path.len() > 2 && new_path[1].to_lowercase().ends_with("_tables") {
+                    new_path[1] = new_path[1].to_lowercase();
+                }
+                new_path[0] = root_folder;
+            }
+        }
+        new_path
+    }
+
+    /// This function returns the paths of all the `PackedFiles` whose structural folders (`db`, `xxx_tables`, `text`,...) are not in lowercase.
+    pub fn find_casing_issues(&self) -> Vec<Vec<String>> {
+        self.packed_files.par_iter()
+            .map(|x| x.get_path())
+            .filter(|x| Self::get_path_with_normalized_casing(x) != *x)
+            .map(|x| x.to_vec())
+            .collect()
+    }
+
+    /// This function lowercases the structural folders of all the `PackedFiles` with casing issues.
+    ///
+    /// It returns the list of "Original Path/New Path" of each moved PackedFile. If the lowercased path is already taken,
+    /// the moved file gets a `_number` appended to his name, like in any other move.
+    pub fn normalize_path_casing(&mut self) -> Vec<(Vec<String>, Vec<String>)> {
+        let mut successes = vec![];
+        for path in self.find_casing_issues() {
+            let new_path = Self::get_path_with_normalized_casing(&path);
+            if let Ok(new_path) = self.move_packedfile(&path, &new_path, false) {
+                successes.push((path, new_path));
+            }
+        }
+
+        successes
+    }
+
+    /// This function exports all the DB Tables in the `PackFile` to a single SQLite database, so they can be queried together.
+    ///
+    /// Each DB Table is exported as a table named after its table folder (like `units_tables`). If there are multiple files
+    /// for the same table, their file names are appended to the name, like `units_tables__my_units`. If, after turning them into
+    /// valid SQL identifiers, two tables end up with the same name, a numeric suffix is added to the later ones, like `units_tables__my_units_1`.
+    pub fn export_all_tables_to_sqlite(&mut self, path: &Path) -> Result<()> {
+        let schema = SCHEMA.read().unwrap();
+        let schema = if let Some(ref schema) = *schema { schema } else { return Err(ErrorKind::SchemaNotFound.into()) };
+
+        let mut connection = DB::open_sqlite_connection(path)?;
+        let mut packed_files = self.get_ref_mut_packed_files_by_type(PackedFileType::DB, false);
+        let table_counts = packed_files.iter().filter_map(|x| x.get_path().get(1).cloned()).counts();
+
+        // SQLite identifiers are case-insensitive, so we need to check for collisions ignoring case.
+        let mut used_names = HashSet::new();
+        for packed_file in &mut packed_files {
+            let packed_file_path = packed_file.get_path().to_vec();
+            let (folder_name, file_name) = match (packed_file_path.get(1), packed_file_path.get(2)) {
+                (Some(folder_name), Some(file_name)) => (folder_name, file_name),
+                _ => continue,
+            };
+
+            // Tables we cannot decode are skipped, so one broken table doesn't stop the export.
+            if let Ok(DecodedPackedFile::DB(table)) = packed_file.decode_return_ref_no_locks(&schema) {
+                let base_name = if table_counts[folder_name] > 1 { DB::get_sql_identifier(&format!("{}__{}", folder_name, file_name)) }
+                    else { DB::get_sql_identifier(folder_name) };
+
+                let mut table_name = base_name.to_owned();
+                let mut suffix = 1;
+                while used_names.contains(&table_name.to_lowercase()) {
+                    table_name = format!("{}_{}", base_name, suffix);
+                    suffix += 1;
+                }
+
+                used_names.insert(table_name.to_lowercase());
+                table.export_to_sqlite_connection(&mut connection, &table_name)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// This function generates the patches with the changes done to the DB Tables of this `PackFile` over the ones in the provided base `PackFile`.
+    ///
+    /// Tables are matched by path. Tables not in the base `PackFile` are compared against an empty table, and tables without changes are not included.
+    pub fn generate_table_patches(&mut self, base: &mut PackFile) -> Result<Vec<TablePatch>> {
+        let schema = SCHEMA.read().unwrap();
+        let schema = if let Some(ref schema) = *schema { schema } else { return Err(ErrorKind::SchemaNotFound.into()) };
+
+        let mut patches = vec![];
+        for packed_file in self.get_ref_mut_packed_files_by_type(PackedFileType::DB, false) {
+            let path = packed_file.get_path().to_vec();
+            if let DecodedPackedFile::DB(table) = packed_file.decode_return_ref_no_locks(&schema)? {
+                let base_table = match base.get_ref_mut_packed_file_by_path(&path).map(|x| x.decode_return_ref_no_locks(&schema)) {
+                    Some(Ok(DecodedPackedFile::DB(base_table))) => base_table.clone(),
+                    _ => DB::new(&table.name, None, table.get_ref_definition()),
+                };
+
+                let mut patch = table.generate_patch(&base_table)?;
+                if !patch.is_empty() {
+                    patch.path = path;
+                    patches.push(patch);
+                }
+            }
+        }
+
+        Ok(patches)
+    }
+
+    /// This function applies the provided patches to the DB Tables of this `PackFile`.
+    ///
+    /// It returns the conflicts found, as the path of each table with the keys of its conflicting rows. Patches for tables not in the `PackFile`
+    /// have all their rows reported as conflicts. Rows with conflicts are skipped if `skip_conflicts` is true. Otherwise, if there are conflicts, nothing is applied.
+    pub fn apply_table_patches(&mut self, patches: &[TablePatch], skip_conflicts: bool) -> Result<Vec<(Vec<String>, Vec<String>)>> {
+        let schema = SCHEMA.read().unwrap();
+        let schema = if let Some(ref schema) = *schema { schema } else { return Err(ErrorKind::SchemaNotFound.into()) };
+
+        // Patch copies of the tables first, so we don't touch the PackFile if we have to abort due to conflicts.
+        let mut patched_tables: Vec<(Vec<String>, DB)> = vec![];
+        let mut conflicts = vec![];
+        for patch in patches {
+            if !patched_tables.iter().any(|(path, _)| path == &patch.path) {
+                if let Some(Ok(DecodedPackedFile::DB(table))) = self.get_ref_mut_packed_file_by_path(&patch.path).map(|x| x.decode_return_ref_no_locks(&schema)) {
+                    patched_tables.push((patch.path.to_vec(), table.clone()));
+                }
+            }
+
+            match patched_tables.iter_mut().find(|(path, _)| path == &patch.path) {
+                Some((_, table)) => {
+                    let keys = table.apply_patch(patch, true)?;
+                    if !keys.is_empty() {
+                        conflicts.push((patch.path.to_vec(), keys));
+                    }
+                }
+                None => conflicts.push((patch.path.to_vec(), patch.get_keys())),
+            }
+        }
+
+        if skip_conflicts || conflicts.is_empty() {
+            for (path, table) in patched_tables {
+                if let Some(packed_file) = self.get_ref_mut_packed_file_by_path(&path) {
+                    packed_file.set_decoded(&DecodedPackedFile::DB(table));
+                }
+            }
+        }
+
+        Ok(conflicts)
+    }
+
     /// This function merges (if possible) the provided DB and LOC tables into one with the provided name.
     ///
     /// NOTE: The merged table will be created in the folder of the first provided file.
@@ -1618,6 +3333,41 @@ impl PackFile {
         self.add_packed_file(&packed_file, true)
     }
 
+    /// This function converts the Loc `PackedFile` in the provided path into a DB Table with the provided name and definition, replacing it.
+    ///
+    /// The new table goes to `db/table_name/`, named as the Loc without its extension. It returns its path.
+    pub fn convert_loc_to_db(&mut self, path: &[String], table_name: &str, definition: &Definition) -> Result<Vec<String>> {
+        let packed_file = self.get_ref_mut_packed_file_by_path(path).ok_or(ErrorKind::PackedFileNotFound)?;
+        let table = match packed_file.decode_return_ref()? {
+            DecodedPackedFile::Loc(table) => table.to_db(table_name, definition)?,
+            _ => return Err(ErrorKind::LocPackedFileIsNotALocPackedFile.into()),
+        };
+
+        let name = path.last().ok_or(ErrorKind::EmptyInput)?;
+        let name = if name.to_lowercase().ends_with(loc::EXTENSION) { &name[..name.len() - loc::EXTENSION.len()] } else { name };
+        let new_path = vec!["db".to_owned(), table_name.to_owned(), name.to_owned()];
+
+        self.remove_packed_file_by_path(path);
+        self.add_packed_file(&PackedFile::new_from_decoded(&DecodedPackedFile::DB(table), &new_path), false)
+    }
+
+    /// This function converts the DB Table in the provided path into a Loc `PackedFile`, replacing it.
+    ///
+    /// The new Loc goes to `text/db/`, named as the table with the Loc extension. It returns its path.
+    pub fn convert_db_to_loc(&mut self, path: &[String]) -> Result<Vec<String>> {
+        let packed_file = self.get_ref_mut_packed_file_by_path(path).ok_or(ErrorKind::PackedFileNotFound)?;
+        let table = match packed_file.decode_return_ref()? {
+            DecodedPackedFile::DB(table) => table.to_loc()?,
+            _ => return Err(ErrorKind::DBTableIsNotADBTable.into()),
+        };
+
+        let name = path.last().ok_or(ErrorKind::EmptyInput)?;
+        let new_path = vec!["text".to_owned(), "db".to_owned(), format!("{}{}", name, loc::EXTENSION)];
+
+        self.remove_packed_file_by_path(path);
+        self.add_packed_file(&PackedFile::new_from_decoded(&DecodedPackedFile::Loc(table), &new_path), false)
+    }
+
     /// This function is used to optimize a `PackFile` by removing extra useless data from it.
     ///
     /// Currently, this function removes:
@@ -1626,71 +3376,432 @@ impl PackFile {
     /// - Empty DB tables (except if the table has the same name as his vanilla counterpart and certain setting is enabled).
     /// - Empty Loc tables (except if the table has the same name as his vanilla counterpart and certain setting is enabled).
     /// - XML files in map folders.
-    pub fn optimize(&mut self, dependencies: &Dependencies) -> Vec<Vec<String>> {
+    ///
+    /// It returns a report with everything that got removed.
+    pub fn optimize(&mut self, dependencies: &Dependencies) -> OptimizeReport {
+
+        // List of PackedFiles to delete.
+        let mut files_to_delete: Vec<Vec<String>> = vec![];
+        let mut report = OptimizeReport::default();
+        let dependencies = dependencies.get_ref_dependency_database();
+
+        // We get the entire list of paths from the dependency database, so we can check if each `PackedFile is trying to overwrite a vanilla one or not.
+        let database_path_list = dependencies.iter().map(|x| x.get_path().to_vec()).collect::<Vec<Vec<String>>>();
+
+        // Get a list of every Loc and DB PackedFiles in our dependency's files. For performance reasons, we decode every one of them here.
+        // Otherwise, they may have to be decoded multiple times, making this function take ages to finish.
+        let (game_dbs, game_locs): (Vec<&DB>, Vec<&Loc>) = dependencies.iter()
+            .filter(|x| (x.get_path().len() == 3 && x.get_path()[0] == "db") || (x.get_path().last().unwrap().ends_with(".loc")))
+            .map(|x| x.get_decoded_from_memory())
+            .filter(|x| x.is_ok())
+            .map(|x| x.unwrap())
+            .partition_map(|x| match x {
+                DecodedPackedFile::DB(db) => Either::Left(db),
+                DecodedPackedFile::Loc(loc) => Either::Right(loc),
+                _ => unreachable!()
+            });
+
+        // We do this in two passes. First, we optimize the data inside the `PackedFiles`. Then, we do a *cleaning* pass, removing empty or useless `PackedFiles`.
+        for packed_file in self.get_ref_mut_packed_files_all() {
+            let path = packed_file.get_path().to_vec();
+
+            // Unless we specifically wanted to, ignore the same-name-as-vanilla files,
+            // as those are probably intended to overwrite vanilla files, not to be optimized.
+            if database_path_list.contains(&path) && !SETTINGS.read().unwrap().settings_bool["optimize_not_renamed_packedfiles"] { continue; }
+
+            // If it's a DB table, try to optimize it.
+            if path.len() == 3 && path[0] == "db" && !game_dbs.is_empty() {
+
+                // Try to decode our table.
+                match packed_file.decode_return_ref_mut() {
+                    Ok(data) => if let DecodedPackedFile::DB(db) = data {
+                        let entry_count = db.get_entry_count();
+                        let is_empty = db.optimize_table(&game_dbs);
+                        let removed_rows = entry_count - db.get_entry_count();
+                        if removed_rows > 0 { report.removed_rows.push((path.to_vec(), removed_rows)); }
+                        if is_empty {
+                            files_to_delete.push(path.to_vec());
+                            report.emptied_tables.push(path.to_vec());
+                        }
+                    },
+                    Err(_) => continue,
+                };
+            }
+
+            // If it's a Loc table, try to optimize it.
+            else if path.last().unwrap().ends_with(".loc") && !game_locs.is_empty() {
+                match packed_file.decode_return_ref_mut() {
+                    Ok(data) => if let DecodedPackedFile::Loc(loc) = data {
+                        let entry_count = loc.get_entry_count();
+                        let is_empty = loc.optimize_table(&game_locs);
+                        let removed_rows = entry_count - loc.get_entry_count();
+                        if removed_rows > 0 { report.removed_rows.push((path.to_vec(), removed_rows)); }
+                        if is_empty {
+                            files_to_delete.push(path.to_vec());
+                            report.emptied_tables.push(path.to_vec());
+                        }
+                    },
+                    Err(_) => continue,
+                };
+            }
+
+            // If it's an xml in a map folder, remove it.
+            else if !path.is_empty() && path.starts_with(&Self::get_terry_map_path()) && path.last().unwrap().ends_with(".xml") {
+                files_to_delete.push(path.to_vec());
+            }
+        }
+
+        // Delete all the files marked for deletion.
+        files_to_delete.iter().for_each(|x| self.remove_packed_file_by_path(x));
+
+        // Return the report, so the caller can know what got removed.
+        report.removed_files = files_to_delete;
+        report
+    }
+
+    /// This function checks all the `.variantmeshdefinition` files in the `PackFile`, reporting every referenced
+    /// model or texture that cannot be found, neither in the `PackFile` nor in the vanilla `PackFiles` of the game selected.
+    ///
+    /// The game doesn't care about casing or slashes in these paths, so neither do we. Files that fail to decode are reported with their error.
+    pub fn validate_variant_meshes(&mut self, dependencies: &Dependencies) -> Result<Vec<VmdIssue>> {
+        let regex = Regex::new(VMD_REFERENCE_REGEX).unwrap();
+
+        // Get all the paths we can reference, in the same format the references will have after normalizing them.
+        let existing_paths = self.get_ref_packed_files_all_paths().par_iter().map(|x| x.join("/").to_lowercase()).collect::<HashSet<String>>();
+        let vanilla_paths = dependencies.get_vanilla_paths()?;
+
+        let mut issues = vec![];
+        for packed_file in self.get_ref_mut_packed_files_by_extension(".variantmeshdefinition") {
+            let path = packed_file.get_path().to_vec();
+            match packed_file.decode_return_ref() {
+                Ok(DecodedPackedFile::Text(text)) => {
+                    for (index, line) in text.get_ref_contents().lines().enumerate() {
+                        for captures in regex.captures_iter(line) {
+                            let reference = captures[1].to_owned();
+                            let reference_normalized = reference.replace('\\', "/").trim_start_matches('/').to_lowercase();
+                            if !existing_paths.contains(&reference_normalized) && !vanilla_paths.contains(&reference_normalized) {
+                                issues.push(VmdIssue {
+                                    path: path.to_vec(),
+                                    reference,
+                                    line: index + 1,
+                                    error: None,
+                                });
+                            }
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(error) => issues.push(VmdIssue {
+                    path,
+                    reference: String::new(),
+                    line: 0,
+                    error: Some(error.to_string()),
+                }),
+            }
+        }
+
+        Ok(issues)
+    }
+
+    /// This function checks the paths of all the `PackedFiles` in the `PackFile` against the path rules of the provided game,
+    /// returning every path breaking a rule, with the reason.
+    ///
+    /// If the game is not supported, the generic Windows rules are used.
+    pub fn validate_paths(&self, game: &str) -> Vec<(Vec<String>, String)> {
+        let path_rules = match SUPPORTED_GAMES.get(game) {
+            Some(game_info) => game_info.path_rules.clone(),
+            None => PathRules::new_windows(),
+        };
+
+        let mut paths = self.get_ref_packed_files_all_paths();
+        paths.sort();
+        paths.iter()
+            .flat_map(|path| path_rules.validate_path(path).into_iter().map(move |reason| (path.to_vec(), reason)))
+            .collect()
+    }
+
+    /// This function decodes the WsModel `PackedFile` in the provided path, returning it with a list of which of its references exist.
+    ///
+    /// References are searched in this `PackFile` and in the vanilla PackFiles of the provided `Dependencies`, ignoring casing and slashes.
+    /// The decoded WsModel is not cached.
+    pub fn decode_ws_model(&self, path: &[String], dependencies: &Dependencies) -> Result<(WsModel, Vec<bool>)> {
+        let packed_file = self.get_ref_packed_file_by_path(path).ok_or_else(|| Error::from(ErrorKind::PackedFileNotFound))?;
+        if !packed_file.get_path().last().map_or(false, |name| name.to_lowercase().ends_with(wsmodel::EXTENSION)) {
+            return Err(ErrorKind::WsModelIsNotAWsModel.into());
+        }
+
+        // If the PackedFile is open as text, use its current contents.
+        let data = match packed_file.get_ref_decoded().encode() {
+            Some(data) => data?,
+            None => packed_file.get_raw_data()?,
+        };
+        let ws_model = WsModel::read(&data)?;
+
+        let mut existing_paths = self.get_ref_packed_files_all_paths().par_iter().map(|x| x.join("/").to_lowercase()).collect::<HashSet<String>>();
+        if let Ok(vanilla_paths) = dependencies.get_vanilla_paths() {
+            existing_paths.extend(vanilla_paths.iter().cloned());
+        }
+
+        let references_exist = ws_model.get_ref_references().iter().map(|x| existing_paths.contains(&x.get_path_normalized())).collect();
+        Ok((ws_model, references_exist))
+    }
+
+    /// This function decodes the UI Layout `PackedFile` in the provided path. The decoded UI Layout is not cached.
+    pub fn decode_ui_layout(&self, path: &[String]) -> Result<UiLayout> {
+        let packed_file = self.get_ref_packed_file_by_path(path).ok_or_else(|| Error::from(ErrorKind::PackedFileNotFound))?;
+        if !UiLayout::is_ui_layout(packed_file.get_path()) {
+            return Err(ErrorKind::UiLayoutIsNotAUiLayout.into());
+        }
+
+        // If the PackedFile is open as text, use its current contents.
+        let data = match packed_file.get_ref_decoded().encode() {
+            Some(data) => data?,
+            None => packed_file.get_raw_data()?,
+        };
+        UiLayout::read(&data)
+    }
+
+    /// This function decodes and re-encodes every decodable `PackedFile` of the `PackFile`, reporting the ones whose data changed in the process.
+    ///
+    /// The `PackFile` itself is not modified. `PackedFiles` that cannot be decoded or have no encoding support are ignored.
+    /// For DB Tables, differences in the header (the UUID gets regenerated on save) are not considered unexpected.
+    pub fn verify_round_trip(&self) -> Result<Vec<(Vec<String>, RoundTripIssue)>> {
+        let schema = SCHEMA.read().unwrap();
+        let schema = if let Some(ref schema) = *schema { schema } else { return Err(ErrorKind::SchemaNotFound.into()) };
+
+        Ok(self.packed_files.par_iter().filter_map(|packed_file| {
+            let mut raw_packed_file = packed_file.get_ref_raw().clone();
+            let data = raw_packed_file.get_data().ok()?;
+            let decoded = DecodedPackedFile::decode_no_locks(&mut raw_packed_file, &schema).ok()?;
+            let path = packed_file.get_path().to_vec();
+
+            match decoded.encode()? {
+                Ok(new_data) => {
+                    if data == new_data { None }
+                    else {
+                        match decoded {
+                            DecodedPackedFile::DB(_) => {
+                                let (_, _, _, _, index) = DB::read_header(&data).ok()?;
+                                let (_, _, _, _, new_index) = DB::read_header(&new_data).ok()?;
+                                if data[index..] == new_data[new_index..] { Some((path, RoundTripIssue::KnownNonStable)) }
+                                else { Some((path, RoundTripIssue::UnexpectedDifference)) }
+                            }
+
+                            // Text files may change their encoding/BOM on save, and videos may be converted between formats.
+                            DecodedPackedFile::Text(_) |
+                            DecodedPackedFile::CaVp8(_) => Some((path, RoundTripIssue::KnownNonStable)),
+                            _ => Some((path, RoundTripIssue::UnexpectedDifference)),
+                        }
+                    }
+                }
+                Err(error) => Some((path, RoundTripIssue::EncodeError(error.to_string()))),
+            }
+        }).collect())
+    }
+
+    /// This function opens the `PackFile` in the provided path with and without lazy loading, and compares the data of all their `PackedFiles`.
+    ///
+    /// The data is compared once decrypted and decompressed. Only the first `MAX_LAZY_LOADING_MISMATCHES` mismatches, sorted by path, are returned.
+    pub fn verify_lazy_loading_consistency(path: &Path) -> Result<Vec<(Vec<String>, LazyLoadingMismatch)>> {
+        let pack_file_lazy = Self::open_packfiles(&[path.to_path_buf()], true, false, false, false)?;
+        let pack_file_full = Self::open_packfiles(&[path.to_path_buf()], false, false, false, false)?;
+
+        // Index both PackFiles by path, so we don't do a linear search per PackedFile.
+        let packed_files_lazy = pack_file_lazy.packed_files.iter().map(|x| (x.get_path(), x)).collect::<HashMap<&[String], &PackedFile>>();
+        let packed_files_full = pack_file_full.packed_files.iter().map(|x| (x.get_path(), x)).collect::<HashMap<&[String], &PackedFile>>();
+
+        let mut mismatches = pack_file_lazy.packed_files.par_iter().filter_map(|packed_file_lazy| {
+            let path = packed_file_lazy.get_path().to_vec();
+            let packed_file_full = match packed_files_full.get(packed_file_lazy.get_path()) {
+                Some(packed_file_full) => packed_file_full,
+                None => return Some((path, LazyLoadingMismatch::MissingPackedFile(true))),
+            };
+
+            let data_lazy = match packed_file_lazy.get_ref_raw().get_data() {
+                Ok(data) => data,
+                Err(error) => return Some((path, LazyLoadingMismatch::ReadError(error.to_string()))),
+            };
+
+            let data_full = match packed_file_full.get_ref_raw().get_data() {
+                Ok(data) => data,
+                Err(error) => return Some((path, LazyLoadingMismatch::ReadError(error.to_string()))),
+            };
+
+            if data_lazy == data_full { None }
+            else {
+                let offset = data_lazy.iter().zip(data_full.iter()).position(|(x, y)| x != y).unwrap_or_else(|| data_lazy.len().min(data_full.len()));
+                Some((path, LazyLoadingMismatch::DataDifference {
+                    offset,
+                    lazy_size: data_lazy.len(),
+                    full_size: data_full.len(),
+                }))
+            }
+        }).collect::<Vec<(Vec<String>, LazyLoadingMismatch)>>();
+
+        mismatches.extend(pack_file_full.packed_files.iter()
+            .filter(|packed_file| !packed_files_lazy.contains_key(packed_file.get_path()))
+            .map(|packed_file| (packed_file.get_path().to_vec(), LazyLoadingMismatch::MissingPackedFile(false))));
+
+        mismatches.sort_by(|x, y| x.0.cmp(&y.0));
+        mismatches.truncate(MAX_LAZY_LOADING_MISMATCHES);
+        Ok(mismatches)
+    }
+
+    /// This function tries to decode every decodable `PackedFile` of the `PackFile`, returning the path of the ones that failed, with the reason.
+    ///
+    /// Each `PackedFile` is decoded from a copy of its raw data, so nothing gets cached. `PackedFiles` already decoded are skipped, as they're known to decode.
+    /// Panics in a decoder are caught and reported as a failure of the `PackedFile` that caused them.
+    ///
+    /// These panics don't generate a crash report, but the panic hook is not touched, as it's global to the process and other threads may panic while we check.
+    pub fn decode_health_check(&mut self) -> Vec<(Vec<String>, String)> {
+        let mut failures = self.packed_files.par_iter()
+            .filter(|packed_file| packed_file.get_ref_decoded() == &DecodedPackedFile::Unknown)
+            .filter_map(|packed_file| {
+                let mut raw_packed_file = packed_file.get_ref_raw().clone();
+                let result = CrashReport::catch_unwind_silently(AssertUnwindSafe(|| Self::decode_for_health_check(&mut raw_packed_file)));
+                let error = match result {
+                    Ok(Ok(_)) => return None,
+                    Ok(Err(error)) => error.to_string(),
+                    Err(payload) => {
+                        let message = if let Some(message) = payload.downcast_ref::<&str>() { message.to_string() }
+                            else if let Some(message) = payload.downcast_ref::<String>() { message.to_owned() }
+                            else { "Unknown panic.".to_owned() };
+                        format!("The decoder crashed: {}", message)
+                    }
+                };
+                Some((packed_file.get_path().to_vec(), error))
+            }).collect::<Vec<(Vec<String>, String)>>();
+
+        failures.sort();
+        failures
+    }
+
+    /// This function decodes the provided `RawPackedFile` with every decoder that supports it, discarding the decoded data.
+    fn decode_for_health_check(raw_packed_file: &mut RawPackedFile) -> Result<()> {
+        match PackedFileType::get_packed_file_type(raw_packed_file.get_path()) {
+            PackedFileType::RigidModel => { RigidModel::read(&raw_packed_file.get_data()?)?; },
+            _ => { DecodedPackedFile::decode(raw_packed_file)?; },
+        }
+
+        // UI Layouts and WsModels are also valid Text PackedFiles, so we need to check their own decoders too.
+        if UiLayout::is_ui_layout(raw_packed_file.get_path()) {
+            UiLayout::read(&raw_packed_file.get_data()?)?;
+        }
+        else if raw_packed_file.get_path().last().map_or(false, |name| name.to_lowercase().ends_with(wsmodel::EXTENSION)) {
+            WsModel::read(&raw_packed_file.get_data()?)?;
+        }
+        Ok(())
+    }
 
-        // List of PackedFiles to delete.
-        let mut files_to_delete: Vec<Vec<String>> = vec![];
-        let dependencies = dependencies.get_ref_dependency_database();
+    /// This function replaces all the references to an asset path in the DB Tables and Text `PackedFiles` of the `PackFile` with a new path.
+    ///
+    /// Only whole path segments are replaced, so replacing `a/b` will change `a/b/c.dds`, but not `a/bc/d.dds`.
+    /// It returns the path of each changed `PackedFile`, with the rows (for DB Tables) or lines (for Text files) changed, starting at 1.
+    pub fn replace_asset_path_references(&mut self, old_path: &str, new_path: &str) -> Result<Vec<(Vec<String>, Vec<usize>)>> {
+        let schema = SCHEMA.read().unwrap();
+        let schema = if let Some(ref schema) = *schema { schema } else { return Err(ErrorKind::SchemaNotFound.into()) };
 
-        // We get the entire list of paths from the dependency database, so we can check if each `PackedFile is trying to overwrite a vanilla one or not.
-        let database_path_list = dependencies.iter().map(|x| x.get_path().to_vec()).collect::<Vec<Vec<String>>>();
+        let old_path = old_path.replace('\\', "/").trim_matches('/').to_ascii_lowercase();
+        let new_path = new_path.replace('\\', "/").trim_matches('/').to_owned();
+        if old_path.is_empty() { return Ok(vec![]); }
 
-        // Get a list of every Loc and DB PackedFiles in our dependency's files. For performance reasons, we decode every one of them here.
-        // Otherwise, they may have to be decoded multiple times, making this function take ages to finish.
-        let (game_dbs, game_locs): (Vec<&DB>, Vec<&Loc>) = dependencies.iter()
-            .filter(|x| (x.get_path().len() == 3 && x.get_path()[0] == "db") || (x.get_path().last().unwrap().ends_with(".loc")))
-            .map(|x| x.get_decoded_from_memory())
-            .filter(|x| x.is_ok())
-            .map(|x| x.unwrap())
-            .partition_map(|x| match x {
-                DecodedPackedFile::DB(db) => Either::Left(db),
-                DecodedPackedFile::Loc(loc) => Either::Right(loc),
-                _ => unreachable!()
-            });
+        let mut changes = vec![];
+        for packed_file in &mut self.packed_files {
+            match PackedFileType::get_packed_file_type(packed_file.get_path()) {
+                PackedFileType::DB | PackedFileType::Text(_) => {},
+                _ => continue,
+            }
 
-        // We do this in two passes. First, we optimize the data inside the `PackedFiles`. Then, we do a *cleaning* pass, removing empty or useless `PackedFiles`.
-        for packed_file in self.get_ref_mut_packed_files_all() {
             let path = packed_file.get_path().to_vec();
+            match packed_file.decode_return_ref_mut_no_locks(&schema) {
+                Ok(DecodedPackedFile::DB(table)) => {
+                    let mut data = table.get_table_data();
+                    let mut rows_changed = vec![];
+                    for (index, row) in data.iter_mut().enumerate() {
+                        let mut changed = false;
+                        for cell in row.iter_mut() {
+                            match cell {
+                                DecodedData::StringU8(ref mut text) |
+                                DecodedData::StringU16(ref mut text) |
+                                DecodedData::OptionalStringU8(ref mut text) |
+                                DecodedData::OptionalStringU16(ref mut text) => {
+                                    if let Some(new_text) = Self::replace_path_segments(text, &old_path, &new_path) {
+                                        *text = new_text;
+                                        changed = true;
+                                    }
+                                }
+                                _ => continue,
+                            }
+                        }
 
-            // Unless we specifically wanted to, ignore the same-name-as-vanilla files,
-            // as those are probably intended to overwrite vanilla files, not to be optimized.
-            if database_path_list.contains(&path) && !SETTINGS.read().unwrap().settings_bool["optimize_not_renamed_packedfiles"] { continue; }
+                        if changed { rows_changed.push(index + 1); }
+                    }
 
-            // If it's a DB table, try to optimize it.
-            if path.len() == 3 && path[0] == "db" && !game_dbs.is_empty() {
+                    if !rows_changed.is_empty() {
+                        table.set_table_data(&data)?;
+                        changes.push((path, rows_changed));
+                    }
+                }
 
-                // Try to decode our table.
-                match packed_file.decode_return_ref_mut() {
-                    Ok(data) => if let DecodedPackedFile::DB(db) = data {
-                        let is_empty = db.optimize_table(&game_dbs);
-                        if is_empty { files_to_delete.push(path.to_vec()); }
-                    },
-                    Err(_) => continue,
-                };
-            }
+                Ok(DecodedPackedFile::Text(text)) => {
+                    let mut lines_changed = vec![];
+                    let contents = text.get_ref_contents().split('\n').enumerate().map(|(index, line)| {
+                        match Self::replace_path_segments(line, &old_path, &new_path) {
+                            Some(new_line) => {
+                                lines_changed.push(index + 1);
+                                new_line
+                            }
+                            None => line.to_owned(),
+                        }
+                    }).collect::<Vec<String>>().join("\n");
 
-            // If it's a Loc table, try to optimize it.
-            else if path.last().unwrap().ends_with(".loc") && !game_locs.is_empty() {
-                match packed_file.decode_return_ref_mut() {
-                    Ok(data) => if let DecodedPackedFile::Loc(loc) = data {
-                        let is_empty = loc.optimize_table(&game_locs);
-                        if is_empty { files_to_delete.push(path.to_vec()); }
-                    },
-                    Err(_) => continue,
-                };
+                    if !lines_changed.is_empty() {
+                        text.set_contents(&contents);
+                        changes.push((path, lines_changed));
+                    }
+                }
+                _ => continue,
             }
+        }
 
-            // If it's an xml in a map folder, remove it.
-            else if !path.is_empty() && path.starts_with(&Self::get_terry_map_path()) && path.last().unwrap().ends_with(".xml") {
-                files_to_delete.push(path.to_vec());
-            }
+        Ok(changes)
+    }
+
+    /// This function replaces the provided path in a string, but only when it matches whole path segments.
+    ///
+    /// The old path is expected to be lowercased and with forward slashes. The separators of the replaced text are kept. Returns `None` if nothing was replaced.
+    fn replace_path_segments(text: &str, old_path: &str, new_path: &str) -> Option<String> {
+        let matches = Self::find_path_segments(text, old_path);
+        if matches.is_empty() { return None; }
+
+        let mut result = String::with_capacity(text.len());
+        let mut last_end = 0;
+        for (start, end) in matches {
+            let new_path = if text[start..end].contains('\\') { new_path.replace('/', "\\") } else { new_path.to_owned() };
+            result.push_str(&text[last_end..start]);
+            result.push_str(&new_path);
+            last_end = end;
         }
 
-        // Delete all the files marked for deletion.
-        files_to_delete.iter().for_each(|x| self.remove_packed_file_by_path(x));
+        result.push_str(&text[last_end..]);
+        Some(result)
+    }
 
-        // Return the deleted files, so the caller can know what got removed.
-        files_to_delete
+    /// This function returns the byte ranges where the provided path is in a string, but only when it matches whole path segments.
+    ///
+    /// The path is expected to be lowercased and with forward slashes.
+    fn find_path_segments(text: &str, path: &str) -> Vec<(usize, usize)> {
+
+        // This keeps the byte positions of the original text, so we can use it to find the matches.
+        let normalized = text.to_ascii_lowercase().replace('\\', "/");
+        let is_path_char = |byte: u8| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'.' || byte == b'-';
+
+        normalized.match_indices(path).filter_map(|(start, _)| {
+            let end = start + path.len();
+            if start > 0 && (is_path_char(normalized.as_bytes()[start - 1]) || normalized.as_bytes()[start - 1] == b'/') { None }
+            else if end < normalized.len() && is_path_char(normalized.as_bytes()[end]) { None }
+            else { Some((start, end)) }
+        }).collect()
     }
 
     /// This function is used to patch Warhammer Siege map packs so their AI actually works.
@@ -1837,7 +3948,7 @@ impl PackFile {
                         match table_type {
                             TSV_NAME_LOC => {
                                 let definition = schema.get_ref_versioned_file_loc()?.get_version(table_version)?;
-                                if let Ok(table) = Loc::import_tsv(&definition, &path, &table_type) {
+                                if let Ok(table) = Loc::import_tsv(&definition, &path, &table_type, false) {
 
                                     // Depending on the name received, call it one thing or another.
                                     let name = match name {
@@ -1868,7 +3979,7 @@ impl PackFile {
                             }
                             _ => {
                                 let definition = schema.get_ref_versioned_file_db(&table_type)?.get_version(table_version)?;
-                                if let Ok(table) = DB::import_tsv(&definition, &path, &table_type) {
+                                if let Ok(table) = DB::import_tsv(&definition, &path, &table_type, false) {
 
                                     // Depending on the name received, call it one thing or another.
                                     let name = match name {
@@ -1928,10 +4039,68 @@ impl PackFile {
         }
     }
 
+    /// This function imports one language from a multi-language TSV into the Loc PackedFiles of the `PackFile`.
+    ///
+    /// The TSV must have a header row, with the keys in the first column and one column per language. The language to import is chosen by the name of its column.
+    /// Rows are matched by their exact key, and rows without a match are left untouched. All the Locs are updated before applying
+    /// any change, so if one of them fails, none of them is changed.
+    ///
+    /// It returns the keys in the TSV not found in any Loc, and the keys in the Locs not found in the TSV, without duplicates.
+    pub fn import_multi_language_loc(&mut self, path: &Path, language_column: &str) -> Result<(Vec<String>, Vec<String>)> {
+        let schema = SCHEMA.read().unwrap();
+        let schema = if let Some(ref schema) = *schema { schema } else { return Err(ErrorKind::SchemaNotFound.into()) };
+
+        let mut reader = ReaderBuilder::new()
+            .delimiter(b'\t')
+            .quoting(false)
+            .has_headers(true)
+            .flexible(true)
+            .from_path(path)?;
+
+        let column = reader.headers()?.iter().position(|x| x == language_column).ok_or_else(|| Error::from(ErrorKind::LocLanguageColumnNotFound(language_column.to_owned())))?;
+        let mut translations = BTreeMap::new();
+        for record in reader.records() {
+            let record = record?;
+            if let Some(key) = record.get(0) {
+                if let Some(translation) = record.get(column) {
+                    translations.insert(key.to_owned(), translation.to_owned());
+                }
+            }
+        }
+
+        // First, import the translations into copies of the Locs, so we don't leave them half-imported if one fails.
+        let mut found_keys = HashSet::new();
+        let mut keys_not_in_tsv = BTreeSet::new();
+        let mut updated_tables = vec![];
+        let mut packed_files = self.get_ref_mut_packed_files_by_type(PackedFileType::Loc, false);
+        for (index, packed_file) in packed_files.iter_mut().enumerate() {
+            if let DecodedPackedFile::Loc(table) = packed_file.decode_return_ref_no_locks(&schema)? {
+                let mut table = table.clone();
+                let (updated_keys, missing_keys) = table.import_translations(&translations)?;
+                if !updated_keys.is_empty() {
+                    updated_tables.push((index, table));
+                }
+
+                found_keys.extend(updated_keys);
+                keys_not_in_tsv.extend(missing_keys);
+            }
+        }
+
+        // Then, once we know all of them worked, apply the changes.
+        for (index, table) in updated_tables {
+            *packed_files[index].get_ref_mut_decoded() = DecodedPackedFile::Loc(table);
+        }
+
+        let keys_not_in_locs = translations.keys().filter(|x| !found_keys.contains(*x)).cloned().collect();
+        Ok((keys_not_in_locs, keys_not_in_tsv.into_iter().collect()))
+    }
+
     /// This function is used to Mass-Export TSV files from a PackFile.
     ///
+    /// It returns the path of each PackedFile that failed to export, with the reason. The rest of them are exported.
+    ///
     /// NOTE: this will OVERWRITE any existing file that has a name conflict with the TSV files provided.
-    pub fn mass_export_tsv(&mut self, path_types: &[PathType], export_path: &PathBuf) -> Result<String> {
+    pub fn mass_export_tsv(&mut self, path_types: &[PathType], export_path: &PathBuf) -> Result<Vec<(Vec<String>, Error)>> {
 
         // Lists of PackedFiles that couldn't be exported for one thing or another and exported PackedFile names,
         // so we make sure we don't overwrite those with the following ones.
@@ -1970,7 +4139,7 @@ impl PackFile {
                                 export_path.push(name.to_owned());
                                 match data.export_tsv(&export_path, &path[1]) {
                                     Ok(_) => exported_files.push(name),
-                                    Err(error) => error_list.push((packed_file.get_path().join("\\"), error)),
+                                    Err(error) => error_list.push((path, error)),
                                 }
 
                             }
@@ -1990,7 +4159,7 @@ impl PackFile {
                                 export_path.push(name.to_owned());
                                 match data.export_tsv(&export_path, &TSV_NAME_LOC) {
                                     Ok(_) => exported_files.push(name),
-                                    Err(error) => error_list.push((packed_file.get_path().join("\\"), error)),
+                                    Err(error) => error_list.push((path, error)),
                                 }
 
                             }
@@ -1998,21 +4167,14 @@ impl PackFile {
                             // Ignore any other PackedFiles.
                             _ => {}
                         }
-                        Err(error) => error_list.push((packed_file.get_path().join("\\"), error)),
+                        Err(error) => error_list.push((path, error)),
                     }
                 });
             }
             None => return Err(Error::from(ErrorKind::SchemaNotFound)),
         }
 
-        // If there has been errors, return ok with the list of errors.
-        if !error_list.is_empty() {
-            let error_files_string = error_list.iter().map(|x| format!("<li>{}</li>", x.0)).collect::<String>();
-            Ok(format!("<p>All exportable files have been exported, except the following ones:</p><ul>{}</ul>", error_files_string))
-        }
-
-        // Otherwise, just return success and an empty error list.
-        else { Ok("<p>All exportable files have been exported.</p>".to_owned()) }
+        Ok(error_list)
     }
 
     /// This function loads to memory the vanilla (made by CA) dependencies of a `PackFile`.
@@ -2024,7 +4186,7 @@ impl PackFile {
 
         // Get all the DB Tables from the main DB `PackFiles`, if it's configured.
         if let Some(paths) = main_db_pack_paths {
-            if let Ok(pack_file) = PackFile::open_packfiles(&paths, true, false, false) {
+            if let Ok(pack_file) = PackFile::open_packfiles(&paths, true, false, false, false) {
                 for packed_file in pack_file.get_ref_packed_files_by_type(PackedFileType::DB, false) {
 
                     // Clone the PackedFile, and add it to the list.
@@ -2038,7 +4200,7 @@ impl PackFile {
 
         // Get all the Loc PackedFiles from the main Loc `PackFiles`, if it's configured.
         if let Some(paths) = main_loc_pack_paths {
-             if let Ok(pack_file) = PackFile::open_packfiles(&paths, true, false, false) {
+             if let Ok(pack_file) = PackFile::open_packfiles(&paths, true, false, false, false) {
                 for packed_file in pack_file.get_ref_packed_files_by_type(PackedFileType::Loc, false) {
 
                     // Clone the PackedFile, and add it to the list.
@@ -2066,7 +4228,7 @@ impl PackFile {
             // First we load the content `PackFiles`.
             if let Some(ref paths) = contents_paths {
                 if let Some(path) = paths.iter().find(|x| x.file_name().unwrap().to_string_lossy() == packfile_name) {
-                    if let Ok(pack_file) = PackFile::open_packfiles(&[path.to_path_buf()], true, false, false) {
+                    if let Ok(pack_file) = PackFile::open_packfiles(&[path.to_path_buf()], true, false, false, false) {
 
                         // Add the current `PackFile` to the done list, so we don't get into cyclic dependencies.
                         already_loaded_dependencies.push(packfile_name.to_owned());
@@ -2086,7 +4248,7 @@ impl PackFile {
             // Then we load the data `PackFiles`.
             if let Some(ref paths) = data_paths {
                 if let Some(path) = paths.iter().find(|x| x.file_name().unwrap().to_string_lossy() == packfile_name) {
-                    if let Ok(pack_file) = PackFile::open_packfiles(&[path.to_path_buf()], true, false, false) {
+                    if let Ok(pack_file) = PackFile::open_packfiles(&[path.to_path_buf()], true, false, false, false) {
 
                         // Add the current `PackFile` to the done list, so we don't get into cyclic dependencies.
                         already_loaded_dependencies.push(packfile_name.to_owned());
@@ -2149,7 +4311,7 @@ impl PackFile {
             pack_file_path.push(x);
             pack_file_path
         }).collect::<Vec<PathBuf>>();
-        Self::open_packfiles(&pack_file_paths, true, true, true)
+        Self::open_packfiles(&pack_file_paths, true, true, true, false)
     }
 
     /// This function allows you to open one or more `PackFiles`.
@@ -2160,17 +4322,21 @@ impl PackFile {
     ///
     /// You can also make it ignore mod PackFiles, so it only open `PackFiles` released by CA, and can choose to lock it,
     /// so the user cannot save it (avoiding the *"I tried to save and got an out-of-memory error!!!"* problem).
+    ///
+    /// If `recovery` is true, broken `PackFiles` are opened in recovery mode, salvaging as much of them as possible.
+    /// If you need to know what couldn't be salvaged, use `PackFile::open_packfile_with_recovery()` instead.
     pub fn open_packfiles(
         packs_paths: &[PathBuf],
         use_lazy_loading: bool,
         ignore_mods: bool,
-        lock_packfile: bool
+        lock_packfile: bool,
+        recovery: bool,
     ) -> Result<Self> {
 
         // If we just have one `PackFile`, just read it. No fancy logic needed. If you're an asshole and tried to break this
         // by passing it no paths, enjoy the error.
         if packs_paths.is_empty() { return Err(ErrorKind::PackFileNoPathProvided.into()) }
-        if packs_paths.len() == 1 { Self::read(&packs_paths[0], use_lazy_loading) }
+        if packs_paths.len() == 1 { Self::read_with_recovery(&packs_paths[0], use_lazy_loading, recovery) }
 
         // Otherwise, read all of them into a *fake* `PackFile` and take care of the duplicated files like the game will do.
         else {
@@ -2190,7 +4356,7 @@ impl PackFile {
             let mut mod_files = vec![];
             let mut movie_files = vec![];
             for path in packs_paths {
-                match Self::read(&path, use_lazy_loading) {
+                match Self::read_with_recovery(&path, use_lazy_loading, recovery) {
                     Ok(pack) => match pack.get_pfh_file_type() {
                         PFHFileType::Boot => boot_files.append(&mut pack.get_packed_files_all()),
                         PFHFileType::Release => release_files.append(&mut pack.get_packed_files_all()),
@@ -2241,17 +4407,158 @@ impl PackFile {
     }
 
     /// This function reads the content of a PackFile into a `PackFile` struct.
+    ///
+    /// Gzip-compressed PackFiles (`.pack.gz`) are decompressed to a temporary file first, and are always fully loaded to memory.
+    /// This has nothing to do with the compression of PackedFiles within a PackFile.
     pub fn read(
         file_path: &PathBuf,
         use_lazy_loading: bool
     ) -> Result<Self> {
 
         // Check if what we received is even a `PackFile`.
-        if !file_path.file_name().unwrap().to_string_lossy().to_string().ends_with(".pack") { return Err(ErrorKind::OpenPackFileInvalidExtension.into()) }
+        let file_name = file_path.file_name().unwrap().to_string_lossy().to_string();
+        if !file_name.ends_with(".pack") && !file_name.ends_with(GZIP_EXTENSION) { return Err(ErrorKind::OpenPackFileInvalidExtension.into()) }
+        Self::read_from_file_or_gzip(file_path, File::open(&file_path)?, use_lazy_loading)
+    }
+
+    /// This function downloads the PackFile in the provided URL and reads it into a `PackFile` struct.
+    ///
+    /// The PackFile is downloaded to a temporary file, so lazy-loading works over it. The returned `PackFile` only has the name of the
+    /// downloaded file as path, so it has to be saved to a new path.
+    pub fn open_from_url(url: &str, use_lazy_loading: bool) -> Result<Self> {
+        Self::open_from_url_with_progress(url, use_lazy_loading, &|_, _| {})
+    }
+
+    /// This function downloads the PackFile in the provided URL and reads it into a `PackFile` struct, like `open_from_url`,
+    /// calling the provided function with the downloaded bytes and the total bytes (if known) while downloading it.
+    pub fn open_from_url_with_progress(url: &str, use_lazy_loading: bool, progress: &dyn Fn(u64, Option<u64>)) -> Result<Self> {
+
+        // Redirects are followed automatically, so here we should only get the final response.
+        let mut response = reqwest::blocking::get(url).map_err(|error| Error::from(ErrorKind::DownloadPackFileError(error.to_string())))?;
+        if !response.status().is_success() {
+            return Err(ErrorKind::DownloadPackFileError(response.status().to_string()).into());
+        }
+
+        let total_size = response.content_length();
+        let mut file = tempfile::tempfile()?;
+        let mut buffer = vec![0; 64 * 1024];
+        let mut downloaded = 0;
+        progress(downloaded, total_size);
+        let operation = OPERATION_PAUSE.start();
+        loop {
+            operation.wait_if_paused();
+            let read = response.read(&mut buffer).map_err(|error| Error::from(ErrorKind::DownloadPackFileError(error.to_string())))?;
+            if read == 0 { break; }
+            file.write_all(&buffer[..read])?;
+            downloaded += read as u64;
+            progress(downloaded, total_size);
+        }
+        file.seek(SeekFrom::Start(0))?;
+
+        // Use the last part of the URL as name, ignoring queries and fragments.
+        let file_name = response.url().path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .filter(|name| !name.is_empty())
+            .unwrap_or("unknown.pack")
+            .to_owned();
+        Self::read_from_file_or_gzip(&PathBuf::from(file_name), file, use_lazy_loading)
+    }
+
+    /// This function tries to open the PackFile in the provided path and, if it fails, tries to salvage as much of it as possible.
+    ///
+    /// In recovery mode, the index is read until the first broken or missing entry, and only the `PackedFiles` whose data can be fully read are kept.
+    /// Nothing is made up: everything returned comes as-is from the PackFile. The report is empty if the PackFile opened normally.
+    /// Gzip-compressed PackFiles cannot be recovered.
+    pub fn open_packfile_with_recovery(path: &Path, use_lazy_loading: bool) -> Result<(Self, RecoveryReport)> {
+        match Self::read(&path.to_path_buf(), use_lazy_loading) {
+            Ok(pack_file) => Ok((pack_file, RecoveryReport::default())),
+            Err(error) => {
+
+                // If it's not even a PackFile, don't try to recover it.
+                if let ErrorKind::OpenPackFileInvalidExtension = error.kind() { return Err(error) }
+
+                let mut report = RecoveryReport::default();
+                report.original_error = error.to_string();
+                let pack_file = Self::read_from_file(&path.to_path_buf(), File::open(path)?, false, Some(&mut report))?;
+                Ok((pack_file, report))
+            }
+        }
+    }
+
+    /// This function reads the content of a PackFile into a `PackFile` struct, opening it in recovery mode if `recovery` is true.
+    fn read_with_recovery(file_path: &PathBuf, use_lazy_loading: bool, recovery: bool) -> Result<Self> {
+        if recovery { Self::open_packfile_with_recovery(file_path, use_lazy_loading).map(|(pack_file, _)| pack_file) }
+        else { Self::read(file_path, use_lazy_loading) }
+    }
+
+    /// This function reads the content of an already opened PackFile into a `PackFile` struct, decompressing it first if it's Gzip-compressed.
+    fn read_from_file_or_gzip(
+        file_path: &PathBuf,
+        mut file: File,
+        use_lazy_loading: bool
+    ) -> Result<Self> {
+
+        // Gzip-compressed PackFiles are detected by their magic bytes, not by their extension.
+        let mut magic = [0; 2];
+        let is_gzip = file.read_exact(&mut magic).is_ok() && magic == GZIP_MAGIC;
+        file.seek(SeekFrom::Start(0))?;
+
+        if is_gzip {
+            let mut decompressed_file = tempfile::tempfile()?;
+            std::io::copy(&mut GzDecoder::new(BufReader::new(file)), &mut decompressed_file)?;
+            decompressed_file.seek(SeekFrom::Start(0))?;
+            Self::read_from_file(file_path, decompressed_file, false, None)
+        }
+        else {
+            Self::read_from_file(file_path, file, use_lazy_loading, None)
+        }
+    }
+
+    /// This function reads only the header and the index of the PackFile in the provided path, returning the info of its PackedFiles.
+    ///
+    /// The data of the PackedFiles is never read, so this is way faster than opening the PackFile when we only need to list its contents.
+    /// Encrypted indexes are decrypted, but encrypted data is not, so the info of encrypted PackedFiles cannot tell if they're compressed.
+    pub fn read_index_only(path: &Path) -> Result<Vec<PackedFileInfo>> {
+        let file_path = path.to_path_buf();
+        let file_name = file_path.file_name().ok_or(ErrorKind::PackFileIsNotAFile)?.to_string_lossy().to_string();
+        if !file_name.ends_with(".pack") { return Err(ErrorKind::OpenPackFileInvalidExtension.into()) }
 
-        // Prepare the PackFile to be read and the virtual PackFile to be written.
         let mut pack_file = BufReader::new(File::open(&file_path)?);
-        let pack_file_name = file_path.file_name().unwrap().to_string_lossy().to_string();
+        let (pack_file_decoded, packed_file_index, packed_file_count, packed_file_index_path_offset, _) = Self::read_header_and_indexes(&mut pack_file, &file_path, false)?;
+        let is_encrypted = pack_file_decoded.bitmask.contains(PFHFlags::HAS_ENCRYPTED_DATA);
+
+        let mut index_position: usize = 0;
+        let mut packed_files_info = Vec::with_capacity(packed_file_count as usize);
+        for packed_files_to_decode in (0..packed_file_count).rev() {
+            let (size, timestamp, is_compressed, path) = pack_file_decoded.read_packed_file_index_entry(&packed_file_index, &mut index_position, packed_file_index_path_offset, packed_files_to_decode)?;
+
+            // Reserved PackedFiles are not shown when opening the PackFile, so we don't list them here either.
+            if path == [RESERVED_NAME_NOTES] || path == [RESERVED_NAME_ROW_COMMENTS] || path == [RESERVED_NAME_SETTINGS] { continue; }
+
+            packed_files_info.push(PackedFileInfo {
+                path,
+                packfile_name: file_name.to_owned(),
+                timestamp,
+                size,
+                is_compressed,
+                compression_format: None,
+                is_encrypted,
+                is_cached: false,
+                cached_type: "Not Yet Cached".to_owned(),
+            });
+        }
+
+        Ok(packed_files_info)
+    }
+
+    /// This function reads the header and the indexes of a PackFile, leaving the reader at the start of the data of its PackedFiles.
+    ///
+    /// It returns the `PackFile` with the data of the header and the PackFile index, the raw PackedFile index, the amount of PackedFiles in it,
+    /// the offset of the path in each entry of the PackedFile index, and the position where the data of the PackedFiles starts.
+    ///
+    /// In recovery mode, incomplete indexes are returned as they are, so the caller can read as many entries from them as possible.
+    fn read_header_and_indexes(pack_file: &mut BufReader<File>, file_path: &PathBuf, recovery: bool) -> Result<(Self, Vec<u8>, u32, usize, u64)> {
+
         let mut pack_file_decoded = Self::new();
 
         // First, we do some quick checkings to ensure it's a valid PackFile.
@@ -2315,21 +4622,35 @@ impl PackFile {
 
         // Ensure the PackFile has all the data needed for the index. If the PackFile's data is encrypted
         // and the PackFile is PFH5, due to how the encryption works, the data should start in a multiple of 8.
-        let mut data_position = u64::from(buffer.len() as u32 + pack_file_index_size + packed_file_index_size);
+        let mut data_position = buffer.len() as u64 + u64::from(pack_file_index_size) + u64::from(packed_file_index_size);
         if pack_file_decoded.bitmask.contains(PFHFlags::HAS_ENCRYPTED_DATA) &&
             pack_file_decoded.bitmask.contains(PFHFlags::HAS_EXTENDED_HEADER) &&
             pack_file_decoded.pfh_version == PFHVersion::PFH5 {
             data_position = if (data_position % 8) > 0 { data_position + 8 - (data_position % 8) } else { data_position };
         }
-        if pack_file_len < data_position { return Err(ErrorKind::PackFileIndexesNotComplete.into()) }
-
-        // Create the buffers for the indexes data.
-        let mut pack_file_index = vec![0; pack_file_index_size as usize];
-        let mut packed_file_index = vec![0; packed_file_index_size as usize];
-
-        // Get the data from both indexes to their buffers.
-        pack_file.read_exact(&mut pack_file_index)?;
-        pack_file.read_exact(&mut packed_file_index)?;
+        let are_indexes_complete = pack_file_len >= data_position;
+        if !are_indexes_complete && !recovery { return Err(ErrorKind::PackFileIndexesNotComplete.into()) }
+
+        // Create the buffers for the indexes data. The sizes come from the header, so we don't trust them
+        // further than the bytes left in the file, to avoid huge allocations from broken headers.
+        let bytes_left = pack_file_len.saturating_sub(buffer.len() as u64);
+        let pack_file_index_size = u64::from(pack_file_index_size).min(bytes_left) as usize;
+        let packed_file_index_size = u64::from(packed_file_index_size).min(bytes_left - pack_file_index_size as u64) as usize;
+        let mut pack_file_index = vec![0; pack_file_index_size];
+        let mut packed_file_index = vec![0; packed_file_index_size];
+
+        // Get the data from both indexes to their buffers. If they're incomplete, we get whatever is left of them.
+        if are_indexes_complete {
+            pack_file.read_exact(&mut pack_file_index)?;
+            pack_file.read_exact(&mut packed_file_index)?;
+        }
+        else {
+            let mut indexes = vec![];
+            pack_file.read_to_end(&mut indexes)?;
+            indexes.truncate(pack_file_index_size + packed_file_index_size);
+            packed_file_index = indexes.split_off(pack_file_index_size.min(indexes.len()));
+            pack_file_index = indexes;
+        }
 
         // Read the PackFile Index.
         let mut pack_file_index_position: usize = 0;
@@ -2337,9 +4658,13 @@ impl PackFile {
         // First, we decode every entry in the PackFile index and store it. It's encoded in StringU8 terminated in 00,
         // so we just read them char by char until hitting 0, then decode the next one and so on.
         // NOTE: This doesn't deal with encryption, as we haven't seen any encrypted PackFile with data in this index.
+        // In recovery mode, we keep the entries we could read and go on, as the PackedFile index is what we actually need.
         for _ in 0..pack_file_count {
-            let pack_file_name = pack_file_index.decode_packedfile_string_u8_0terminated(pack_file_index_position, &mut pack_file_index_position)?;
-            pack_file_decoded.pack_files.push(pack_file_name);
+            match pack_file_index.decode_packedfile_string_u8_0terminated(pack_file_index_position, &mut pack_file_index_position) {
+                Ok(pack_file_name) => pack_file_decoded.pack_files.push(pack_file_name),
+                Err(_) if recovery => break,
+                Err(error) => return Err(error),
+            }
         }
 
         // Depending on the version of the PackFile and his bitmask, the PackedFile index has one format or another.
@@ -2365,49 +4690,58 @@ impl PackFile {
             PFHVersion::PFH0 => 4
         };
 
+        Ok((pack_file_decoded, packed_file_index, packed_file_count, packed_file_index_path_offset, data_position))
+    }
+
+    /// This function reads the content of an already opened PackFile into a `PackFile` struct.
+    ///
+    /// If a recovery report is provided, broken index entries and PackedFiles are skipped instead of failing, and recorded in the report.
+    fn read_from_file(
+        file_path: &PathBuf,
+        file: File,
+        use_lazy_loading: bool,
+        mut recovery_report: Option<&mut RecoveryReport>,
+    ) -> Result<Self> {
+
+        // Prepare the PackFile to be read and the virtual PackFile to be written.
+        let mut pack_file = BufReader::new(file);
+        let pack_file_name = file_path.file_name().unwrap().to_string_lossy().to_string();
+        let pack_file_len = pack_file.get_ref().metadata()?.len();
+        let (mut pack_file_decoded, packed_file_index, packed_file_count, packed_file_index_path_offset, mut data_position) = Self::read_header_and_indexes(&mut pack_file, file_path, recovery_report.is_some())?;
+
         // Prepare the needed stuff to read the PackedFiles.
         let mut index_position: usize = 0;
+        // If we want to, memory-map the PackFile to speed up random reads. If it fails, we just fall back to the reader.
+        // This is safe as long as nobody modifies the PackFile while we have it open, and we keep it open until we're done with it.
+        let mmap = if use_lazy_loading && SETTINGS.read().unwrap().settings_bool["use_memory_mapped_files"] {
+            unsafe { Mmap::map(pack_file.get_ref()) }.ok().map(Arc::new)
+        } else { None };
+
         let pack_file = Arc::new(Mutex::new(pack_file));
         for packed_files_to_decode in (0..packed_file_count).rev() {
 
-            // Get his size. If it's encrypted, decrypt it first.
-            let size = if pack_file_decoded.bitmask.contains(PFHFlags::HAS_ENCRYPTED_INDEX) {
-                let encrypted_size = packed_file_index.decode_integer_u32(index_position)?;
-                decrypt_index_item_file_length(encrypted_size, packed_files_to_decode as u32)
-            } else {
-                packed_file_index.decode_integer_u32(index_position)?
+            // Get the data of the PackedFile from the index. If we're recovering a broken PackFile, stop at the first broken entry, as we cannot know where the next one starts.
+            let (size, timestamp, is_compressed, path) = match pack_file_decoded.read_packed_file_index_entry(&packed_file_index, &mut index_position, packed_file_index_path_offset, packed_files_to_decode) {
+                Ok(entry) => entry,
+                Err(error) => match recovery_report {
+                    Some(ref mut report) => {
+                        report.parsing_stopped_at = Some(format!("PackedFile index, entry {} of {}: {}", packed_file_count - packed_files_to_decode, packed_file_count, error));
+                        break;
+                    }
+                    None => return Err(error),
+                }
             };
 
-            // If we have the last modified date of the PackedFiles in the Index, get it. Otherwise, default to 0,
-            // so we have something to write in case we want to enable them for our PackFile.
-            let timestamp = if pack_file_decoded.bitmask.contains(PFHFlags::HAS_INDEX_WITH_TIMESTAMPS) {
-                match pack_file_decoded.pfh_version {
-                    PFHVersion::PFH6 | PFHVersion::PFH5 | PFHVersion::PFH4 => {
-                        let timestamp = i64::from(packed_file_index.decode_integer_u32(index_position + 4)?);
-                        if pack_file_decoded.bitmask.contains(PFHFlags::HAS_ENCRYPTED_INDEX) {
-                            i64::from(decrypt_index_item_file_length(timestamp as u32, packed_files_to_decode as u32))
-                        } else { timestamp }
+            // When recovering, PackedFiles with their data out of the PackFile are skipped. As data is stored in order, all the ones after it will be skipped too.
+            if let Some(ref mut report) = recovery_report {
+                if data_position + u64::from(size) > pack_file_len {
+                    if report.parsing_stopped_at.is_none() {
+                        report.parsing_stopped_at = Some(format!("PackedFile data, byte {} of {}", data_position, pack_file_len));
                     }
-
-                    // We haven't found a single encrypted PFH3/PFH0 PackFile to test, so always assume these are unencrypted. Also, PFH0 doesn't seem to have a timestamp.
-                    PFHVersion::PFH3 | PFHVersion::PFH2 => (packed_file_index.decode_integer_i64(index_position + 4)? / WINDOWS_TICK) - SEC_TO_UNIX_EPOCH,
-                    PFHVersion::PFH0 => 0,
+                    report.unreadable_packed_files.push((path, "Data out of the PackFile.".to_owned()));
+                    continue;
                 }
-            } else { 0 };
-
-            // Update his offset, and get his compression data if it has it.
-            index_position += packed_file_index_path_offset;
-            let is_compressed = if let PFHVersion::PFH5 = pack_file_decoded.pfh_version {
-                if let Ok(true) = packed_file_index.decode_bool(index_position - 1) { true }
-                else { false }
-            } else { false };
-
-            // Get his path. Like the PackFile index, it's a StringU8 terminated in 00. We get it and split it in folders for easy use.
-            let path = if pack_file_decoded.bitmask.contains(PFHFlags::HAS_ENCRYPTED_INDEX) {
-                decrypt_index_item_filename(&packed_file_index[index_position..], size as u8, &mut index_position)
             }
-            else { packed_file_index.decode_packedfile_string_u8_0terminated(index_position, &mut index_position)? };
-            let path = path.split('\\').map(|x| x.to_owned()).collect::<Vec<String>>();
 
             // Once we are done, we create the PackedFile and add it to the PackedFile list.
             let raw_data = RawPackedFile::read_from_data(
@@ -2418,6 +4752,7 @@ impl PackFile {
                 if pack_file_decoded.bitmask.contains(PFHFlags::HAS_ENCRYPTED_DATA) { Some(pack_file_decoded.pfh_version) } else { None },
                 PackedFileData::OnDisk(RawOnDisk::new(
                     pack_file.clone(),
+                    mmap.clone(),
                     data_position,
                     size,
                     is_compressed,
@@ -2436,6 +4771,14 @@ impl PackFile {
                 }
             }
 
+            else if packed_file.get_path() == &[RESERVED_NAME_ROW_COMMENTS] {
+                if let Ok(data) = packed_file.get_raw_data_and_keep_it() {
+                    if let Ok(row_comments) = from_slice(&data) {
+                        pack_file_decoded.row_comments = row_comments;
+                    }
+                }
+            }
+
             else if packed_file.get_path() == &[RESERVED_NAME_SETTINGS] {
                 if let Ok(data) = packed_file.get_raw_data_and_keep_it() {
                     pack_file_decoded.settings = if let Ok(settings) = PackFileSettings::load(&data) {
@@ -2460,6 +4803,20 @@ impl PackFile {
             else { data_position += u64::from(size); }
         }
 
+        // When recovering, we load every PackedFile we found to memory, so the ones we cannot read are detected now, and not when saving.
+        if let Some(report) = recovery_report {
+            let mut packed_files = vec![];
+            for mut packed_file in pack_file_decoded.packed_files.drain(..) {
+                match packed_file.get_ref_raw().get_data().and_then(|_| packed_file.get_ref_mut_raw().load_data()) {
+                    Ok(_) => packed_files.push(packed_file),
+                    Err(error) => report.unreadable_packed_files.push((packed_file.get_path().to_vec(), error.to_string())),
+                }
+            }
+            pack_file_decoded.packed_files = packed_files;
+            pack_file_decoded.clear_modified();
+            return Ok(pack_file_decoded);
+        }
+
         // If at this point we have not reached the end of the PackFile, there is something wrong with it.
         // NOTE: Arena PackFiles have extra data at the end. If we detect one of those PackFiles, take that into account.
         if pack_file_decoded.pfh_version == PFHVersion::PFH5 && pack_file_decoded.bitmask.contains(PFHFlags::HAS_EXTENDED_HEADER) {
@@ -2471,15 +4828,80 @@ impl PackFile {
         if !use_lazy_loading { for packed_file in &mut pack_file_decoded.packed_files { packed_file.get_ref_mut_raw().load_data()?; }}
 
         // Return our PackFile.
+        pack_file_decoded.clear_modified();
         Ok(pack_file_decoded)
     }
 
+    /// This function reads an entry of the PackedFile index, returning the size, timestamp, compression state and path of its PackedFile.
+    fn read_packed_file_index_entry(
+        &self,
+        packed_file_index: &[u8],
+        index_position: &mut usize,
+        packed_file_index_path_offset: usize,
+        packed_files_to_decode: u32,
+    ) -> Result<(u32, i64, bool, Vec<String>)> {
+
+        // Get his size. If it's encrypted, decrypt it first.
+        let size = if self.bitmask.contains(PFHFlags::HAS_ENCRYPTED_INDEX) {
+            let encrypted_size = packed_file_index.decode_integer_u32(*index_position)?;
+            decrypt_index_item_file_length(encrypted_size, packed_files_to_decode as u32)
+        } else {
+            packed_file_index.decode_integer_u32(*index_position)?
+        };
+
+        // If we have the last modified date of the PackedFiles in the Index, get it. Otherwise, default to 0,
+        // so we have something to write in case we want to enable them for our PackFile.
+        let timestamp = if self.bitmask.contains(PFHFlags::HAS_INDEX_WITH_TIMESTAMPS) {
+            match self.pfh_version {
+                PFHVersion::PFH6 | PFHVersion::PFH5 | PFHVersion::PFH4 => {
+                    let timestamp = i64::from(packed_file_index.decode_integer_u32(*index_position + 4)?);
+                    if self.bitmask.contains(PFHFlags::HAS_ENCRYPTED_INDEX) {
+                        i64::from(decrypt_index_item_file_length(timestamp as u32, packed_files_to_decode as u32))
+                    } else { timestamp }
+                }
+
+                // We haven't found a single encrypted PFH3/PFH0 PackFile to test, so always assume these are unencrypted. Also, PFH0 doesn't seem to have a timestamp.
+                PFHVersion::PFH3 | PFHVersion::PFH2 => (packed_file_index.decode_integer_i64(*index_position + 4)? / WINDOWS_TICK) - SEC_TO_UNIX_EPOCH,
+                PFHVersion::PFH0 => 0,
+            }
+        } else { 0 };
+
+        // Update his offset, and get his compression data if it has it.
+        *index_position += packed_file_index_path_offset;
+        let is_compressed = if let PFHVersion::PFH5 = self.pfh_version {
+            if let Ok(true) = packed_file_index.decode_bool(*index_position - 1) { true }
+            else { false }
+        } else { false };
+
+        // Get his path. Like the PackFile index, it's a StringU8 terminated in 00. We get it and split it in folders for easy use.
+        if *index_position >= packed_file_index.len() { return Err(ErrorKind::PackFileIndexesNotComplete.into()) }
+        let path = if self.bitmask.contains(PFHFlags::HAS_ENCRYPTED_INDEX) {
+            decrypt_index_item_filename(&packed_file_index[*index_position..], size as u8, index_position)
+        }
+        else { packed_file_index.decode_packedfile_string_u8_0terminated(*index_position, index_position)? };
+        let path = path.split('\\').map(|x| x.to_owned()).collect::<Vec<String>>();
+
+        Ok((size, timestamp, is_compressed, path))
+    }
+
+    /// This function saves the `PackFile` like `save`, returning the size of the file on disk before and after saving it.
+    ///
+    /// Saving always writes the data of the `PackedFiles` one after another, so any space left by removed or shrunk `PackedFiles` is reclaimed.
+    /// The `PackedFiles` are kept in the same order `save` uses, as the games need it.
+    ///
+    /// If the `PackFile` didn't exist on disk before, its size before saving is 0.
+    pub fn save_compacted(&mut self, new_path: Option<PathBuf>) -> Result<(u64, u64)> {
+        let old_size = std::fs::metadata(&self.file_path).map(|x| x.len()).unwrap_or(0);
+        self.save(new_path)?;
+        let new_size = std::fs::metadata(&self.file_path)?.len();
+        Ok((old_size, new_size))
+    }
+
     /// This function tries to save a `PackFile` to a file in the filesystem.
     ///
     /// If no path is passed, the `PackFile` will be saved in his current path.
     /// If a path is passed as `new_path` the `PackFile` will be saved in that path.
     pub fn save(&mut self, new_path: Option<PathBuf>) -> Result<()> {
-
         // If any of the problematic masks in the header is set or is one of CA's, return an error.
         if !self.is_editable(*SETTINGS.read().unwrap().settings_bool.get("allow_editing_of_ca_packfiles").unwrap()) { return Err(ErrorKind::PackFileIsNonEditable.into()) }
 
@@ -2496,6 +4918,22 @@ impl PackFile {
             self.packed_files.push(packed_file);
         }
 
+        // Same with the row comments of DB Tables.
+        if !self.row_comments.is_empty() {
+            let mut data = vec![];
+            data.write_all(&to_string_pretty(&self.row_comments)?.as_bytes())?;
+            let raw_data = RawPackedFile::read_from_vec(vec![RESERVED_NAME_ROW_COMMENTS.to_owned()], self.get_file_name(), 0, false, data);
+            let packed_file = PackedFile::new_from_raw(&raw_data);
+            self.packed_files.push(packed_file);
+        }
+
+        // If we want reproducible saves, strip all the timestamps, as they're the only thing that changes between saves of the same content.
+        let reproducible_save = SETTINGS.read().unwrap().settings_bool["reproducible_save"];
+        if reproducible_save {
+            self.bitmask.remove(PFHFlags::HAS_INDEX_WITH_TIMESTAMPS);
+            self.packed_files.iter_mut().for_each(|x| x.get_ref_mut_raw().set_timestamp(0));
+        }
+
         // Saving PackFile settings.
         let mut data = vec![];
         data.write_all(&to_string_pretty(&self.settings)?.as_bytes())?;
@@ -2506,14 +4944,24 @@ impl PackFile {
         // For some bizarre reason, if the PackedFiles are not alphabetically sorted they may or may not crash the game for particular people.
         // So, to fix it, we have to sort all the PackedFiles here by path.
         // NOTE: This sorting has to be CASE INSENSITIVE. This means for "ac", "Ab" and "aa" it'll be "aa", "Ab", "ac".
-        self.packed_files.sort_unstable_by_key(|a| a.get_path().join("\\").to_lowercase());
+        // NOTE: If the PackFile has a custom order set, we respect it, as some games use the order for overrides.
+        if !self.settings.settings_bool.get("keep_packed_file_order").copied().unwrap_or(false) {
+            self.packed_files.sort_unstable_by_key(|a| a.get_path().join("\\").to_lowercase());
+        }
+
+        // PackedFiles we don't know the compression format of are compressed with the one of the game selected.
+        let default_compression_format = SUPPORTED_GAMES.get(&**GAME_SELECTED.read().unwrap()).map(|x| x.compression_format).unwrap_or(CompressionFormat::Lzma1);
 
         // We ensure that all the data is loaded and in his right form (compressed/encrypted) before attempting to save.
-        // We need to do this here because we need later on their compressed size.
+        // We need to do this here because we need later on their compressed size. This is the only part of the save
+        // that can be paused, as once we start writing the file it has to be written in one go.
+        let operation = OPERATION_PAUSE.start();
         for packed_file in &mut self.packed_files {
+            operation.wait_if_paused();
 
             // If we decoded it, re-encode it. Otherwise, just load it.
             packed_file.encode()?;
+            let compression_format = packed_file.get_ref_raw().get_compression_format().unwrap_or(default_compression_format);
 
             // Remember: first compress (only PFH5), then encrypt.
             let (path, data, is_compressed, is_encrypted, should_be_compressed, should_be_encrypted) = packed_file.get_ref_mut_raw().get_data_and_info_from_memory()?;
@@ -2525,7 +4973,7 @@ impl PackFile {
             }
 
             if *should_be_compressed && !*is_compressed {
-                *data = compress_data(&data)?;
+                *data = compress_data(&data, compression_format)?;
                 *is_compressed = true;
             }
             else if !*should_be_compressed && *is_compressed {
@@ -2540,6 +4988,7 @@ impl PackFile {
                 *should_be_encrypted = None;
             }
         }
+        drop(operation);
 
         // First we encode the indexes and the data (just in case we compressed it).
         let mut pack_file_index = vec![];
@@ -2575,9 +5024,6 @@ impl PackFile {
             packed_file_index.push(0);
         }
 
-        // Create the file to save to, and save the header and the indexes.
-        let mut file = BufWriter::new(File::create(&self.file_path)?);
-
         // Write the entire header.
         let mut header = vec![];
         header.encode_string_u8(&self.pfh_version.get_value());
@@ -2588,7 +5034,7 @@ impl PackFile {
         header.encode_integer_u32(packed_file_index.len() as u32);
 
         // Update the creation time, then save it. PFH0 files don't have timestamp in the headers.
-        self.timestamp = get_current_time();
+        self.timestamp = if reproducible_save { 0 } else { get_current_time() };
         match self.pfh_version {
             PFHVersion::PFH6 | PFHVersion::PFH5 | PFHVersion::PFH4 => header.encode_integer_u32(self.timestamp as u32),
             PFHVersion::PFH3 | PFHVersion::PFH2 => header.encode_integer_i64((self.timestamp + SEC_TO_UNIX_EPOCH) * WINDOWS_TICK),
@@ -2616,22 +5062,154 @@ impl PackFile {
             header.extend_from_slice(&self.extra_subheader_data);
         }
 
-        // Write the indexes and the data of the PackedFiles. No need to keep the data, as it has been preloaded before.
-        file.write_all(&header)?;
-        file.write_all(&pack_file_index)?;
-        file.write_all(&packed_file_index)?;
-        for packed_file in &self.packed_files {
-            let data = packed_file.get_ref_raw().get_raw_data()?;
-            file.write_all(&data)?;
+        // All the data is in memory now, but the memory maps of the file cannot be alive while we truncate it, so drop them.
+        // Where the data is on disk is kept until the save succeeds, so we can still revert PackedFiles if we fail to create the file.
+        self.packed_files.iter_mut().for_each(|x| x.get_ref_mut_raw().clear_disk_data_mmap());
+
+        // Create the file to save to, and save the header, the indexes and the data. If the path is a gzip one, compress the entire file.
+        let file = BufWriter::new(File::create(&self.file_path)?);
+        if self.get_file_name().ends_with(GZIP_EXTENSION) {
+            let mut file = GzEncoder::new(file, Compression::default());
+            self.write_header_and_data(&mut file, &header, &pack_file_index, &packed_file_index)?;
+            file.finish()?.flush()?;
+        }
+        else {
+            let mut file = file;
+            self.write_header_and_data(&mut file, &header, &pack_file_index, &packed_file_index)?;
+            file.flush()?;
         }
 
         // Remove again the reserved PackedFiles.
         self.remove_packed_file_by_path(&[RESERVED_NAME_NOTES.to_owned()]);
+        self.remove_packed_file_by_path(&[RESERVED_NAME_ROW_COMMENTS.to_owned()]);
         self.remove_packed_file_by_path(&[RESERVED_NAME_SETTINGS.to_owned()]);
 
-        // If nothing has failed, return success.
+        // The data we read the PackedFiles from has been overwritten, so forget about it.
+        self.packed_files.iter_mut().for_each(|x| x.get_ref_mut_raw().clear_disk_data());
+
+        // If nothing has failed, mark everything as unmodified and return success.
+        self.clear_modified();
+        Ok(())
+    }
+
+    /// This function writes the header, the indexes and the data of the PackedFiles of this `PackFile` to the provided writer.
+    ///
+    /// No need to keep the data, as it has been preloaded before.
+    fn write_header_and_data<W: Write>(&self, file: &mut W, header: &[u8], pack_file_index: &[u8], packed_file_index: &[u8]) -> Result<()> {
+        file.write_all(header)?;
+        file.write_all(pack_file_index)?;
+        file.write_all(packed_file_index)?;
+        for packed_file in &self.packed_files {
+            let data = packed_file.get_ref_raw().get_raw_data()?;
+            file.write_all(&data)?;
+        }
         Ok(())
     }
+
+    /// This function installs the saved `PackFile` in the `/data` folder of the currently selected game.
+    ///
+    /// Any previous install of the `PackFile` there is removed first. The new one is hardlinked if possible, falling back to a copy
+    /// if hardlinking fails (for example, if the game is in another drive). If the `PackFile` is already in that folder, this does nothing.
+    pub fn install_to_game_data_path(&self) -> Result<()> {
+        let data_path = get_game_selected_data_path().ok_or_else(|| Error::from(ErrorKind::GameSelectedPathNotCorrectlyConfigured))?;
+        let file_name = self.file_path.file_name().ok_or_else(|| Error::from(ErrorKind::PackFileIsNotAFile))?;
+        let install_path = data_path.join(file_name);
+
+        if let (Ok(source), Ok(destination)) = (self.file_path.canonicalize(), install_path.canonicalize()) {
+            if source == destination { return Ok(()) }
+        }
+
+        let install = || -> std::io::Result<()> {
+            if install_path.is_file() {
+                std::fs::remove_file(&install_path)?;
+            }
+
+            if std::fs::hard_link(&self.file_path, &install_path).is_err() {
+                std::fs::copy(&self.file_path, &install_path)?;
+            }
+            Ok(())
+        };
+
+        install().map_err(|error| Error::from(ErrorKind::PackFileInstallFailed(error.to_string())))
+    }
+
+    /// This function marks the `PackFile` and all its `PackedFiles` as unmodified, with no changes left to journal.
+    fn clear_modified(&mut self) {
+        self.modified = false;
+        self.packed_files.par_iter_mut().for_each(|x| {
+            x.set_modified(false);
+            x.set_unjournaled_changes(false);
+        });
+    }
+}
+
+/// Implementation of `ExplodeManifest`.
+impl ExplodeManifest {
+
+    /// This function creates the manifest of the provided `PackFile`, with the provided per-file metadata.
+    fn new(pack_file: &PackFile, packed_files: Vec<ExplodeManifestEntry>) -> Self {
+        Self {
+            file_name: pack_file.get_file_name(),
+            pfh_version: pack_file.pfh_version.get_value().to_owned(),
+            pfh_file_type: pack_file.pfh_file_type.get_value(),
+            bitmask: pack_file.bitmask.bits(),
+            timestamp: pack_file.timestamp,
+            game_version: pack_file.game_version,
+            build_number: pack_file.build_number,
+            authoring_tool: pack_file.authoring_tool.to_owned(),
+            extra_subheader_data: pack_file.extra_subheader_data.to_vec(),
+            pack_files: pack_file.pack_files.to_vec(),
+            notes: pack_file.notes.clone(),
+            row_comments: pack_file.row_comments.clone(),
+            settings: pack_file.settings.clone(),
+            packed_files,
+        }
+    }
+}
+
+/// Implementation of `ExplodeManifestEntry`.
+impl ExplodeManifestEntry {
+
+    /// This function creates the manifest entry of the provided `PackedFile`.
+    fn new(packed_file: &PackedFile, table: Option<VersionControlTable>) -> Self {
+        let raw = packed_file.get_ref_raw();
+        Self {
+            path: packed_file.get_path().to_vec(),
+            timestamp: raw.get_timestamp(),
+            should_be_compressed: raw.get_should_be_compressed(),
+            should_be_encrypted: raw.get_should_be_encrypted().map(|x| x.get_value().to_owned()),
+            table,
+        }
+    }
+
+    /// This function creates a `RawPackedFile` with the provided data and the metadata of this entry.
+    fn get_raw_packed_file(&self, pack_file_name: &str, data: Vec<u8>) -> Result<RawPackedFile> {
+        let mut raw_data = RawPackedFile::read_from_vec(self.path.to_vec(), pack_file_name.to_owned(), self.timestamp, false, data);
+        raw_data.set_should_be_compressed(self.should_be_compressed);
+        raw_data.set_should_be_encrypted(match self.should_be_encrypted {
+            Some(ref version) => Some(PFHVersion::get_version(version)?),
+            None => None,
+        });
+        Ok(raw_data)
+    }
+}
+
+/// Implementation of `OptimizeReport`.
+impl OptimizeReport {
+
+    /// This function returns the total amount of rows removed because they were identical to vanilla ones, including the ones of removed tables.
+    pub fn get_total_removed_rows(&self) -> usize {
+        self.removed_rows.iter().map(|(_, rows)| rows).sum()
+    }
+}
+
+/// Implementation of `Default` for `ExtractOverwritePolicy`.
+///
+/// Overwriting is the default, as that's what a normal extraction does.
+impl Default for ExtractOverwritePolicy {
+    fn default() -> Self {
+        Self::Overwrite
+    }
 }
 
 /// Implementaion of trait `Default` for `PackFile`.
@@ -2763,6 +5341,7 @@ impl Default for PackFileSettings {
 
         settings_text.insert("diagnostics_files_to_ignore".to_owned(), "".to_owned());
         settings_bool.insert("disable_autosaves".to_owned(), false);
+        settings_bool.insert("keep_packed_file_order".to_owned(), false);
 
         Self {
             settings_text,
@@ -17,6 +17,8 @@ meaning the code that takes care of loading/writing their data from/to disk.
 You'll rarely have to touch anything here.
 !*/
 
+use memmap2::Mmap;
+
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::io::prelude::*;
@@ -28,7 +30,7 @@ use rpfm_error::Error;
 
 use crate::packedfile::animpack::AnimPacked;
 use crate::packfile::*;
-use crate::packfile::compression::decompress_data;
+use crate::packfile::compression::{CompressionFormat, decompress_data, get_decompressed_size};
 use crate::packedfile::{DecodedPackedFile, PackedFileType};
 use crate::packedfile::table::{db::DB, loc::Loc};
 use crate::schema::Schema;
@@ -43,6 +45,12 @@ use crate::SCHEMA;
 pub struct PackedFile {
     raw: RawPackedFile,
     decoded: DecodedPackedFile,
+
+    /// If the `PackedFile` has been modified since it was loaded or saved. Any mutable access to its data counts as a modification.
+    modified: bool,
+
+    /// If the `PackedFile` has changes that have not been written to the recovery journal yet. Set along with `modified`, and when reverting it.
+    unjournaled: bool,
 }
 
 /// This struct represents a `PackedFile` in memory in his raw form.
@@ -66,6 +74,12 @@ pub struct RawPackedFile {
 
     /// the data of the PackedFile. Use the getter/setter functions to interact with it.
     data: PackedFileData,
+
+    /// The location of the data of the PackedFile in the PackFile it was read from, if it was read from one. Used to revert any change done to it.
+    disk_data: Option<RawOnDisk>,
+
+    /// The format the data was compressed with when we decompressed it, if it was compressed. Used to compress it back with the same format.
+    compression_format: Option<CompressionFormat>,
 }
 
 /// This enum represents the data of a `PackedFile`, in his current state.
@@ -85,6 +99,9 @@ pub struct RawOnDisk {
 
     /// Reader over the PackFile containing the PackedFile.
     reader: Arc<Mutex<BufReader<File>>>,
+
+    /// Memory map over the PackFile containing the PackedFile. If present, it's used instead of the reader.
+    mmap: Option<Arc<Mmap>>,
     start: u64,
     size: u32,
     is_compressed: bool,
@@ -107,9 +124,15 @@ pub struct PackedFileInfo {
     /// This is the ***Last Modified*** time.
     pub timestamp: i64,
 
+    /// This is the size in bytes of the data of the `PackedFile`, as it is stored.
+    pub size: u32,
+
     /// If the `PackedFile` is compressed or not.
     pub is_compressed: bool,
 
+    /// The format the `PackedFile` is or was compressed with, if we know it.
+    pub compression_format: Option<CompressionFormat>,
+
     /// If the `PackedFile` is encrypted or not.
     pub is_encrypted: bool,
 
@@ -140,8 +163,12 @@ impl PackedFile {
                 should_be_compressed: false,
                 should_be_encrypted: None,
                 data: PackedFileData::OnMemory(vec![], false, None),
+                disk_data: None,
+                compression_format: None,
             },
             decoded: DecodedPackedFile::Unknown,
+            modified: true,
+            unjournaled: true,
         }
     }
 
@@ -150,6 +177,8 @@ impl PackedFile {
         Self {
             raw: data.clone(),
             decoded: DecodedPackedFile::Unknown,
+            modified: true,
+            unjournaled: true,
         }
     }
 
@@ -163,8 +192,12 @@ impl PackedFile {
                 should_be_compressed: false,
                 should_be_encrypted: None,
                 data: PackedFileData::OnMemory(vec![], false, None),
+                disk_data: None,
+                compression_format: None,
             },
             decoded: data.clone(),
+            modified: true,
+            unjournaled: true,
         }
     }
 
@@ -173,6 +206,8 @@ impl PackedFile {
         Ok(Self {
             raw: RawPackedFile::read_from_path(path, packed_file_path.to_vec())?,
             decoded: DecodedPackedFile::Unknown,
+            modified: true,
+            unjournaled: true,
         })
     }
 
@@ -227,11 +262,22 @@ impl PackedFile {
 
     /// This function returns a mutable reference to the `RawPackedFile` part of a `PackedFile`.
     pub fn get_ref_mut_raw(&mut self) -> &mut RawPackedFile {
+        self.modified = true;
+        self.unjournaled = true;
         &mut self.raw
     }
 
     /// This function returns a mutable reference to the `DecodedPackedFile` part of a `PackedFile`.
     pub fn get_ref_mut_decoded(&mut self) -> &mut DecodedPackedFile {
+        self.modified = true;
+        self.unjournaled = true;
+        &mut self.decoded
+    }
+
+    /// This function returns a mutable reference to the `DecodedPackedFile` part of a `PackedFile`, without marking it as modified.
+    ///
+    /// Only use it for changes that don't touch the data of the `PackedFile`, like swapping the definition of a table for an equivalent one.
+    pub fn get_ref_mut_decoded_no_modify(&mut self) -> &mut DecodedPackedFile {
         &mut self.decoded
     }
 
@@ -248,11 +294,15 @@ impl PackedFile {
     /// This function replace the `RawPackedFile` part of a `PackedFile` with the provided one.
     pub fn set_raw(&mut self, data: &RawPackedFile) {
         self.raw = data.clone();
+        self.modified = true;
+        self.unjournaled = true;
     }
 
     /// This function replace the `DecodedPackedFile` part of a `PackedFile` with the provided one.
     pub fn set_decoded(&mut self, data: &DecodedPackedFile) {
         self.decoded = data.clone();
+        self.modified = true;
+        self.unjournaled = true;
     }
 
     /// This function tries to get the decoded data from a `PackedFile`, returning an error if the file was not decoded previously.
@@ -268,6 +318,44 @@ impl PackedFile {
         self.raw.get_path()
     }
 
+    /// This function returns if the `PackedFile` has been modified since it was loaded or last saved.
+    pub fn is_modified(&self) -> bool {
+        self.modified
+    }
+
+    /// This function sets the modified state of the `PackedFile`.
+    ///
+    /// Marking it as modified also marks it as having changes to journal. Marking it as unmodified doesn't touch that.
+    pub fn set_modified(&mut self, modified: bool) {
+        self.modified = modified;
+        if modified {
+            self.unjournaled = true;
+        }
+    }
+
+    /// This function returns if the `PackedFile` has changes that have not been written to the recovery journal yet.
+    pub(crate) fn has_unjournaled_changes(&self) -> bool {
+        self.unjournaled
+    }
+
+    /// This function sets if the `PackedFile` has changes that have not been written to the recovery journal yet.
+    pub(crate) fn set_unjournaled_changes(&mut self, unjournaled: bool) {
+        self.unjournaled = unjournaled;
+    }
+
+    /// This function discards all the changes done to the `PackedFile`, restoring the data it had in the PackFile it was read from.
+    ///
+    /// The decoded data is discarded too, so it gets decoded again from the restored data the next time it's needed.
+    pub fn revert_to_disk(&mut self) -> Result<()> {
+        self.raw.revert_to_disk()?;
+        self.decoded = DecodedPackedFile::Unknown;
+        self.modified = false;
+
+        // The revert itself is a change the journal doesn't know about.
+        self.unjournaled = true;
+        Ok(())
+    }
+
     /// This function tries to decode a `RawPackedFile` into a `DecodedPackedFile`, storing the results in the `Packedfile`.
     pub fn decode(&mut self) -> Result<()> {
         if self.decoded == DecodedPackedFile::Unknown {
@@ -305,6 +393,8 @@ impl PackedFile {
         if self.decoded == DecodedPackedFile::Unknown {
             self.decoded = DecodedPackedFile::decode(&mut self.raw)?;
         }
+        self.modified = true;
+        self.unjournaled = true;
         Ok(&mut self.decoded)
     }
 
@@ -327,6 +417,8 @@ impl PackedFile {
         if self.decoded == DecodedPackedFile::Unknown {
             self.decoded = DecodedPackedFile::decode_no_locks(&mut self.raw, schema)?;
         }
+        self.modified = true;
+        self.unjournaled = true;
         Ok(&mut self.decoded)
     }
 
@@ -387,11 +479,25 @@ impl PackedFile {
         self.raw.get_data()
     }
 
+    /// This function returns up to `len` bytes of the data of a PackedFile, starting at `offset`.
+    ///
+    /// The range is clamped to the size of the data, so out-of-range requests return a partial or empty result.
+    pub fn get_data_range(&self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        self.raw.get_data_range(offset, len)
+    }
+
     /// This function returns the data of a PackedFile.
     pub fn get_raw_data_and_keep_it(&mut self) -> Result<Vec<u8>> {
         self.raw.get_data_and_keep_it()
     }
 
+    /// This function loads to memory data previously read from the provided location on disk.
+    ///
+    /// This only changes where the data is kept, not the data itself, so the `PackedFile` is not marked as modified.
+    pub fn load_prefetched_data(&mut self, raw_on_disk: &RawOnDisk, data: Vec<u8>) {
+        self.raw.load_prefetched_data(raw_on_disk, data);
+    }
+
     /// This function returns the data of a PackedFile, making sure we clear the cache before it.
     pub fn get_raw_data_and_clean_cache(&mut self) -> Result<Vec<u8>> {
         if self.decoded != DecodedPackedFile::Unknown {
@@ -403,26 +509,31 @@ impl PackedFile {
     /// This function replaces the raw data of a PackedFile with the provided one.
     pub fn set_raw_data(&mut self, data: &[u8]) {
         self.raw.set_data(data);
+        self.modified = true;
+        self.unjournaled = true;
     }
 
     /// This function extracts the provided PackedFile into the provided path.
     pub fn extract_packed_file(&mut self, destination_path: &Path) -> Result<()> {
 
+        // We join his internal path with his destination path, so we have his full path on disk.
+        let current_path = destination_path.to_path_buf().join(self.get_path().iter().collect::<PathBuf>());
+        self.extract_packed_file_to_path(&current_path)
+    }
+
+    /// This function extracts the provided PackedFile to the exact file path provided, creating its parent folders if needed.
+    pub fn extract_packed_file_to_path(&mut self, file_path: &Path) -> Result<()> {
+
         // Save it, in case it's cached.
         self.encode()?;
 
-        // We get his internal path without his name.
-        let mut internal_path = self.get_path().to_vec();
-        let file_name = internal_path.pop().unwrap();
-
-        // Then, we join his internal path with his destination path, so we have his almost-full path (his final path without his name).
-        // This way we can create the entire folder structure up to the file itself.
-        let mut current_path = destination_path.to_path_buf().join(internal_path.iter().collect::<PathBuf>());
-        DirBuilder::new().recursive(true).create(&current_path)?;
+        // Create the entire folder structure up to the file itself.
+        if let Some(parent) = file_path.parent() {
+            DirBuilder::new().recursive(true).create(parent)?;
+        }
 
-        // Finish the path and try to save the file to disk.
-        current_path.push(&file_name);
-        let mut file = BufWriter::new(File::create(&current_path)?);
+        // Then try to save the file to disk.
+        let mut file = BufWriter::new(File::create(file_path)?);
         if file.write_all(&self.get_raw_data()?).is_err() {
             return Err(ErrorKind::ExtractError(self.get_path().to_vec()).into());
         }
@@ -453,6 +564,8 @@ impl RawPackedFile {
             should_be_compressed,
             should_be_encrypted: None,
             data: PackedFileData::OnMemory(data, should_be_compressed, None),
+            disk_data: None,
+            compression_format: None,
         }
     }
 
@@ -465,6 +578,7 @@ impl RawPackedFile {
         should_be_encrypted: Option<PFHVersion>,
         data: PackedFileData
     ) -> Self {
+        let disk_data = if let PackedFileData::OnDisk(ref raw_on_disk) = data { Some(raw_on_disk.clone()) } else { None };
         Self {
             path,
             packfile_name,
@@ -472,6 +586,8 @@ impl RawPackedFile {
             should_be_compressed,
             should_be_encrypted,
             data,
+            disk_data,
+            compression_format: None,
         }
     }
 
@@ -488,6 +604,35 @@ impl RawPackedFile {
         Ok(RawPackedFile::read_from_vec(path_as_packed_file, String::new(), get_last_modified_time_from_file(&file.get_ref()), false, data))
     }
 
+    /// This function restores the data of a `RawPackedFile` to the one it had in the PackFile it was read from, discarding any change done to it.
+    ///
+    /// If the `RawPackedFile` was not read from a PackFile (for example, if it's a new file), or its PackFile has been saved since then, this returns an error.
+    pub fn revert_to_disk(&mut self) -> Result<()> {
+        match self.disk_data {
+            Some(ref raw_on_disk) => {
+                self.data = PackedFileData::OnDisk(raw_on_disk.clone());
+                Ok(())
+            }
+            None => Err(ErrorKind::PackedFileHasNoDiskData(self.path.to_vec()).into()),
+        }
+    }
+
+    /// This function removes the info about the location of the data of the `RawPackedFile` in the PackFile it was read from.
+    ///
+    /// Use it when that location is no longer valid, like after overwriting the PackFile.
+    pub fn clear_disk_data(&mut self) {
+        self.disk_data = None;
+    }
+
+    /// This function removes the memory map from the info about the location of the data of the `RawPackedFile` in the PackFile it was read from.
+    ///
+    /// The location is kept, so the data can still be read through the file. Use it before truncating the PackFile, as memory maps cannot survive that.
+    pub fn clear_disk_data_mmap(&mut self) {
+        if let Some(ref mut raw_on_disk) = self.disk_data {
+            raw_on_disk.mmap = None;
+        }
+    }
+
     /// This function loads the data of a `RawPackedFile` to memory, if it isn't loaded already.
     pub fn load_data(&mut self) -> Result<()> {
         let data_on_memory = if let PackedFileData::OnDisk(ref raw_on_disk) = self.data {
@@ -495,9 +640,42 @@ impl RawPackedFile {
         } else { return Ok(()) };
 
         self.data = data_on_memory;
+        self.detect_compression_format();
         Ok(())
     }
 
+    /// This function returns if the data of the `RawPackedFile` is loaded to memory.
+    pub fn is_loaded(&self) -> bool {
+        if let PackedFileData::OnMemory(_, _, _) = self.data { true } else { false }
+    }
+
+    /// This function returns the location of the data of the `RawPackedFile` on disk, if it isn't loaded to memory yet.
+    ///
+    /// Reading from it doesn't touch the `RawPackedFile`, so it can be done from another thread. Use `load_prefetched_data` to store the result.
+    pub fn get_data_on_disk(&self) -> Option<RawOnDisk> {
+        if let PackedFileData::OnDisk(ref raw_on_disk) = self.data { Some(raw_on_disk.clone()) } else { None }
+    }
+
+    /// This function loads to memory data previously read from the provided location on disk.
+    ///
+    /// If the data of the `RawPackedFile` is no longer in that location (it has been loaded or replaced since then) the data is discarded.
+    pub fn load_prefetched_data(&mut self, raw_on_disk: &RawOnDisk, data: Vec<u8>) {
+        let is_same_location = if let PackedFileData::OnDisk(ref current) = self.data { current.is_same_location(raw_on_disk) } else { false };
+        if is_same_location {
+            self.data = PackedFileData::OnMemory(data, raw_on_disk.get_compression_state(), raw_on_disk.get_encryption());
+            self.detect_compression_format();
+        }
+    }
+
+    /// This function stores the compression format of the data of the `RawPackedFile`, if it's loaded to memory, compressed and not encrypted.
+    ///
+    /// Encrypted data gets its format detected once it's decrypted and decompressed.
+    fn detect_compression_format(&mut self) {
+        if let PackedFileData::OnMemory(ref data, true, None) = self.data {
+            self.compression_format = Some(CompressionFormat::detect(data));
+        }
+    }
+
     /// This function returns the RAW data of the `RawPackedFile` without loading it to memory.
     ///
     /// This means this data is not decompressed/decrypted. For particular situations.
@@ -532,6 +710,30 @@ impl RawPackedFile {
         }
     }
 
+    /// This function returns up to `len` bytes of the data of the `RawPackedFile`, starting at `offset`, without loading it to memory.
+    ///
+    /// For uncompressed and unencrypted data on disk, only the requested range is read. Otherwise, the data is decompressed/decrypted first and then sliced.
+    /// The range is clamped to the size of the data, so out-of-range requests return a partial or empty result.
+    pub fn get_data_range(&self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        let clamp = |size: u64| {
+            let start = offset.min(size);
+            let end = start.saturating_add(len as u64).min(size);
+            (start, end)
+        };
+
+        match self.data {
+            PackedFileData::OnDisk(ref raw_on_disk) if !raw_on_disk.get_compression_state() && !raw_on_disk.get_encryption_state() => {
+                let (start, end) = clamp(u64::from(raw_on_disk.get_size()));
+                raw_on_disk.read_range_relative(start, (end - start) as usize)
+            }
+            _ => {
+                let data = self.get_data()?;
+                let (start, end) = clamp(data.len() as u64);
+                Ok(data[start as usize..end as usize].to_vec())
+            }
+        }
+    }
+
     /// This function returns the data of the provided `RawPackedFile` loading it to memory in the process if it isn't already loaded.
     ///
     /// It's for when you need to keep the data for multiple uses.
@@ -539,7 +741,10 @@ impl RawPackedFile {
         let data = match self.data {
             PackedFileData::OnMemory(ref mut data, ref mut is_compressed, ref mut is_encrypted) => {
                 if is_encrypted.is_some() { *data = decrypt_packed_file(&data); }
-                if *is_compressed { *data = decompress_data(&data)?; }
+                if *is_compressed {
+                    self.compression_format = Some(CompressionFormat::detect(data));
+                    *data = decompress_data(&data)?;
+                }
                 *is_compressed = false;
                 *is_encrypted = None;
                 return Ok(data.to_vec())
@@ -547,7 +752,10 @@ impl RawPackedFile {
             PackedFileData::OnDisk(ref raw_on_disk) => {
                 let mut data = raw_on_disk.read()?;
                 if raw_on_disk.get_encryption_state() { data = decrypt_packed_file(&data); }
-                if raw_on_disk.get_compression_state() { decompress_data(&data)? }
+                if raw_on_disk.get_compression_state() {
+                    self.compression_format = Some(CompressionFormat::detect(&data));
+                    decompress_data(&data)?
+                }
                 else { data }
             }
         };
@@ -564,7 +772,10 @@ impl RawPackedFile {
         let data = match self.data {
             PackedFileData::OnMemory(ref mut data, ref mut is_compressed, ref mut is_encrypted) => {
                 if is_encrypted.is_some() { *data = decrypt_packed_file(&data); }
-                if *is_compressed { *data = decompress_data(&data)?; }
+                if *is_compressed {
+                    self.compression_format = Some(CompressionFormat::detect(data));
+                    *data = decompress_data(&data)?;
+                }
                 *is_compressed = false;
                 *is_encrypted = None;
                 return Ok(data)
@@ -572,7 +783,10 @@ impl RawPackedFile {
             PackedFileData::OnDisk(ref raw_on_disk) => {
                 let mut data = raw_on_disk.read()?;
                 if raw_on_disk.get_encryption_state() { data = decrypt_packed_file(&data); }
-                if raw_on_disk.get_compression_state() { decompress_data(&data)? }
+                if raw_on_disk.get_compression_state() {
+                    self.compression_format = Some(CompressionFormat::detect(&data));
+                    decompress_data(&data)?
+                }
                 else { data }
             }
         };
@@ -608,6 +822,22 @@ impl RawPackedFile {
         }
     }
 
+    /// This function returns the size of the data of the provided `RawPackedFile` once decompressed.
+    ///
+    /// If the data is on disk, it only reads what it needs to get the size, without loading the data to memory.
+    pub fn get_decompressed_size(&self) -> Result<u32> {
+        match self.data {
+            PackedFileData::OnMemory(ref data, is_compressed, is_encrypted) => {
+                if is_compressed {
+                    if is_encrypted.is_some() { get_decompressed_size(&decrypt_packed_file(&data)) }
+                    else { get_decompressed_size(&data) }
+                }
+                else { Ok(data.len() as u32) }
+            },
+            PackedFileData::OnDisk(ref raw_on_disk) => raw_on_disk.read_decompressed_size(),
+        }
+    }
+
     /// This function returns the current compression state of the provided `RawPackedFile`.
     pub fn get_compression_state(&self) -> bool {
         match self.data {
@@ -616,6 +846,19 @@ impl RawPackedFile {
         }
     }
 
+    /// This function returns the format the data of the `RawPackedFile` is compressed with or, if it has been decompressed, the one it was compressed with.
+    ///
+    /// The format is only known once the data has been loaded to memory, so this never touches the disk.
+    /// If the data has never been compressed or it hasn't been loaded yet, this returns None.
+    pub fn get_compression_format(&self) -> Option<CompressionFormat> {
+        self.compression_format
+    }
+
+    /// This function sets the format the data of the `RawPackedFile` should be compressed with, if it gets compressed.
+    pub fn set_compression_format(&mut self, format: Option<CompressionFormat>) {
+        self.compression_format = format;
+    }
+
     /// This function returns if the `RawPackedFile` should be compressed or not.
     pub fn get_should_be_compressed(&self) -> bool{
         self.should_be_compressed
@@ -687,8 +930,11 @@ impl RawPackedFile {
 impl RawOnDisk {
 
     /// This function creates a new RawOnDisk.
+    ///
+    /// If a memory map of the PackFile is provided, reads will be done from it instead of from the reader.
     pub fn new(
         reader: Arc<Mutex<BufReader<File>>>,
+        mmap: Option<Arc<Mmap>>,
         start: u64,
         size: u32,
         is_compressed: bool,
@@ -696,6 +942,7 @@ impl RawOnDisk {
     ) -> Self {
         Self {
             reader,
+            mmap,
             start,
             size,
             is_compressed,
@@ -706,10 +953,7 @@ impl RawOnDisk {
 
     /// This function tries to read and return the raw data of the PackedFile.
     pub fn read(&self) -> Result<Vec<u8>> {
-        let mut data = vec![0; self.size as usize];
-        let mut file = self.reader.lock().unwrap();
-        file.seek(SeekFrom::Start(self.start))?;
-        file.read_exact(&mut data)?;
+        let data = self.read_range(self.start, self.size as usize)?;
 
         let mut current_hash = self.hash.lock().unwrap();
         let mut hasher = DefaultHasher::new();
@@ -734,6 +978,49 @@ impl RawOnDisk {
         self.size
     }
 
+    /// This function returns if both `RawOnDisk` point to the same data of the same PackFile.
+    pub fn is_same_location(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.reader, &other.reader) && self.start == other.start && self.size == other.size
+    }
+
+    /// This function returns the size of the PackedFile once decompressed.
+    ///
+    /// For compressed and unencrypted PackedFiles, this only reads the bytes containing the size, not the full data.
+    pub fn read_decompressed_size(&self) -> Result<u32> {
+        if !self.is_compressed { Ok(self.size) }
+        else if self.is_encrypted.is_some() { get_decompressed_size(&decrypt_packed_file(&self.read()?)) }
+        else { get_decompressed_size(&self.read_range(self.start, 4)?) }
+    }
+
+    /// This function reads `size` bytes of the data of the PackedFile, starting at `offset` from the beginning of its data.
+    ///
+    /// Unlike `read`, this doesn't check the hash of the data, as it doesn't read it all.
+    pub fn read_range_relative(&self, offset: u64, size: usize) -> Result<Vec<u8>> {
+        if size == 0 { return Ok(vec![]); }
+        self.read_range(self.start + offset, size)
+    }
+
+    /// This function reads `size` bytes from the PackFile, starting at `start`.
+    ///
+    /// If we have the PackFile memory-mapped, this is just a copy from the mapped region. Otherwise, we seek and read with the reader.
+    fn read_range(&self, start: u64, size: usize) -> Result<Vec<u8>> {
+        match self.mmap {
+            Some(ref mmap) => {
+                let start = start as usize;
+                let end = start + size;
+                if end > mmap.len() { return Err(ErrorKind::PackedFileDataCouldNotBeLoaded.into()) }
+                Ok(mmap[start..end].to_vec())
+            }
+            None => {
+                let mut data = vec![0; size];
+                let mut file = self.reader.lock().unwrap();
+                file.seek(SeekFrom::Start(start))?;
+                file.read_exact(&mut data)?;
+                Ok(data)
+            }
+        }
+    }
+
     /// This function returns if the PackedFile is compressed or not.
     pub fn get_compression_state(&self) -> bool {
         self.is_compressed
@@ -765,6 +1052,17 @@ impl PartialEq for PackedFileData {
     }
 }
 
+/// Implementation of `PartialEq` for `RawOnDisk`.
+impl PartialEq for RawOnDisk {
+    fn eq(&self, other: &RawOnDisk) -> bool {
+        Arc::ptr_eq(&self.reader, &other.reader) &&
+        self.start == other.start &&
+        self.size == other.size &&
+        self.is_compressed == other.is_compressed &&
+        self.is_encrypted == other.is_encrypted
+    }
+}
+
 /// Implementation to create a `PackedFileInfo` from a `PackedFile`.
 impl From<&PackedFile> for PackedFileInfo {
     fn from(packedfile: &PackedFile) -> Self {
@@ -775,7 +1073,9 @@ impl From<&PackedFile> for PackedFileInfo {
             path: packedfile.get_path().to_vec(),
             packfile_name: packedfile.get_ref_raw().get_packfile_name().to_owned(),
             timestamp: packedfile.get_ref_raw().get_timestamp(),
+            size: packedfile.get_raw_data_size(),
             is_compressed: packedfile.get_ref_raw().get_compression_state(),
+            compression_format: packedfile.get_ref_raw().get_compression_format(),
             is_encrypted: packedfile.get_ref_raw().get_encryption_state(),
             is_cached,
             cached_type,
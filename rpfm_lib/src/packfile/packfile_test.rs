@@ -12,9 +12,65 @@
 Module containing test for the `PackFile` module, just to make sure we don't break it... again...
 !*/
 
+use std::collections::BTreeMap;
+use std::env::temp_dir;
 use std::path::PathBuf;
 
-use super::PackFile;
+use crate::packedfile::DecodedPackedFile;
+use crate::packedfile::table::DecodedData;
+use crate::packedfile::table::db::DB;
+use crate::schema::{Definition, Field, FieldType};
+use super::{PackFile, PFHVersion};
+use super::packedfile::{PackedFile, RawPackedFile};
+
+/// This function creates an in-memory `PackFile` with empty `PackedFiles` in the provided paths.
+fn new_pack_file_with_paths(paths: &[&str]) -> PackFile {
+    let mut pack_file = PackFile::new_with_name("test.pack", PFHVersion::PFH5);
+    for path in paths {
+        let raw_data = RawPackedFile::read_from_vec(to_path(path), "test.pack".to_owned(), 0, false, vec![]);
+        pack_file.add_packed_file(&PackedFile::new_from_raw(&raw_data), false).unwrap();
+    }
+    pack_file
+}
+
+/// This function adds to the provided `PackFile` a decoded `DB` Table in the provided path, with a key column, the provided extra fields, and the provided rows.
+fn add_decoded_table(pack_file: &mut PackFile, path: &str, fields: &[(&str, FieldType)], rows: &[Vec<DecodedData>]) {
+    let mut definition = Definition::new(1);
+    definition.get_ref_mut_fields().push(Field::new("key".to_owned(), FieldType::StringU8, true, None, 0, false, None, None, None, String::new(), -1, 0, BTreeMap::new()));
+    for (name, field_type) in fields {
+        definition.get_ref_mut_fields().push(Field::new(name.to_string(), field_type.clone(), false, None, 0, false, None, None, None, String::new(), -1, 0, BTreeMap::new()));
+    }
+
+    let path = to_path(path);
+    let mut table = DB::new(&path[1], None, &definition);
+    table.set_table_data(rows).unwrap();
+
+    let raw_data = RawPackedFile::read_from_vec(path, "test.pack".to_owned(), 0, false, table.save().unwrap());
+    let mut packed_file = PackedFile::new_from_raw(&raw_data);
+    packed_file.set_decoded(&DecodedPackedFile::DB(table));
+    pack_file.add_packed_file(&packed_file, false).unwrap();
+}
+
+/// This function returns the paths and the current data of all the `PackedFiles` of the provided `PackFile`, in order.
+fn get_paths_and_data(pack_file: &PackFile) -> Vec<(Vec<String>, Vec<u8>)> {
+    pack_file.get_ref_packed_files_all().iter().map(|packed_file| {
+        let data = match packed_file.get_ref_decoded().encode() {
+            Some(data) => data.unwrap(),
+            None => packed_file.get_raw_data().unwrap(),
+        };
+        (packed_file.get_path().to_vec(), data)
+    }).collect()
+}
+
+/// This function turns a string path into the format used by `PackFile`.
+fn to_path(path: &str) -> Vec<String> {
+    path.split('/').map(|x| x.to_owned()).collect()
+}
+
+/// This function turns a list of string paths into the format used by `PackFile`.
+fn to_paths(paths: &[&str]) -> Vec<Vec<String>> {
+    paths.iter().map(|x| to_path(x)).collect()
+}
 
 #[test]
 fn test_decode_pfh5() {
@@ -121,3 +177,75 @@ fn test_encode_pfh0() {
 
 	assert_eq!(pack_file_base, pack_file_new);
 }
+
+#[test]
+fn test_find_casing_issues() {
+    let pack_file = new_pack_file_with_paths(&[
+        "DB/units_tables/my_units",
+        "db/Land_Units_Tables/my_land_units",
+        "db/units_tables/Fine_Name",
+        "Text/db/my_mod.loc",
+        "ui/Skins/Texture.PNG",
+        "Custom_Folder/file.txt",
+    ]);
+
+    let mut issues = pack_file.find_casing_issues();
+    issues.sort();
+
+    // Only the root folders and the table folders count. File names, subfolders and unknown root folders are left alone.
+    assert_eq!(issues, to_paths(&[
+        "DB/units_tables/my_units",
+        "Text/db/my_mod.loc",
+        "db/Land_Units_Tables/my_land_units",
+    ]));
+}
+
+#[test]
+fn test_normalize_path_casing() {
+    let mut pack_file = new_pack_file_with_paths(&[
+        "DB/units_tables/my_units",
+        "db/units_tables/my_units",
+        "Text/db/My_Mod.loc",
+    ]);
+
+    let mut moved = pack_file.normalize_path_casing();
+    moved.sort();
+
+    // If the lowercased path is taken, the moved file is renamed instead of overwriting the existing one.
+    assert_eq!(moved, vec![
+        (to_path("DB/units_tables/my_units"), to_path("db/units_tables/my_units_0")),
+        (to_path("Text/db/My_Mod.loc"), to_path("text/db/My_Mod.loc")),
+    ]);
+
+    assert!(pack_file.find_casing_issues().is_empty());
+    assert!(pack_file.packedfile_exists(&to_path("db/units_tables/my_units")));
+}
+
+#[test]
+fn test_export_import_version_control() {
+    let mut pack_file = PackFile::new_with_name("test.pack", PFHVersion::PFH5);
+    add_decoded_table(&mut pack_file, "db/test_tables/foo", &[("number", FieldType::I32)], &[
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::I32(1)],
+        vec![DecodedData::StringU8("b".to_owned()), DecodedData::I32(2)],
+    ]);
+
+    // A binary file with the name the TSV of the previous table would have if both were in the same folder.
+    let raw_data = RawPackedFile::read_from_vec(to_path("db/test_tables/foo.tsv"), "test.pack".to_owned(), 0, false, vec![1, 2, 3, 4]);
+    pack_file.add_packed_file(&PackedFile::new_from_raw(&raw_data), false).unwrap();
+
+    // Tables with sequences cannot be imported from TSV, so they must survive as binary files.
+    let sequence = FieldType::SequenceU32(Definition::new(-1));
+    add_decoded_table(&mut pack_file, "db/test_tables/bar", &[("sequence", sequence.clone())], &[
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::default(&sequence)],
+    ]);
+
+    let path = temp_dir().join("rpfm_packfile_test_version_control");
+    if path.is_dir() {
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    pack_file.export_for_version_control(&path).unwrap();
+    let imported = PackFile::import_from_version_control(&path).unwrap();
+
+    assert_eq!(get_paths_and_data(&imported), get_paths_and_data(&pack_file));
+}
@@ -75,8 +75,9 @@ use std::collections::BTreeMap;
 use std::fs::{DirBuilder, File};
 use std::{fmt, fmt::Display};
 use std::io::{BufReader, Write};
+use std::path::Path;
 
-use rpfm_error::{ErrorKind, Result};
+use rpfm_error::{Error, ErrorKind, Result};
 
 use crate::assembly_kit::localisable_fields::RawLocalisableField;
 use crate::assembly_kit::table_definition::{RawDefinition, RawField};
@@ -103,6 +104,9 @@ pub const BRANCH: &str = "master";
 /// Current structural version of the Schema, for compatibility purpouses.
 const CURRENT_STRUCTURAL_VERSION: u16 = 3;
 
+/// Maximum amount of tables a reference cycle can go through before we stop following it.
+const MAX_REFERENCE_CYCLE_LENGTH: usize = 6;
+
 //---------------------------------------------------------------------------//
 //                              Enum & Structs
 //---------------------------------------------------------------------------//
@@ -158,6 +162,17 @@ pub struct Definition {
     localised_fields: Vec<Field>,
 }
 
+/// This struct represents a single `Definition` of a DB Table, exported out of the `Schema` so it can be shared on its own.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct SharedDefinition {
+
+    /// The name of the table this definition is for.
+    table_name: String,
+
+    /// The definition itself.
+    definition: Definition,
+}
+
 /// This struct holds all the relevant data do properly decode a field from a versioned PackedFile.
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct Field {
@@ -199,7 +214,13 @@ pub struct Field {
     is_bitwise: i32,
 
     /// Variable that specifies the "Enum" values for each value in this field.
-    enum_values: BTreeMap<i32, String>
+    enum_values: BTreeMap<i32, String>,
+
+    /// `Some(false)` if the field's data should not be edited by hand, like auto-generated ids. `None` means it's editable.
+    is_editable: Option<bool>,
+
+    /// `Some(true)` if the field is an ordering/id column, whose values must be unique and sequential. `None` means it's not. Only applicable to integer fields.
+    is_ordering: Option<bool>,
 }
 
 /// This enum defines every type of field the lib can encode/decode.
@@ -218,6 +239,123 @@ pub enum FieldType {
     SequenceU32(Definition)
 }
 
+/// This struct contains the differences between the DB Tables of two `Schemas`.
+///
+/// It's done from the point of view of the second schema, so "new" means "it's in the second schema, but not in the first one".
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct SchemaComparison {
+
+    /// Tables only present in the second schema.
+    pub new_tables: Vec<String>,
+
+    /// Tables only present in the first schema.
+    pub removed_tables: Vec<String>,
+
+    /// Tables present in both schemas, but with differences in their definitions.
+    pub changed_tables: BTreeMap<String, TableComparison>,
+}
+
+/// This struct contains the differences between the definitions of a table in two `Schemas`.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct TableComparison {
+
+    /// Versions only present in the second schema.
+    pub new_versions: Vec<i32>,
+
+    /// Versions only present in the first schema.
+    pub removed_versions: Vec<i32>,
+
+    /// Versions present in both schemas, but with different fields.
+    pub changed_versions: BTreeMap<i32, DefinitionComparison>,
+}
+
+/// This struct contains the field-level differences between two versions of the same `Definition`.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct DefinitionComparison {
+
+    /// Fields only present in the second definition.
+    pub new_fields: Vec<String>,
+
+    /// Fields only present in the first definition.
+    pub removed_fields: Vec<String>,
+
+    /// Fields with the same name but different type, as `(field, old type, new type)`. These are the ones that break tables.
+    pub type_changes: Vec<(String, String, String)>,
+
+    /// Any other change on fields with the same name, as `(field, property, old value, new value)`.
+    pub other_changes: Vec<(String, String, String, String)>,
+}
+
+/// This struct represents a cycle of references between DB Tables in a `Schema`.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct ReferenceCycle {
+
+    /// Steps of the cycle, as `(table, column, referenced column)`. Each step references the table of the next one,
+    /// and the last one references the table of the first one.
+    pub path: Vec<(String, String, String)>,
+
+    /// If all the references of the cycle point to key columns. These are usually intended mutual references
+    /// and are informational. If any of them points to a non-key column, the cycle is probably a schema mistake.
+    pub is_expected: bool,
+}
+
+/// This enum represents the formats a `Schema` report can be exported to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+/// This struct represents the result of an automatic schema update.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchemaAutoUpdateReport {
+
+    /// If an update was downloaded.
+    pub updated: bool,
+
+    /// Version of the local schemas before the update, if we had them.
+    pub old_version: Option<String>,
+
+    /// Version of the local schemas after the update. If there was no update, it's the same as the old one.
+    pub new_version: Option<String>,
+
+    /// The error that stopped the update, if any. If there is one, the local schemas have not been changed.
+    pub error: Option<String>,
+}
+
+/// This enum represents the ways to resolve a conflict between two definitions of the same table version when merging two `Schemas`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum MergeStrategy {
+
+    /// Keep the definition of the schema we're merging into.
+    PreferMine,
+
+    /// Replace it with the definition of the schema we're merging from.
+    PreferTheirs,
+
+    /// Keep the definition of the schema we're merging into, and return the other one in the report, so it can be reviewed later.
+    KeepBoth,
+}
+
+/// This struct contains the result of merging a `Schema` into another one.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct MergeReport {
+
+    /// Tables only present in the schema we merged from, added as they were.
+    pub new_tables: Vec<String>,
+
+    /// Versions only present in the schema we merged from, added to tables present in both, as `(table, version)`.
+    pub new_versions: Vec<(String, i32)>,
+
+    /// Versions present in both schemas with different definitions, with their field-level differences.
+    /// The differences are from the point of view of the schema we merged from.
+    pub conflicts: BTreeMap<String, BTreeMap<i32, DefinitionComparison>>,
+
+    /// Conflicting definitions of the schema we merged from, by table. They're not added to the schema, as they would replace
+    /// the real definition of their version, so they're left here to be reviewed. Only used with `MergeStrategy::KeepBoth`.
+    pub kept_definitions: BTreeMap<String, Vec<Definition>>,
+}
+
 /// This enum controls the possible responses from the server when asking if there is a new Schema update.
 #[derive(Debug, Serialize, Deserialize)]
 pub enum APIResponseSchema {
@@ -452,6 +590,12 @@ impl Schema {
         from_reader(file).map_err(From::from)
     }
 
+    /// This function loads a `Schema` to memory from a file anywhere on disk.
+    pub fn load_from_path(path: &Path) -> Result<Self> {
+        let file = BufReader::new(File::open(path)?);
+        from_reader(file).map_err(From::from)
+    }
+
     /// This function saves a `Schema` from memory to a file in the `schemas/` folder.
     pub fn save(&mut self, schema_file: &str) -> Result<()> {
         let mut file_path = get_config_path()?.join(SCHEMA_FOLDER);
@@ -460,7 +604,12 @@ impl Schema {
         DirBuilder::new().recursive(true).create(&file_path)?;
 
         file_path.push(schema_file);
-        let mut file = File::create(&file_path)?;
+        self.save_to_path(&file_path)
+    }
+
+    /// This function saves a `Schema` from memory to a file anywhere on disk.
+    pub fn save_to_path(&mut self, path: &Path) -> Result<()> {
+        let mut file = File::create(path)?;
         let config = PrettyConfig::default();
 
         self.sort();
@@ -577,6 +726,314 @@ impl Schema {
         Ok(())
     }
 
+    /// This function exports the `Definition` of a specific version of a DB Table to a `.json` file, so it can be shared on its own.
+    pub fn export_definition(&self, table_name: &str, version: i32, path: &Path) -> Result<()> {
+        let definition = self.get_ref_versioned_file_db(table_name)?.get_version(version)?;
+        let shared_definition = SharedDefinition {
+            table_name: table_name.to_owned(),
+            definition: definition.clone(),
+        };
+
+        let mut file = File::create(&path)?;
+        file.write_all(serde_json::to_string_pretty(&shared_definition)?.as_bytes())?;
+        Ok(())
+    }
+
+    /// This function imports a `Definition` previously exported with `export_definition` into the `Schema`.
+    ///
+    /// If the table has no definition with the same version, the imported one is just added to it. If it has one, and it's
+    /// different from the imported one, this fails unless `overwrite` is `true`. It returns the table name and version imported.
+    pub fn import_definition(&mut self, path: &Path, overwrite: bool) -> Result<(String, i32)> {
+        let file = BufReader::new(File::open(&path)?);
+        let shared_definition: SharedDefinition = serde_json::from_reader(file)?;
+        let table_name = shared_definition.table_name;
+        let definition = shared_definition.definition;
+        let version = definition.get_version();
+
+        match self.get_ref_mut_versioned_file_db(&table_name) {
+            Ok(versioned_file) => {
+                if let Ok(current_definition) = versioned_file.get_version(version) {
+                    if current_definition != &definition && !overwrite {
+                        return Err(ErrorKind::SchemaDefinitionAlreadyExists(table_name, version).into());
+                    }
+                }
+                versioned_file.add_version(&definition);
+            }
+            Err(_) => self.add_versioned_file(&VersionedFile::DB(table_name.to_owned(), vec![definition])),
+        }
+
+        Ok((table_name, version))
+    }
+
+    /// This function compares the DB Tables of this `Schema` with the ones of another `Schema`, returning their differences.
+    ///
+    /// `self` is considered the old schema, and `other` the new one.
+    pub fn compare(&self, other: &Schema) -> SchemaComparison {
+        let mut comparison = SchemaComparison::default();
+        let tables_old = self.get_ref_versioned_file_db_all();
+        let tables_new = other.get_ref_versioned_file_db_all();
+
+        for table_new in &tables_new {
+            if let VersionedFile::DB(table_name, versions_new) = table_new {
+                match self.get_ref_versioned_file_db(table_name) {
+                    Ok(VersionedFile::DB(_, versions_old)) => {
+                        let mut table_comparison = TableComparison::default();
+                        for definition_new in versions_new {
+                            match versions_old.iter().find(|x| x.version == definition_new.version) {
+                                Some(definition_old) => if definition_old != definition_new {
+                                    table_comparison.changed_versions.insert(definition_new.version, definition_old.compare(definition_new));
+                                }
+                                None => table_comparison.new_versions.push(definition_new.version),
+                            }
+                        }
+
+                        table_comparison.removed_versions = versions_old.iter()
+                            .filter(|x| versions_new.iter().all(|y| y.version != x.version))
+                            .map(|x| x.version)
+                            .collect();
+
+                        if table_comparison != TableComparison::default() {
+                            comparison.changed_tables.insert(table_name.to_owned(), table_comparison);
+                        }
+                    }
+                    _ => comparison.new_tables.push(table_name.to_owned()),
+                }
+            }
+        }
+
+        comparison.removed_tables = tables_old.iter()
+            .filter_map(|x| if let VersionedFile::DB(table_name, _) = x { Some(table_name) } else { None })
+            .filter(|x| other.get_ref_versioned_file_db(x).is_err())
+            .cloned()
+            .collect();
+
+        comparison.new_tables.sort();
+        comparison.removed_tables.sort();
+        comparison
+    }
+
+    /// This function merges the definitions of another `Schema` into this one, returning a report of what changed and what conflicted.
+    ///
+    /// Tables and versions missing in this schema are added. Versions present in both with different definitions are conflicts,
+    /// and get resolved using the provided strategy. With `MergeStrategy::KeepBoth`, the other definition is not added to the schema,
+    /// but returned in the report, so it can be reviewed later. Saving the merged schema is up to the caller.
+    pub fn merge(&mut self, other: &Schema, strategy: MergeStrategy) -> Result<MergeReport> {
+        if self.version != other.version {
+            return Err(ErrorKind::SchemaStructuralVersionMismatch(self.version, other.version).into());
+        }
+
+        let mut report = MergeReport::default();
+        for versioned_file_other in &other.versioned_files {
+            let name = match versioned_file_other {
+                VersionedFile::AnimFragment(_) => "AnimFragment".to_owned(),
+                VersionedFile::AnimTable(_) => "AnimTable".to_owned(),
+                VersionedFile::DB(table_name, _) => table_name.to_owned(),
+                VersionedFile::DepManager(_) => "DepManager".to_owned(),
+                VersionedFile::Loc(_) => "Loc".to_owned(),
+                VersionedFile::MatchedCombat(_) => "MatchedCombat".to_owned(),
+            };
+
+            let versioned_file = match self.versioned_files.iter_mut().find(|x| x.conflict(versioned_file_other)) {
+                Some(versioned_file) => versioned_file,
+                None => {
+                    self.versioned_files.push(versioned_file_other.clone());
+                    report.new_tables.push(name);
+                    continue;
+                }
+            };
+
+            for definition_other in versioned_file_other.get_version_list() {
+                let definition_comparison = match versioned_file.get_version(definition_other.version) {
+                    Ok(definition) if definition == definition_other => continue,
+                    Ok(definition) => definition.compare(definition_other),
+                    Err(_) => {
+                        versioned_file.add_version(definition_other);
+                        report.new_versions.push((name.to_owned(), definition_other.version));
+                        continue;
+                    }
+                };
+
+                report.conflicts.entry(name.to_owned()).or_insert_with(BTreeMap::new).insert(definition_other.version, definition_comparison);
+                match strategy {
+                    MergeStrategy::PreferMine => {},
+                    MergeStrategy::PreferTheirs => versioned_file.add_version(definition_other),
+                    MergeStrategy::KeepBoth => report.kept_definitions.entry(name.to_owned()).or_insert_with(Vec::new).push(definition_other.clone()),
+                }
+            }
+        }
+
+        report.new_tables.sort();
+        report.new_versions.sort();
+        Ok(report)
+    }
+
+    /// This function returns all the reference cycles between the DB Tables of this `Schema`.
+    ///
+    /// Only the last definition of each table is checked, and cycles longer than `MAX_REFERENCE_CYCLE_LENGTH` tables are ignored.
+    pub fn get_reference_cycles(&self) -> Vec<ReferenceCycle> {
+        let mut references = BTreeMap::new();
+        let mut key_columns = BTreeMap::new();
+        for versioned_file in self.get_ref_versioned_file_db_all() {
+            if let VersionedFile::DB(table_name, definitions) = versioned_file {
+                if let Some(definition) = definitions.get(0) {
+                    let table_references = definition.fields.iter()
+                        .filter_map(|field| field.is_reference.as_ref().map(|(ref_table, ref_column)| (field.name.to_owned(), format!("{}_tables", ref_table), ref_column.to_owned())))
+                        .collect::<Vec<(String, String, String)>>();
+
+                    references.insert(table_name.to_owned(), table_references);
+                    key_columns.insert(table_name.to_owned(), definition.fields.iter().filter(|x| x.is_key).map(|x| x.name.to_owned()).collect::<Vec<String>>());
+                }
+            }
+        }
+
+        let mut cycles = vec![];
+        for table_name in references.keys() {
+            Self::find_reference_cycles(&references, table_name, table_name, &mut vec![], &mut cycles);
+        }
+
+        cycles.iter().map(|cycle| {
+            let is_expected = cycle.iter().all(|(_, _, ref_column, ref_table)| key_columns.get(ref_table).map_or(false, |keys| keys.contains(ref_column)));
+
+            ReferenceCycle {
+                path: cycle.iter().map(|(table, column, ref_column, _)| (table.to_owned(), column.to_owned(), ref_column.to_owned())).collect(),
+                is_expected,
+            }
+        }).collect()
+    }
+
+    /// This function exports a human-readable report of all the DB Tables of this `Schema` to the provided path.
+    ///
+    /// Tables are listed alphabetically, with all their versions from newest to oldest, and the fields of each version.
+    /// References to tables within the report are rendered as links to them.
+    pub fn export_report(&self, path: &Path, format: ReportFormat) -> Result<()> {
+        let mut tables = self.get_ref_versioned_file_db_all().into_iter()
+            .filter_map(|x| if let VersionedFile::DB(table_name, definitions) = x { Some((table_name, definitions)) } else { None })
+            .collect::<Vec<(&String, &Vec<Definition>)>>();
+        tables.sort_by(|x, y| x.0.cmp(y.0));
+
+        let mut report = String::new();
+        match format {
+            ReportFormat::Markdown => report.push_str("# Schema Report\n\n"),
+            ReportFormat::Html => report.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Schema Report</title>\n</head>\n<body>\n<h1>Schema Report</h1>\n"),
+        }
+
+        for (table_name, definitions) in &tables {
+            match format {
+                ReportFormat::Markdown => report.push_str(&format!("<a id=\"{}\"></a>\n\n## {}\n\n", table_name, Self::escape_report_text(table_name, format))),
+                ReportFormat::Html => report.push_str(&format!("<h2 id=\"{0}\">{0}</h2>\n", Self::escape_report_text(table_name, format))),
+            }
+
+            for definition in definitions.iter() {
+                let headers = ["Name", "Type", "Key", "Default Value", "Reference", "Description"];
+                let rows = definition.get_ref_fields().iter().map(|field| {
+                    let reference = match field.get_is_reference() {
+                        Some((ref_table, ref_column)) => {
+                            let ref_table_name = format!("{}_tables", ref_table);
+                            let text = Self::escape_report_text(&format!("{}/{}", ref_table_name, ref_column), format);
+                            if tables.iter().any(|(table_name, _)| **table_name == ref_table_name) {
+                                match format {
+                                    ReportFormat::Markdown => format!("[{}](#{})", text, ref_table_name),
+                                    ReportFormat::Html => format!("<a href=\"#{}\">{}</a>", Self::escape_report_text(&ref_table_name, format), text),
+                                }
+                            } else { text }
+                        }
+                        None => String::new(),
+                    };
+
+                    let field_type = match field.get_ref_field_type() {
+                        FieldType::SequenceU16(_) => "SequenceU16".to_owned(),
+                        FieldType::SequenceU32(_) => "SequenceU32".to_owned(),
+                        field_type => field_type.to_string(),
+                    };
+
+                    vec![
+                        Self::escape_report_text(field.get_name(), format),
+                        field_type,
+                        if field.get_is_key() { "Yes".to_owned() } else { String::new() },
+                        Self::escape_report_text(field.get_default_value().as_deref().unwrap_or(""), format),
+                        reference,
+                        Self::escape_report_text(field.get_description(), format),
+                    ]
+                }).collect::<Vec<Vec<String>>>();
+
+                match format {
+                    ReportFormat::Markdown => {
+                        report.push_str(&format!("### Version {}\n\n", definition.get_version()));
+                        report.push_str(&format!("| {} |\n", headers.join(" | ")));
+                        report.push_str(&format!("|{}\n", " --- |".repeat(headers.len())));
+                        for row in &rows {
+                            report.push_str(&format!("| {} |\n", row.join(" | ")));
+                        }
+                        report.push('\n');
+                    }
+                    ReportFormat::Html => {
+                        report.push_str(&format!("<h3>Version {}</h3>\n<table border=\"1\">\n", definition.get_version()));
+                        report.push_str(&format!("<tr>{}</tr>\n", headers.iter().map(|x| format!("<th>{}</th>", x)).collect::<String>()));
+                        for row in &rows {
+                            report.push_str(&format!("<tr>{}</tr>\n", row.iter().map(|x| format!("<td>{}</td>", x)).collect::<String>()));
+                        }
+                        report.push_str("</table>\n");
+                    }
+                }
+            }
+        }
+
+        if format == ReportFormat::Html {
+            report.push_str("</body>\n</html>\n");
+        }
+
+        let mut file = File::create(path)?;
+        file.write_all(report.as_bytes())?;
+        Ok(())
+    }
+
+    /// This function escapes the provided text so it can be safely put in a report of the provided format.
+    fn escape_report_text(text: &str, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Markdown => text.replace('\\', "\\\\")
+                .replace('|', "\\|")
+                .replace("\r\n", "<br>")
+                .replace('\n', "<br>")
+                .replace('\r', "<br>"),
+            ReportFormat::Html => text.replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('"', "&quot;")
+                .replace("\r\n", "<br>")
+                .replace('\n', "<br>")
+                .replace('\r', "<br>"),
+        }
+    }
+
+    /// This function follows the references of the provided table, storing every path that leads back to `start_table`.
+    ///
+    /// To not report the same cycle once per table in it, cycles are only stored starting from their alphabetically-first table.
+    fn find_reference_cycles(
+        references: &BTreeMap<String, Vec<(String, String, String)>>,
+        start_table: &str,
+        current_table: &str,
+        path: &mut Vec<(String, String, String, String)>,
+        cycles: &mut Vec<Vec<(String, String, String, String)>>
+    ) {
+        if let Some(table_references) = references.get(current_table) {
+            for (column, ref_table, ref_column) in table_references {
+                path.push((current_table.to_owned(), column.to_owned(), ref_column.to_owned(), ref_table.to_owned()));
+
+                if ref_table == start_table {
+                    cycles.push(path.to_vec());
+                }
+
+                else if ref_table.as_str() > start_table &&
+                    path.len() < MAX_REFERENCE_CYCLE_LENGTH &&
+                    path.iter().all(|(table, _, _, _)| table != ref_table) {
+                    Self::find_reference_cycles(references, start_table, ref_table, path, cycles);
+                }
+
+                path.pop();
+            }
+        }
+    }
+
     /// This function allow us to update all Schemas from any legacy version into the current one.
     ///
     /// NOTE FOR DEV: If you make a new Schema Version, add its update function here.
@@ -610,13 +1067,14 @@ impl Schema {
             repo.set_head(&master_refname)?;
         }
 
-        // Fetch the info of the master branch.
-        repo.find_remote(REMOTE)?.fetch(&[BRANCH], None, None)?;
-        let analysis = {
-            let fetch_head = repo.find_reference("FETCH_HEAD")?;
-            let fetch_commit = repo.reference_to_annotated_commit(&fetch_head)?;
-            repo.merge_analysis(&[&fetch_commit])?
-        };
+        // Fetch the info of the master branch. If this fails (for example, if we're offline), we still need to reset the repo, so don't return yet.
+        let analysis = repo.find_remote(REMOTE)
+            .and_then(|mut remote| remote.fetch(&[BRANCH], None, None))
+            .and_then(|_| {
+                let fetch_head = repo.find_reference("FETCH_HEAD")?;
+                let fetch_commit = repo.reference_to_annotated_commit(&fetch_head)?;
+                repo.merge_analysis(&[&fetch_commit])
+            });
 
         // Reset the repo to his original state after the check
         if current_branch_name != master_refname {
@@ -626,6 +1084,8 @@ impl Schema {
             let _ = repo.stash_pop(0, None);
         }
 
+        let analysis = analysis?;
+
         if analysis.0.is_up_to_date() {
             Ok(APIResponseSchema::NoUpdate)
         }
@@ -649,13 +1109,8 @@ impl Schema {
             Err(_) => {
 
                 // If it fails to open, it means either we don't have the .git folder, or we don't have a folder at all.
-                // In either case, recreate it and redownload the schemas repo. No more steps are needed here.
-                let _ = std::fs::remove_dir_all(&schema_path);
-                DirBuilder::new().recursive(true).create(&schema_path)?;
-                match Repository::clone(SCHEMA_REPO, &schema_path) {
-                    Ok(_) => return Ok(()),
-                    Err(_) => return Err(ErrorKind::SchemaUpdateError.into()),
-                }
+                // In either case, redownload the schemas repo. No more steps are needed here.
+                return Self::clone_schema_repo(&schema_path);
             }
         };
 
@@ -674,7 +1129,17 @@ impl Schema {
 
         // If it worked, now we have to do a pull from master. Sadly, git2-rs does not support pull.
         // Instead, we kinda force a fast-forward. Made in StackOverflow.
-        repo.find_remote(REMOTE)?.fetch(&[BRANCH], None, None)?;
+        // If the fetch fails (for example, if we're offline), reset the repo to his original state before returning.
+        if let Err(error) = repo.find_remote(REMOTE).and_then(|mut remote| remote.fetch(&[BRANCH], None, None)) {
+            if current_branch_name != master_refname {
+                let _ = repo.set_head(&current_branch_name);
+            }
+            if stash_id.is_ok() {
+                let _ = repo.stash_pop(0, None);
+            }
+            return Err(error.into());
+        }
+
         let (analysis, fetch_commit_id) = {
             let fetch_head = repo.find_reference("FETCH_HEAD")?;
             let fetch_commit = repo.reference_to_annotated_commit(&fetch_head)?;
@@ -707,8 +1172,8 @@ impl Schema {
         // - If we're not in the branch: covered by the branch switch.
         // - If the branches diverged: this one... the cleanest way to deal with it should be redownload the repo.
         else if analysis.0.is_normal() || analysis.0.is_none() || analysis.0.is_unborn() {
-            let _ = std::fs::remove_dir_all(&schema_path);
-            Self::update_schema_repo()
+            drop(repo);
+            Self::clone_schema_repo(&schema_path)
         }
         else {
 
@@ -723,6 +1188,61 @@ impl Schema {
             Err(ErrorKind::SchemaUpdateError.into())
         }
     }
+
+    /// This function downloads the schema repository to the provided path, replacing whatever is there.
+    ///
+    /// The repository is downloaded to a temporary folder first, so if the download fails, the current schemas are left untouched.
+    fn clone_schema_repo(schema_path: &Path) -> Result<()> {
+        let mut temp_path = schema_path.to_path_buf();
+        temp_path.set_file_name(format!("{}_download", schema_path.file_name().map(|x| x.to_string_lossy().to_string()).unwrap_or_default()));
+        let _ = std::fs::remove_dir_all(&temp_path);
+        DirBuilder::new().recursive(true).create(&temp_path)?;
+
+        match Repository::clone(SCHEMA_REPO, &temp_path) {
+            Ok(repo) => {
+                drop(repo);
+                let _ = std::fs::remove_dir_all(&schema_path);
+                std::fs::rename(&temp_path, &schema_path)?;
+                Ok(())
+            }
+            Err(_) => {
+                let _ = std::fs::remove_dir_all(&temp_path);
+                Err(ErrorKind::SchemaUpdateError.into())
+            }
+        }
+    }
+
+    /// This function returns the version of the local schemas, which is the id of the commit of the schema repository they're at.
+    pub fn get_schemas_version() -> Result<String> {
+        let repo = Repository::open(get_schemas_path()?)?;
+        let commit = repo.head()?.peel_to_commit()?;
+        Ok(commit.id().to_string())
+    }
+
+    /// This function checks if there is a schema update and, if there is one, downloads it.
+    ///
+    /// This never fails. If something goes wrong, like not having internet connection, the error is returned in the report and the
+    /// local schemas are left as they were. Reloading the schemas after an update is up to the caller.
+    pub fn auto_update() -> SchemaAutoUpdateReport {
+        let mut report = SchemaAutoUpdateReport {
+            old_version: Self::get_schemas_version().ok(),
+            ..Default::default()
+        };
+
+        let result = match Self::check_update() {
+            Ok(APIResponseSchema::NoUpdate) => Ok(false),
+            Ok(APIResponseSchema::NewUpdate) | Ok(APIResponseSchema::NoLocalFiles) => Self::update_schema_repo().map(|_| true),
+            Err(error) => Err(error),
+        };
+
+        match result {
+            Ok(updated) => report.updated = updated,
+            Err(error) => report.error = Some(error.to_string()),
+        }
+
+        report.new_version = Self::get_schemas_version().ok();
+        report
+    }
 }
 
 /// Implementation of `VersionedFile`.
@@ -856,6 +1376,53 @@ impl VersionedFile {
             VersionedFile::MatchedCombat(versions) => if let Some(position) = versions.iter_mut().position(|x| x.version == version) { versions.remove(position); }
         }
     }
+
+    /// This function changes the name and type of the field with the provided index in the provided version, returning the versions that got updated.
+    ///
+    /// If `apply_to_all_versions` is true, the same change is done in the other versions that have a field with the original name of the field,
+    /// unless they already have another field with the new name. Versions without a field with that name are left untouched.
+    pub fn update_field(
+        &mut self,
+        version: i32,
+        index: usize,
+        name: &str,
+        field_type: &FieldType,
+        apply_to_all_versions: bool,
+    ) -> Result<Vec<i32>> {
+        let definition = self.get_ref_mut_version(version)?;
+        let field = definition.fields.get_mut(index).ok_or_else(|| Error::from(ErrorKind::SchemaFieldNotFound(index)))?;
+        let old_name = field.get_name().to_owned();
+        field.set_name(name);
+        field.set_field_type(field_type.clone());
+
+        let mut updated = vec![version];
+        if apply_to_all_versions {
+            let versions = match self {
+                VersionedFile::AnimFragment(versions) |
+                VersionedFile::AnimTable(versions) |
+                VersionedFile::DB(_, versions) |
+                VersionedFile::DepManager(versions) |
+                VersionedFile::Loc(versions) |
+                VersionedFile::MatchedCombat(versions) => versions,
+            };
+
+            for definition in versions.iter_mut().filter(|x| x.version != version) {
+                let position = match definition.fields.iter().position(|x| x.get_name() == old_name) {
+                    Some(position) => position,
+                    None => continue,
+                };
+
+                if old_name != name && definition.fields.iter().any(|x| x.get_name() == name) { continue; }
+
+                definition.fields[position].set_name(name);
+                definition.fields[position].set_field_type(field_type.clone());
+                updated.push(definition.version);
+            }
+        }
+
+        updated.sort_unstable();
+        Ok(updated)
+    }
 }
 
 /// Implementation of `Definition`.
@@ -885,6 +1452,29 @@ impl Definition {
         &mut self.fields
     }
 
+    /// This function sets if the field with the provided index is a key field or not.
+    ///
+    /// Float fields cannot be keys, so trying to mark one of them as key returns an error.
+    pub fn set_key_field(&mut self, index: usize, is_key: bool) -> Result<()> {
+        let field = self.fields.get_mut(index).ok_or_else(|| Error::from(ErrorKind::SchemaFieldNotFound(index)))?;
+        field.set_is_key(is_key)
+    }
+
+    /// This function sets if the field with the provided index can be edited by hand or not.
+    pub fn set_editable_field(&mut self, index: usize, is_editable: bool) -> Result<()> {
+        let field = self.fields.get_mut(index).ok_or_else(|| Error::from(ErrorKind::SchemaFieldNotFound(index)))?;
+        field.set_is_editable(is_editable);
+        Ok(())
+    }
+
+    /// This function sets if the field with the provided index is an ordering column or not.
+    ///
+    /// Only integer fields can be ordering columns, so trying to mark any other field as one returns an error.
+    pub fn set_ordering_field(&mut self, index: usize, is_ordering: bool) -> Result<()> {
+        let field = self.fields.get_mut(index).ok_or_else(|| Error::from(ErrorKind::SchemaFieldNotFound(index)))?;
+        field.set_is_ordering(is_ordering)
+    }
+
     /// This function returns the reference and lookup data of a definition.
     pub fn get_reference_data(&self) -> BTreeMap<i32, (String, String, Option<Vec<String>>)> {
         self.fields.iter()
@@ -940,6 +1530,43 @@ impl Definition {
         fields.iter().find(|x| x.get_name() == name).unwrap().clone()
     }
 
+    /// This function compares the fields of this `Definition` with the ones of another `Definition`, returning their differences.
+    ///
+    /// `self` is considered the old definition, and `other` the new one. Fields are matched by name.
+    pub fn compare(&self, other: &Definition) -> DefinitionComparison {
+        let mut comparison = DefinitionComparison::default();
+        for field_new in &other.fields {
+            match self.fields.iter().find(|x| x.name == field_new.name) {
+                Some(field_old) => {
+                    if field_old.field_type != field_new.field_type {
+                        comparison.type_changes.push((field_new.name.to_owned(), field_old.field_type.to_string(), field_new.field_type.to_string()));
+                    }
+
+                    if field_old.is_key != field_new.is_key {
+                        comparison.other_changes.push((field_new.name.to_owned(), "Is Key".to_owned(), field_old.is_key.to_string(), field_new.is_key.to_string()));
+                    }
+
+                    if field_old.is_reference != field_new.is_reference {
+                        let reference_to_string = |reference: &Option<(String, String)>| if let Some((ref_table, ref_column)) = reference { format!("{}, {}", ref_table, ref_column) } else { String::new() };
+                        comparison.other_changes.push((field_new.name.to_owned(), "Is Reference".to_owned(), reference_to_string(&field_old.is_reference), reference_to_string(&field_new.is_reference)));
+                    }
+
+                    if field_old.default_value != field_new.default_value {
+                        comparison.other_changes.push((field_new.name.to_owned(), "Default Value".to_owned(), field_old.default_value.clone().unwrap_or_default(), field_new.default_value.clone().unwrap_or_default()));
+                    }
+                }
+                None => comparison.new_fields.push(field_new.name.to_owned()),
+            }
+        }
+
+        comparison.removed_fields = self.fields.iter()
+            .filter(|x| other.fields.iter().all(|y| y.name != x.name))
+            .map(|x| x.name.to_owned())
+            .collect();
+
+        comparison
+    }
+
     /// This function updates the fields in the provided definition with the data in the provided RawDefinition.
     ///
     /// Not all data is updated though, only:
@@ -965,8 +1592,10 @@ impl Definition {
                         }
                     }
 
-                    if raw_field.default_value.is_some() {
-                        field.default_value = raw_field.default_value.clone();
+                    if let Some(ref default_value) = raw_field.default_value {
+                        if let Some(default_value) = Field::check_default_value(&field.field_type, default_value) {
+                            field.default_value = Some(default_value);
+                        }
                     }
 
                     if raw_field.filename_relative_path.is_some() {
@@ -1144,7 +1773,9 @@ impl Field {
             description,
             ca_order,
             is_bitwise,
-            enum_values
+            enum_values,
+            is_editable: None,
+            is_ordering: None,
         }
     }
 
@@ -1183,11 +1814,45 @@ impl Field {
         self.is_key
     }
 
+    /// Setter for the `is_key` field. Float fields cannot be keys, so trying to mark one of them as key returns an error.
+    pub fn set_is_key(&mut self, is_key: bool) -> Result<()> {
+        if is_key && self.field_type == FieldType::F32 {
+            return Err(ErrorKind::SchemaFieldCannotBeKey(self.name.to_owned()).into());
+        }
+
+        self.is_key = is_key;
+        Ok(())
+    }
+
     /// Getter for the `default_value` field.
     pub fn get_default_value(&self) -> &Option<String> {
         &self.default_value
     }
 
+    /// This function checks if the provided default value is valid for the provided field type, returning it normalized if it is.
+    ///
+    /// Booleans accept `true`/`false` and `1`/`0`, and are normalized to `true`/`false`. Numbers must be valid for their type.
+    /// Sequences cannot have default values.
+    pub fn check_default_value(field_type: &FieldType, default_value: &str) -> Option<String> {
+        let default_value = default_value.trim();
+        match field_type {
+            FieldType::Boolean => match &*default_value.to_lowercase() {
+                "true" | "1" => Some("true".to_owned()),
+                "false" | "0" => Some("false".to_owned()),
+                _ => None,
+            },
+            FieldType::F32 => default_value.parse::<f32>().ok().map(|_| default_value.to_owned()),
+            FieldType::I16 => default_value.parse::<i16>().ok().map(|_| default_value.to_owned()),
+            FieldType::I32 => default_value.parse::<i32>().ok().map(|_| default_value.to_owned()),
+            FieldType::I64 => default_value.parse::<i64>().ok().map(|_| default_value.to_owned()),
+            FieldType::StringU8 |
+            FieldType::StringU16 |
+            FieldType::OptionalStringU8 |
+            FieldType::OptionalStringU16 => Some(default_value.to_owned()),
+            FieldType::SequenceU16(_) | FieldType::SequenceU32(_) => None,
+        }
+    }
+
     /// Getter for the `max_length` field.
     pub fn get_max_length(&self) -> i32 {
         self.max_length
@@ -1243,6 +1908,39 @@ impl Field {
     pub fn get_enum_values_to_string(&self) -> String {
         self.enum_values.iter().map(|(x, y)| format!("{},{}", x, y)).join(";")
     }
+
+    /// Getter for the `is_editable` field. Fields are editable unless explicitly marked otherwise.
+    pub fn get_is_editable(&self) -> bool {
+        self.is_editable.unwrap_or(true)
+    }
+
+    /// Setter for the `is_editable` field.
+    pub fn set_is_editable(&mut self, is_editable: bool) {
+        self.is_editable = if is_editable { None } else { Some(false) };
+    }
+
+    /// Getter for the `is_ordering` field. Fields are not ordering columns unless explicitly marked as such.
+    pub fn get_is_ordering(&self) -> bool {
+        self.is_ordering.unwrap_or(false)
+    }
+
+    /// Setter for the `is_ordering` field. Only integer fields can be ordering columns, so trying to mark any other field as one returns an error.
+    pub fn set_is_ordering(&mut self, is_ordering: bool) -> Result<()> {
+        if is_ordering && !self.is_integer() {
+            return Err(ErrorKind::SchemaFieldCannotBeOrdering(self.name.to_owned()).into());
+        }
+
+        self.is_ordering = if is_ordering { Some(true) } else { None };
+        Ok(())
+    }
+
+    /// This function returns if the field is an integer field. Bitwise fields are not considered integers, as they're split into booleans.
+    pub fn is_integer(&self) -> bool {
+        match self.field_type {
+            FieldType::I16 | FieldType::I32 | FieldType::I64 => self.is_bitwise <= 1,
+            _ => false,
+        }
+    }
 }
 
 /// Default implementation of `Schema`.
@@ -1272,6 +1970,8 @@ impl Default for Field {
             ca_order: -1,
             is_bitwise: 0,
             enum_values: BTreeMap::new(),
+            is_editable: None,
+            is_ordering: None,
         }
     }
 }
@@ -1341,7 +2041,7 @@ impl From<&RawField> for Field {
         field.name = raw_field.name.to_owned();
         field.field_type = field_type;
         field.is_key = raw_field.primary_key == "1";
-        field.default_value = raw_field.default_value.clone();
+        field.default_value = raw_field.default_value.as_ref().and_then(|x| Self::check_default_value(&field.field_type, x));
         field.max_length = max_length;
         field.is_filename = raw_field.is_filename.is_some();
         field.filename_relative_path = raw_field.filename_relative_path.clone();
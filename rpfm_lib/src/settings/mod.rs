@@ -20,8 +20,8 @@ use ron::de::{from_reader, from_str};
 use ron::ser::{to_string_pretty, PrettyConfig};
 use serde_derive::{Serialize, Deserialize};
 
-use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
 use std::fs::File;
 use std::io::{BufReader, BufWriter, Write};
 
@@ -41,6 +41,22 @@ pub const ZIP_PATH: &str = "7zip_path";
 /// Key of the MyMod path in the settings";
 pub const MYMOD_BASE_PATH: &str = "mymods_base_path";
 
+/// This struct holds the preferences of the columns of a table, shared by all the tables with the same name.
+///
+/// Columns are referenced by name, so preferences survive changes in the definition of the table.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TablePrefs {
+
+    /// Names of the columns, in the order they should be shown. Columns not in the list go after these ones, in their default order.
+    pub column_order: Vec<String>,
+
+    /// Names of the columns that should be hidden.
+    pub hidden_columns: BTreeSet<String>,
+
+    /// Widths of the columns, by name.
+    pub column_widths: BTreeMap<String, i32>,
+}
+
 /// This struct hold every setting of the lib and of RPFM_UI/CLI.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Settings {
@@ -74,6 +90,11 @@ impl Settings {
         settings_string.insert("font_name".to_owned(), "".to_owned());
         settings_string.insert("font_size".to_owned(), "".to_owned());
         settings_string.insert("recent_files".to_owned(), "[]".to_owned());
+        settings_string.insert("recent_files_max_amount".to_owned(), "10".to_owned());
+        settings_string.insert("table_prefs".to_owned(), "{}".to_owned());
+        settings_string.insert("tsv_column_mappings".to_owned(), "{}".to_owned());
+        settings_string.insert("global_search_max_results".to_owned(), "0".to_owned());
+        settings_string.insert("prefetch_memory_budget".to_owned(), "512".to_owned());
 
         // UI Settings.
         settings_bool.insert("start_maximized".to_owned(), false);
@@ -82,9 +103,11 @@ impl Settings {
         settings_bool.insert("allow_editing_of_ca_packfiles".to_owned(), false);
         settings_bool.insert("check_updates_on_start".to_owned(), true);
         settings_bool.insert("check_schema_updates_on_start".to_owned(), true);
+        settings_bool.insert("auto_update_schemas_on_start".to_owned(), false);
         settings_bool.insert("check_template_updates_on_start".to_owned(), true);
         settings_bool.insert("enable_diagnostics_tool".to_owned(), true);
         settings_bool.insert("use_lazy_loading".to_owned(), true);
+        settings_bool.insert("use_memory_mapped_files".to_owned(), false);
         settings_bool.insert("optimize_not_renamed_packedfiles".to_owned(), false);
         settings_bool.insert("disable_uuid_regeneration_on_db_tables".to_owned(), false);
         settings_bool.insert("packfile_treeview_resize_to_fit".to_owned(), false);
@@ -102,6 +125,10 @@ impl Settings {
         settings_bool.insert("check_for_missing_table_definitions".to_owned(), false);
         settings_bool.insert("enable_debug_menu".to_owned(), false);
         settings_bool.insert("spoof_ca_authoring_tool".to_owned(), false);
+        settings_bool.insert("enable_batch_operation_logs".to_owned(), false);
+        settings_bool.insert("reproducible_save".to_owned(), false);
+        settings_bool.insert("tsv_import_map_columns_by_header".to_owned(), false);
+        settings_bool.insert("install_after_save".to_owned(), false);
 
         Self {
             paths,
@@ -150,36 +177,127 @@ impl Settings {
         Ok(())
     }
 
-    pub fn get_recent_files(&self) -> Vec<String> {
-        from_str(self.settings_string.get("recent_files").unwrap()).unwrap()
+    /// This function returns the list of recently opened PackFiles, from newest to oldest.
+    ///
+    /// PackFiles that no longer exist are left out, as are the ones over the max amount of recent files.
+    pub fn get_recent_files(&self) -> Vec<PathBuf> {
+        let mut recent_files = self.get_recent_files_unchecked().into_iter().filter(|x| x.is_file()).collect::<Vec<PathBuf>>();
+        recent_files.truncate(self.get_recent_files_max_amount());
+        recent_files
     }
 
-    pub fn set_recent_files(&mut self, recent_files: &[String]) {
+    /// This function replaces the list of recently opened PackFiles with the provided one, and saves the settings to disk.
+    pub fn set_recent_files(&mut self, recent_files: &[PathBuf]) {
         let config = PrettyConfig::default();
         *self.settings_string.get_mut("recent_files").unwrap() = to_string_pretty(&recent_files, config).unwrap();
         let _ = self.save();
     }
 
-    pub fn update_recent_files(&mut self, new_path: &str) {
+    /// This function adds the provided PackFile to the top of the list of recently opened PackFiles, and saves the settings to disk.
+    ///
+    /// If the PackFile was already in the list, it's moved to the top. The list is capped to the max amount of recent files.
+    pub fn add_recent_file(&mut self, new_path: &Path) {
         *self = Self::load(None).unwrap_or_else(|_|Settings::new());
-        if let Some(recent_files) = self.settings_string.get("recent_files") {
-            let mut recent_files: Vec<String> = from_str(recent_files).unwrap();
+        let mut recent_files = self.get_recent_files_unchecked();
 
-            if let Some(index) = recent_files.iter().position(|x| x == new_path) {
-                recent_files.remove(index);
-            }
+        if let Some(index) = recent_files.iter().position(|x| x == new_path) {
+            recent_files.remove(index);
+        }
 
-            recent_files.reverse();
-            recent_files.push(new_path.to_owned());
-            recent_files.reverse();
+        recent_files.insert(0, new_path.to_path_buf());
+        recent_files.truncate(self.get_recent_files_max_amount());
+        self.set_recent_files(&recent_files);
+    }
 
-            // Limit it to 10 Packfiles.
-            recent_files.truncate(10);
+    /// This function clears the list of recently opened PackFiles, and saves the settings to disk.
+    pub fn clear_recent_files(&mut self) {
+        self.set_recent_files(&[]);
+    }
 
-            let config = PrettyConfig::default();
-            *self.settings_string.get_mut("recent_files").unwrap() = to_string_pretty(&recent_files, config).unwrap();
-            let _ = self.save();
+    /// This function returns the max amount of recently opened PackFiles to remember.
+    pub fn get_recent_files_max_amount(&self) -> usize {
+        self.settings_string.get("recent_files_max_amount").and_then(|x| x.parse::<usize>().ok()).unwrap_or(10)
+    }
+
+    /// This function returns the max amount of matches a global search can return. 0 means no limit.
+    pub fn get_global_search_max_results(&self) -> Option<usize> {
+        self.settings_string.get("global_search_max_results").and_then(|x| x.parse::<usize>().ok()).filter(|x| *x > 0)
+    }
+
+    /// This function returns the max amount of memory, in bytes, the data of the PackedFiles loaded to memory can use before prefetching stops.
+    pub fn get_prefetch_memory_budget(&self) -> u64 {
+        self.settings_string.get("prefetch_memory_budget").and_then(|x| x.parse::<u64>().ok()).unwrap_or(512) * 1024 * 1024
+    }
+
+    /// This function returns the column preferences of the table with the provided name.
+    ///
+    /// Columns not in the provided list of columns, like the ones removed from the table's definition, are ignored.
+    pub fn get_table_prefs(&self, table_name: &str, column_names: &[String]) -> TablePrefs {
+        let mut table_prefs = self.get_table_prefs_all().remove(table_name).unwrap_or_default();
+        table_prefs.column_order.retain(|x| column_names.contains(x));
+        table_prefs.hidden_columns.retain(|x| column_names.contains(x));
+        table_prefs.column_widths.retain(|x, _| column_names.contains(x));
+        table_prefs
+    }
+
+    /// This function replaces the column preferences of the table with the provided name, and saves the settings to disk.
+    ///
+    /// Empty preferences remove the table from the stored ones.
+    pub fn set_table_prefs(&mut self, table_name: &str, table_prefs: &TablePrefs) -> Result<()> {
+        *self = Self::load(None).unwrap_or_else(|_|Settings::new());
+        let mut table_prefs_all = self.get_table_prefs_all();
+        if *table_prefs == TablePrefs::default() { table_prefs_all.remove(table_name); }
+        else { table_prefs_all.insert(table_name.to_owned(), table_prefs.clone()); }
+
+        let config = PrettyConfig::default();
+        *self.settings_string.get_mut("table_prefs").unwrap() = to_string_pretty(&table_prefs_all, config)?;
+        self.save()
+    }
+
+    /// This function returns the column preferences of all the tables, by table name.
+    fn get_table_prefs_all(&self) -> BTreeMap<String, TablePrefs> {
+        self.settings_string.get("table_prefs").and_then(|x| from_str(x).ok()).unwrap_or_else(BTreeMap::new)
+    }
+
+    /// This function returns the column mapping for TSV files with the provided name of the table with the provided name, if it exists.
+    ///
+    /// Mappings go from the column headers of the TSV files to the names of the fields of the table.
+    pub fn get_tsv_column_mapping(&self, table_name: &str, mapping_name: &str) -> Option<BTreeMap<String, String>> {
+        self.get_tsv_column_mappings_all().remove(table_name).and_then(|mut x| x.remove(mapping_name))
+    }
+
+    /// This function returns the names of the column mappings for TSV files of the table with the provided name.
+    pub fn get_tsv_column_mapping_names(&self, table_name: &str) -> Vec<String> {
+        self.get_tsv_column_mappings_all().remove(table_name).map(|x| x.keys().cloned().collect()).unwrap_or_else(Vec::new)
+    }
+
+    /// This function replaces the column mapping for TSV files with the provided name of the table with the provided name, and saves the settings to disk.
+    ///
+    /// Passing no mapping removes it from the stored ones.
+    pub fn set_tsv_column_mapping(&mut self, table_name: &str, mapping_name: &str, mapping: Option<&BTreeMap<String, String>>) -> Result<()> {
+        *self = Self::load(None).unwrap_or_else(|_|Settings::new());
+        let mut mappings_all = self.get_tsv_column_mappings_all();
+        let mappings = mappings_all.entry(table_name.to_owned()).or_insert_with(BTreeMap::new);
+        match mapping {
+            Some(mapping) => { mappings.insert(mapping_name.to_owned(), mapping.clone()); }
+            None => { mappings.remove(mapping_name); }
         }
+
+        if mappings.is_empty() { mappings_all.remove(table_name); }
+
+        let config = PrettyConfig::default();
+        *self.settings_string.entry("tsv_column_mappings".to_owned()).or_insert_with(String::new) = to_string_pretty(&mappings_all, config)?;
+        self.save()
+    }
+
+    /// This function returns the column mappings for TSV files of all the tables, by table name and mapping name.
+    fn get_tsv_column_mappings_all(&self) -> BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>> {
+        self.settings_string.get("tsv_column_mappings").and_then(|x| from_str(x).ok()).unwrap_or_else(BTreeMap::new)
+    }
+
+    /// This function returns the list of recently opened PackFiles as it's stored, without checking if they still exist.
+    fn get_recent_files_unchecked(&self) -> Vec<PathBuf> {
+        self.settings_string.get("recent_files").and_then(|x| from_str(x).ok()).unwrap_or_else(Vec::new)
     }
 }
 
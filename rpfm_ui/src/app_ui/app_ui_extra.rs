@@ -40,7 +40,7 @@ use std::ffi::OsStr;
 use std::path::PathBuf;
 use std::rc::Rc;
 
-use rpfm_error::{ErrorKind, Result};
+use rpfm_error::{Error, ErrorKind, Result};
 
 use rpfm_lib::common::*;
 use rpfm_lib::GAME_SELECTED;
@@ -70,7 +70,7 @@ use crate::template_ui::{TemplateUI, SaveTemplateUI};
 use crate::QString;
 use crate::UI_STATE;
 use crate::ui::GameSelectedIcons;
-use crate::utils::{create_grid_layout, show_dialog};
+use crate::utils::{create_grid_layout, log_to_status_bar, show_dialog};
 
 //-------------------------------------------------------------------------------//
 //                             Implementations
@@ -240,17 +240,59 @@ impl AppUI {
         CENTRAL_COMMAND.send_message_qt(Command::OpenPackFiles(pack_file_paths.to_vec()));
 
         if pack_file_paths.len() == 1 {
-            SETTINGS.write().unwrap().update_recent_files(&pack_file_paths[0].to_str().unwrap().to_owned());
+            SETTINGS.write().unwrap().add_recent_file(&pack_file_paths[0]);
         }
 
+        // Check what response we got.
+        let response = CENTRAL_COMMAND.recv_message_qt_try();
+        Self::load_opened_packfile(app_ui, pack_file_contents_ui, global_search_ui, response, pack_file_paths, game_folder)
+    }
+
+    /// This function downloads the PackFile in the provided URL and opens it, showing the progress of the download in the status bar.
+    ///
+    /// The PackFile is not on disk, so saving it always asks for a path.
+    pub unsafe fn open_packfile_from_url(
+        app_ui: &Rc<Self>,
+        pack_file_contents_ui: &Rc<PackFileContentsUI>,
+        global_search_ui: &Rc<GlobalSearchUI>,
+        url: &str,
+    ) -> Result<()> {
+
+        // Destroy whatever it's in the PackedFile's view, to avoid data corruption. We don't care about this result.
+        let _ = Self::purge_them_all(app_ui, pack_file_contents_ui, false);
+
+        app_ui.main_window.set_enabled(false);
+        CENTRAL_COMMAND.send_message_qt(Command::OpenPackFileFromURL(url.to_owned()));
+
+        let response = CENTRAL_COMMAND.recv_message_qt_try_with_progress(&|downloaded, total| {
+            let downloaded = (downloaded / 1024).to_string();
+            match total {
+                Some(total) => log_to_status_bar(&tre("download_progress_total", &[&downloaded, &(total / 1024).to_string()])),
+                None => log_to_status_bar(&tre("download_progress", &[&downloaded])),
+            }
+        }, &|error| show_dialog(&app_ui.main_window, error, false));
+
+        Self::load_opened_packfile(app_ui, pack_file_contents_ui, global_search_ui, response, &[], "")
+    }
+
+    /// This function updates the UI with the PackFile we just opened, using the response of the background thread to the command that opened it.
+    ///
+    /// The paths and game folder are the ones used to open it, if it was opened from disk. Otherwise, pass them empty.
+    unsafe fn load_opened_packfile(
+        app_ui: &Rc<Self>,
+        pack_file_contents_ui: &Rc<PackFileContentsUI>,
+        global_search_ui: &Rc<GlobalSearchUI>,
+        response: Response,
+        pack_file_paths: &[PathBuf],
+        game_folder: &str,
+    ) -> Result<()> {
+
         let timer = SETTINGS.read().unwrap().settings_string["autosave_interval"].parse::<i32>().unwrap_or(10);
         if timer > 0 {
             app_ui.timer_backup_autosave.set_interval(timer * 60 * 1000);
             app_ui.timer_backup_autosave.start_0a();
         }
 
-        // Check what response we got.
-        let response = CENTRAL_COMMAND.recv_message_qt_try();
         match response {
 
             // If it's success....
@@ -424,6 +466,7 @@ impl AppUI {
     ) -> Result<()> {
 
         let mut result = Ok(());
+        let mut saved = false;
         app_ui.main_window.set_enabled(false);
 
         // First, we need to save all open `PackedFiles` to the backend. If one fails, we want to know what one.
@@ -432,7 +475,9 @@ impl AppUI {
         CENTRAL_COMMAND.send_message_qt(Command::GetPackFilePath);
         let response = CENTRAL_COMMAND.recv_message_qt();
         let mut path = if let Response::PathBuf(path) = response { path } else { panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response) };
-        if !path.is_file() || save_as {
+
+        // PackFiles not read from disk only have their name as path. Don't mistake them for a file with the same name in the current folder.
+        if !path.is_absolute() || !path.is_file() || save_as {
 
             // Create the FileDialog to save the PackFile and configure it.
             let file_dialog = QFileDialog::from_q_widget_q_string(
@@ -440,13 +485,13 @@ impl AppUI {
                 &qtr("save_packfile"),
             );
             file_dialog.set_accept_mode(qt_widgets::q_file_dialog::AcceptMode::AcceptSave);
-            file_dialog.set_name_filter(&QString::from_std_str("PackFiles (*.pack)"));
+            file_dialog.set_name_filter(&QString::from_std_str("PackFiles (*.pack *.pack.gz)"));
             file_dialog.set_confirm_overwrite(true);
             file_dialog.set_default_suffix(&QString::from_std_str("pack"));
             file_dialog.select_file(&QString::from_std_str(&path.file_name().unwrap().to_string_lossy()));
 
             // If we are saving an existing PackFile with another name, we start in his current path.
-            if path.is_file() {
+            if path.is_absolute() && path.is_file() {
                 path.pop();
                 file_dialog.set_directory_q_string(&QString::from_std_str(path.to_string_lossy().as_ref().to_owned()));
             }
@@ -472,6 +517,7 @@ impl AppUI {
 
                         UI_STATE.set_operational_mode(app_ui, None);
                         UI_STATE.set_is_modified(false, app_ui, pack_file_contents_ui);
+                        saved = true;
                     }
                     Response::Error(error) => result = Err(error),
 
@@ -490,6 +536,7 @@ impl AppUI {
                     let packfile_item = pack_file_contents_ui.packfile_contents_tree_model.item_1a(0);
                     packfile_item.set_tool_tip(&QString::from_std_str(new_pack_file_tooltip(&pack_file_info)));
                     UI_STATE.set_is_modified(false, app_ui, pack_file_contents_ui);
+                    saved = true;
                 }
                 Response::Error(error) => result = Err(error),
 
@@ -498,6 +545,19 @@ impl AppUI {
             }
         }
 
+        // Installing it is done after the save, so if it fails the user knows the PackFile has been saved anyway.
+        if saved && SETTINGS.read().unwrap().settings_bool["install_after_save"] {
+            CENTRAL_COMMAND.send_message_qt(Command::InstallPackFile);
+            let response = CENTRAL_COMMAND.recv_message_qt_try();
+            match response {
+                Response::Success => {},
+                Response::Error(error) => show_dialog(&app_ui.main_window, tre("install_after_save_error", &[&error.to_string()]), false),
+
+                // In ANY other situation, it's a message problem.
+                _ => panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response),
+            }
+        }
+
         // Then we re-enable the main Window and return whatever we've received.
         app_ui.main_window.set_enabled(true);
         result
@@ -696,7 +756,6 @@ impl AppUI {
         for path in SETTINGS.read().unwrap().get_recent_files() {
 
             // That means our file is a valid PackFile and it needs to be added to the menu.
-            let path = PathBuf::from(&path);
             if path.is_file() {
                 let mod_name = path.file_name().unwrap().to_string_lossy().as_ref().to_owned();
                 let open_mod_action = app_ui.packfile_open_recent.add_action_q_string(&QString::from_std_str(mod_name));
@@ -1130,6 +1189,49 @@ impl AppUI {
         }
     }
 
+    /// This function checks if there is any schema update and, if there is one, downloads it and reloads the schemas without asking.
+    ///
+    /// If something fails, like not having internet connection, the current schemas are kept and the error is shown in the status bar.
+    /// The download is done by the network thread. Only the reload of the schemas, if there was an update, blocks the UI.
+    pub unsafe fn auto_update_schemas(app_ui: &Rc<Self>) {
+
+        // If the schema is locked, don't even try to download the update, as we cannot reload it.
+        CENTRAL_COMMAND.send_message_qt(Command::IsSchemaLocked);
+        let response = CENTRAL_COMMAND.recv_message_qt();
+        match response {
+            Response::Bool(true) => return log_to_status_bar(&Error::from(ErrorKind::SchemaLocked).to_terminal()),
+            Response::Bool(false) => {},
+            _ => panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response),
+        }
+
+        CENTRAL_COMMAND.send_message_qt_to_network(Command::AutoUpdateSchemas);
+        let response = CENTRAL_COMMAND.recv_message_network_to_qt_try();
+        let report = match response {
+            Response::SchemaAutoUpdateReport(report) => report,
+            _ => panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response),
+        };
+
+        let old_version = report.old_version.map(|x| x.chars().take(7).collect::<String>()).unwrap_or_else(|| "-".to_owned());
+        let new_version = report.new_version.map(|x| x.chars().take(7).collect::<String>()).unwrap_or_else(|| "-".to_owned());
+        if let Some(error) = report.error {
+            log_to_status_bar(&tre("schema_auto_update_error", &[&old_version, &error]));
+        }
+        else if report.updated {
+            app_ui.main_window.set_enabled(false);
+            CENTRAL_COMMAND.send_message_qt(Command::ReloadSchemas);
+            let response = CENTRAL_COMMAND.recv_message_qt_try();
+            app_ui.main_window.set_enabled(true);
+
+            match response {
+                Response::Success => log_to_status_bar(&tre("schema_auto_update_success", &[&old_version, &new_version])),
+
+                // This happens if the schema got locked while we were downloading the update.
+                Response::Error(error) => log_to_status_bar(&error.to_terminal()),
+                _ => panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response),
+            }
+        }
+    }
+
     /// This function checks if there is any newer version of RPFM's templates released.
     ///
     /// If the `use_dialog` is false, we only show a dialog in case of update available. Useful for checks at start.
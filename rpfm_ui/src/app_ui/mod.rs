@@ -100,6 +100,7 @@ pub struct AppUI {
     //-------------------------------------------------------------------------------//
     pub packfile_new_packfile: QPtr<QAction>,
     pub packfile_open_packfile: QPtr<QAction>,
+    pub packfile_open_packfile_from_url: QPtr<QAction>,
     pub packfile_save_packfile: QPtr<QAction>,
     pub packfile_save_packfile_as: QPtr<QAction>,
     pub packfile_open_recent: QBox<QMenu>,
@@ -352,6 +353,7 @@ impl AppUI {
         // Populate the `PackFile` menu.
         let packfile_new_packfile = menu_bar_packfile.add_action_q_string(&qtr("new_packfile"));
         let packfile_open_packfile = menu_bar_packfile.add_action_q_string(&qtr("open_packfile"));
+        let packfile_open_packfile_from_url = menu_bar_packfile.add_action_q_string(&qtr("open_packfile_from_url"));
         let packfile_save_packfile = menu_bar_packfile.add_action_q_string(&qtr("save_packfile"));
         let packfile_save_packfile_as = menu_bar_packfile.add_action_q_string(&qtr("save_packfile_as"));
         let packfile_open_recent = QMenu::from_q_string_q_widget(&qtr("open_recent"), &menu_bar_packfile);
@@ -655,6 +657,7 @@ impl AppUI {
             // Menus.
             packfile_new_packfile,
             packfile_open_packfile,
+            packfile_open_packfile_from_url,
             packfile_save_packfile,
             packfile_save_packfile_as,
             packfile_open_recent,
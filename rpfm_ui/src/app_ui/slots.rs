@@ -14,6 +14,7 @@ Module with all the code related to the main `AppUISlot`.
 
 use qt_widgets::QAction;
 use qt_widgets::{QFileDialog, q_file_dialog::{FileMode, Option as QFileDialogOption}};
+use qt_widgets::QInputDialog;
 use qt_widgets::QMessageBox;
 use qt_widgets::SlotOfQPoint;
 
@@ -77,6 +78,7 @@ pub struct AppUISlots {
     pub packfile_open_menu: QBox<SlotNoArgs>,
     pub packfile_new_packfile: QBox<SlotOfBool>,
     pub packfile_open_packfile: QBox<SlotOfBool>,
+    pub packfile_open_packfile_from_url: QBox<SlotOfBool>,
     pub packfile_save_packfile: QBox<SlotOfBool>,
     pub packfile_save_packfile_as: QBox<SlotOfBool>,
     pub packfile_load_all_ca_packfiles: QBox<SlotOfBool>,
@@ -267,7 +269,7 @@ impl AppUISlots {
                         &app_ui.main_window,
                         &qtr("open_packfiles"),
                     );
-                    file_dialog.set_name_filter(&QString::from_std_str("PackFiles (*.pack)"));
+                    file_dialog.set_name_filter(&QString::from_std_str("PackFiles (*.pack *.pack.gz)"));
                     file_dialog.set_file_mode(FileMode::ExistingFiles);
 
                     // Run it and expect a response (1 => Accept, 0 => Cancel).
@@ -289,6 +291,26 @@ impl AppUISlots {
             }
         ));
 
+        // What happens when we trigger the "Open PackFile from URL" action.
+        let packfile_open_packfile_from_url = SlotOfBool::new(&app_ui.main_window, clone!(
+            app_ui,
+            pack_file_contents_ui,
+            global_search_ui => move |_| {
+
+                // Check first if there has been changes in the PackFile.
+                if AppUI::are_you_sure(&app_ui, false) {
+
+                    // If the dialog is cancelled, we get an empty string.
+                    let url = QInputDialog::get_text_3a(&app_ui.main_window, &qtr("open_packfile_from_url_title"), &qtr("open_packfile_from_url_label")).to_std_string();
+                    if !url.trim().is_empty() {
+                        if let Err(error) = AppUI::open_packfile_from_url(&app_ui, &pack_file_contents_ui, &global_search_ui, url.trim()) {
+                            return show_dialog(&app_ui.main_window, error, false);
+                        }
+                    }
+                }
+            }
+        ));
+
         // What happens when we trigger the "Save PackFile" action.
         let packfile_save_packfile = SlotOfBool::new(&app_ui.main_window, clone!(
             app_ui,
@@ -1021,11 +1043,16 @@ impl AppUISlots {
                 CENTRAL_COMMAND.send_message_qt(Command::OptimizePackFile);
                 let response = CENTRAL_COMMAND.recv_message_qt_try();
                 match response {
-                    Response::VecVecString(response) => {
-                        let response = response.iter().map(|x| TreePathType::File(x.to_vec())).collect::<Vec<TreePathType>>();
+                    Response::OptimizeReport(report) => {
+                        let response = report.removed_files.iter().map(|x| TreePathType::File(x.to_vec())).collect::<Vec<TreePathType>>();
 
                         pack_file_contents_ui.packfile_contents_tree_view.update_treeview(true, TreeViewOperation::Delete(response));
-                        show_dialog(&app_ui.main_window, tr("optimize_packfile_success"), true);
+                        show_dialog(&app_ui.main_window, tre("optimize_packfile_success", &[
+                            &report.get_total_removed_rows().to_string(),
+                            &report.removed_rows.len().to_string(),
+                            &report.emptied_tables.len().to_string(),
+                            &report.removed_files.len().to_string(),
+                        ]), true);
                     }
                     _ => panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response),
                 }
@@ -1418,6 +1445,7 @@ impl AppUISlots {
             packfile_open_menu,
             packfile_new_packfile,
             packfile_open_packfile,
+            packfile_open_packfile_from_url,
             packfile_save_packfile,
             packfile_save_packfile_as,
             packfile_load_all_ca_packfiles,
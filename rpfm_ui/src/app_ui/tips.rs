@@ -25,6 +25,7 @@ pub unsafe fn set_tips(app_ui: &Rc<AppUI>) {
     //-----------------------------------------------//
     app_ui.packfile_new_packfile.set_status_tip(&qtr("tt_packfile_new_packfile"));
     app_ui.packfile_open_packfile.set_status_tip(&qtr("tt_packfile_open_packfile"));
+    app_ui.packfile_open_packfile_from_url.set_status_tip(&qtr("tt_packfile_open_packfile_from_url"));
     app_ui.packfile_save_packfile.set_status_tip(&qtr("tt_packfile_save_packfile"));
     app_ui.packfile_save_packfile_as.set_status_tip(&qtr("tt_packfile_save_packfile_as"));
     app_ui.packfile_load_all_ca_packfiles.set_status_tip(&qtr("tt_packfile_load_all_ca_packfiles"));
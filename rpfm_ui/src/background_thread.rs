@@ -15,14 +15,16 @@ Basically, this does the heavy load of the program.
 !*/
 
 use open::that_in_background;
+use chrono::Local;
 use rayon::prelude::*;
 use uuid::Uuid;
 
 use std::collections::BTreeMap;
 use std::env::temp_dir;
-use std::fs::File;
+use std::fs::{File, OpenOptions};
 use std::io::{BufWriter, Read, Write};
 use std::path::PathBuf;
+use std::sync::atomic::Ordering;
 use std::thread;
 
 use rpfm_error::{Error, ErrorKind};
@@ -35,11 +37,14 @@ use rpfm_lib::GAME_SELECTED;
 use rpfm_lib::packfile::PFHFileType;
 use rpfm_lib::packedfile::*;
 use rpfm_lib::packedfile::animpack::AnimPack;
-use rpfm_lib::packedfile::table::db::DB;
+use rpfm_lib::packedfile::image::Image;
+use rpfm_lib::packedfile::table::db::{DB, TablePatch};
 use rpfm_lib::packedfile::table::loc::{Loc, TSV_NAME_LOC};
 use rpfm_lib::packedfile::text::{Text, TextType};
-use rpfm_lib::packfile::{PackFile, PackFileInfo, packedfile::PackedFile, PathType, PFHFlags, RESERVED_NAME_NOTES};
+use rpfm_lib::packfile::{PackFile, PackFileInfo, packedfile::PackedFile, PathType, PFHFlags, RecoveryReport, RESERVED_NAME_NOTES};
+use rpfm_lib::packfile::journal::Journal;
 use rpfm_lib::schema::*;
+use rpfm_lib::OPERATION_PAUSE;
 use rpfm_lib::SCHEMA;
 use rpfm_lib::SETTINGS;
 use rpfm_lib::SUPPORTED_GAMES;
@@ -47,11 +52,14 @@ use rpfm_lib::template::Template;
 
 use crate::app_ui::NewPackedFile;
 use crate::CENTRAL_COMMAND;
-use crate::communications::{Command, Notification, Response, THREADS_COMMUNICATION_ERROR};
+use crate::communications::{Command, Notification, PREFETCH_GENERATION, Response, THREADS_COMMUNICATION_ERROR};
 use crate::locale::tre;
 use crate::RPFM_PATH;
 use crate::views::table::TableType;
 
+/// Name of the file where batch operations are logged, if logging them is enabled.
+const BATCH_OPERATIONS_LOG: &str = "batch_operations.log";
+
 /// This is the background loop that's going to be executed in a parallel thread to the UI. No UI or "Unsafe" stuff here.
 ///
 /// All communication between this and the UI thread is done use the `CENTRAL_COMMAND` static.
@@ -69,6 +77,15 @@ pub fn background_loop() {
 
     let mut dependencies = Dependencies::default();
 
+    // Edits to columns marked as non-editable are rejected when saving tables from their views, unless disabled for the session.
+    let mut protect_non_editable_columns = true;
+
+    // Changes to the loaded schema are rejected while this is enabled. Only for the current session.
+    let mut schema_locked = false;
+
+    // Journal of the changes done to the open PackFile since it was opened or saved, to recover them after a crash.
+    let mut journal = Journal::default();
+
     //---------------------------------------------------------------------------------------//
     // Looping forever and ever...
     //---------------------------------------------------------------------------------------//
@@ -77,10 +94,21 @@ pub fn background_loop() {
         // Wait until you get something through the channel. This hangs the thread until we got something,
         // so it doesn't use processing power until we send it a message.
         let response = CENTRAL_COMMAND.recv_message_rust();
+        let record_in_journal = is_journaled_command(&response);
+
+        // If the schema is locked, reject the commands that change it before they touch it.
+        if schema_locked && is_schema_changing_command(&response) {
+            CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::SchemaLocked.into()));
+            continue;
+        }
+
         match response {
 
             // In case we want to reset the PackFile to his original state (dummy)...
-            Command::ResetPackFile => pack_file_decoded = PackFile::new(),
+            Command::ResetPackFile => {
+                pack_file_decoded = PackFile::new();
+                journal = Journal::default();
+            }
 
             // In case we want to remove a Secondary Packfile from memory...
             Command::RemovePackFileExtra(path) => { pack_files_decoded_extra.remove(&path); },
@@ -90,6 +118,7 @@ pub fn background_loop() {
                 let game_selected = GAME_SELECTED.read().unwrap();
                 let pack_version = SUPPORTED_GAMES.get(&**game_selected).unwrap().pfh_version[0];
                 pack_file_decoded = PackFile::new_with_name("unknown.pack", pack_version);
+                journal = Journal::default();
 
                 if let Ok(version_number) = get_game_selected_exe_version_number() {
                     pack_file_decoded.set_game_version(version_number);
@@ -98,10 +127,83 @@ pub fn background_loop() {
 
             // In case we want to "Open one or more PackFiles"...
             Command::OpenPackFiles(paths) => {
-                match PackFile::open_packfiles(&paths, SETTINGS.read().unwrap().settings_bool["use_lazy_loading"], false, false) {
+                match PackFile::open_packfiles(&paths, SETTINGS.read().unwrap().settings_bool["use_lazy_loading"], false, false, false) {
+                    Ok(pack_file) => {
+                        pack_file_decoded = pack_file;
+
+                        // We can only recover changes of a PackFile that exists as-is on disk.
+                        journal = if paths.len() == 1 { Journal::new(&pack_file_decoded) } else { Journal::default() };
+
+                        // Force decoding of table/locs, so they're in memory for the diagnostics to work.
+                        if let Some(ref schema) = *SCHEMA.read().unwrap() {
+                            let mut packed_files = pack_file_decoded.get_ref_mut_packed_files_by_types(&[PackedFileType::DB, PackedFileType::Loc], false);
+                            packed_files.par_iter_mut().for_each(|x| {
+                                let _ = x.decode_no_locks(schema);
+                            });
+                        }
+
+                        CENTRAL_COMMAND.send_message_rust(Response::PackFileInfo(PackFileInfo::from(&pack_file_decoded)));
+                    }
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to open a broken PackFile...
+            Command::OpenPackFileRecovery(path) => {
+                match PackFile::open_packfile_with_recovery(&path, SETTINGS.read().unwrap().settings_bool["use_lazy_loading"]) {
+                    Ok((pack_file, report)) => {
+                        pack_file_decoded = pack_file;
+
+                        // A recovered PackFile is not the one on disk, so there is nothing to recover changes onto.
+                        journal = if report == RecoveryReport::default() { Journal::new(&pack_file_decoded) } else { Journal::default() };
+
+                        // Force decoding of table/locs, so they're in memory for the diagnostics to work.
+                        if let Some(ref schema) = *SCHEMA.read().unwrap() {
+                            let mut packed_files = pack_file_decoded.get_ref_mut_packed_files_by_types(&[PackedFileType::DB, PackedFileType::Loc], false);
+                            packed_files.par_iter_mut().for_each(|x| {
+                                let _ = x.decode_no_locks(schema);
+                            });
+                        }
+
+                        CENTRAL_COMMAND.send_message_rust(Response::PackFileInfoRecoveryReport((PackFileInfo::from(&pack_file_decoded), report)));
+                    }
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to open a PackFile from an URL...
+            Command::OpenPackFileFromURL(url) => {
+                let progress = |downloaded, total| CENTRAL_COMMAND.send_message_notification_to_qt(Notification::Progress(downloaded, total));
+                match PackFile::open_from_url_with_progress(&url, SETTINGS.read().unwrap().settings_bool["use_lazy_loading"], &progress) {
+                    Ok(pack_file) => {
+                        pack_file_decoded = pack_file;
+
+                        // It's not on disk, so there is nothing to recover changes onto.
+                        journal = Journal::default();
+
+                        // Force decoding of table/locs, so they're in memory for the diagnostics to work.
+                        if let Some(ref schema) = *SCHEMA.read().unwrap() {
+                            let mut packed_files = pack_file_decoded.get_ref_mut_packed_files_by_types(&[PackedFileType::DB, PackedFileType::Loc], false);
+                            packed_files.par_iter_mut().for_each(|x| {
+                                let _ = x.decode_no_locks(schema);
+                            });
+                        }
+
+                        CENTRAL_COMMAND.send_message_rust(Response::PackFileInfo(PackFileInfo::from(&pack_file_decoded)));
+                    }
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to create a PackFile from a folder...
+            Command::CreatePackFileFromFolder((path, game)) => {
+                match PackFile::from_folder(&path, &game) {
                     Ok(pack_file) => {
                         pack_file_decoded = pack_file;
 
+                        // It's not on disk, so there is nothing to recover changes onto.
+                        journal = Journal::default();
+
                         // Force decoding of table/locs, so they're in memory for the diagnostics to work.
                         if let Some(ref schema) = *SCHEMA.read().unwrap() {
                             let mut packed_files = pack_file_decoded.get_ref_mut_packed_files_by_types(&[PackedFileType::DB, PackedFileType::Loc], false);
@@ -120,7 +222,7 @@ pub fn background_loop() {
             Command::OpenPackFileExtra(path) => {
                 match pack_files_decoded_extra.get(&path) {
                     Some(pack_file) => CENTRAL_COMMAND.send_message_rust(Response::PackFileInfo(PackFileInfo::from(pack_file))),
-                    None => match PackFile::open_packfiles(&[path.to_path_buf()], true, false, true) {
+                    None => match PackFile::open_packfiles(&[path.to_path_buf()], true, false, true, false) {
                          Ok(pack_file) => {
                             CENTRAL_COMMAND.send_message_rust(Response::PackFileInfo(PackFileInfo::from(&pack_file)));
                             pack_files_decoded_extra.insert(path.to_path_buf(), pack_file);
@@ -135,6 +237,7 @@ pub fn background_loop() {
                 match PackFile::open_all_ca_packfiles() {
                     Ok(pack_file) => {
                         pack_file_decoded = pack_file;
+                        journal = Journal::default();
                         CENTRAL_COMMAND.send_message_rust(Response::PackFileInfo(PackFileInfo::from(&pack_file_decoded)));
                     }
                     Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
@@ -144,7 +247,13 @@ pub fn background_loop() {
             // In case we want to "Save a PackFile"...
             Command::SavePackFile => {
                 match pack_file_decoded.save(None) {
-                    Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::PackFileInfo(From::from(&pack_file_decoded))),
+                    Ok(_) => {
+
+                        // After a clean save there is nothing to recover, so start a new journal.
+                        let _ = journal.clear();
+                        journal = Journal::new(&pack_file_decoded);
+                        CENTRAL_COMMAND.send_message_rust(Response::PackFileInfo(From::from(&pack_file_decoded)));
+                    }
                     Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(Error::from(ErrorKind::SavePackFileGeneric(error.to_string())))),
                 }
             }
@@ -152,11 +261,25 @@ pub fn background_loop() {
             // In case we want to "Save a PackFile As"...
             Command::SavePackFileAs(path) => {
                 match pack_file_decoded.save(Some(path.to_path_buf())) {
-                    Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::PackFileInfo(From::from(&pack_file_decoded))),
+                    Ok(_) => {
+
+                        // After a clean save there is nothing to recover, so start a new journal.
+                        let _ = journal.clear();
+                        journal = Journal::new(&pack_file_decoded);
+                        CENTRAL_COMMAND.send_message_rust(Response::PackFileInfo(From::from(&pack_file_decoded)));
+                    }
                     Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(Error::from(ErrorKind::SavePackFileGeneric(error.to_string())))),
                 }
             }
 
+            // In case we want to install the PackFile in the /data folder of the Game Selected...
+            Command::InstallPackFile => {
+                match pack_file_decoded.install_to_game_data_path() {
+                    Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
             // In case we want to change the current settings...
             Command::SetSettings(settings) => {
                 *SETTINGS.write().unwrap() = settings;
@@ -271,7 +394,9 @@ pub fn background_loop() {
 
             // In case we want to optimize our PackFile...
             Command::OptimizePackFile => {
-                CENTRAL_COMMAND.send_message_rust(Response::VecVecString(pack_file_decoded.optimize(&dependencies)));
+                let report = pack_file_decoded.optimize(&dependencies);
+                log_batch_operation("OptimizePackFile", &report.removed_files.iter().map(|x| (x.join("/"), "remove".to_owned(), "ok".to_owned())).collect::<Vec<_>>());
+                CENTRAL_COMMAND.send_message_rust(Response::OptimizeReport(report));
             }
 
             // In case we want to Patch the SiegeAI of a PackFile...
@@ -354,7 +479,7 @@ pub fn background_loop() {
             Command::AddPackedFiles((source_paths, destination_paths)) => {
                 let mut broke = false;
                 for (source_path, destination_path) in source_paths.iter().zip(destination_paths.iter()) {
-                    if let Err(error) = pack_file_decoded.add_from_file(source_path, destination_path.to_vec(), true) {
+                    if let Err(error) = pack_file_decoded.add_from_file(source_path, destination_path.to_vec(), true, true) {
                         CENTRAL_COMMAND.send_message_rust(Response::Error(error));
                         broke = true;
                         break;
@@ -368,8 +493,8 @@ pub fn background_loop() {
             }
 
             // In case we want to add one or more entire folders to our PackFile...
-            Command::AddPackedFilesFromFolder(paths) => {
-                match pack_file_decoded.add_from_folders(&paths, true) {
+            Command::AddPackedFilesFromFolder((paths, preserve_timestamps)) => {
+                match pack_file_decoded.add_from_folders(&paths, true, preserve_timestamps) {
                     Ok(paths) => CENTRAL_COMMAND.send_message_rust(Response::VecPathType(paths.iter().map(|x| PathType::File(x.to_vec())).collect())),
                     Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
 
@@ -408,27 +533,7 @@ pub fn background_loop() {
 
                     // Find the PackedFile we want and send back the response.
                     match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
-                        Some(ref mut packed_file) => {
-                            match packed_file.decode_return_ref() {
-                                Ok(packed_file_data) => {
-                                    match packed_file_data {
-                                        DecodedPackedFile::AnimFragment(data) => CENTRAL_COMMAND.send_message_rust(Response::AnimFragmentPackedFileInfo((data.clone(), From::from(&**packed_file)))),
-                                        DecodedPackedFile::AnimPack(data) => CENTRAL_COMMAND.send_message_rust(Response::AnimPackPackedFileInfo((data.get_file_list(), From::from(&**packed_file)))),
-                                        DecodedPackedFile::AnimTable(data) => CENTRAL_COMMAND.send_message_rust(Response::AnimTablePackedFileInfo((data.clone(), From::from(&**packed_file)))),
-                                        DecodedPackedFile::CaVp8(data) => CENTRAL_COMMAND.send_message_rust(Response::CaVp8PackedFileInfo((data.clone(), From::from(&**packed_file)))),
-                                        DecodedPackedFile::DB(table) => CENTRAL_COMMAND.send_message_rust(Response::DBPackedFileInfo((table.clone(), From::from(&**packed_file)))),
-                                        DecodedPackedFile::Image(image) => CENTRAL_COMMAND.send_message_rust(Response::ImagePackedFileInfo((image.clone(), From::from(&**packed_file)))),
-                                        DecodedPackedFile::Loc(table) => CENTRAL_COMMAND.send_message_rust(Response::LocPackedFileInfo((table.clone(), From::from(&**packed_file)))),
-                                        DecodedPackedFile::MatchedCombat(data) => CENTRAL_COMMAND.send_message_rust(Response::MatchedCombatPackedFileInfo((data.clone(), From::from(&**packed_file)))),
-                                        DecodedPackedFile::RigidModel(rigid_model) => CENTRAL_COMMAND.send_message_rust(Response::RigidModelPackedFileInfo((rigid_model.clone(), From::from(&**packed_file)))),
-                                        DecodedPackedFile::Text(text) => CENTRAL_COMMAND.send_message_rust(Response::TextPackedFileInfo((text.clone(), From::from(&**packed_file)))),
-                                        _ => CENTRAL_COMMAND.send_message_rust(Response::Unknown),
-
-                                    }
-                                }
-                                Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
-                            }
-                        }
+                        Some(packed_file) => send_decoded_packed_file(packed_file),
                         None => CENTRAL_COMMAND.send_message_rust(Response::Error(Error::from(ErrorKind::PackedFileNotFound))),
                     }
                 }
@@ -443,7 +548,28 @@ pub fn background_loop() {
                     }
                 }
                 else if let Some(packed_file) = pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    let non_editable_changes = match (packed_file.get_ref_decoded(), &decoded_packed_file) {
+                        (DecodedPackedFile::DB(old_table), DecodedPackedFile::DB(new_table)) if protect_non_editable_columns => old_table.get_non_editable_changes(new_table),
+                        _ => vec![],
+                    };
+
+                    if !non_editable_changes.is_empty() {
+                        CENTRAL_COMMAND.send_message_save_packedfile(Response::Error(ErrorKind::NonEditableColumnsModified(non_editable_changes).into()));
+                        continue;
+                    }
+
+                    // WsModels and UI Layouts are just a view over a Text PackedFile, so we store them back as raw data, to not break the Text view.
+                    let is_ws_model = match decoded_packed_file {
+                        DecodedPackedFile::WsModel(_) | DecodedPackedFile::UiLayout(_) => true,
+                        _ => false,
+                    };
                     *packed_file.get_ref_mut_decoded() = decoded_packed_file;
+                    if is_ws_model {
+                        if let Err(error) = packed_file.encode_and_clean_cache() {
+                            CENTRAL_COMMAND.send_message_save_packedfile(Response::Error(error));
+                            continue;
+                        }
+                    }
                 }
                 CENTRAL_COMMAND.send_message_save_packedfile(Response::Success);
             }
@@ -469,16 +595,48 @@ pub fn background_loop() {
             // In case we want to Mass-Import TSV Files...
             Command::MassImportTSV(paths, name) => {
                 match pack_file_decoded.mass_import_tsv(&paths, name, true) {
-                    Ok(result) => CENTRAL_COMMAND.send_message_rust(Response::VecVecStringVecVecString(result)),
-                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    Ok(result) => {
+                        let mut entries = result.0.iter().map(|x| (x.join("/"), "replace".to_owned(), "ok".to_owned())).collect::<Vec<_>>();
+                        entries.extend(result.1.iter().map(|x| (x.join("/"), "import".to_owned(), "ok".to_owned())));
+                        log_batch_operation("MassImportTSV", &entries);
+                        CENTRAL_COMMAND.send_message_rust(Response::VecVecStringVecVecString(result));
+                    }
+                    Err(error) => {
+                        log_batch_operation("MassImportTSV", &paths.iter().map(|x| (x.to_string_lossy().to_string(), "import".to_owned(), format!("error: {}", error))).collect::<Vec<_>>());
+                        CENTRAL_COMMAND.send_message_rust(Response::Error(error));
+                    }
                 }
             }
 
             // In case we want to Mass-Export TSV Files...
             Command::MassExportTSV(path_types, path) => {
+
+                // Only DB and Loc tables are exported, so those are the only ones we log.
+                let paths = pack_file_decoded.get_paths_from_path_types(&path_types).into_iter().filter(|x| match PackedFileType::get_packed_file_type(x) {
+                    PackedFileType::DB | PackedFileType::Loc => true,
+                    _ => false,
+                }).collect::<Vec<Vec<String>>>();
+
                 match pack_file_decoded.mass_export_tsv(&path_types, &path) {
-                    Ok(result) => CENTRAL_COMMAND.send_message_rust(Response::String(result)),
-                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    Ok(failures) => {
+                        log_batch_operation("MassExportTSV", &paths.iter().map(|x| {
+                            let outcome = match failures.iter().find(|(path, _)| path == x) {
+                                Some((_, error)) => format!("error: {}", error),
+                                None => "ok".to_owned(),
+                            };
+                            (x.join("/"), "export".to_owned(), outcome)
+                        }).collect::<Vec<_>>());
+
+                        let message = if failures.is_empty() { "<p>All exportable files have been exported.</p>".to_owned() } else {
+                            let error_files_string = failures.iter().map(|(path, _)| format!("<li>{}</li>", path.join("\\"))).collect::<String>();
+                            format!("<p>All exportable files have been exported, except the following ones:</p><ul>{}</ul>", error_files_string)
+                        };
+                        CENTRAL_COMMAND.send_message_rust(Response::String(message));
+                    }
+                    Err(error) => {
+                        log_batch_operation("MassExportTSV", &paths.iter().map(|x| (x.join("/"), "export".to_owned(), format!("error: {}", error))).collect::<Vec<_>>());
+                        CENTRAL_COMMAND.send_message_rust(Response::Error(error));
+                    }
                 }
             }
 
@@ -511,8 +669,17 @@ pub fn background_loop() {
             // In case we want to merge DB or Loc Tables from a PackFile...
             Command::MergeTables(paths, name, delete_source_files) => {
                 match pack_file_decoded.merge_tables(&paths, &name, delete_source_files) {
-                    Ok(data) => CENTRAL_COMMAND.send_message_rust(Response::VecString(data)),
-                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    Ok(data) => {
+                        let action = if delete_source_files { "merge and remove" } else { "merge" };
+                        let mut entries = paths.iter().map(|x| (x.join("/"), action.to_owned(), "ok".to_owned())).collect::<Vec<_>>();
+                        entries.push((data.join("/"), "create".to_owned(), "ok".to_owned()));
+                        log_batch_operation("MergeTables", &entries);
+                        CENTRAL_COMMAND.send_message_rust(Response::VecString(data));
+                    }
+                    Err(error) => {
+                        log_batch_operation("MergeTables", &paths.iter().map(|x| (x.join("/"), "merge".to_owned(), format!("error: {}", error))).collect::<Vec<_>>());
+                        CENTRAL_COMMAND.send_message_rust(Response::Error(error));
+                    }
                 }
             }
 
@@ -620,14 +787,14 @@ pub fn background_loop() {
             }
 
             // In case we want to import a TSV as a PackedFile...
-            Command::ImportTSV((internal_path, external_path)) => {
+            Command::ImportTSV((internal_path, external_path, map_columns_by_header)) => {
                 match pack_file_decoded.get_ref_mut_packed_file_by_path(&internal_path) {
                     Some(packed_file) => match packed_file.get_decoded() {
-                        DecodedPackedFile::DB(data) => match DB::import_tsv(&data.get_definition(), &external_path, &internal_path[1]) {
+                        DecodedPackedFile::DB(data) => match DB::import_tsv(&data.get_definition(), &external_path, &internal_path[1], map_columns_by_header) {
                             Ok(data) => CENTRAL_COMMAND.send_message_rust(Response::TableType(TableType::DB(data))),
                             Err(error) =>  CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
                         },
-                        DecodedPackedFile::Loc(data) => match Loc::import_tsv(&data.get_definition(), &external_path, &TSV_NAME_LOC) {
+                        DecodedPackedFile::Loc(data) => match Loc::import_tsv(&data.get_definition(), &external_path, &TSV_NAME_LOC, map_columns_by_header) {
                             Ok(data) => CENTRAL_COMMAND.send_message_rust(Response::TableType(TableType::Loc(data))),
                             Err(error) =>  CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
                         },
@@ -745,11 +912,12 @@ pub fn background_loop() {
                                 match packed_file.decode_return_ref_mut() {
                                     Ok(data) => {
                                         if let DecodedPackedFile::DB(ref mut data) = data {
-                                            match DB::import_tsv(&data.get_definition(), &external_path, &path[1]) {
+                                            match DB::import_tsv(&data.get_definition(), &external_path, &path[1], false) {
                                                 Ok(new_data) => {
+                                                    let diff = data.get_diff(&new_data);
                                                     *data = new_data;
                                                     match packed_file.encode_and_clean_cache() {
-                                                        Ok(_) => CENTRAL_COMMAND.send_message_save_packedfile(Response::Success),
+                                                        Ok(_) => CENTRAL_COMMAND.send_message_save_packedfile(Response::TableDiff(diff)),
                                                         Err(error) => CENTRAL_COMMAND.send_message_save_packedfile(Response::Error(error)),
                                                     }
                                                 }
@@ -757,11 +925,12 @@ pub fn background_loop() {
                                             }
                                         }
                                         else if let DecodedPackedFile::Loc(ref mut data) = data {
-                                            match Loc::import_tsv(&data.get_definition(), &external_path, &TSV_NAME_LOC) {
+                                            match Loc::import_tsv(&data.get_definition(), &external_path, &TSV_NAME_LOC, false) {
                                                 Ok(new_data) => {
+                                                    let diff = data.get_diff(&new_data);
                                                     *data = new_data;
                                                     match packed_file.encode_and_clean_cache() {
-                                                        Ok(_) => CENTRAL_COMMAND.send_message_save_packedfile(Response::Success),
+                                                        Ok(_) => CENTRAL_COMMAND.send_message_save_packedfile(Response::TableDiff(diff)),
                                                         Err(error) => CENTRAL_COMMAND.send_message_save_packedfile(Response::Error(error)),
                                                     }
                                                 }
@@ -975,8 +1144,1337 @@ pub fn background_loop() {
 
             Command::RebuildDependencies => dependencies.rebuild(pack_file_decoded.get_packfiles_list()),
 
-            // These two belong to the network thread, not to this one!!!!
-            Command::CheckUpdates | Command::CheckSchemaUpdates | Command::CheckTemplateUpdates => panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response),
+            // In case we want to fix the casing of the paths of our PackedFiles...
+            Command::NormalizePathCasing => CENTRAL_COMMAND.send_message_rust(Response::VecVecStringVecString(pack_file_decoded.normalize_path_casing())),
+
+            // In case we want to export a single definition from the schema...
+            Command::ExportDefinition(table_name, version, path) => {
+                match *SCHEMA.read().unwrap() {
+                    Some(ref schema) => match schema.export_definition(&table_name, version, &path) {
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::SchemaNotFound.into())),
+                }
+            }
+
+            // In case we want to import a single definition into the schema...
+            Command::ImportDefinition(path, overwrite) => {
+                let mut schema = SCHEMA.write().unwrap();
+                match *schema {
+                    Some(ref mut schema) => match schema.import_definition(&path, overwrite) {
+                        Ok(result) => match schema.save(&SUPPORTED_GAMES.get(&**GAME_SELECTED.read().unwrap()).unwrap().schema) {
+                            Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::StringI32(result)),
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        }
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::SchemaNotFound.into())),
+                }
+            }
+
+            // In case we want to delete a version of a definition from the loaded schema...
+            Command::DeleteDefinitionVersion((packed_file_type, table_name, version)) => {
+                let result = match *SCHEMA.write().unwrap() {
+                    Some(ref mut schema) => match packed_file_type {
+                        PackedFileType::AnimTable => schema.get_ref_mut_versioned_file_animtable(),
+                        PackedFileType::AnimFragment => schema.get_ref_mut_versioned_file_anim_fragment(),
+                        PackedFileType::DB => schema.get_ref_mut_versioned_file_db(&table_name),
+                        PackedFileType::Loc => schema.get_ref_mut_versioned_file_loc(),
+                        PackedFileType::MatchedCombat => schema.get_ref_mut_versioned_file_matched_combat(),
+                        _ => Err(ErrorKind::SchemaVersionedFileNotFound.into()),
+                    }.map(|versioned_file| versioned_file.remove_version(version)),
+                    None => Err(ErrorKind::SchemaNotFound.into()),
+                };
+
+                match result {
+                    Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to get the size of every folder of the PackFile...
+            Command::GetSizeSummary => CENTRAL_COMMAND.send_message_rust(Response::BTreeMapVecStringU64U64Usize(pack_file_decoded.get_tree_size_summary())),
+
+            // In case we want to check the references of the variantmeshdefinitions of the PackFile...
+            Command::ValidateVariantMeshes => {
+                match pack_file_decoded.validate_variant_meshes(&dependencies) {
+                    Ok(issues) => CENTRAL_COMMAND.send_message_rust(Response::VecVmdIssue(issues)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to check if our PackedFiles survive a decode/encode cycle...
+            Command::VerifyRoundTrip => {
+                match pack_file_decoded.verify_round_trip() {
+                    Ok(issues) => CENTRAL_COMMAND.send_message_rust(Response::VecVecStringRoundTripIssue(issues)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to add an image as DDS...
+            Command::AddImageAsDDS((source_path, path, format, generate_mipmaps)) => {
+                match Image::new_dds_from_file(&source_path, format, generate_mipmaps) {
+                    Ok(image) => {
+                        let mut packed_file = PackedFile::new(path, pack_file_decoded.get_file_name());
+                        packed_file.set_raw_data(image.get_data());
+                        match pack_file_decoded.add_packed_file(&packed_file, true) {
+                            Ok(path) => CENTRAL_COMMAND.send_message_rust(Response::VecString(path)),
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        }
+                    }
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to see an image as PNG...
+            Command::ExportImageAsPNG(path) => {
+                match pack_file_decoded.get_ref_packed_file_by_path(&path) {
+                    Some(packed_file) => match packed_file.get_raw_data() {
+                        Ok(data) => match Image::read(&data).and_then(|image| image.to_png()) {
+                            Ok(png) => {
+                                let mut temporal_file_path = temp_dir();
+                                temporal_file_path.push(format!("{}.png", Uuid::new_v4()));
+                                match File::create(&temporal_file_path) {
+                                    Ok(mut file) => {
+                                        if file.write_all(&png).is_ok() {
+                                            that_in_background(&temporal_file_path);
+                                            CENTRAL_COMMAND.send_message_rust(Response::PathBuf(temporal_file_path));
+                                        }
+                                        else {
+                                            CENTRAL_COMMAND.send_message_rust(Response::Error(Error::from(ErrorKind::IOGenericWrite(vec![temporal_file_path.display().to_string();1]))));
+                                        }
+                                    }
+                                    Err(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(Error::from(ErrorKind::IOGenericWrite(vec![temporal_file_path.display().to_string();1])))),
+                                }
+                            }
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        }
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to know what referenced tables are missing from our PackFile and its dependencies...
+            Command::GetMissingDependencyTables => {
+                match dependencies.get_missing_tables(&mut pack_file_decoded) {
+                    Ok(report) => CENTRAL_COMMAND.send_message_rust(Response::MissingTablesReport(report)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to compare two schemas...
+            Command::CompareSchemas(path_old, path_new) => {
+                match Schema::load_from_path(&path_old).and_then(|schema_old| Schema::load_from_path(&path_new).map(|schema_new| schema_old.compare(&schema_new))) {
+                    Ok(comparison) => CENTRAL_COMMAND.send_message_rust(Response::SchemaComparison(comparison)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to get the comments of the rows of a DB Table...
+            Command::GetRowComments(path) => {
+                let comments = pack_file_decoded.get_row_comments(&path).cloned().unwrap_or_default();
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    Some(packed_file) => match packed_file.decode_return_ref() {
+                        Ok(DecodedPackedFile::DB(table)) => CENTRAL_COMMAND.send_message_rust(Response::VecOptionString(table.get_row_comments(&comments))),
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::DBTableIsNotADBTable.into())),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to set the comment of a row of a DB Table...
+            Command::SetRowComment(path, row, comment) => {
+                let mut comments = pack_file_decoded.get_row_comments(&path).cloned().unwrap_or_default();
+                let result = match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    Some(packed_file) => match packed_file.decode_return_ref() {
+                        Ok(DecodedPackedFile::DB(table)) => table.set_row_comment(&mut comments, row, &comment),
+                        Ok(_) => Err(ErrorKind::DBTableIsNotADBTable.into()),
+                        Err(error) => Err(error),
+                    }
+                    None => Err(ErrorKind::PackedFileNotFound.into()),
+                };
+
+                match result {
+                    Ok(_) => {
+                        pack_file_decoded.set_row_comments(&path, &comments);
+                        CENTRAL_COMMAND.send_message_rust(Response::Success);
+                    }
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to replace all the references to an asset path...
+            Command::ReplaceAssetPathReferences((old_path, new_path)) => {
+                match pack_file_decoded.replace_asset_path_references(&old_path, &new_path) {
+                    Ok(changes) => CENTRAL_COMMAND.send_message_rust(Response::VecVecStringVecUsize(changes)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to export the list of PackedFiles to a CSV file...
+            Command::ExportFileListCSV(path) => {
+                match pack_file_decoded.export_file_list_csv(&path) {
+                    Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to change the key fields of a Definition...
+            Command::SetDefinitionKeyField((table_name, version, index, is_key)) => {
+                let result = match *SCHEMA.write().unwrap() {
+                    Some(ref mut schema) => schema.get_ref_mut_versioned_file_db(&table_name)
+                        .and_then(|versioned_file| versioned_file.get_ref_mut_version(version))
+                        .and_then(|definition| definition.set_key_field(index, is_key).map(|_| definition.clone())),
+                    None => Err(ErrorKind::SchemaNotFound.into()),
+                };
+
+                match result {
+                    Ok(definition) => {
+
+                        // Update the already decoded tables using this definition, and regenerate their dependency data.
+                        // This only changes schema metadata, not the data of the tables, so they're not marked as modified.
+                        for packed_file in pack_file_decoded.get_ref_mut_packed_files_by_type(PackedFileType::DB, false) {
+                            if let DecodedPackedFile::DB(ref mut table) = packed_file.get_ref_mut_decoded_no_modify() {
+                                if table.get_ref_table_name() == table_name && table.get_ref_definition().get_version() == version {
+                                    table.set_definition(&definition);
+                                }
+                            }
+                        }
+
+                        dependencies.invalidate_cached_data(&table_name);
+                        let dependency_data = DB::get_dependency_data(
+                            &pack_file_decoded,
+                            &table_name,
+                            &definition,
+                            &dependencies,
+                            &[],
+                        );
+
+                        CENTRAL_COMMAND.send_message_rust(Response::BTreeMapI32DependencyData(dependency_data));
+                    }
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to get part of the data of a PackedFile...
+            Command::GetPackedFileBytesRange((path, offset, len)) => {
+                match pack_file_decoded.get_ref_packed_file_by_path(&path) {
+                    Some(packed_file) => match packed_file.get_data_range(offset, len) {
+                        Ok(data) => CENTRAL_COMMAND.send_message_rust(Response::VecU8(data)),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to get the order of the PackedFiles of our PackFile...
+            Command::GetPackedFileOrder => CENTRAL_COMMAND.send_message_rust(Response::VecVecString(pack_file_decoded.get_packed_file_order())),
+
+            // In case we want to change the order of the PackedFiles of our PackFile...
+            Command::SetPackedFileOrder(ordered_paths) => {
+                match pack_file_decoded.set_packed_file_order(&ordered_paths) {
+                    Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to export a DB Table to SQLite...
+            Command::ExportTableToSQLite((path, database_path)) => {
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    Some(packed_file) => match packed_file.decode_return_ref() {
+                        Ok(DecodedPackedFile::DB(table)) => match table.export_to_sqlite(&database_path, &table.name) {
+                            Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        }
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::DBTableIsNotADBTable.into())),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to export all the DB Tables to SQLite...
+            Command::ExportAllTablesToSQLite(database_path) => {
+                match pack_file_decoded.export_all_tables_to_sqlite(&database_path) {
+                    Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to delete PackedFiles from a PackFile, but only if they're not referenced...
+            Command::DeletePackedFilesChecked(item_types, force) => {
+                match pack_file_decoded.remove_packed_files_checked(&item_types, force) {
+                    Ok(result) => CENTRAL_COMMAND.send_message_rust(Response::VecPathTypeVecReference(result)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to import a language from a multi-language TSV into our Locs...
+            Command::ImportMultiLangLoc((external_path, language_column)) => {
+                match pack_file_decoded.import_multi_language_loc(&external_path, &language_column) {
+                    Ok(result) => CENTRAL_COMMAND.send_message_rust(Response::VecStringVecString(result)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to know if the PackFile has been modified since it was loaded or saved...
+            Command::IsPackFileModified => CENTRAL_COMMAND.send_message_rust(Response::Bool(pack_file_decoded.is_modified())),
+
+            // In case we want to get all the distinct values of a column of a table, including the ones from the dependencies...
+            Command::GetDistinctValues((table_name, column_name)) => {
+                match dependencies.get_distinct_column_values(&mut pack_file_decoded, &table_name, &column_name) {
+                    Ok(values) => CENTRAL_COMMAND.send_message_rust(Response::VecStringUsizeUsize(values)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to check if the dependency PackFiles of our PackFile exist...
+            Command::CheckDependencyPackFilesExist => {
+                let game_selected = GAME_SELECTED.read().unwrap().to_owned();
+                CENTRAL_COMMAND.send_message_rust(Response::VecStringOptionPathBuf(pack_file_decoded.resolve_dependency_packfiles(&game_selected)));
+            }
+
+            // In case we want to convert a string column of a table between required and optional...
+            Command::ConvertColumnStringOptionality((path, column, optional)) => {
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    Some(packed_file) => match packed_file.decode_return_ref_mut() {
+                        Ok(DecodedPackedFile::DB(ref mut table)) => match table.convert_column_string_optionality(column, optional) {
+                            Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        }
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::DBTableIsNotADBTable.into())),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to decode a PackedFile from one of the extra PackFiles, to browse it side by side with ours...
+            Command::DecodePackedFileFromExtra((pack_file_path, path)) => {
+                match pack_files_decoded_extra.get_mut(&pack_file_path) {
+                    Some(pack_file) => match pack_file.get_ref_mut_packed_file_by_path(&path) {
+                        Some(packed_file) => send_decoded_packed_file(packed_file),
+                        None => CENTRAL_COMMAND.send_message_rust(Response::Error(Error::from(ErrorKind::PackedFileNotFound))),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::CannotFindExtraPackFile(pack_file_path).into())),
+                }
+            }
+
+            // In case we want to launch a global search on one of the extra PackFiles...
+            Command::GlobalSearchExtra((pack_file_path, mut global_search)) => {
+                match pack_files_decoded_extra.get_mut(&pack_file_path) {
+                    Some(pack_file) => {
+                        global_search.search(pack_file);
+                        let packed_files_info = global_search.get_results_packed_file_info(pack_file);
+                        CENTRAL_COMMAND.send_message_rust(Response::GlobalSearchVecPackedFileInfo((global_search, packed_files_info)));
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::CannotFindExtraPackFile(pack_file_path).into())),
+                }
+            }
+
+            // Extra PackFiles are read-only, so any attempt to save a PackedFile from a view into them is rejected.
+            Command::SavePackedFileFromViewExtra((pack_file_path, _, _)) => {
+                CENTRAL_COMMAND.send_message_save_packedfile(Response::Error(ErrorKind::ExtraPackFileIsReadOnly(pack_file_path).into()));
+            }
+
+            // In case we want to find duplicated PackedFiles in our PackFile...
+            Command::FindDuplicateFiles => CENTRAL_COMMAND.send_message_rust(Response::VecVecVecString(pack_file_decoded.find_duplicate_files())),
+
+            // In case we want to remove the duplicated PackedFiles of our PackFile...
+            Command::DeduplicateFiles => {
+                let removed_paths = pack_file_decoded.deduplicate_files();
+                CENTRAL_COMMAND.send_message_rust(Response::VecPathType(removed_paths.iter().map(|x| PathType::File(x.to_vec())).collect()));
+            }
+
+            // In case we want to export a DB Table as a Markdown table...
+            Command::ExportTableAsMarkdown((path, markdown_path, max_rows)) => {
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    Some(packed_file) => match packed_file.decode_return_ref() {
+                        Ok(DecodedPackedFile::DB(table)) => match table.export_markdown(&markdown_path, max_rows) {
+                            Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        }
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::DBTableIsNotADBTable.into())),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to know which tables of the Schema are in the dependency database, and vice versa...
+            Command::GetSchemaVsDependencyCoverage => {
+                match dependencies.get_schema_coverage() {
+                    Ok(coverage) => CENTRAL_COMMAND.send_message_rust(Response::VecStringVecStringVecString(coverage)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to generate a patch with the changes of our tables over the ones of another PackFile...
+            Command::GeneratePatch((base_path, patch_path)) => {
+                match PackFile::open_packfiles(&[base_path], true, false, true, false) {
+                    Ok(mut base_pack_file) => match pack_file_decoded.generate_table_patches(&mut base_pack_file) {
+                        Ok(patches) => match TablePatch::save_to_file(&patches, &patch_path) {
+                            Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        }
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to apply a patch to our tables...
+            Command::ApplyPatch((patch_path, skip_conflicts)) => {
+                match TablePatch::load_from_file(&patch_path) {
+                    Ok(patches) => match pack_file_decoded.apply_table_patches(&patches, skip_conflicts) {
+                        Ok(conflicts) => CENTRAL_COMMAND.send_message_rust(Response::VecVecStringVecString(conflicts)),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to get the list of recently opened PackFiles...
+            Command::GetRecentFiles => CENTRAL_COMMAND.send_message_rust(Response::VecPathBuf(SETTINGS.read().unwrap().get_recent_files())),
+
+            // In case we want to add a PackFile to the list of recently opened PackFiles...
+            Command::AddRecentFile(path) => SETTINGS.write().unwrap().add_recent_file(&path),
+
+            // In case we want to decode raw table data with a specific definition from the schema...
+            Command::DecodeRawTable((data, table_name, version)) => {
+                let result = match *SCHEMA.read().unwrap() {
+                    Some(ref schema) => schema.get_ref_versioned_file_db(&table_name)
+                        .and_then(|versioned_file| versioned_file.get_version(version))
+                        .and_then(|definition| DB::read_with_definition(&data, &table_name, definition)),
+                    None => Err(ErrorKind::SchemaNotFound.into()),
+                };
+
+                match result {
+                    Ok(table) => CENTRAL_COMMAND.send_message_rust(Response::DB(table)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to discard the changes done to a PackedFile...
+            Command::RevertPackedFile(path) => {
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    Some(packed_file) => match packed_file.revert_to_disk() {
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to export the notes of the PackFile to a file...
+            Command::ExportNotes(path) => match pack_file_decoded.export_notes(&path) {
+                Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+            }
+
+            // In case we want to import the notes of the PackFile from a file...
+            Command::ImportNotes((path, append)) => match pack_file_decoded.import_notes(&path, append) {
+                Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+            }
+
+            // In case we want to mark/unmark a field of a definition as editable...
+            Command::SetDefinitionEditableField((table_name, version, index, is_editable)) => {
+                let result = match *SCHEMA.write().unwrap() {
+                    Some(ref mut schema) => schema.get_ref_mut_versioned_file_db(&table_name)
+                        .and_then(|versioned_file| versioned_file.get_ref_mut_version(version))
+                        .and_then(|definition| definition.set_editable_field(index, is_editable).map(|_| definition.clone()))
+
+                        // Save the schema, so the flag is not lost when we reload it.
+                        .and_then(|definition| schema.save(&SUPPORTED_GAMES.get(&**GAME_SELECTED.read().unwrap()).unwrap().schema).map(|_| definition)),
+                    None => Err(ErrorKind::SchemaNotFound.into()),
+                };
+
+                match result {
+                    Ok(definition) => {
+
+                        // Update the already decoded tables using this definition, so the new flag is taken into account when saving them.
+                        // This only changes schema metadata, not the data of the tables, so they're not marked as modified.
+                        for packed_file in pack_file_decoded.get_ref_mut_packed_files_by_type(PackedFileType::DB, false) {
+                            if let DecodedPackedFile::DB(ref mut table) = packed_file.get_ref_mut_decoded_no_modify() {
+                                if table.get_ref_table_name() == table_name && table.get_ref_definition().get_version() == version {
+                                    table.set_definition(&definition);
+                                }
+                            }
+                        }
+
+                        CENTRAL_COMMAND.send_message_rust(Response::Success);
+                    }
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to enable/disable the protection of non-editable columns for this session...
+            Command::SetNonEditableColumnsProtection(enable) => protect_non_editable_columns = enable,
+
+            // In case we want to know which PackFiles contain a path, and which one of them the game uses...
+            Command::GetOverrideChain(path) => match pack_file_decoded.get_override_chain(&path) {
+                Ok(chain) => CENTRAL_COMMAND.send_message_rust(Response::VecStringPFHFileType(chain)),
+                Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+            }
+
+            // In case we want to find the rows of a table matching a comparison...
+            Command::FindTableRows((path, column, op, value)) => {
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    Some(packed_file) => match packed_file.decode_return_ref() {
+                        Ok(DecodedPackedFile::DB(table)) => match table.find_rows(column, op, &value) {
+                            Ok(rows) => CENTRAL_COMMAND.send_message_rust(Response::VecUsize(rows)),
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        }
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::DBTableIsNotADBTable.into())),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to know which definition is used to decode a DB Table...
+            Command::GetDefinitionForPath(path) => {
+                let result = match pack_file_decoded.get_ref_packed_file_by_path(&path) {
+                    Some(packed_file) => match (packed_file.get_packed_file_type_by_path(), path.get(1)) {
+
+                        // If it's already decoded, use its current data, so we get the version it'll be saved with.
+                        (PackedFileType::DB, Some(table_name)) => match *SCHEMA.read().unwrap() {
+                            Some(ref schema) => {
+                                let data = match packed_file.get_ref_decoded().encode() {
+                                    Some(data) => data,
+                                    None => packed_file.get_raw_data(),
+                                };
+                                data.and_then(|data| DB::get_definition_from_data(&data, table_name, schema))
+                            }
+                            None => Err(ErrorKind::SchemaNotFound.into()),
+                        }
+                        _ => Err(ErrorKind::DBTableIsNotADBTable.into()),
+                    }
+                    None => Err(ErrorKind::PackedFileNotFound.into()),
+                };
+
+                match result {
+                    Ok(definition) => CENTRAL_COMMAND.send_message_rust(Response::Definition(definition)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to import a key-value file as a Loc PackedFile...
+            Command::ImportLocFromKeyValue((internal_path, external_path, delimiter)) => {
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&internal_path) {
+                    Some(packed_file) => match packed_file.decode_return_ref() {
+                        Ok(DecodedPackedFile::Loc(data)) => match data.import_from_keyvalue(&external_path, delimiter) {
+                            Ok(data) => CENTRAL_COMMAND.send_message_rust(Response::TableType(TableType::Loc(data))),
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        }
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::LocPackedFileIsNotALocPackedFile.into())),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to know how many entries each DB Table of the PackFile has...
+            Command::GetEntryCountsByTable => match pack_file_decoded.count_entries_by_table() {
+                Ok(result) => CENTRAL_COMMAND.send_message_rust(Response::BTreeMapStringUsizeVecVecString(result)),
+                Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+            }
+
+            // In case we want to convert the PackFile to another version...
+            Command::ConvertPackFileVersion(pfh_version) => match pack_file_decoded.convert_to_pfh_version(pfh_version) {
+                Ok(warnings) => CENTRAL_COMMAND.send_message_rust(Response::VecString(warnings)),
+                Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+            }
+
+            // In case we want to replace all matches in a Global Search, but only within some paths...
+            Command::GlobalSearchReplaceAllInPaths((mut global_search, path_filter)) => {
+                let old_path_filter = global_search.path_filter.to_vec();
+                global_search.path_filter = path_filter;
+                let _ = global_search.replace_all(&mut pack_file_decoded);
+                global_search.path_filter = old_path_filter;
+
+                let packed_files_info = global_search.get_results_packed_file_info(&mut pack_file_decoded);
+                CENTRAL_COMMAND.send_message_rust(Response::GlobalSearchVecPackedFileInfo((global_search, packed_files_info)));
+            }
+
+            // In case we want to find the reference cycles of the currently loaded schema...
+            Command::DetectReferenceCycles => {
+                match &*SCHEMA.read().unwrap() {
+                    Some(ref schema) => CENTRAL_COMMAND.send_message_rust(Response::VecReferenceCycle(schema.get_reference_cycles())),
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::SchemaNotFound.into())),
+                }
+            }
+
+            // In case we want to export a table PackedFile together with its definition...
+            Command::ExportPackedFileWithDefinition((path, destination_path)) => {
+                match pack_file_decoded.export_packed_file_with_definition(&path, &destination_path) {
+                    Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to import a table PackedFile exported together with its definition...
+            Command::ImportPackedFileWithDefinition(source_path) => {
+                match pack_file_decoded.import_packed_file_with_definition(&source_path) {
+                    Ok(path) => CENTRAL_COMMAND.send_message_rust(Response::VecString(path)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to enable/disable a flag of the bitmask of the currently open PackFile...
+            Command::SetPackFileFlag((flag, state)) => {
+                match pack_file_decoded.set_pfh_flag(flag, state) {
+                    Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to get the state of the flags of the bitmask of the currently open PackFile...
+            Command::GetPackFileFlags => CENTRAL_COMMAND.send_message_rust(Response::VecStringPFHFlagsBool(pack_file_decoded.get_pfh_flags())),
+
+            // In case we want to get all the Text PackedFiles with their language...
+            Command::ListTextFiles => CENTRAL_COMMAND.send_message_rust(Response::VecVecStringTextType(pack_file_decoded.list_text_files())),
+
+            // In case we want to get some rows of a table...
+            Command::GetTableRows((path, start, count)) => {
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    Some(packed_file) => match packed_file.decode_return_ref() {
+                        Ok(DecodedPackedFile::DB(table)) => CENTRAL_COMMAND.send_message_rust(Response::VecVecDecodedData(table.get_rows_paged(start, count))),
+                        Ok(DecodedPackedFile::Loc(table)) => CENTRAL_COMMAND.send_message_rust(Response::VecVecDecodedData(table.get_rows_paged(start, count))),
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileTypeUnknown.into())),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to get the amount of rows of a table...
+            Command::GetTableRowCount(path) => {
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    Some(packed_file) => match packed_file.decode_return_ref() {
+                        Ok(DecodedPackedFile::DB(table)) => CENTRAL_COMMAND.send_message_rust(Response::Usize(table.get_entry_count())),
+                        Ok(DecodedPackedFile::Loc(table)) => CENTRAL_COMMAND.send_message_rust(Response::Usize(table.get_entry_count())),
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileTypeUnknown.into())),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to replace some rows of a table...
+            Command::SetTableRows((path, start, rows)) => {
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    Some(packed_file) => {
+                        let result = match packed_file.decode_return_ref_mut() {
+                            Ok(DecodedPackedFile::DB(table)) => table.set_rows_paged(start, &rows),
+                            Ok(DecodedPackedFile::Loc(table)) => table.set_rows_paged(start, &rows),
+                            Ok(_) => Err(ErrorKind::PackedFileTypeUnknown.into()),
+                            Err(error) => Err(error),
+                        };
+
+                        match result {
+                            Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        }
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to decode a WsModel PackedFile...
+            Command::DecodePackedFileWsModel(path) => {
+                match pack_file_decoded.decode_ws_model(&path, &dependencies) {
+                    Ok(data) => CENTRAL_COMMAND.send_message_rust(Response::WsModelVecBool(data)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to check if the paths of our PackedFiles are valid for a game...
+            Command::ValidatePaths(game) => CENTRAL_COMMAND.send_message_rust(Response::VecVecStringString(pack_file_decoded.validate_paths(&game))),
+
+            // In case we want to explode the open PackFile into a folder...
+            Command::ExplodePackFile(path) => {
+                match pack_file_decoded.explode(&path) {
+                    Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to implode a PackFile from a folder...
+            Command::ImplodePackFile(path) => {
+                match PackFile::implode(&path) {
+                    Ok(pack_file) => {
+                        pack_file_decoded = pack_file;
+                        journal = Journal::default();
+                        CENTRAL_COMMAND.send_message_rust(Response::PackFileInfo(PackFileInfo::from(&pack_file_decoded)));
+                    }
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to preview the changes a TSV import would do to a DB Table...
+            Command::PreviewTSVImport((internal_path, external_path)) => {
+                let schema = SCHEMA.read().unwrap();
+                match *schema {
+                    Some(ref schema) => match pack_file_decoded.get_ref_mut_packed_file_by_path(&internal_path) {
+                        Some(packed_file) => match packed_file.decode_return_ref_no_locks(schema) {
+                            Ok(DecodedPackedFile::DB(data)) => match data.diff_tsv(&external_path) {
+                                Ok(diff) => CENTRAL_COMMAND.send_message_rust(Response::TableDiff(diff)),
+                                Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                            },
+                            Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::DBTableIsNotADBTable.into())),
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        },
+                        None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                    },
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::SchemaNotFound.into())),
+                }
+            }
+
+            // In case we want to get a row of a table by its key...
+            Command::GetRowByKey((table_name, key_values)) => CENTRAL_COMMAND.send_message_rust(Response::VecVecStringUsizeVecDecodedData(pack_file_decoded.get_rows_by_key(&table_name, &key_values))),
+
+            // In case we want to recover the changes lost in a crash from the journal of a PackFile...
+            Command::RecoverFromJournal(path) => {
+                match Journal::recover(&path, SETTINGS.read().unwrap().settings_bool["use_lazy_loading"]) {
+                    Ok((pack_file, recovered_journal)) => {
+                        pack_file_decoded = pack_file;
+                        journal = recovered_journal;
+
+                        // Force decoding of table/locs, so they're in memory for the diagnostics to work.
+                        if let Some(ref schema) = *SCHEMA.read().unwrap() {
+                            let mut packed_files = pack_file_decoded.get_ref_mut_packed_files_by_types(&[PackedFileType::DB, PackedFileType::Loc], false);
+                            packed_files.par_iter_mut().for_each(|x| {
+                                let _ = x.decode_no_locks(schema);
+                            });
+                        }
+
+                        CENTRAL_COMMAND.send_message_rust(Response::PackFileInfo(PackFileInfo::from(&pack_file_decoded)));
+                    }
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to export only the untranslated rows of a Loc...
+            Command::ExportLocFiltered((internal_path, external_path, only_untranslated, rule)) => {
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&internal_path) {
+                    Some(packed_file) => match packed_file.decode_return_ref() {
+                        Ok(DecodedPackedFile::Loc(data)) => match data.export_tsv_filtered(&external_path, only_untranslated, &rule) {
+                            Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        }
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::LocPackedFileIsNotALocPackedFile.into())),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to import a TSV with only some of the rows of a Loc...
+            Command::ImportLocPartialTSV((internal_path, external_path, map_columns_by_header)) => {
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&internal_path) {
+                    Some(packed_file) => match packed_file.decode_return_ref() {
+                        Ok(DecodedPackedFile::Loc(data)) => match data.import_tsv_partial(&external_path, map_columns_by_header) {
+                            Ok(data) => CENTRAL_COMMAND.send_message_rust(Response::TableType(TableType::Loc(data))),
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        }
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::LocPackedFileIsNotALocPackedFile.into())),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to save a PackFile, knowing how much its size changed...
+            Command::SavePackFileCompacted => {
+                match pack_file_decoded.save_compacted(None) {
+                    Ok((old_size, new_size)) => {
+                        let _ = journal.clear();
+                        journal = Journal::new(&pack_file_decoded);
+                        CENTRAL_COMMAND.send_message_rust(Response::PackFileInfoU64U64((From::from(&pack_file_decoded), old_size, new_size)));
+                    }
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(Error::from(ErrorKind::SavePackFileGeneric(error.to_string())))),
+                }
+            }
+
+            // In case we want to decode a UI Layout PackedFile...
+            Command::DecodePackedFileLayout(path) => {
+                match pack_file_decoded.decode_ui_layout(&path) {
+                    Ok(data) => CENTRAL_COMMAND.send_message_rust(Response::UiLayout(data)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to update all the tables of the PackFile to their latest definition...
+            Command::MigrateAllTablesToLatest => {
+                match *SCHEMA.read().unwrap() {
+                    Some(ref schema) => match pack_file_decoded.migrate_all_tables_to_latest(schema) {
+                        Ok(result) => {
+                            let mut entries = result.0.iter().map(|(path, old, new)| (path.join("/"), format!("update {} -> {}", old, new), "ok".to_owned())).collect::<Vec<_>>();
+                            entries.extend(result.1.iter().map(|(path, error)| (path.join("/"), "update".to_owned(), format!("error: {}", error))));
+                            log_batch_operation("MigrateAllTablesToLatest", &entries);
+                            CENTRAL_COMMAND.send_message_rust(Response::VecVecStringI32I32VecVecStringString(result));
+                        }
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::SchemaNotFound.into())),
+                }
+            }
+
+            // In case we want to know which rows reference a key...
+            Command::FindReferencingRows((table_name, key_value)) => {
+                match *SCHEMA.read().unwrap() {
+                    Some(ref schema) => CENTRAL_COMMAND.send_message_rust(Response::VecVecStringUsizeString(pack_file_decoded.find_referencing_rows(schema, &table_name, &key_value))),
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::SchemaNotFound.into())),
+                }
+            }
+
+            // In case we want to extract PackedFiles from a PackFile, with custom names...
+            Command::ExtractWithOptions((item_types, path, options)) => {
+                match pack_file_decoded.extract_packed_files_by_type_with_options(&item_types, &path, &options) {
+                    Ok(report) => CENTRAL_COMMAND.send_message_rust(Response::ExtractReport(report)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to fix the ordering columns of a table...
+            Command::RenumberOrderingColumns(path) => {
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    Some(packed_file) => match packed_file.decode_return_ref_mut() {
+                        Ok(DecodedPackedFile::DB(table)) => match table.renumber_ordering_columns() {
+                            Ok(columns) => CENTRAL_COMMAND.send_message_rust(Response::VecString(columns)),
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        }
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::DBTableIsNotADBTable.into())),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to decode a PackedFile from its path as a string...
+            Command::DecodePackedFileByStringPath(path) => {
+                let path = PackFile::path_from_string(&path);
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    Some(packed_file) => send_decoded_packed_file(packed_file),
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(Error::from(ErrorKind::PackedFileNotFound))),
+                }
+            }
+
+            // In case we want to get the column preferences of a table...
+            Command::GetTablePrefs((table_name, column_names)) => CENTRAL_COMMAND.send_message_rust(Response::TablePrefs(SETTINGS.read().unwrap().get_table_prefs(&table_name, &column_names))),
+
+            // In case we want to change the column preferences of a table...
+            Command::SetTablePrefs((table_name, table_prefs)) => match SETTINGS.write().unwrap().set_table_prefs(&table_name, &table_prefs) {
+                Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+            }
+
+            // In case we want to extract the PackedFiles modified after a date...
+            Command::ExtractFilesModifiedAfter((timestamp, path)) => {
+                match pack_file_decoded.get_files_modified_after(timestamp) {
+                    Ok(paths) => {
+                        let item_types = paths.iter().map(|x| PathType::File(x.to_vec())).collect::<Vec<PathType>>();
+                        if item_types.is_empty() {
+                            CENTRAL_COMMAND.send_message_rust(Response::VecVecString(paths));
+                        }
+                        else {
+                            match pack_file_decoded.extract_packed_files_by_type(&item_types, &path) {
+                                Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::VecVecString(paths)),
+                                Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                            }
+                        }
+                    }
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to convert a Loc PackedFile into a DB Table...
+            Command::ConvertLocToDB((path, table_name)) => {
+                let definition = match *SCHEMA.read().unwrap() {
+                    Some(ref schema) => schema.get_ref_last_definition_db(&table_name, &dependencies).map(|x| x.clone()),
+                    None => Err(ErrorKind::SchemaNotFound.into()),
+                };
+
+                match definition.and_then(|definition| pack_file_decoded.convert_loc_to_db(&path, &table_name, &definition)) {
+                    Ok(new_path) => CENTRAL_COMMAND.send_message_rust(Response::VecString(new_path)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to convert a DB Table into a Loc PackedFile...
+            Command::ConvertDBToLoc(path) => {
+                match pack_file_decoded.convert_db_to_loc(&path) {
+                    Ok(new_path) => CENTRAL_COMMAND.send_message_rust(Response::VecString(new_path)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to export a report of the schema...
+            Command::ExportSchemaReport((path, format)) => {
+                match *SCHEMA.read().unwrap() {
+                    Some(ref schema) => match schema.export_report(&path, format) {
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::SchemaNotFound.into())),
+                }
+            }
+
+            // In case we want to set a value in a column of the rows of a table matching a filter...
+            Command::SetColumnWhere((path, target_column, value, filter_column, op, filter_value)) => {
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    Some(packed_file) => match packed_file.decode_return_ref_mut() {
+                        Ok(DecodedPackedFile::DB(ref mut table)) => match table.set_column_where(target_column, value, filter_column, op, &filter_value) {
+                            Ok(changed_rows) => CENTRAL_COMMAND.send_message_rust(Response::Usize(changed_rows)),
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        }
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::DBTableIsNotADBTable.into())),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to know to which game the open PackFile belongs to...
+            Command::GuessPackFileGame => CENTRAL_COMMAND.send_message_rust(Response::VecString(pack_file_decoded.guess_game())),
+
+            // In case we want to check how many bytes of a DB Table its definition consumes...
+            Command::AnalyzeTableByteConsumption(path) => {
+                let result = match pack_file_decoded.get_ref_packed_file_by_path(&path) {
+                    Some(packed_file) => match packed_file.get_packed_file_type_by_path() {
+                        PackedFileType::DB => match *SCHEMA.read().unwrap() {
+                            Some(ref schema) => packed_file.get_raw_data()
+                                .and_then(|data| DB::get_definition_from_data(&data, &path[1], schema)
+                                .and_then(|definition| DB::analyze_byte_consumption(&data, &definition))),
+                            None => Err(ErrorKind::SchemaNotFound.into()),
+                        }
+                        _ => Err(ErrorKind::DBTableIsNotADBTable.into()),
+                    }
+                    None => Err(ErrorKind::PackedFileNotFound.into()),
+                };
+
+                match result {
+                    Ok(report) => CENTRAL_COMMAND.send_message_rust(Response::TableByteConsumption(report)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to export some rows of a DB Table as TSV...
+            Command::ExportTableRows((internal_path, external_path, rows)) => {
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&internal_path) {
+                    Some(packed_file) => match packed_file.decode_return_ref() {
+                        Ok(DecodedPackedFile::DB(data)) => match data.export_tsv_rows(&external_path, &internal_path[1], &rows) {
+                            Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        },
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::DBTableIsNotADBTable.into())),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to change the name and type of a field of a definition...
+            Command::UpdateDefinition((table_name, version, index, name, field_type, apply_to_all_versions)) => {
+                let result = match *SCHEMA.write().unwrap() {
+                    Some(ref mut schema) => schema.get_ref_mut_versioned_file_db(&table_name)
+                        .and_then(|versioned_file| versioned_file.update_field(version, index, &name, &field_type, apply_to_all_versions)),
+                    None => Err(ErrorKind::SchemaNotFound.into()),
+                };
+
+                match result {
+                    Ok(versions) => {
+
+                        // The data of the already decoded tables using these definitions may no longer match them, so we encode them back
+                        // with their old definition and let them be decoded again with the new one when needed.
+                        let mut error = None;
+                        for packed_file in pack_file_decoded.get_ref_mut_packed_files_by_type(PackedFileType::DB, false) {
+                            let is_affected = if let DecodedPackedFile::DB(ref table) = packed_file.get_ref_decoded() {
+                                table.get_ref_table_name() == table_name && versions.contains(&table.get_ref_definition().get_version())
+                            } else { false };
+
+                            if is_affected {
+                                if let Err(encode_error) = packed_file.encode_and_clean_cache() {
+                                    error = Some(encode_error);
+                                    break;
+                                }
+                            }
+                        }
+
+                        dependencies.invalidate_cached_data(&table_name);
+                        match error {
+                            Some(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                            None => CENTRAL_COMMAND.send_message_rust(Response::VecI32(versions)),
+                        }
+                    }
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to check the conflicts of the open PackFile with the other PackFiles of a load order...
+            Command::AnalyzeLoadOrderImpact(load_order) => match pack_file_decoded.analyze_load_order_impact(&load_order) {
+                Ok(report) => CENTRAL_COMMAND.send_message_rust(Response::LoadOrderImpactReport(report)),
+                Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+            }
+
+            // In case we want to import a TSV as a table using a stored column mapping...
+            Command::ImportTSVWithMapping((internal_path, external_path, mapping_name)) => {
+                let table_name = if internal_path.len() == 3 && internal_path[0] == "db" { internal_path[1].to_owned() } else { TSV_NAME_LOC.to_owned() };
+                let mapping = SETTINGS.read().unwrap().get_tsv_column_mapping(&table_name, &mapping_name);
+                match mapping {
+                    Some(mapping) => match pack_file_decoded.get_ref_mut_packed_file_by_path(&internal_path) {
+                        Some(packed_file) => match packed_file.decode_return_ref() {
+                            Ok(DecodedPackedFile::DB(data)) => match DB::import_tsv_with_mapping(&data.get_definition(), &external_path, &table_name, &mapping) {
+                                Ok((data, defaulted_fields)) => CENTRAL_COMMAND.send_message_rust(Response::TableTypeVecString((TableType::DB(data), defaulted_fields))),
+                                Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                            },
+                            Ok(DecodedPackedFile::Loc(data)) => match Loc::import_tsv_with_mapping(&data.get_definition(), &external_path, &table_name, &mapping) {
+                                Ok((data, defaulted_fields)) => CENTRAL_COMMAND.send_message_rust(Response::TableTypeVecString((TableType::Loc(data), defaulted_fields))),
+                                Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                            },
+                            Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::DBTableIsNotADBTable.into())),
+                            Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                        }
+                        None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::ImportTSVMappingNotFound(mapping_name).into())),
+                }
+            }
+
+            // In case we want to store or remove a column mapping for TSV files...
+            Command::SetTSVColumnMapping((table_name, mapping_name, mapping)) => match SETTINGS.write().unwrap().set_tsv_column_mapping(&table_name, &mapping_name, mapping.as_ref()) {
+                Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+            }
+
+            // In case we want to know the column mappings for TSV files stored for a table...
+            Command::GetTSVColumnMappingNames(table_name) => CENTRAL_COMMAND.send_message_rust(Response::VecString(SETTINGS.read().unwrap().get_tsv_column_mapping_names(&table_name))),
+
+            // In case we want to get a row of a table as a list of fields and values...
+            Command::GetRowVertical((path, row)) => {
+                let result = match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    Some(packed_file) => match packed_file.decode_return_ref() {
+                        Ok(DecodedPackedFile::DB(table)) => table.export_row_vertical(row),
+                        Ok(DecodedPackedFile::Loc(table)) => table.export_row_vertical(row),
+                        Ok(_) => Err(ErrorKind::DBTableIsNotADBTable.into()),
+                        Err(error) => Err(error),
+                    }
+                    None => Err(ErrorKind::PackedFileNotFound.into()),
+                };
+
+                match result {
+                    Ok(text) => CENTRAL_COMMAND.send_message_rust(Response::String(text)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to reload the schemas from disk, like after they have been updated by the network thread...
+            Command::ReloadSchemas => {
+                let game_selected = GAME_SELECTED.read().unwrap().to_owned();
+                pack_file_decoded.get_ref_mut_packed_files_by_type(PackedFileType::DB, false).par_iter_mut().for_each(|x| { let _ = x.encode_and_clean_cache(); });
+                *SCHEMA.write().unwrap() = Schema::load(&SUPPORTED_GAMES.get(&*game_selected).unwrap().schema).ok();
+                if let Some(ref schema) = *SCHEMA.read().unwrap() {
+                    pack_file_decoded.get_ref_mut_packed_files_by_type(PackedFileType::DB, false).par_iter_mut().for_each(|x| { let _ = x.decode_no_locks(&schema); });
+                }
+                dependencies.rebuild(pack_file_decoded.get_packfiles_list());
+                CENTRAL_COMMAND.send_message_rust(Response::Success);
+            }
+
+            // In case we want to get all the PackedFiles that fail to decode...
+            Command::DecodeHealthCheck => CENTRAL_COMMAND.send_message_rust(Response::VecVecStringString(pack_file_decoded.decode_health_check())),
+
+            // In case we want to export a PackFile in a version control friendly format...
+            Command::ExportForVersionControl(path) => {
+                match pack_file_decoded.export_for_version_control(&path) {
+                    Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to rebuild a PackFile exported for version control...
+            Command::ImportFromVersionControl(path) => {
+                match PackFile::import_from_version_control(&path) {
+                    Ok(pack_file) => {
+                        pack_file_decoded = pack_file;
+                        journal = Journal::default();
+                        CENTRAL_COMMAND.send_message_rust(Response::PackFileInfo(PackFileInfo::from(&pack_file_decoded)));
+                    }
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to get the tables that are behind the version of the same tables in the dependency database...
+            Command::FindOutdatedTableVersions => CENTRAL_COMMAND.send_message_rust(Response::VecVecStringI32I32(pack_file_decoded.find_outdated_table_versions(&dependencies))),
+
+            // In case we want to decompress only some PackedFiles of the PackFile...
+            Command::DecompressPaths(paths) => {
+                match pack_file_decoded.decompress_paths(&paths) {
+                    Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to walk the first row of a table with a list of field types...
+            Command::WalkTableRow((data, fields)) => {
+                match DB::walk_first_row(&data, &fields) {
+                    Ok(fields) => CENTRAL_COMMAND.send_message_rust(Response::VecFieldTypeRangeDecodedData(fields)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to get the loc keys a DB Table needs for its localised fields...
+            Command::GetExpectedLocKeys(path) => {
+                match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    Some(packed_file) => match packed_file.decode_return_ref() {
+                        Ok(DecodedPackedFile::DB(data)) => {
+                            let game_selected = GAME_SELECTED.read().unwrap().to_owned();
+                            let loc_keys = data.generate_expected_loc_keys(&SUPPORTED_GAMES.get(&*game_selected).unwrap().loc_key_rules);
+                            CENTRAL_COMMAND.send_message_rust(Response::VecString(loc_keys));
+                        }
+                        Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::DBTableIsNotADBTable.into())),
+                        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                    }
+                    None => CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::PackedFileNotFound.into())),
+                }
+            }
+
+            // In case we want to merge another schema into the loaded one. The merged schema is not saved to disk, that's up to the user.
+            Command::MergeSchema((path, strategy)) => {
+                let merged = match *SCHEMA.write().unwrap() {
+                    Some(ref mut schema) => match Schema::load_from_path(&path).and_then(|schema_other| {
+
+                        // The merge may replace definitions, so we need to re-decode the tables with the merged schema.
+                        pack_file_decoded.get_ref_mut_packed_files_by_type(PackedFileType::DB, false).par_iter_mut().for_each(|x| { let _ = x.encode_and_clean_cache(); });
+                        let report = schema.merge(&schema_other, strategy);
+                        pack_file_decoded.get_ref_mut_packed_files_by_type(PackedFileType::DB, false).par_iter_mut().for_each(|x| { let _ = x.decode_no_locks(&schema); });
+                        report
+                    }) {
+                        Ok(report) => {
+                            CENTRAL_COMMAND.send_message_rust(Response::MergeReport(report));
+                            true
+                        }
+                        Err(error) => {
+                            CENTRAL_COMMAND.send_message_rust(Response::Error(error));
+                            false
+                        }
+                    }
+                    None => {
+                        CENTRAL_COMMAND.send_message_rust(Response::Error(ErrorKind::SchemaNotFound.into()));
+                        false
+                    }
+                };
+
+                // The dependencies need the schema lock, so they can only be rebuilt once we have released it.
+                if merged {
+                    dependencies.rebuild(pack_file_decoded.get_packfiles_list());
+                }
+            }
+
+            // In case we want to generate a schema skeleton for the undecodable tables of the PackFile...
+            Command::GenerateSchemaSkeleton(path) => {
+                match pack_file_decoded.generate_schema_skeleton().and_then(|mut schema| schema.save_to_path(&path)) {
+                    Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to load PackedFiles to memory ahead of time...
+            Command::PrefetchPackedFiles(paths) => {
+                let generation = PREFETCH_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+                let memory_budget = SETTINGS.read().unwrap().get_prefetch_memory_budget();
+                let mut loaded_data_size = pack_file_decoded.get_loaded_data_size();
+                let sources = pack_file_decoded.get_prefetch_sources(&paths);
+
+                // The data is read in a worker and sent back to this thread to store it, so the commands sent
+                // meanwhile don't have to wait for the disk reads.
+                thread::spawn(move || {
+                    for (path, raw_on_disk) in sources {
+                        if PREFETCH_GENERATION.load(Ordering::SeqCst) != generation { break; }
+
+                        loaded_data_size += u64::from(raw_on_disk.get_size());
+                        if loaded_data_size > memory_budget { break; }
+
+                        if let Ok(data) = raw_on_disk.read() {
+                            CENTRAL_COMMAND.send_message_qt(Command::StorePrefetchedData((path, raw_on_disk, data)));
+                        }
+                    }
+                });
+            }
+
+            // In case the prefetch worker has data for us...
+            Command::StorePrefetchedData((path, raw_on_disk, data)) => {
+                if let Some(packed_file) = pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
+                    packed_file.load_prefetched_data(&raw_on_disk, data);
+                }
+            }
+
+            // In case we want to check if lazy loading gives us the same data as a full load...
+            Command::VerifyLazyLoadingConsistency(path) => {
+                match PackFile::verify_lazy_loading_consistency(&path) {
+                    Ok(mismatches) => CENTRAL_COMMAND.send_message_rust(Response::VecVecStringLazyLoadingMismatch(mismatches)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to export a DB Table with its references resolved...
+            Command::ExportTableResolved((internal_path, external_path, replace_values)) => {
+                let table = match pack_file_decoded.get_ref_mut_packed_file_by_path(&internal_path) {
+                    Some(packed_file) => match packed_file.decode_return_ref() {
+                        Ok(DecodedPackedFile::DB(table)) => Ok(table.clone()),
+                        Ok(_) => Err(ErrorKind::DBTableIsNotADBTable.into()),
+                        Err(error) => Err(error),
+                    }
+                    None => Err(ErrorKind::PackedFileNotFound.into()),
+                };
+
+                let result = table.and_then(|table| match *SCHEMA.read().unwrap() {
+                    Some(ref schema) => table.export_tsv_resolved(&external_path, &pack_file_decoded, &dependencies, schema, replace_values),
+                    None => Err(ErrorKind::SchemaNotFound.into()),
+                });
+
+                match result {
+                    Ok(_) => CENTRAL_COMMAND.send_message_rust(Response::Success),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // In case we want to lock/unlock the schema...
+            Command::SetSchemaLock(lock) => schema_locked = lock,
+            Command::IsSchemaLocked => CENTRAL_COMMAND.send_message_rust(Response::Bool(schema_locked)),
+
+            // In case we want to list the contents of a PackFile without opening it...
+            Command::ReadPackFileIndexOnly(path) => {
+                match PackFile::read_index_only(&path) {
+                    Ok(packed_files_info) => CENTRAL_COMMAND.send_message_rust(Response::VecPackedFileInfo(packed_files_info)),
+                    Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
+                }
+            }
+
+            // These are usually handled when sent, but just in case they reach this thread...
+            Command::PauseOperation => OPERATION_PAUSE.pause(),
+            Command::ResumeOperation => OPERATION_PAUSE.resume(),
+            Command::CancelPrefetch => { PREFETCH_GENERATION.fetch_add(1, Ordering::SeqCst); },
+
+            // These two belong to the network thread, not to this one!!!!
+            Command::CheckUpdates | Command::CheckSchemaUpdates | Command::CheckTemplateUpdates | Command::AutoUpdateSchemas => panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response),
+        }
+
+        // Journaling is best-effort. If it fails, we lose the ability to recover, not the changes themselves.
+        if record_in_journal {
+            let _ = journal.record_changes(&mut pack_file_decoded);
+        }
+    }
+}
+
+/// This function returns if the provided command can change the open PackFile or its PackedFiles, so we need to check it for changes to journal.
+fn is_journaled_command(command: &Command) -> bool {
+    match command {
+        Command::NewPackedFile(..) |
+        Command::AddPackedFiles(..) |
+        Command::AddPackedFilesFromFolder(..) |
+        Command::AddPackedFilesFromPackFile(..) |
+        Command::SavePackedFileFromView(..) |
+        Command::DeletePackedFiles(..) |
+        Command::RenamePackedFiles(..) |
+        Command::MassImportTSV(..) |
+        Command::MergeTables(..) |
+        Command::UpdateTable(..) |
+        Command::MigrateAllTablesToLatest |
+        Command::RenumberOrderingColumns(..) |
+        Command::ConvertLocToDB(..) |
+        Command::ConvertDBToLoc(..) |
+        Command::SetColumnWhere(..) |
+        Command::GlobalSearchReplaceMatches(..) |
+        Command::GlobalSearchReplaceAll(..) |
+        Command::GlobalSearchReplaceAllInPaths(..) |
+        Command::SetCaVp8Format(..) |
+        Command::SavePackedFileFromExternalView(..) |
+        Command::AnimPackUnpack(..) |
+        Command::GenerateDummyAnimPack |
+        Command::ApplyTemplate(..) |
+        Command::OptimizePackFile |
+        Command::PatchSiegeAI |
+        Command::NormalizePathCasing |
+        Command::AddImageAsDDS(..) |
+        Command::ReplaceAssetPathReferences(..) |
+        Command::DeletePackedFilesChecked(..) |
+        Command::ImportMultiLangLoc(..) |
+        Command::ConvertColumnStringOptionality(..) |
+        Command::DeduplicateFiles |
+        Command::ApplyPatch(..) |
+        Command::RevertPackedFile(..) |
+        Command::ImportPackedFileWithDefinition(..) |
+        Command::SetTableRows(..) |
+        Command::SetRowComment(..) |
+        Command::ImportNotes(..) |
+        Command::SetPackedFileOrder(..) |
+        Command::SetPackFileFlag(..) |
+        Command::SetPackFileType(..) |
+        Command::ChangeDataIsCompressed(..) |
+        Command::DecompressPaths(..) |
+        Command::ConvertPackFileVersion(..) |
+        Command::ChangeIndexIncludesTimestamp(..) |
+        Command::SetDependencyPackFilesList(..) |
+        Command::SetPackFileSettings(..) => true,
+        _ => false,
+    }
+}
+
+/// This function returns if the provided command can change the loaded schema, so we can reject it if the schema is locked.
+fn is_schema_changing_command(command: &Command) -> bool {
+    match command {
+        Command::SaveSchema(..) |
+        Command::ImportDefinition(..) |
+        Command::DeleteDefinitionVersion(..) |
+        Command::SetDefinitionKeyField(..) |
+        Command::SetDefinitionEditableField(..) |
+        Command::UpdateDefinition(..) |
+        Command::MergeSchema(..) |
+        Command::UpdateSchemas |
+        Command::ReloadSchemas |
+        Command::UpdateCurrentSchemaFromAssKit(..) => true,
+        _ => false,
+    }
+}
+
+/// This function appends the provided entries to the batch operations log in RPFM's folder, if logging them is enabled.
+///
+/// Each entry is the path of the item processed, the action done over it, and its outcome. All of them are written with the same timestamp.
+fn log_batch_operation(operation: &str, entries: &[(String, String, String)]) {
+    if !*SETTINGS.read().unwrap().settings_bool.get("enable_batch_operation_logs").unwrap() { return; }
+
+    // Write everything in one go, so logs from different operations don't get mixed.
+    let timestamp = Local::now().to_rfc3339();
+    let log = entries.iter().map(|(path, action, outcome)| format!("{}\t{}\t{}\t{}\t{}\n", timestamp, operation, path, action, outcome)).collect::<String>();
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(RPFM_PATH.join(BATCH_OPERATIONS_LOG)) {
+        let _ = file.write_all(log.as_bytes());
+    }
+}
+
+/// This function decodes the provided PackedFile, and sends it back to the UI with the response corresponding to its type.
+fn send_decoded_packed_file(packed_file: &mut PackedFile) {
+    match packed_file.decode_return_ref() {
+        Ok(packed_file_data) => {
+            match packed_file_data {
+                DecodedPackedFile::AnimFragment(data) => CENTRAL_COMMAND.send_message_rust(Response::AnimFragmentPackedFileInfo((data.clone(), From::from(&*packed_file)))),
+                DecodedPackedFile::AnimPack(data) => CENTRAL_COMMAND.send_message_rust(Response::AnimPackPackedFileInfo((data.get_file_list(), From::from(&*packed_file)))),
+                DecodedPackedFile::AnimTable(data) => CENTRAL_COMMAND.send_message_rust(Response::AnimTablePackedFileInfo((data.clone(), From::from(&*packed_file)))),
+                DecodedPackedFile::CaVp8(data) => CENTRAL_COMMAND.send_message_rust(Response::CaVp8PackedFileInfo((data.clone(), From::from(&*packed_file)))),
+                DecodedPackedFile::DB(table) => CENTRAL_COMMAND.send_message_rust(Response::DBPackedFileInfo((table.clone(), From::from(&*packed_file)))),
+                DecodedPackedFile::Image(image) => CENTRAL_COMMAND.send_message_rust(Response::ImagePackedFileInfo((image.clone(), From::from(&*packed_file)))),
+                DecodedPackedFile::Loc(table) => CENTRAL_COMMAND.send_message_rust(Response::LocPackedFileInfo((table.clone(), From::from(&*packed_file)))),
+                DecodedPackedFile::MatchedCombat(data) => CENTRAL_COMMAND.send_message_rust(Response::MatchedCombatPackedFileInfo((data.clone(), From::from(&*packed_file)))),
+                DecodedPackedFile::RigidModel(rigid_model) => CENTRAL_COMMAND.send_message_rust(Response::RigidModelPackedFileInfo((rigid_model.clone(), From::from(&*packed_file)))),
+                DecodedPackedFile::Text(text) => CENTRAL_COMMAND.send_message_rust(Response::TextPackedFileInfo((text.clone(), From::from(&*packed_file)))),
+                _ => CENTRAL_COMMAND.send_message_rust(Response::Unknown),
+
+            }
         }
+        Err(error) => CENTRAL_COMMAND.send_message_rust(Response::Error(error)),
     }
 }
@@ -17,26 +17,32 @@ use qt_core::QEventLoop;
 use crossbeam::channel::{Receiver, Sender, unbounded};
 
 use std::collections::BTreeMap;
+use std::ops::Range;
 use std::path::PathBuf;
 use std::process::exit;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use rpfm_error::Error;
 
+use rpfm_lib::dependencies::MissingTablesReport;
 use rpfm_lib::diagnostics::Diagnostics;
 use rpfm_lib::global_search::GlobalSearch;
 use rpfm_lib::global_search::MatchHolder;
 use rpfm_lib::packedfile::ca_vp8::{CaVp8, SupportedFormats};
-use rpfm_lib::packedfile::DecodedPackedFile;
-use rpfm_lib::packedfile::image::Image;
-use rpfm_lib::packedfile::table::{DependencyData, anim_fragment::AnimFragment, animtable::AnimTable, db::DB, loc::Loc, matched_combat::MatchedCombat};
-use rpfm_lib::packedfile::text::Text;
+use rpfm_lib::packedfile::{DecodedPackedFile, PackedFileType};
+use rpfm_lib::packedfile::image::{DdsFormat, Image};
+use rpfm_lib::packedfile::table::{DecodedData, DependencyData, TableDiff, anim_fragment::AnimFragment, animtable::AnimTable, db::{CompareOp, DB, TableByteConsumption}, loc::{Loc, UntranslatedRule}, matched_combat::MatchedCombat};
+use rpfm_lib::packedfile::text::{Text, TextType};
+use rpfm_lib::packedfile::uilayout::UiLayout;
+use rpfm_lib::packedfile::wsmodel::WsModel;
 use rpfm_lib::packedfile::rigidmodel::RigidModel;
-use rpfm_lib::packfile::{PackFileInfo, PackFileSettings, PathType, PFHFileType};
-use rpfm_lib::packfile::packedfile::{PackedFile, PackedFileInfo};
-use rpfm_lib::schema::{APIResponseSchema, Definition, Schema};
+use rpfm_lib::packfile::{ExtractOptions, ExtractReport, LazyLoadingMismatch, LoadOrderImpactReport, OptimizeReport, PackFileInfo, PackFileSettings, PathType, PFHFileType, PFHFlags, PFHVersion, RecoveryReport, Reference, RoundTripIssue, VmdIssue};
+use rpfm_lib::packfile::packedfile::{PackedFile, PackedFileInfo, RawOnDisk};
+use rpfm_lib::schema::{APIResponseSchema, Definition, FieldType, MergeReport, MergeStrategy, ReferenceCycle, ReportFormat, Schema, SchemaAutoUpdateReport, SchemaComparison};
 use rpfm_lib::settings::*;
 use rpfm_lib::template::Template;
 use rpfm_lib::updater::APIResponse;
+use rpfm_lib::OPERATION_PAUSE;
 
 use crate::app_ui::NewPackedFile;
 use crate::views::table::TableType;
@@ -46,6 +52,9 @@ use crate::ui_state::shortcuts::Shortcuts;
 pub const THREADS_COMMUNICATION_ERROR: &str = "Error in thread communication system. Response received: ";
 pub const THREADS_SENDER_ERROR: &str = "Error in thread communication system. Sender failed to send message.";
 
+/// Generation of the current prefetch. Prefetch workers stop as soon as it changes, so increasing it cancels the running prefetch.
+pub static PREFETCH_GENERATION: AtomicU64 = AtomicU64::new(0);
+
 //-------------------------------------------------------------------------------//
 //                              Enums & Structs
 //-------------------------------------------------------------------------------//
@@ -95,6 +104,10 @@ pub enum Command {
     /// This command is used when we want to save our currently open `PackFile` as another `PackFile`.
     SavePackFileAs(PathBuf),
 
+    /// This command is used when we want to install our currently open `PackFile` in the `/data` folder of the Game Selected.
+    /// It's sent after a successful save if the `install_after_save` setting is enabled, so failing to install doesn't fail the save.
+    InstallPackFile,
+
     /// This command is used when we want to save our settings to disk. It requires the settings to save.
     SetSettings(Settings),
 
@@ -234,7 +247,8 @@ pub enum Command {
     GlobalSearchReplaceAll(GlobalSearch),
 
     /// This command is used when we want to add entire folders to the PackFile. The tuples contains their path in disk and their starting path in the PackFile.
-    AddPackedFilesFromFolder(Vec<(PathBuf, Vec<String>)>),
+    /// The bool is for keeping the last modified time of the files as their timestamps.
+    AddPackedFilesFromFolder((Vec<(PathBuf, Vec<String>)>, bool)),
 
     /// This command is used to decode all tables referenced by columns in the provided definition and return their data.
     /// It requires the table name, the definition of the table to get the reference data from and the list of PackedFiles to ignore.
@@ -261,8 +275,8 @@ pub enum Command {
     /// This command is used to export a table as TSV. Requires the internal and destination paths for the PackedFile.
     ExportTSV((Vec<String>, PathBuf)),
 
-    /// This command is used to import a TSV as a table. Requires the internal and destination paths for the PackedFile.
-    ImportTSV((Vec<String>, PathBuf)),
+    /// This command is used to import a TSV as a table. Requires the internal and destination paths for the PackedFile, and if the columns should be mapped by their headers.
+    ImportTSV((Vec<String>, PathBuf, bool)),
 
     /// This command is used to open in the defaul file manager the folder of the currently open PackFile.
     OpenContainingFolder,
@@ -328,7 +342,369 @@ pub enum Command {
     GetMissingDefinitions,
 
     /// This command is used to rebuild the dependencies of a PackFile.
-    RebuildDependencies
+    RebuildDependencies,
+
+    /// This command is used to lowercase the structural folders of the PackedFiles with path-casing issues in the open PackFile.
+    NormalizePathCasing,
+
+    /// This command is used to export the definition of a specific version of a DB Table to a json file. It contains the table name, the version and the destination path.
+    ExportDefinition(String, i32, PathBuf),
+
+    /// This command is used to import a definition from a json file into the currently loaded schema. It contains the source path, and if we allow overwriting existing definitions.
+    ImportDefinition(PathBuf, bool),
+
+    /// This command is used to delete a version of a definition from the currently loaded schema, without saving it to disk.
+    /// It contains the type of the PackedFile, the name of the table (only used for DB Tables), and the version to delete.
+    DeleteDefinitionVersion((PackedFileType, String, i32)),
+
+    /// This command is used to get the size (compressed and uncompressed) and amount of files of every folder in the open PackFile.
+    GetSizeSummary,
+
+    /// This command is used to check for broken model/texture references in the variantmeshdefinitions of the open PackFile.
+    ValidateVariantMeshes,
+
+    /// This command is used to check if all the decodable PackedFiles of the open PackFile are re-encoded without changes.
+    VerifyRoundTrip,
+
+    /// This command is used to add an image from disk to the open PackFile, converted to DDS. It contains the source path, the destination path,
+    /// the DDS format to use and if we want mipmaps generated.
+    AddImageAsDDS((PathBuf, Vec<String>, DdsFormat, bool)),
+
+    /// This command is used to export an image PackedFile as PNG to a temporal file, and open it in the default image viewer.
+    ExportImageAsPNG(Vec<String>),
+
+    /// This command is used to get the referenced tables the open PackFile needs, but cannot be found anywhere.
+    GetMissingDependencyTables,
+
+    /// This command is used to compare the tables of two schema files. It contains the paths of the old and the new schemas.
+    CompareSchemas(PathBuf, PathBuf),
+
+    /// This command is used to get the comments of all the rows of a DB Table, in the same order as its rows.
+    GetRowComments(Vec<String>),
+
+    /// This command is used to set the comment of a row of a DB Table. It contains the path of the table, the row, and the comment. An empty comment removes it.
+    SetRowComment(Vec<String>, usize, String),
+
+    /// This command is used to replace all the references to an asset path in DB Tables and Text files. It contains the old and the new path.
+    ReplaceAssetPathReferences((String, String)),
+
+    /// This command is used to export the list of PackedFiles of the open PackFile, with their types and sizes, to a CSV file.
+    ExportFileListCSV(PathBuf),
+
+    /// This command is used to mark/unmark a field of a DB Definition as key in the currently loaded schema. It contains the table name, the version, the field index and if it should be a key.
+    SetDefinitionKeyField((String, i32, usize, bool)),
+
+    /// This command is used to get a range of bytes from the data of a PackedFile. It contains the path of the PackedFile, the offset, and the amount of bytes to get.
+    GetPackedFileBytesRange((Vec<String>, u64, usize)),
+
+    /// This command is used to get the paths of the PackedFiles of the open PackFile, in the order they'll be saved.
+    GetPackedFileOrder,
+
+    /// This command is used to set the order the PackedFiles of the open PackFile will be saved in. It must contain all their paths.
+    SetPackedFileOrder(Vec<Vec<String>>),
+
+    /// This command is used to export a DB Table to a SQLite database. It contains the path of the table, and the path of the database.
+    ExportTableToSQLite((Vec<String>, PathBuf)),
+
+    /// This command is used to export all the DB Tables of the open PackFile to a single SQLite database. It contains the path of the database.
+    ExportAllTablesToSQLite(PathBuf),
+
+    /// This command is used to delete one or more PackedFiles from a PackFile, but only if nothing references them. It contains the PathType of each PackedFile to delete, and if we should delete them even if they're referenced.
+    DeletePackedFilesChecked(Vec<PathType>, bool),
+
+    /// This command is used to import a language from a multi-language TSV into the Loc PackedFiles of the open PackFile. It contains the path of the TSV, and the name of the language column.
+    ImportMultiLangLoc((PathBuf, String)),
+
+    /// This command is used to check if the open PackFile has been modified since it was loaded or last saved.
+    IsPackFileModified,
+
+    /// This command is used to get all the distinct values of a column of a DB Table, from both the open PackFile and the dependencies. It contains the name of the table and the name of the column.
+    GetDistinctValues((String, String)),
+
+    /// This command is used to check if the dependency PackFiles of the open PackFile exist in the `/data` folder of the Game Selected.
+    CheckDependencyPackFilesExist,
+
+    /// This command is used to convert a string column of a DB Table between its required and optional variants. It contains the path of the table, the column, and if it should become optional.
+    ConvertColumnStringOptionality((Vec<String>, usize, bool)),
+
+    /// This command is used to decode a PackedFile from one of the extra PackFiles. It contains the path of the extra PackFile, and the path of the PackedFile.
+    DecodePackedFileFromExtra((PathBuf, Vec<String>)),
+
+    /// This command is used to launch a global search over one of the extra PackFiles. It contains the path of the extra PackFile, and the search.
+    GlobalSearchExtra((PathBuf, GlobalSearch)),
+
+    /// This command is used when we try to save an edited `PackedFile` back to one of the extra PackFiles. As they're read-only, it always fails.
+    SavePackedFileFromViewExtra((PathBuf, Vec<String>, DecodedPackedFile)),
+
+    /// This command is used to find the groups of byte-identical PackedFiles in the open PackFile.
+    FindDuplicateFiles,
+
+    /// This command is used to remove all but one PackedFile of each group of byte-identical PackedFiles in the open PackFile.
+    DeduplicateFiles,
+
+    /// This command is used to export a DB Table as a Markdown table. It contains the path of the table, the path of the Markdown file, and the max amount of rows to export, if any.
+    ExportTableAsMarkdown((Vec<String>, PathBuf, Option<usize>)),
+
+    /// This command is used to compare the DB Tables in the Schema with the ones in the dependency database.
+    GetSchemaVsDependencyCoverage,
+
+    /// This command is used to generate a patch file with the changes done to the DB Tables of the open PackFile over the ones of another PackFile. It contains the path of the base PackFile, and the path of the patch file.
+    GeneratePatch((PathBuf, PathBuf)),
+
+    /// This command is used to apply a patch file to the DB Tables of the open PackFile. It contains the path of the patch file, and if rows with conflicts should be skipped.
+    ApplyPatch((PathBuf, bool)),
+
+    /// This command is used to get the list of recently opened PackFiles.
+    GetRecentFiles,
+
+    /// This command is used to add a PackFile to the list of recently opened PackFiles. It contains the path of the PackFile.
+    AddRecentFile(PathBuf),
+
+    /// This command is used to decode raw DB Table data with the definition of the loaded schema for a specific version. It contains the data, the table name and the version.
+    DecodeRawTable((Vec<u8>, String, i32)),
+
+    /// This command is used to discard all the changes done to a PackedFile, restoring the data it has in the PackFile on disk. It contains the path of the PackedFile.
+    RevertPackedFile(Vec<String>),
+
+    /// This command is used to export the notes of the currently open PackFile to a file. It contains the destination path.
+    ExportNotes(PathBuf),
+
+    /// This command is used to import the notes of the currently open PackFile from a file. It contains the source path, and if the notes should be appended to the current ones instead of replacing them.
+    ImportNotes((PathBuf, bool)),
+
+    /// This command is used to mark/unmark a field of a DB Definition as editable in the currently loaded schema. It contains the table name, the version, the field index and if it should be editable.
+    SetDefinitionEditableField((String, i32, usize, bool)),
+
+    /// This command is used to enable/disable, for the current session, the rejection of edits to non-editable columns when saving tables from their views.
+    SetNonEditableColumnsProtection(bool),
+
+    /// This command is used to get the PackFiles containing a path, sorted by priority following the game's override rules. It contains the path.
+    GetOverrideChain(Vec<String>),
+
+    /// This command is used to find the rows of a DB Table whose value in a column satisfies a comparison. It contains the path of the table, the column, the comparison and the value to compare against.
+    FindTableRows((Vec<String>, usize, CompareOp, DecodedData)),
+
+    /// This command is used to get the definition used to decode a DB Table, using the version in its header. It contains the path of the table.
+    GetDefinitionForPath(Vec<String>),
+
+    /// This command is used to import a plain-text key-value file as a Loc PackedFile. It contains the internal path of the Loc PackedFile, the path of the file and the delimiter.
+    ImportLocFromKeyValue((Vec<String>, PathBuf, char)),
+
+    /// This command is used to get the amount of entries of each DB Table in the currently open PackFile, by table name.
+    GetEntryCountsByTable,
+
+    /// This command is used to convert the currently open PackFile to another `PFHVersion`. It contains the new version.
+    ConvertPackFileVersion(PFHVersion),
+
+    /// This command is used when we want to replace all matches of a Global Search, but only on the `PackedFiles` under the provided paths.
+    GlobalSearchReplaceAllInPaths((GlobalSearch, Vec<Vec<String>>)),
+
+    /// This command is used when we want to get the reference cycles between the tables of the currently loaded Schema.
+    DetectReferenceCycles,
+
+    /// This command is used when we want to export the raw data of a table PackedFile together with the definition needed to decode it to a folder.
+    ExportPackedFileWithDefinition((Vec<String>, PathBuf)),
+
+    /// This command is used when we want to import a table PackedFile exported together with its definition, decoding it with that definition.
+    ImportPackedFileWithDefinition(PathBuf),
+
+    /// This command is used when we want to enable or disable an individual flag of the bitmask of the currently open PackFile.
+    SetPackFileFlag((PFHFlags, bool)),
+
+    /// This command is used when we want to get the name and state of every flag of the bitmask of the currently open PackFile.
+    GetPackFileFlags,
+
+    /// This command is used when we want to get the paths of all the Text PackedFiles in the currently open PackFile, with their language.
+    ListTextFiles,
+
+    /// This command is used when we want to get some rows of a table. It contains the path of the table, the first row and the amount of rows.
+    GetTableRows((Vec<String>, usize, usize)),
+
+    /// This command is used when we want to get the amount of rows of a table.
+    GetTableRowCount(Vec<String>),
+
+    /// This command is used when we want to replace some rows of a table. It contains the path of the table, the first row and the new rows.
+    SetTableRows((Vec<String>, usize, Vec<Vec<DecodedData>>)),
+
+    /// This command is used when we want to decode a WsModel PackedFile, with the list of which of its references exist.
+    DecodePackedFileWsModel(Vec<String>),
+
+    /// This command is used to check the paths of all PackedFiles of the open PackFile against the path rules of the provided game.
+    ValidatePaths(String),
+
+    /// This command is used to explode the open PackFile into the provided folder.
+    ExplodePackFile(PathBuf),
+
+    /// This command is used to implode a PackFile from the provided folder, and open it.
+    ImplodePackFile(PathBuf),
+
+    /// This command is used to get the differences between a DB Table of the open PackFile and a TSV file, without importing it.
+    /// It contains the path of the table and the path of the TSV file.
+    PreviewTSVImport((Vec<String>, PathBuf)),
+
+    /// This command is used to search all the DB Tables of a table in the open PackFile for rows with the provided key values.
+    GetRowByKey((String, Vec<String>)),
+
+    /// This command is used to open the PackFile in the provided path, and recover the changes lost in a crash from its journal.
+    RecoverFromJournal(PathBuf),
+
+    /// This command is used to export to TSV only the untranslated (or translated) rows of a Loc PackedFile. It contains the path of the Loc,
+    /// the path of the TSV, if we want the untranslated rows, and the rule to decide if a row is untranslated.
+    ExportLocFiltered((Vec<String>, PathBuf, bool, UntranslatedRule)),
+
+    /// This command is used to import a TSV with only some of the rows of a Loc PackedFile, leaving the rest of the rows intact.
+    /// It contains the path of the Loc, the path of the TSV, and if we want to map the columns by their headers.
+    ImportLocPartialTSV((Vec<String>, PathBuf, bool)),
+
+    /// This command is used to save the open PackFile reporting the size it had before and after saving it.
+    SavePackFileCompacted,
+
+    /// This command is used when we want to decode a UI Layout PackedFile as a tree of elements.
+    DecodePackedFileLayout(Vec<String>),
+
+    /// This command is used when we want to update all the DB Tables of the PackFile to their latest definition in the Schema.
+    MigrateAllTablesToLatest,
+
+    /// This command is used when we want to open a PackFile from an URL. The download progress is reported through the notification channel.
+    OpenPackFileFromURL(String),
+
+    /// This command is used to find all the rows in the PackFile referencing a key. It contains the name of the referenced table and the value of the key.
+    FindReferencingRows((String, String)),
+
+    /// This command is used when we want to extract one or more PackedFiles from a PackFile, naming them according to the provided options.
+    ExtractWithOptions((Vec<PathType>, PathBuf, ExtractOptions)),
+
+    /// This command is used to rewrite the ordering columns of a DB Table to a clean sequence. It contains the path of the table.
+    RenumberOrderingColumns(Vec<String>),
+
+    /// This command is used to decode a PackedFile from its path in string form, like `db/foo_tables/data`.
+    /// It returns the same responses as `DecodePackedFile`: `Response::Unknown` if the PackedFile type is not supported, and an error if the PackedFile is not found.
+    DecodePackedFileByStringPath(String),
+
+    /// This command is used to get the column preferences of a table. It contains the name of the table and the names of its current columns.
+    GetTablePrefs((String, Vec<String>)),
+
+    /// This command is used to set the column preferences of a table. It contains the name of the table and its preferences.
+    SetTablePrefs((String, TablePrefs)),
+
+    /// This command is used to open a PackFile, salvaging as much as possible from it if it's broken.
+    OpenPackFileRecovery(PathBuf),
+
+    /// This command is used to extract all the PackedFiles modified after a timestamp. It contains the timestamp and the destination folder.
+    ExtractFilesModifiedAfter((i64, PathBuf)),
+
+    /// This command is used to convert a Loc PackedFile into a DB Table, replacing it. It contains the path of the Loc and the name of the table.
+    ConvertLocToDB((Vec<String>, String)),
+
+    /// This command is used to convert a DB Table into a Loc PackedFile, replacing it. It contains the path of the table.
+    ConvertDBToLoc(Vec<String>),
+
+    /// This command is used to export a human-readable report of the currently loaded schema. It contains the destination path and the format of the report.
+    ExportSchemaReport((PathBuf, ReportFormat)),
+
+    /// This command is used to set a value in a column of the rows of a DB Table matching a filter. It contains the path of the table,
+    /// the target column, the value, the filter column, the comparison and the value to compare against.
+    SetColumnWhere((Vec<String>, usize, DecodedData, usize, CompareOp, DecodedData)),
+
+    /// This command is used to get the keys of the games the currently open PackFile may belong to, from the most to the least likely.
+    GuessPackFileGame,
+
+    /// This command is used to create a new PackFile in memory from all the files in a folder. It contains the path of the folder and the key of the game.
+    CreatePackFileFromFolder((PathBuf, String)),
+
+    /// This command is used to check how many bytes of a DB Table the definition used to decode it consumes. It contains the path of the table.
+    AnalyzeTableByteConsumption(Vec<String>),
+
+    /// This command is used to pause the long operation running in the background thread, at the next point where it can be safely paused.
+    ///
+    /// As the background thread is busy while running the operation, this one is handled when sent, without going through the background thread.
+    PauseOperation,
+
+    /// This command is used to resume the paused operation, from where it was paused. Like `PauseOperation`, it's handled when sent.
+    ResumeOperation,
+
+    /// This command is used to export some rows of a DB Table as TSV. It contains the internal path of the table, the destination path and the rows to export, in order.
+    ExportTableRows((Vec<String>, PathBuf, Vec<usize>)),
+
+    /// This command is used to change the name and type of a field of a DB Definition in the currently loaded schema. It contains the table name, the version,
+    /// the field index, the new name, the new type and if the change should be done too in the other versions with a field with the same name.
+    UpdateDefinition((String, i32, usize, String, FieldType, bool)),
+
+    /// This command is used to check which PackedFiles of the currently open PackFile override or are overridden by the ones in the other PackFiles
+    /// of a load order. It contains the names of the PackFiles in the load order, including the open one.
+    AnalyzeLoadOrderImpact(Vec<String>),
+
+    /// This command is used to import a TSV as a table, using a stored column mapping. Requires the internal and destination paths for the PackedFile, and the name of the mapping.
+    ImportTSVWithMapping((Vec<String>, PathBuf, String)),
+
+    /// This command is used to store a column mapping for TSV files of a table. It contains the table name, the mapping name and the mapping. Without mapping, it's removed.
+    SetTSVColumnMapping((String, String, Option<BTreeMap<String, String>>)),
+
+    /// This command is used to get the names of the stored column mappings for TSV files of a table. It contains the table name.
+    GetTSVColumnMappingNames(String),
+
+    /// This command is used to get a row of a DB or Loc Table as a list of `field: value` lines. It contains the path of the table and the row.
+    GetRowVertical((Vec<String>, usize)),
+
+    /// This command is used when we want to check for schema updates and, if there is one, download it without asking. It's handled by the network thread.
+    AutoUpdateSchemas,
+
+    /// This command is used when we want to reload the schemas from disk, and rebuild the cache and dependencies with them.
+    ReloadSchemas,
+
+    /// This command is used when we want to try to decode every decodable PackedFile in the PackFile, to get the ones that fail to decode.
+    DecodeHealthCheck,
+
+    /// This command is used to export the open PackFile into the provided folder in a version control friendly format, with tables as TSV.
+    ExportForVersionControl(PathBuf),
+
+    /// This command is used to rebuild a PackFile exported for version control from the provided folder, and open it.
+    ImportFromVersionControl(PathBuf),
+
+    /// This command is used to get the DB Tables of the open PackFile with a version older than the one of the same table in the dependency database.
+    FindOutdatedTableVersions,
+
+    /// This command is used to decompress only the PackedFiles with the provided paths, leaving the rest of the PackFile as it is.
+    DecompressPaths(Vec<Vec<String>>),
+
+    /// This command is used to decode the first row of the provided table data field by field, with the provided field types.
+    WalkTableRow((Vec<u8>, Vec<FieldType>)),
+
+    /// This command is used to get the loc keys the game expects for the localised fields of the DB Table with the provided path.
+    GetExpectedLocKeys(Vec<String>),
+
+    /// This command is used to merge the schema in the provided path into the currently loaded one, resolving conflicts with the provided strategy.
+    MergeSchema((PathBuf, MergeStrategy)),
+
+    /// This command is used to generate a schema with empty definitions for the undecodable tables of the open PackFile, and save it to the provided path.
+    GenerateSchemaSkeleton(PathBuf),
+
+    /// This command is used to load to memory, in a worker thread, the data of the provided PackedFiles of a lazy-loaded PackFile.
+    ///
+    /// It cancels any prefetch already running, and stops once the loaded data exceeds the memory budget in the settings.
+    PrefetchPackedFiles(Vec<Vec<String>>),
+
+    /// This command is used to cancel the running prefetch. Like `PauseOperation`, it's handled when sent.
+    CancelPrefetch,
+
+    /// This command is used by the prefetch worker to store in the open PackFile the data it read from the provided location on disk.
+    StorePrefetchedData((Vec<String>, RawOnDisk, Vec<u8>)),
+
+    /// This command is used to check if the PackFile in the provided path loads the same data with and without lazy loading.
+    VerifyLazyLoadingConsistency(PathBuf),
+
+    /// This command is used to export the DB Table with the provided path to a TSV file with its references resolved, for documentation.
+    /// If the bool is true, resolved values replace the referenced ones instead of going in their own columns.
+    ExportTableResolved((Vec<String>, PathBuf, bool)),
+
+    /// This command is used to lock/unlock, for the current session, the loaded Schema. While locked, commands that change it fail.
+    SetSchemaLock(bool),
+
+    /// This command is used when we want to know if the loaded Schema is locked.
+    IsSchemaLocked,
+
+    /// This command is used to get the info of the PackedFiles of the PackFile in the provided path, reading only its index.
+    ReadPackFileIndexOnly(PathBuf),
 }
 
 /// This enum defines the responses (messages) you can send to the to the UI thread as result of a command.
@@ -447,12 +823,162 @@ pub enum Response {
 
     /// Response to return `Vec<Definition>`.
     VecDefinition(Vec<Definition>),
+
+    /// Response to return `Vec<(Vec<String>, Vec<String>)>`.
+    VecVecStringVecString(Vec<(Vec<String>, Vec<String>)>),
+
+    /// Response to return `(String, i32)`.
+    StringI32((String, i32)),
+
+    /// Response to return `BTreeMap<Vec<String>, (u64, u64, usize)>`.
+    BTreeMapVecStringU64U64Usize(BTreeMap<Vec<String>, (u64, u64, usize)>),
+
+    /// Response to return `Vec<VmdIssue>`.
+    VecVmdIssue(Vec<VmdIssue>),
+
+    /// Response to return `Vec<(Vec<String>, RoundTripIssue)>`.
+    VecVecStringRoundTripIssue(Vec<(Vec<String>, RoundTripIssue)>),
+
+    /// Response to return `MissingTablesReport`.
+    MissingTablesReport(MissingTablesReport),
+
+    /// Response to return `SchemaComparison`.
+    SchemaComparison(SchemaComparison),
+
+    /// Response to return `Vec<Option<String>>`.
+    VecOptionString(Vec<Option<String>>),
+
+    /// Response to return `Vec<(Vec<String>, Vec<usize>)>`.
+    VecVecStringVecUsize(Vec<(Vec<String>, Vec<usize>)>),
+
+    /// Response to return `Vec<u8>`.
+    VecU8(Vec<u8>),
+
+    /// Response to return `(Vec<PathType>, Vec<Reference>)`.
+    VecPathTypeVecReference((Vec<PathType>, Vec<Reference>)),
+
+    /// Response to return `(Vec<String>, Vec<String>)`.
+    VecStringVecString((Vec<String>, Vec<String>)),
+
+    /// Response to return `Vec<(String, usize, usize)>`.
+    VecStringUsizeUsize(Vec<(String, usize, usize)>),
+
+    /// Response to return `Vec<(String, Option<PathBuf>)>`.
+    VecStringOptionPathBuf(Vec<(String, Option<PathBuf>)>),
+
+    /// Response to return `Vec<Vec<Vec<String>>>`.
+    VecVecVecString(Vec<Vec<Vec<String>>>),
+
+    /// Response to return `(Vec<String>, Vec<String>, Vec<String>)`.
+    VecStringVecStringVecString((Vec<String>, Vec<String>, Vec<String>)),
+
+    /// Response to return `Vec<PathBuf>`.
+    VecPathBuf(Vec<PathBuf>),
+
+    /// Response to return `Vec<(String, PFHFileType)>`.
+    VecStringPFHFileType(Vec<(String, PFHFileType)>),
+
+    /// Response to return `Vec<usize>`.
+    VecUsize(Vec<usize>),
+
+    /// Response to return `Definition`.
+    Definition(Definition),
+
+    /// Response to return `(BTreeMap<String, usize>, Vec<Vec<String>>)`.
+    BTreeMapStringUsizeVecVecString((BTreeMap<String, usize>, Vec<Vec<String>>)),
+
+    /// Response to return `OptimizeReport`.
+    OptimizeReport(OptimizeReport),
+
+    /// Response to return `Vec<ReferenceCycle>`.
+    VecReferenceCycle(Vec<ReferenceCycle>),
+
+    /// Response to return `Vec<(String, PFHFlags, bool)>`.
+    VecStringPFHFlagsBool(Vec<(String, PFHFlags, bool)>),
+
+    /// Response to return `TableDiff`.
+    TableDiff(TableDiff),
+
+    /// Response to return `Vec<(Vec<String>, TextType)>`.
+    VecVecStringTextType(Vec<(Vec<String>, TextType)>),
+
+    /// Response to return `Vec<Vec<DecodedData>>`.
+    VecVecDecodedData(Vec<Vec<DecodedData>>),
+
+    /// Response to return `usize`.
+    Usize(usize),
+
+    /// Response to return `(WsModel, Vec<bool>)`.
+    WsModelVecBool((WsModel, Vec<bool>)),
+
+    /// Response to return `Vec<(Vec<String>, String)>`.
+    VecVecStringString(Vec<(Vec<String>, String)>),
+
+    /// Response to return `Vec<(Vec<String>, usize, Vec<DecodedData>)>`.
+    VecVecStringUsizeVecDecodedData(Vec<(Vec<String>, usize, Vec<DecodedData>)>),
+
+    /// Response to return `(PackFileInfo, u64, u64)`.
+    PackFileInfoU64U64((PackFileInfo, u64, u64)),
+
+    /// Response to return `UiLayout`.
+    UiLayout(UiLayout),
+
+    /// Response to return `(Vec<(Vec<String>, i32, i32)>, Vec<(Vec<String>, String)>)`.
+    VecVecStringI32I32VecVecStringString((Vec<(Vec<String>, i32, i32)>, Vec<(Vec<String>, String)>)),
+
+    /// Response to return `Vec<(Vec<String>, usize, String)>`.
+    VecVecStringUsizeString(Vec<(Vec<String>, usize, String)>),
+
+    /// Response to return `ExtractReport`.
+    ExtractReport(ExtractReport),
+
+    /// Response to return `TablePrefs`.
+    TablePrefs(TablePrefs),
+
+    /// Response to return `(PackFileInfo, RecoveryReport)`.
+    PackFileInfoRecoveryReport((PackFileInfo, RecoveryReport)),
+
+    /// Response to return `TableByteConsumption`.
+    TableByteConsumption(TableByteConsumption),
+
+    /// Response to return `Vec<i32>`.
+    VecI32(Vec<i32>),
+
+    /// Response to return `LoadOrderImpactReport`.
+    LoadOrderImpactReport(LoadOrderImpactReport),
+
+    /// Response to return `(TableType, Vec<String>)`.
+    TableTypeVecString((TableType, Vec<String>)),
+
+    /// Response to return `SchemaAutoUpdateReport`.
+    SchemaAutoUpdateReport(SchemaAutoUpdateReport),
+
+    /// Response to return `Vec<(Vec<String>, i32, i32)>`.
+    VecVecStringI32I32(Vec<(Vec<String>, i32, i32)>),
+
+    /// Response to return `Vec<(FieldType, Range<usize>, DecodedData)>`.
+    VecFieldTypeRangeDecodedData(Vec<(FieldType, Range<usize>, DecodedData)>),
+
+    /// Response to return `MergeReport`.
+    MergeReport(MergeReport),
+
+    /// Response to return `Vec<(Vec<String>, LazyLoadingMismatch)>`.
+    VecVecStringLazyLoadingMismatch(Vec<(Vec<String>, LazyLoadingMismatch)>),
+
+    /// Response to return `Vec<PackedFileInfo>`.
+    VecPackedFileInfo(Vec<PackedFileInfo>),
+
+    /// Response to return `DB`.
+    DB(DB),
 }
 
 #[derive(Debug)]
 pub enum Notification {
     Error(Error),
     Done,
+
+    /// Progress of a running operation. Contains the amount of work done, and the total amount, if known.
+    Progress(u64, Option<u64>),
 }
 
 //-------------------------------------------------------------------------------//
@@ -497,8 +1023,15 @@ impl CentralCommand {
     /// This function serves to send message from the main thread to the background thread.
     #[allow(dead_code)]
     pub fn send_message_qt(&self, data: Command) {
-        if self.sender_qt.send(data).is_err() {
-            panic!(THREADS_SENDER_ERROR);
+        match data {
+
+            // These ones need to work while the background thread is busy, so we don't queue them.
+            Command::PauseOperation => OPERATION_PAUSE.pause(),
+            Command::ResumeOperation => OPERATION_PAUSE.resume(),
+            Command::CancelPrefetch => { PREFETCH_GENERATION.fetch_add(1, Ordering::SeqCst); },
+            _ => if self.sender_qt.send(data).is_err() {
+                panic!(THREADS_SENDER_ERROR);
+            }
         }
     }
 
@@ -656,6 +1189,7 @@ impl CentralCommand {
                 Ok(data) => match data{
                     Notification::Done => return Response::Success,
                     Notification::Error(error) => return Response::Error(error),
+                    Notification::Progress(..) => {},
                 }
                 Err(error) => if error.is_disconnected() {
                     panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response)
@@ -683,6 +1217,40 @@ impl CentralCommand {
         }
     }
 
+    /// This functions serves to receive messages from the background thread into the main thread.
+    ///
+    /// Like `recv_message_qt_try`, but passing the progress notifications we get while waiting to the provided function.
+    ///
+    /// Error notifications we get while waiting are not from the operation we're waiting for, as that one replies with a `Response`.
+    /// They're passed to the provided error function, so they're not lost.
+    pub fn recv_message_qt_try_with_progress(&self, progress: &dyn Fn(u64, Option<u64>), error: &dyn Fn(Error)) -> Response {
+        let event_loop = unsafe { QEventLoop::new_0a() };
+        loop {
+
+            // Check the response and, in case of error, try again. If the error is "Disconnected", CTD.
+            let response = self.receiver_qt.try_recv() ;
+            match response {
+                Ok(data) => return data,
+                Err(error) => if error.is_disconnected() { panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response) }
+            }
+
+            // Only show the last progress notification we got.
+            let mut last_progress = None;
+            while let Ok(notification) = self.receiver_notification_to_qt.try_recv() {
+                match notification {
+                    Notification::Progress(done, total) => last_progress = Some((done, total)),
+                    Notification::Error(notification_error) => error(notification_error),
+                    Notification::Done => {},
+                }
+            }
+
+            if let Some((done, total)) = last_progress {
+                progress(done, total);
+            }
+            unsafe { event_loop.process_events_0a() };
+        }
+    }
+
     /// This functions serves to receive messages from the network thread into the main thread.
     ///
     /// This function will keep asking for a response, keeping the UI responsive. Use it for heavy tasks.
@@ -47,18 +47,19 @@ use std::rc::Rc;
 
 use rpfm_lib::packfile::PathType;
 use rpfm_lib::global_search::{GlobalSearch, MatchHolder, schema::SchemaMatches, table::{TableMatches, TableMatch}, text::TextMatches};
+use rpfm_lib::SETTINGS;
 
 use crate::app_ui::AppUI;
 use crate::CENTRAL_COMMAND;
 use crate::communications::{Command, Response, THREADS_COMMUNICATION_ERROR};
 use crate::diagnostics_ui::DiagnosticsUI;
 use crate::ffi::{new_treeview_filter_safe, trigger_treeview_filter_safe};
-use crate::locale::qtr;
+use crate::locale::{qtr, tre};
 use crate::packfile_contents_ui::PackFileContentsUI;
 use crate::pack_tree::{PackTree, TreeViewOperation};
 use crate::packedfile_views::{View, ViewType};
 use crate::QString;
-use crate::utils::{create_grid_layout, show_dialog};
+use crate::utils::{create_grid_layout, log_to_status_bar, show_dialog};
 use crate::UI_STATE;
 
 pub mod connections;
@@ -402,6 +403,7 @@ impl GlobalSearchUI {
         global_search.pattern = global_search_ui.global_search_search_line_edit.text().to_std_string();
         global_search.case_sensitive = global_search_ui.global_search_case_sensitive_checkbox.is_checked();
         global_search.use_regex = global_search_ui.global_search_use_regex_checkbox.is_checked();
+        global_search.max_results = SETTINGS.read().unwrap().get_global_search_max_results();
 
         // If we don't have text to search, return.
         if global_search.pattern.is_empty() { return; }
@@ -446,6 +448,14 @@ impl GlobalSearchUI {
                 Self::load_table_matches_to_ui(&model_loc, &tree_view_loc, &global_search.matches_loc);
                 Self::load_text_matches_to_ui(&model_text, &tree_view_text, &global_search.matches_text);
                 Self::load_schema_matches_to_ui(&model_schema, &tree_view_schema, &global_search.matches_schema);
+
+                // Let the user know there are more matches than the ones shown.
+                if global_search.truncated {
+                    if let Some(max_results) = global_search.max_results {
+                        log_to_status_bar(&tre("global_search_truncated", &[&max_results.to_string()]));
+                    }
+                }
+
                 UI_STATE.set_global_search(&global_search);
                 pack_file_contents_ui.packfile_contents_tree_view.update_treeview(true, TreeViewOperation::UpdateTooltip(packed_files_info));
             }
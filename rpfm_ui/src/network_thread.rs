@@ -52,6 +52,9 @@ pub fn network_loop() {
                 }
             }
 
+            // When we want to update the schemas without asking, if there is an update available...
+            Command::AutoUpdateSchemas => CENTRAL_COMMAND.send_message_network_to_qt(Response::SchemaAutoUpdateReport(Schema::auto_update())),
+
             // When we want to check if there is a template update available.
             Command::CheckTemplateUpdates => {
                 match Template::check_update() {
@@ -120,6 +120,7 @@ impl IconType {
                         TextType::Plain => &TREEVIEW_ICONS.text_txt,
                         TextType::Markdown => &TREEVIEW_ICONS.text_txt,
                         TextType::Json => &TREEVIEW_ICONS.text_txt,
+                        TextType::Csv => &TREEVIEW_ICONS.text_txt,
                     }
                 }
 
@@ -175,6 +176,7 @@ impl IconType {
                         TextType::Plain => &TREEVIEW_ICONS.text_txt,
                         TextType::Markdown => &TREEVIEW_ICONS.text_txt,
                         TextType::Json => &TREEVIEW_ICONS.text_txt,
+                        TextType::Csv => &TREEVIEW_ICONS.text_txt,
                     }
                 }
 
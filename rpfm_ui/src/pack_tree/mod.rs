@@ -1551,6 +1551,7 @@ fn new_packed_file_tooltip(info: &PackedFileInfo) -> String {
             <li><b>Last Modified:</b> <i>{:?}</i></li> \
             <li><b>Is Encrypted:</b> <i>{}</i></li> \
             <li><b>Is Compressed:</b> <i>{}</i></li> \
+            <li><b>Compression Format:</b> <i>{}</i></li> \
             <li><b>Is Cached:</b> <i>{}</i></li> \
             <li><b>Cached type:</b> <i>{}</i></li> \
         </ul>",
@@ -1558,6 +1559,7 @@ fn new_packed_file_tooltip(info: &PackedFileInfo) -> String {
         NaiveDateTime::from_timestamp(info.timestamp, 0),
         info.is_encrypted,
         info.is_compressed,
+        info.compression_format.map(|x| x.to_string()).unwrap_or_else(|| "-".to_owned()),
         info.is_cached,
         info.cached_type
     )
@@ -36,7 +36,6 @@ use rpfm_lib::packedfile::table::loc::Loc;
 use rpfm_lib::packedfile::table::matched_combat::MatchedCombat;
 use rpfm_lib::packedfile::table::Table;
 use rpfm_lib::packedfile::PackedFileType;
-use rpfm_lib::SCHEMA;
 use rpfm_lib::schema::{Definition, FieldType};
 
 use crate::app_ui::AppUI;
@@ -487,17 +486,13 @@ impl PackedFileDecoderViewSlots {
                     let model_index = indexes.at(0);
                     let version = view.table_model_old_versions.item_from_index(model_index).text().to_std_string().parse::<i32>().unwrap();
 
-                    if let Some(ref mut schema) = *SCHEMA.write().unwrap() {
-                        let versioned_file = match view.packed_file_type {
-                            PackedFileType::AnimTable => schema.get_ref_mut_versioned_file_animtable(),
-                            PackedFileType::AnimFragment => schema.get_ref_mut_versioned_file_anim_fragment(),
-                            PackedFileType::DB => schema.get_ref_mut_versioned_file_db(&view.packed_file_path[1]),
-                            PackedFileType::Loc => schema.get_ref_mut_versioned_file_loc(),
-                            PackedFileType::MatchedCombat => schema.get_ref_mut_versioned_file_matched_combat(),
-                            _ => unimplemented!(),
-                        }.unwrap();
-
-                        versioned_file.remove_version(version);
+                    let table_name = view.packed_file_path.get(1).cloned().unwrap_or_default();
+                    CENTRAL_COMMAND.send_message_qt(Command::DeleteDefinitionVersion((view.packed_file_type, table_name, version)));
+                    let response = CENTRAL_COMMAND.recv_message_qt();
+                    match response {
+                        Response::Success => {},
+                        Response::Error(error) => show_dialog(&view.table_view, error, false),
+                        _ => panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response),
                     }
                     view.load_versions_list();
                 }
@@ -292,6 +292,7 @@ impl PackedFileView {
                     Response::Success => {
                         Ok(())
                     }
+                    Response::Error(error) => Err(error),
 
                     // In ANY other situation, it's a message problem.
                     _ => panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response),
@@ -302,6 +303,9 @@ impl PackedFileView {
                 let response = CENTRAL_COMMAND.recv_message_save_packedfile_try();
                 match response {
                     Response::Success => {},
+
+                    // Keep the changes of tables, so they can be highlighted the next time the table is opened.
+                    Response::TableDiff(diff) => UI_STATE.set_table_diff(&self.get_path(), &diff),
                     Response::Error(error) => show_dialog(&pack_file_contents_ui.packfile_contents_tree_view, error, false),
                     _ => panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response),
                 }
@@ -91,6 +91,7 @@ impl PackedFileTextView {
             TextType::Plain => QString::from_std_str(PLAIN),
             TextType::Markdown => QString::from_std_str(MARKDOWN),
             TextType::Json => QString::from_std_str(JSON),
+            TextType::Csv => QString::from_std_str(PLAIN),
         };
 
         let editor = new_text_editor_safe(&packed_file_view.get_mut_widget().static_upcast());
@@ -125,6 +126,7 @@ impl PackedFileTextView {
             TextType::Plain => QString::from_std_str(PLAIN),
             TextType::Markdown => QString::from_std_str(MARKDOWN),
             TextType::Json => QString::from_std_str(JSON),
+            TextType::Csv => QString::from_std_str(PLAIN),
         };
 
         set_text_safe(&self.editor, &QString::from_std_str(data.get_ref_contents()).as_ptr(), &highlighting_mode.as_ptr());
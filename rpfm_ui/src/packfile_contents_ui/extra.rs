@@ -98,7 +98,7 @@ impl PackFileContentsUI {
     ) {
         app_ui.main_window.set_enabled(false);
         let paths_to_send = paths.iter().cloned().zip(paths_packedfile.iter().cloned()).collect();
-        CENTRAL_COMMAND.send_message_qt(Command::AddPackedFilesFromFolder(paths_to_send));
+        CENTRAL_COMMAND.send_message_qt(Command::AddPackedFilesFromFolder((paths_to_send, true)));
         let response = CENTRAL_COMMAND.recv_message_qt();
         match response {
             Response::VecPathType(paths_packedfile) => {
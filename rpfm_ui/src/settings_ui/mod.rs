@@ -88,14 +88,23 @@ pub struct SettingsUI {
     pub extra_network_update_channel_label: QBox<QLabel>,
     pub extra_packfile_autosave_interval_label: QBox<QLabel>,
     pub extra_packfile_autosave_amount_label: QBox<QLabel>,
+    pub extra_packfile_recent_files_max_amount_label: QBox<QLabel>,
+    pub extra_global_search_max_results_label: QBox<QLabel>,
     pub extra_network_check_updates_on_start_label: QBox<QLabel>,
     pub extra_network_check_schema_updates_on_start_label: QBox<QLabel>,
+    pub extra_network_auto_update_schemas_on_start_label: QBox<QLabel>,
     pub extra_network_check_template_updates_on_start_label: QBox<QLabel>,
     pub extra_packfile_allow_editing_of_ca_packfiles_label: QBox<QLabel>,
     pub extra_packfile_optimize_not_renamed_packedfiles_label: QBox<QLabel>,
     pub extra_packfile_enable_diagnostics_label: QBox<QLabel>,
     pub extra_packfile_use_lazy_loading_label: QBox<QLabel>,
+    pub extra_packfile_use_memory_mapped_files_label: QBox<QLabel>,
+    pub extra_packfile_prefetch_memory_budget_label: QBox<QLabel>,
     pub extra_packfile_disable_uuid_regeneration_on_db_tables_label: QBox<QLabel>,
+    pub extra_packfile_install_after_save_label: QBox<QLabel>,
+    pub extra_packfile_tsv_import_map_columns_by_header_label: QBox<QLabel>,
+    pub extra_packfile_reproducible_save_label: QBox<QLabel>,
+    pub extra_packfile_enable_batch_operation_logs_label: QBox<QLabel>,
     pub ui_global_use_dark_theme_label: QBox<QLabel>,
     pub ui_window_start_maximized_label: QBox<QLabel>,
     pub ui_window_hide_background_icon_label: QBox<QLabel>,
@@ -107,14 +116,23 @@ pub struct SettingsUI {
     pub extra_network_update_channel_combobox: QBox<QComboBox>,
     pub extra_packfile_autosave_interval_spinbox: QBox<QSpinBox>,
     pub extra_packfile_autosave_amount_spinbox: QBox<QSpinBox>,
+    pub extra_packfile_recent_files_max_amount_spinbox: QBox<QSpinBox>,
+    pub extra_global_search_max_results_spinbox: QBox<QSpinBox>,
     pub extra_network_check_updates_on_start_checkbox: QBox<QCheckBox>,
     pub extra_network_check_schema_updates_on_start_checkbox: QBox<QCheckBox>,
+    pub extra_network_auto_update_schemas_on_start_checkbox: QBox<QCheckBox>,
     pub extra_network_check_template_updates_on_start_checkbox: QBox<QCheckBox>,
     pub extra_packfile_allow_editing_of_ca_packfiles_checkbox: QBox<QCheckBox>,
     pub extra_packfile_optimize_not_renamed_packedfiles_checkbox: QBox<QCheckBox>,
     pub extra_packfile_enable_diagnostics_checkbox: QBox<QCheckBox>,
     pub extra_packfile_use_lazy_loading_checkbox: QBox<QCheckBox>,
+    pub extra_packfile_use_memory_mapped_files_checkbox: QBox<QCheckBox>,
+    pub extra_packfile_prefetch_memory_budget_spinbox: QBox<QSpinBox>,
     pub extra_packfile_disable_uuid_regeneration_on_db_tables_checkbox: QBox<QCheckBox>,
+    pub extra_packfile_install_after_save_checkbox: QBox<QCheckBox>,
+    pub extra_packfile_tsv_import_map_columns_by_header_checkbox: QBox<QCheckBox>,
+    pub extra_packfile_reproducible_save_checkbox: QBox<QCheckBox>,
+    pub extra_packfile_enable_batch_operation_logs_checkbox: QBox<QCheckBox>,
     pub ui_global_use_dark_theme_checkbox: QBox<QCheckBox>,
     pub ui_window_start_maximized_checkbox: QBox<QCheckBox>,
     pub ui_window_hide_background_icon_checkbox: QBox<QCheckBox>,
@@ -169,7 +187,7 @@ pub struct SettingsUI {
     //-------------------------------------------------------------------------------//
     // Hidden section of the `Settings` dialog.
     //-------------------------------------------------------------------------------//
-    pub recent_files: Arc<RwLock<Vec<String>>>,
+    pub recent_files: Arc<RwLock<Vec<PathBuf>>>,
 }
 
 //-------------------------------------------------------------------------------//
@@ -306,12 +324,23 @@ impl SettingsUI {
         let extra_packfile_autosave_interval_spinbox = QSpinBox::new_1a(&general_frame);
         let extra_packfile_autosave_amount_spinbox = QSpinBox::new_1a(&general_frame);
 
+        // Recent files stuff.
+        let extra_packfile_recent_files_max_amount_label = QLabel::from_q_string_q_widget(&qtr("settings_recent_files_max_amount"), &general_frame);
+        let extra_packfile_recent_files_max_amount_spinbox = QSpinBox::new_1a(&general_frame);
+
+        // Global search stuff.
+        let extra_global_search_max_results_label = QLabel::from_q_string_q_widget(&qtr("settings_global_search_max_results"), &general_frame);
+        let extra_global_search_max_results_spinbox = QSpinBox::new_1a(&general_frame);
+        extra_global_search_max_results_spinbox.set_maximum(i32::MAX);
+
         // Update checkers.
         let extra_network_check_updates_on_start_label = QLabel::from_q_string_q_widget(&qtr("settings_check_updates_on_start"), &general_frame);
         let extra_network_check_schema_updates_on_start_label = QLabel::from_q_string_q_widget(&qtr("settings_check_schema_updates_on_start"), &general_frame);
+        let extra_network_auto_update_schemas_on_start_label = QLabel::from_q_string_q_widget(&qtr("settings_auto_update_schemas_on_start"), &general_frame);
         let extra_network_check_template_updates_on_start_label = QLabel::from_q_string_q_widget(&qtr("settings_check_template_updates_on_start"), &general_frame);
         let extra_network_check_updates_on_start_checkbox = QCheckBox::from_q_widget(&general_frame);
         let extra_network_check_schema_updates_on_start_checkbox = QCheckBox::from_q_widget(&general_frame);
+        let extra_network_auto_update_schemas_on_start_checkbox = QCheckBox::from_q_widget(&general_frame);
         let extra_network_check_template_updates_on_start_checkbox = QCheckBox::from_q_widget(&general_frame);
 
         // Behavior settings.
@@ -363,32 +392,41 @@ impl SettingsUI {
         general_grid.add_widget_5a(&extra_network_check_schema_updates_on_start_label, 6, 0, 1, 1);
         general_grid.add_widget_5a(&extra_network_check_schema_updates_on_start_checkbox, 6, 1, 1, 1);
 
-        general_grid.add_widget_5a(&extra_network_check_template_updates_on_start_label, 7, 0, 1, 1);
-        general_grid.add_widget_5a(&extra_network_check_template_updates_on_start_checkbox, 7, 1, 1, 1);
+        general_grid.add_widget_5a(&extra_network_auto_update_schemas_on_start_label, 7, 0, 1, 1);
+        general_grid.add_widget_5a(&extra_network_auto_update_schemas_on_start_checkbox, 7, 1, 1, 1);
+
+        general_grid.add_widget_5a(&extra_network_check_template_updates_on_start_label, 8, 0, 1, 1);
+        general_grid.add_widget_5a(&extra_network_check_template_updates_on_start_checkbox, 8, 1, 1, 1);
+
+        general_grid.add_widget_5a(&extra_packfile_allow_editing_of_ca_packfiles_label, 9, 0, 1, 1);
+        general_grid.add_widget_5a(&extra_packfile_allow_editing_of_ca_packfiles_checkbox, 9, 1, 1, 1);
+
+        general_grid.add_widget_5a(&extra_packfile_optimize_not_renamed_packedfiles_label, 10, 0, 1, 1);
+        general_grid.add_widget_5a(&extra_packfile_optimize_not_renamed_packedfiles_checkbox, 10, 1, 1, 1);
 
-        general_grid.add_widget_5a(&extra_packfile_allow_editing_of_ca_packfiles_label, 8, 0, 1, 1);
-        general_grid.add_widget_5a(&extra_packfile_allow_editing_of_ca_packfiles_checkbox, 8, 1, 1, 1);
+        general_grid.add_widget_5a(&extra_packfile_enable_diagnostics_label, 11, 0, 1, 1);
+        general_grid.add_widget_5a(&extra_packfile_enable_diagnostics_checkbox, 11, 1, 1, 1);
 
-        general_grid.add_widget_5a(&extra_packfile_optimize_not_renamed_packedfiles_label, 9, 0, 1, 1);
-        general_grid.add_widget_5a(&extra_packfile_optimize_not_renamed_packedfiles_checkbox, 9, 1, 1, 1);
+        general_grid.add_widget_5a(&extra_packfile_recent_files_max_amount_label, 12, 0, 1, 1);
+        general_grid.add_widget_5a(&extra_packfile_recent_files_max_amount_spinbox, 12, 1, 1, 1);
 
-        general_grid.add_widget_5a(&extra_packfile_enable_diagnostics_label, 10, 0, 1, 1);
-        general_grid.add_widget_5a(&extra_packfile_enable_diagnostics_checkbox, 10, 1, 1, 1);
+        general_grid.add_widget_5a(&extra_global_search_max_results_label, 13, 0, 1, 1);
+        general_grid.add_widget_5a(&extra_global_search_max_results_spinbox, 13, 1, 1, 1);
 
-        general_grid.add_widget_5a(&ui_global_use_dark_theme_label, 13, 0, 1, 1);
-        general_grid.add_widget_5a(&ui_global_use_dark_theme_checkbox, 13, 1, 1, 1);
+        general_grid.add_widget_5a(&ui_global_use_dark_theme_label, 14, 0, 1, 1);
+        general_grid.add_widget_5a(&ui_global_use_dark_theme_checkbox, 14, 1, 1, 1);
 
-        general_grid.add_widget_5a(&ui_window_start_maximized_label, 14, 0, 1, 1);
-        general_grid.add_widget_5a(&ui_window_start_maximized_checkbox, 14, 1, 1, 1);
+        general_grid.add_widget_5a(&ui_window_start_maximized_label, 15, 0, 1, 1);
+        general_grid.add_widget_5a(&ui_window_start_maximized_checkbox, 15, 1, 1, 1);
 
-        general_grid.add_widget_5a(&ui_window_hide_background_icon_label, 15, 0, 1, 1);
-        general_grid.add_widget_5a(&ui_window_hide_background_icon_checkbox, 15, 1, 1, 1);
+        general_grid.add_widget_5a(&ui_window_hide_background_icon_label, 16, 0, 1, 1);
+        general_grid.add_widget_5a(&ui_window_hide_background_icon_checkbox, 16, 1, 1, 1);
 
-        //general_grid.add_widget_5a(&general_packfile_treeview_resize_to_fit_label, 14, 0, 1, 1);
-        //general_grid.add_widget_5a(&general_packfile_treeview_resize_to_fit_checkbox, 14, 1, 1, 1);
+        //general_grid.add_widget_5a(&general_packfile_treeview_resize_to_fit_label, 15, 0, 1, 1);
+        //general_grid.add_widget_5a(&general_packfile_treeview_resize_to_fit_checkbox, 15, 1, 1, 1);
 
-        general_grid.add_widget_5a(&general_packfile_treeview_expand_treeview_when_adding_items_label, 16, 0, 1, 1);
-        general_grid.add_widget_5a(&general_packfile_treeview_expand_treeview_when_adding_items_checkbox, 16, 1, 1, 1);
+        general_grid.add_widget_5a(&general_packfile_treeview_expand_treeview_when_adding_items_label, 17, 0, 1, 1);
+        general_grid.add_widget_5a(&general_packfile_treeview_expand_treeview_when_adding_items_checkbox, 17, 1, 1, 1);
 
         main_grid.add_widget_5a(&general_frame, 2, 0, 3, 1);
 
@@ -467,6 +505,25 @@ impl SettingsUI {
         let extra_packfile_use_lazy_loading_label = QLabel::from_q_string_q_widget(&qtr("settings_use_lazy_loading"), &debug_frame);
         let extra_packfile_use_lazy_loading_checkbox = QCheckBox::from_q_widget(&debug_frame);
 
+        let extra_packfile_use_memory_mapped_files_label = QLabel::from_q_string_q_widget(&qtr("settings_use_memory_mapped_files"), &debug_frame);
+        let extra_packfile_use_memory_mapped_files_checkbox = QCheckBox::from_q_widget(&debug_frame);
+
+        let extra_packfile_prefetch_memory_budget_label = QLabel::from_q_string_q_widget(&qtr("settings_prefetch_memory_budget"), &debug_frame);
+        let extra_packfile_prefetch_memory_budget_spinbox = QSpinBox::new_1a(&debug_frame);
+        extra_packfile_prefetch_memory_budget_spinbox.set_maximum(i32::MAX);
+
+        let extra_packfile_enable_batch_operation_logs_label = QLabel::from_q_string_q_widget(&qtr("settings_enable_batch_operation_logs"), &debug_frame);
+        let extra_packfile_enable_batch_operation_logs_checkbox = QCheckBox::from_q_widget(&debug_frame);
+
+        let extra_packfile_reproducible_save_label = QLabel::from_q_string_q_widget(&qtr("settings_reproducible_save"), &debug_frame);
+        let extra_packfile_reproducible_save_checkbox = QCheckBox::from_q_widget(&debug_frame);
+
+        let extra_packfile_tsv_import_map_columns_by_header_label = QLabel::from_q_string_q_widget(&qtr("settings_tsv_import_map_columns_by_header"), &debug_frame);
+        let extra_packfile_tsv_import_map_columns_by_header_checkbox = QCheckBox::from_q_widget(&debug_frame);
+
+        let extra_packfile_install_after_save_label = QLabel::from_q_string_q_widget(&qtr("settings_install_after_save"), &debug_frame);
+        let extra_packfile_install_after_save_checkbox = QCheckBox::from_q_widget(&debug_frame);
+
         let debug_clear_autosave_folder_button = QPushButton::from_q_string_q_widget(&qtr("settings_debug_clear_autosave_folder"), &debug_frame);
         let debug_clear_schema_folder_button = QPushButton::from_q_string_q_widget(&qtr("settings_debug_clear_schema_folder"), &debug_frame);
 
@@ -482,6 +539,24 @@ impl SettingsUI {
         debug_grid.add_widget_5a(&extra_packfile_use_lazy_loading_label, 11, 0, 1, 1);
         debug_grid.add_widget_5a(&extra_packfile_use_lazy_loading_checkbox, 11, 1, 1, 1);
 
+        debug_grid.add_widget_5a(&extra_packfile_use_memory_mapped_files_label, 12, 0, 1, 1);
+        debug_grid.add_widget_5a(&extra_packfile_use_memory_mapped_files_checkbox, 12, 1, 1, 1);
+
+        debug_grid.add_widget_5a(&extra_packfile_enable_batch_operation_logs_label, 13, 0, 1, 1);
+        debug_grid.add_widget_5a(&extra_packfile_enable_batch_operation_logs_checkbox, 13, 1, 1, 1);
+
+        debug_grid.add_widget_5a(&extra_packfile_reproducible_save_label, 14, 0, 1, 1);
+        debug_grid.add_widget_5a(&extra_packfile_reproducible_save_checkbox, 14, 1, 1, 1);
+
+        debug_grid.add_widget_5a(&extra_packfile_tsv_import_map_columns_by_header_label, 15, 0, 1, 1);
+        debug_grid.add_widget_5a(&extra_packfile_tsv_import_map_columns_by_header_checkbox, 15, 1, 1, 1);
+
+        debug_grid.add_widget_5a(&extra_packfile_install_after_save_label, 16, 0, 1, 1);
+        debug_grid.add_widget_5a(&extra_packfile_install_after_save_checkbox, 16, 1, 1, 1);
+
+        debug_grid.add_widget_5a(&extra_packfile_prefetch_memory_budget_label, 17, 0, 1, 1);
+        debug_grid.add_widget_5a(&extra_packfile_prefetch_memory_budget_spinbox, 17, 1, 1, 1);
+
         debug_grid.add_widget_5a(&debug_clear_autosave_folder_button, 90, 0, 1, 1);
         debug_grid.add_widget_5a(&debug_clear_schema_folder_button, 90, 1, 1, 1);
 
@@ -544,14 +619,23 @@ impl SettingsUI {
             extra_network_update_channel_label,
             extra_packfile_autosave_amount_label,
             extra_packfile_autosave_interval_label,
+            extra_packfile_recent_files_max_amount_label,
+            extra_global_search_max_results_label,
             extra_network_check_updates_on_start_label,
             extra_network_check_schema_updates_on_start_label,
+            extra_network_auto_update_schemas_on_start_label,
             extra_network_check_template_updates_on_start_label,
             extra_packfile_allow_editing_of_ca_packfiles_label,
             extra_packfile_optimize_not_renamed_packedfiles_label,
             extra_packfile_enable_diagnostics_label,
             extra_packfile_use_lazy_loading_label,
+            extra_packfile_use_memory_mapped_files_label,
+            extra_packfile_prefetch_memory_budget_label,
             extra_packfile_disable_uuid_regeneration_on_db_tables_label,
+            extra_packfile_install_after_save_label,
+            extra_packfile_tsv_import_map_columns_by_header_label,
+            extra_packfile_reproducible_save_label,
+            extra_packfile_enable_batch_operation_logs_label,
             ui_global_use_dark_theme_label,
             ui_window_start_maximized_label,
             ui_window_hide_background_icon_label,
@@ -563,14 +647,23 @@ impl SettingsUI {
             extra_network_update_channel_combobox,
             extra_packfile_autosave_amount_spinbox,
             extra_packfile_autosave_interval_spinbox,
+            extra_packfile_recent_files_max_amount_spinbox,
+            extra_global_search_max_results_spinbox,
             extra_network_check_updates_on_start_checkbox,
             extra_network_check_schema_updates_on_start_checkbox,
+            extra_network_auto_update_schemas_on_start_checkbox,
             extra_network_check_template_updates_on_start_checkbox,
             extra_packfile_allow_editing_of_ca_packfiles_checkbox,
             extra_packfile_optimize_not_renamed_packedfiles_checkbox,
             extra_packfile_enable_diagnostics_checkbox,
             extra_packfile_use_lazy_loading_checkbox,
+            extra_packfile_use_memory_mapped_files_checkbox,
+            extra_packfile_prefetch_memory_budget_spinbox,
             extra_packfile_disable_uuid_regeneration_on_db_tables_checkbox,
+            extra_packfile_install_after_save_checkbox,
+            extra_packfile_tsv_import_map_columns_by_header_checkbox,
+            extra_packfile_reproducible_save_checkbox,
+            extra_packfile_enable_batch_operation_logs_checkbox,
             ui_global_use_dark_theme_checkbox,
             ui_window_start_maximized_checkbox,
             ui_window_hide_background_icon_checkbox,
@@ -667,17 +760,26 @@ impl SettingsUI {
         // Load the General Stuff.
         self.extra_packfile_autosave_amount_spinbox.set_value(settings.settings_string["autosave_amount"].parse::<i32>().unwrap_or(10));
         self.extra_packfile_autosave_interval_spinbox.set_value(settings.settings_string["autosave_interval"].parse::<i32>().unwrap_or(10));
+        self.extra_packfile_recent_files_max_amount_spinbox.set_value(settings.get_recent_files_max_amount() as i32);
+        self.extra_global_search_max_results_spinbox.set_value(settings.get_global_search_max_results().unwrap_or(0) as i32);
         self.ui_global_use_dark_theme_checkbox.set_checked(settings.settings_bool["use_dark_theme"]);
         self.ui_window_start_maximized_checkbox.set_checked(settings.settings_bool["start_maximized"]);
         self.ui_window_hide_background_icon_checkbox.set_checked(settings.settings_bool["hide_background_icon"]);
         self.extra_network_check_updates_on_start_checkbox.set_checked(settings.settings_bool["check_updates_on_start"]);
         self.extra_network_check_schema_updates_on_start_checkbox.set_checked(settings.settings_bool["check_schema_updates_on_start"]);
+        self.extra_network_auto_update_schemas_on_start_checkbox.set_checked(settings.settings_bool["auto_update_schemas_on_start"]);
         self.extra_network_check_template_updates_on_start_checkbox.set_checked(settings.settings_bool["check_template_updates_on_start"]);
         self.extra_packfile_allow_editing_of_ca_packfiles_checkbox.set_checked(settings.settings_bool["allow_editing_of_ca_packfiles"]);
         self.extra_packfile_optimize_not_renamed_packedfiles_checkbox.set_checked(settings.settings_bool["optimize_not_renamed_packedfiles"]);
         self.extra_packfile_enable_diagnostics_checkbox.set_checked(settings.settings_bool["enable_diagnostics_tool"]);
         self.extra_packfile_use_lazy_loading_checkbox.set_checked(settings.settings_bool["use_lazy_loading"]);
+        self.extra_packfile_use_memory_mapped_files_checkbox.set_checked(settings.settings_bool["use_memory_mapped_files"]);
+        self.extra_packfile_prefetch_memory_budget_spinbox.set_value((settings.get_prefetch_memory_budget() / 1024 / 1024) as i32);
         self.extra_packfile_disable_uuid_regeneration_on_db_tables_checkbox.set_checked(settings.settings_bool["disable_uuid_regeneration_on_db_tables"]);
+        self.extra_packfile_install_after_save_checkbox.set_checked(settings.settings_bool["install_after_save"]);
+        self.extra_packfile_tsv_import_map_columns_by_header_checkbox.set_checked(settings.settings_bool["tsv_import_map_columns_by_header"]);
+        self.extra_packfile_reproducible_save_checkbox.set_checked(settings.settings_bool["reproducible_save"]);
+        self.extra_packfile_enable_batch_operation_logs_checkbox.set_checked(settings.settings_bool["enable_batch_operation_logs"]);
         self.general_packfile_treeview_resize_to_fit_checkbox.set_checked(settings.settings_bool["packfile_treeview_resize_to_fit"]);
         self.general_packfile_treeview_expand_treeview_when_adding_items_checkbox.set_checked(settings.settings_bool["expand_treeview_when_adding_items"]);
 
@@ -741,17 +843,26 @@ impl SettingsUI {
         // Get the General Settings.
         settings.settings_string.insert("autosave_amount".to_owned(), self.extra_packfile_autosave_amount_spinbox.value().to_string());
         settings.settings_string.insert("autosave_interval".to_owned(), self.extra_packfile_autosave_interval_spinbox.value().to_string());
+        settings.settings_string.insert("recent_files_max_amount".to_owned(), self.extra_packfile_recent_files_max_amount_spinbox.value().to_string());
+        settings.settings_string.insert("global_search_max_results".to_owned(), self.extra_global_search_max_results_spinbox.value().to_string());
         settings.settings_bool.insert("use_dark_theme".to_owned(), self.ui_global_use_dark_theme_checkbox.is_checked());
         settings.settings_bool.insert("start_maximized".to_owned(), self.ui_window_start_maximized_checkbox.is_checked());
         settings.settings_bool.insert("hide_background_icon".to_owned(), self.ui_window_hide_background_icon_checkbox.is_checked());
         settings.settings_bool.insert("check_updates_on_start".to_owned(), self.extra_network_check_updates_on_start_checkbox.is_checked());
         settings.settings_bool.insert("check_schema_updates_on_start".to_owned(), self.extra_network_check_schema_updates_on_start_checkbox.is_checked());
+        settings.settings_bool.insert("auto_update_schemas_on_start".to_owned(), self.extra_network_auto_update_schemas_on_start_checkbox.is_checked());
         settings.settings_bool.insert("check_template_updates_on_start".to_owned(), self.extra_network_check_template_updates_on_start_checkbox.is_checked());
         settings.settings_bool.insert("allow_editing_of_ca_packfiles".to_owned(), self.extra_packfile_allow_editing_of_ca_packfiles_checkbox.is_checked());
         settings.settings_bool.insert("optimize_not_renamed_packedfiles".to_owned(), self.extra_packfile_optimize_not_renamed_packedfiles_checkbox.is_checked());
         settings.settings_bool.insert("enable_diagnostics_tool".to_owned(), self.extra_packfile_enable_diagnostics_checkbox.is_checked());
         settings.settings_bool.insert("use_lazy_loading".to_owned(), self.extra_packfile_use_lazy_loading_checkbox.is_checked());
+        settings.settings_bool.insert("use_memory_mapped_files".to_owned(), self.extra_packfile_use_memory_mapped_files_checkbox.is_checked());
+        settings.settings_string.insert("prefetch_memory_budget".to_owned(), self.extra_packfile_prefetch_memory_budget_spinbox.value().to_string());
         settings.settings_bool.insert("disable_uuid_regeneration_on_db_tables".to_owned(), self.extra_packfile_disable_uuid_regeneration_on_db_tables_checkbox.is_checked());
+        settings.settings_bool.insert("install_after_save".to_owned(), self.extra_packfile_install_after_save_checkbox.is_checked());
+        settings.settings_bool.insert("tsv_import_map_columns_by_header".to_owned(), self.extra_packfile_tsv_import_map_columns_by_header_checkbox.is_checked());
+        settings.settings_bool.insert("reproducible_save".to_owned(), self.extra_packfile_reproducible_save_checkbox.is_checked());
+        settings.settings_bool.insert("enable_batch_operation_logs".to_owned(), self.extra_packfile_enable_batch_operation_logs_checkbox.is_checked());
         settings.settings_bool.insert("packfile_treeview_resize_to_fit".to_owned(), self.general_packfile_treeview_resize_to_fit_checkbox.is_checked());
         settings.settings_bool.insert("expand_treeview_when_adding_items".to_owned(), self.general_packfile_treeview_expand_treeview_when_adding_items_checkbox.is_checked());
 
@@ -53,21 +53,38 @@ pub unsafe fn set_tips(settings_ui: &Rc<SettingsUI>) {
     //-----------------------------------------------//
 
     let autosave_amount_tip = qtr("tt_settings_autosave_amount");
+    let recent_files_max_amount_tip = qtr("tt_settings_recent_files_max_amount");
+    let global_search_max_results_tip = qtr("tt_settings_global_search_max_results");
     let extra_network_check_updates_on_start_tip = qtr("tt_extra_network_check_updates_on_start_tip");
     let extra_network_check_schema_updates_on_start_tip = qtr("tt_extra_network_check_schema_updates_on_start_tip");
+    let extra_network_auto_update_schemas_on_start_tip = qtr("tt_extra_network_auto_update_schemas_on_start_tip");
     let extra_packfile_allow_editing_of_ca_packfiles_tip = qtr("tt_extra_packfile_allow_editing_of_ca_packfiles_tip");
     let extra_packfile_optimize_not_renamed_packedfiles_tip = qtr("tt_extra_packfile_optimize_not_renamed_packedfiles_tip");
     let extra_packfile_use_dependency_checker_tip = qtr("tt_extra_packfile_use_dependency_checker_tip");
     let extra_packfile_use_lazy_loading_tip = qtr("tt_extra_packfile_use_lazy_loading_tip");
+    let extra_packfile_use_memory_mapped_files_tip = qtr("tt_extra_packfile_use_memory_mapped_files_tip");
+    let extra_packfile_prefetch_memory_budget_tip = qtr("tt_extra_packfile_prefetch_memory_budget_tip");
     let extra_disable_uuid_regeneration_on_db_tables_label_tip = qtr("tt_extra_disable_uuid_regeneration_on_db_tables_label_tip");
+    let extra_packfile_install_after_save_tip = qtr("tt_extra_packfile_install_after_save_tip");
+    let extra_packfile_tsv_import_map_columns_by_header_tip = qtr("tt_extra_packfile_tsv_import_map_columns_by_header_tip");
+    let extra_packfile_reproducible_save_tip = qtr("tt_extra_packfile_reproducible_save_tip");
+    let extra_packfile_enable_batch_operation_logs_tip = qtr("tt_extra_packfile_enable_batch_operation_logs_tip");
 
     settings_ui.extra_packfile_autosave_amount_label.set_tool_tip(&autosave_amount_tip);
     settings_ui.extra_packfile_autosave_amount_spinbox.set_tool_tip(&autosave_amount_tip);
 
+    settings_ui.extra_packfile_recent_files_max_amount_label.set_tool_tip(&recent_files_max_amount_tip);
+    settings_ui.extra_packfile_recent_files_max_amount_spinbox.set_tool_tip(&recent_files_max_amount_tip);
+
+    settings_ui.extra_global_search_max_results_label.set_tool_tip(&global_search_max_results_tip);
+    settings_ui.extra_global_search_max_results_spinbox.set_tool_tip(&global_search_max_results_tip);
+
     settings_ui.extra_network_check_updates_on_start_label.set_tool_tip(&extra_network_check_updates_on_start_tip);
     settings_ui.extra_network_check_updates_on_start_checkbox.set_tool_tip(&extra_network_check_updates_on_start_tip);
     settings_ui.extra_network_check_schema_updates_on_start_label.set_tool_tip(&extra_network_check_schema_updates_on_start_tip);
     settings_ui.extra_network_check_schema_updates_on_start_checkbox.set_tool_tip(&extra_network_check_schema_updates_on_start_tip);
+    settings_ui.extra_network_auto_update_schemas_on_start_label.set_tool_tip(&extra_network_auto_update_schemas_on_start_tip);
+    settings_ui.extra_network_auto_update_schemas_on_start_checkbox.set_tool_tip(&extra_network_auto_update_schemas_on_start_tip);
     settings_ui.extra_packfile_allow_editing_of_ca_packfiles_label.set_tool_tip(&extra_packfile_allow_editing_of_ca_packfiles_tip);
     settings_ui.extra_packfile_allow_editing_of_ca_packfiles_checkbox.set_tool_tip(&extra_packfile_allow_editing_of_ca_packfiles_tip);
     settings_ui.extra_packfile_optimize_not_renamed_packedfiles_label.set_tool_tip(&extra_packfile_optimize_not_renamed_packedfiles_tip);
@@ -76,8 +93,20 @@ pub unsafe fn set_tips(settings_ui: &Rc<SettingsUI>) {
     settings_ui.extra_packfile_enable_diagnostics_checkbox.set_tool_tip(&extra_packfile_use_dependency_checker_tip);
     settings_ui.extra_packfile_use_lazy_loading_label.set_tool_tip(&extra_packfile_use_lazy_loading_tip);
     settings_ui.extra_packfile_use_lazy_loading_checkbox.set_tool_tip(&extra_packfile_use_lazy_loading_tip);
+    settings_ui.extra_packfile_use_memory_mapped_files_label.set_tool_tip(&extra_packfile_use_memory_mapped_files_tip);
+    settings_ui.extra_packfile_use_memory_mapped_files_checkbox.set_tool_tip(&extra_packfile_use_memory_mapped_files_tip);
+    settings_ui.extra_packfile_prefetch_memory_budget_label.set_tool_tip(&extra_packfile_prefetch_memory_budget_tip);
+    settings_ui.extra_packfile_prefetch_memory_budget_spinbox.set_tool_tip(&extra_packfile_prefetch_memory_budget_tip);
     settings_ui.extra_packfile_disable_uuid_regeneration_on_db_tables_label.set_tool_tip(&extra_disable_uuid_regeneration_on_db_tables_label_tip);
     settings_ui.extra_packfile_disable_uuid_regeneration_on_db_tables_checkbox.set_tool_tip(&extra_disable_uuid_regeneration_on_db_tables_label_tip);
+    settings_ui.extra_packfile_install_after_save_label.set_tool_tip(&extra_packfile_install_after_save_tip);
+    settings_ui.extra_packfile_install_after_save_checkbox.set_tool_tip(&extra_packfile_install_after_save_tip);
+    settings_ui.extra_packfile_tsv_import_map_columns_by_header_label.set_tool_tip(&extra_packfile_tsv_import_map_columns_by_header_tip);
+    settings_ui.extra_packfile_tsv_import_map_columns_by_header_checkbox.set_tool_tip(&extra_packfile_tsv_import_map_columns_by_header_tip);
+    settings_ui.extra_packfile_reproducible_save_label.set_tool_tip(&extra_packfile_reproducible_save_tip);
+    settings_ui.extra_packfile_reproducible_save_checkbox.set_tool_tip(&extra_packfile_reproducible_save_tip);
+    settings_ui.extra_packfile_enable_batch_operation_logs_label.set_tool_tip(&extra_packfile_enable_batch_operation_logs_tip);
+    settings_ui.extra_packfile_enable_batch_operation_logs_checkbox.set_tool_tip(&extra_packfile_enable_batch_operation_logs_tip);
 
     //-----------------------------------------------//
     // `Debug` tips.
@@ -198,7 +198,9 @@ impl UI {
         if SETTINGS.read().unwrap().settings_bool["check_updates_on_start"] { AppUI::check_updates(&app_ui, false) };
 
         // If we have it enabled in the prefs, check if there are schema updates.
-        if SETTINGS.read().unwrap().settings_bool["check_schema_updates_on_start"] { AppUI::check_schema_updates(&app_ui, false) };
+        // If auto-updates are enabled, we skip the check and update them directly.
+        if SETTINGS.read().unwrap().settings_bool["auto_update_schemas_on_start"] { AppUI::auto_update_schemas(&app_ui) }
+        else if SETTINGS.read().unwrap().settings_bool["check_schema_updates_on_start"] { AppUI::check_schema_updates(&app_ui, false) };
 
         // If we have it enabled in the prefs, check if there are templates updates.
         if SETTINGS.read().unwrap().settings_bool["check_template_updates_on_start"] { AppUI::check_template_updates(&app_ui, false) };
@@ -14,6 +14,7 @@ Module with all the code related to the main `UIState`.
 This module contains the code needed to keep track of the current state of the UI.
 !*/
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -21,6 +22,7 @@ use std::rc::Rc;
 
 use rpfm_lib::diagnostics::Diagnostics;
 use rpfm_lib::global_search::GlobalSearch;
+use rpfm_lib::packedfile::table::TableDiff;
 
 use crate::app_ui::AppUI;
 use crate::packedfile_views::PackedFileView;
@@ -58,6 +60,9 @@ pub struct UIState {
 
     /// This stores the current `Diagnostics`.
     diagnostics: Arc<RwLock<Diagnostics>>,
+
+    /// This stores the changes of the tables saved back from an external program, until the tables are opened again.
+    table_diffs: Arc<RwLock<BTreeMap<Vec<String>, TableDiff>>>,
 }
 
 //-------------------------------------------------------------------------------//
@@ -77,6 +82,7 @@ impl Default for UIState {
             operational_mode: Arc::new(RwLock::new(OperationalMode::Normal)),
             global_search: Arc::new(RwLock::new(GlobalSearch::default())),
             diagnostics: Arc::new(RwLock::new(Diagnostics::default())),
+            table_diffs: Arc::new(RwLock::new(BTreeMap::new())),
         }
     }
 }
@@ -161,4 +167,14 @@ impl UIState {
     pub fn set_diagnostics(&self, diagnostics: &Diagnostics) {
         *self.diagnostics.write().unwrap() = diagnostics.clone();
     }
+
+    /// This function stores the changes of the table with the provided path, replacing any previous ones.
+    pub fn set_table_diff(&self, path: &[String], diff: &TableDiff) {
+        self.table_diffs.write().unwrap().insert(path.to_vec(), diff.clone());
+    }
+
+    /// This function returns the stored changes of the table with the provided path, if any, removing them from the `UIState`.
+    pub fn take_table_diff(&self, path: &[String]) -> Option<TableDiff> {
+        self.table_diffs.write().unwrap().remove(path)
+    }
 }
@@ -74,6 +74,7 @@ use crate::utils::atomic_from_ptr;
 use crate::utils::create_grid_layout;
 use crate::utils::show_dialog;
 use crate::utils::ptr_from_atomic;
+use crate::UI_STATE;
 
 use self::slots::*;
 use self::utils::*;
@@ -589,6 +590,13 @@ impl TableView {
             table_name.as_ref()
         );
 
+        // If the table was edited in an external program, highlight what changed in the last import.
+        if let Some(ref path) = packed_file_path {
+            if let Some(diff) = UI_STATE.take_table_diff(&path.read().unwrap()) {
+                packed_file_table_view.highlight_diff(&diff);
+            }
+        }
+
         // Set the connections and return success.
         connections::set_connections(&packed_file_table_view, &packed_file_table_view_slots);
         shortcuts::set_shortcuts(&packed_file_table_view);
@@ -42,6 +42,9 @@ use cpp_core::Ref;
 use std::collections::BTreeMap;
 use std::sync::atomic::Ordering;
 
+use rpfm_lib::packedfile::table::TableDiff;
+
+use crate::locale::tre;
 use crate::utils::{atomic_from_ptr, create_grid_layout, log_to_status_bar};
 use crate::packedfile_views::utils::set_modified;
 use crate::pack_tree::*;
@@ -1250,4 +1253,31 @@ impl TableView {
             self.table_view_primary.horizontal_header().resize_sections(ResizeMode::ResizeToContents);
         }
     }
+
+    /// This function paints the changed cells and the added rows of the provided `TableDiff`, like if they were edited in the view.
+    ///
+    /// Removed rows are no longer in the table, so they're only reported in the status bar.
+    pub unsafe fn highlight_diff(&self, diff: &TableDiff) {
+        let color_modified = get_color_modified();
+        for (row, column) in &diff.changed_cells {
+            let item = self.table_model.item_2a(*row as i32, *column as i32);
+            if !item.is_null() {
+                item.set_background(&QBrush::from_q_color(color_modified.as_ref().unwrap()));
+            }
+        }
+
+        let color_added = get_color_added();
+        for row in &diff.added_rows {
+            for column in 0..self.table_model.column_count_0a() {
+                let item = self.table_model.item_2a(*row as i32, column);
+                if !item.is_null() {
+                    item.set_background(&QBrush::from_q_color(color_added.as_ref().unwrap()));
+                }
+            }
+        }
+
+        if !diff.removed_rows.is_empty() {
+            log_to_status_bar(&tre("table_diff_removed_rows", &[&diff.removed_rows.len().to_string()]));
+        }
+    }
 }
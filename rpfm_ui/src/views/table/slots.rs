@@ -350,7 +350,7 @@ impl TableViewSlots {
                     if file_dialog.exec() == 1 {
                         let path = PathBuf::from(file_dialog.selected_files().at(0).to_std_string());
 
-                        CENTRAL_COMMAND.send_message_qt(Command::ImportTSV((packed_file_path.read().unwrap().to_vec(), path)));
+                        CENTRAL_COMMAND.send_message_qt(Command::ImportTSV((packed_file_path.read().unwrap().to_vec(), path, SETTINGS.read().unwrap().settings_bool["tsv_import_map_columns_by_header"])));
                         let response = CENTRAL_COMMAND.recv_message_qt_try();
                         match response {
                             Response::TableType(data) => {